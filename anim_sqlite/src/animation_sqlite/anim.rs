@@ -62,6 +62,14 @@ impl Animation for SqliteAnimation {
         self.db.get_num_edits().unwrap_or(0)
     }
 
+    fn brush_presets(&self) -> Vec<BrushPreset> {
+        self.db.brush_presets()
+    }
+
+    fn palette(&self) -> Vec<Swatch> {
+        self.db.palette()
+    }
+
     fn read_edit_log<'a>(&'a self, range: Range<usize>) -> Box<dyn 'a+Stream<Item=AnimationEdit, Error=()>> {
         self.db.read_edit_log(range)
     }
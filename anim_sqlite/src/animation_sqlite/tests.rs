@@ -367,7 +367,7 @@ fn read_brush_strokes_from_edit_log() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -403,7 +403,7 @@ fn read_brush_strokes_from_edit_log() {
     assert!(match edits[3] {
         AnimationEdit::Layer(2, LayerEdit::Paint(_when, PaintEdit::
             BrushProperties(ElementId::Assigned(_element_id), ref brush_properties)))
-                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 },
+                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None },
             _ => false
     });
     assert!(match edits[6] {
@@ -416,6 +416,210 @@ fn read_brush_strokes_from_edit_log() {
     });
 }
 
+#[test]
+fn default_brush_presets_are_stored() {
+    let anim = SqliteAnimation::new_in_memory();
+    anim.panic_on_error();
+
+    assert!(anim.brush_presets() == BrushPreset::default_presets());
+    assert!(anim.get_num_edits() == 0);
+}
+
+#[test]
+fn opening_file_without_brush_presets_adds_them() {
+    use rusqlite::Connection;
+    use std::fs;
+    use std::env;
+
+    // Create an animation file, then remove the brush presets table as if it was created by an earlier version
+    let path = env::temp_dir().join("flo_anim_sqlite_upgrade_test.flo");
+    fs::remove_file(&path).ok();
+
+    SqliteAnimation::new_with_file(&path).unwrap();
+    Connection::open(&path).unwrap().execute_batch("DROP TABLE Flo_BrushPreset;").unwrap();
+
+    let anim = SqliteAnimation::from_existing_database(Connection::open(&path).unwrap()).unwrap();
+    anim.panic_on_error();
+
+    assert!(anim.brush_presets() == BrushPreset::default_presets());
+}
+
+#[test]
+fn replace_and_remove_brush_presets() {
+    let anim        = SqliteAnimation::new_in_memory();
+    let mut thick   = BrushProperties::new();
+    thick.size      = 30.0;
+    thick.color     = Color::Rgba(0.5, 0.25, 0.0, 1.0);
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetBrushPreset(BrushPreset::new("Ink", BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw, thick)),
+        AnimationEdit::SetBrushPreset(BrushPreset::new("Nib", BrushDefinition::Calligraphy(CalligraphyDefinition::default()), BrushDrawingStyle::Draw, BrushProperties::new())),
+        AnimationEdit::RemoveBrushPreset("Eraser".to_string())
+    ]);
+    anim.panic_on_error();
+
+    let presets = anim.brush_presets();
+
+    assert!(presets.len() == 2);
+    assert!(presets[0].name == "Ink");
+    assert!(presets[0].properties == thick);
+    assert!(presets[1].name == "Nib");
+    assert!(presets[1].definition == BrushDefinition::Calligraphy(CalligraphyDefinition::default()));
+}
+
+#[test]
+fn store_brush_properties_with_gradient() {
+    let anim            = SqliteAnimation::new_in_memory();
+    let mut shaded      = BrushProperties::new();
+    shaded.gradient     = Some(BrushGradient { gradient_type: BrushGradientType::Radial, end_color: Color::Rgba(1.0, 0.5, 0.0, 1.0) });
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetBrushPreset(BrushPreset::new("Shaded", BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw, shaded))
+    ]);
+    anim.panic_on_error();
+
+    let presets = anim.brush_presets();
+    let shaded_preset = presets.iter().filter(|preset| preset.name == "Shaded").nth(0).unwrap();
+
+    assert!(shaded_preset.properties == shaded);
+}
+
+#[test]
+fn read_brush_presets_from_edit_log() {
+    let anim    = SqliteAnimation::new_in_memory();
+    let preset  = BrushPreset::new("Wide eraser", BrushDefinition::Ink(InkDefinition::default_eraser()), BrushDrawingStyle::Erase, BrushProperties::new());
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetBrushPreset(preset.clone()),
+        AnimationEdit::RemoveBrushPreset("Ink".to_string())
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(0..2);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::SetBrushPreset(preset),
+        AnimationEdit::RemoveBrushPreset("Ink".to_string())
+    ]);
+}
+
+#[test]
+fn replace_and_remove_swatches() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetSwatch(Swatch::new("Sky", Color::Rgba(0.5, 0.75, 1.0, 1.0))),
+        AnimationEdit::SetSwatch(Swatch::new("Grass", Color::Hsluv(120.0, 80.0, 50.0, 1.0))),
+        AnimationEdit::SetSwatch(Swatch::new("Sky", Color::Rgba(0.25, 0.5, 1.0, 1.0))),
+        AnimationEdit::SetSwatch(Swatch::new("Sand", Color::Rgba(0.9, 0.8, 0.5, 1.0))),
+        AnimationEdit::RemoveSwatch("Grass".to_string())
+    ]);
+    anim.panic_on_error();
+
+    assert!(anim.palette() == vec![
+        Swatch::new("Sky", Color::Rgba(0.25, 0.5, 1.0, 1.0)),
+        Swatch::new("Sand", Color::Rgba(0.9, 0.8, 0.5, 1.0))
+    ]);
+}
+
+#[test]
+fn read_swatches_from_edit_log() {
+    let anim    = SqliteAnimation::new_in_memory();
+    let swatch  = Swatch::new("Grass", Color::Hsluv(120.0, 80.0, 50.0, 1.0));
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetSwatch(swatch.clone()),
+        AnimationEdit::RemoveSwatch("Grass".to_string())
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(0..2);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::SetSwatch(swatch),
+        AnimationEdit::RemoveSwatch("Grass".to_string())
+    ]);
+}
+
+#[test]
+fn read_ink_brush_with_pressure_curve_from_edit_log() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let mut ink_defn        = InkDefinition::default();
+    ink_defn.pressure_curve = PressureCurve::new(vec![(0.25, 0.5), (0.75, 0.9)]);
+    ink_defn.velocity_width = Some(20.0);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(ink_defn.clone()), 
+                BrushDrawingStyle::Draw
+            )
+        ))
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(0..3);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits.len() == 3);
+    assert!(match edits[2] {
+        AnimationEdit::Layer(2, LayerEdit::Paint(_when, PaintEdit::SelectBrush(
+                ElementId::Assigned(_element_id),
+                BrushDefinition::Ink(ref read_defn), 
+                BrushDrawingStyle::Draw
+            )
+        ))  => read_defn == &ink_defn,
+        _   => false
+    });
+}
+
+#[test]
+fn read_ink_brush_with_linear_pressure_curve_points_from_edit_log() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let mut ink_defn        = InkDefinition::default();
+    ink_defn.pressure_curve = PressureCurve::new(vec![(0.5, 0.5)]);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(ink_defn.clone()), 
+                BrushDrawingStyle::Draw
+            )
+        ))
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(2..3);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits.len() == 1);
+    assert!(match edits[0] {
+        AnimationEdit::Layer(2, LayerEdit::Paint(_when, PaintEdit::SelectBrush(
+                ElementId::Assigned(_element_id),
+                BrushDefinition::Ink(ref read_defn), 
+                BrushDrawingStyle::Draw
+            )
+        ))  => read_defn.pressure_curve.points == vec![(0.5, 0.5)],
+        _   => false
+    });
+}
+
 #[test]
 fn will_assign_element_ids() {
     let animation = SqliteAnimation::new_in_memory();;
@@ -430,7 +634,7 @@ fn will_assign_element_ids() {
                 BrushDrawingStyle::Draw
             )
         )),
-        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
         AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                 RawPoint::from((10.0, 10.0)),
                 RawPoint::from((20.0, 5.0))
@@ -465,7 +669,7 @@ fn fetch_element_by_id() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -516,7 +720,7 @@ fn read_frame_after_edits() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -549,7 +753,7 @@ fn read_frame_after_edits() {
         assert!(match &elements[1] {
             &Vector::BrushProperties(ref props) => Some(props.brush_properties()),
             _ => None
-        } == Some(&BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }));
+        } == Some(&BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }));
 
         assert!(match &elements[3] {
             &Vector::BrushStroke(ref brush_stroke) => Some(brush_stroke.points()),
@@ -646,3 +850,226 @@ fn move_existing_element() {
         assert!(false)
     }
 }
+
+#[test]
+fn rotate_existing_element() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()), 
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(50), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+        
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Rotate)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(50.0, 60.0)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(TimeCurve::new(TimePoint::new(0.5, 0.0, Duration::from_millis(442)), TimePoint::new(0.5, 0.0, Duration::from_millis(442))))),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Attach(ElementId::Assigned(50)))
+    ]);
+    anim.panic_on_error();
+
+    let motion = anim.get_motion(ElementId::Assigned(100));
+    assert!(motion.is_some());
+    assert!(motion.as_ref().unwrap().motion_type() == MotionType::Rotate);
+
+    if let Some(Motion::Rotate(rotate)) = motion {
+        assert!(rotate.origin == (50.0, 60.0));
+        assert!(rotate.rotate == TimeCurve::new(TimePoint::new(0.5, 0.0, Duration::from_millis(442)), TimePoint::new(0.5, 0.0, Duration::from_millis(442))));
+    } else {
+        assert!(false)
+    }
+}
+
+#[test]
+fn delete_element() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()), 
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(50), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(51), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+        AnimationEdit::Element(ElementId::Assigned(50), Duration::from_millis(442), ElementEdit::Delete)
+    ]);
+    anim.panic_on_error();
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+    let frame = layer.get_frame_at_time(Duration::from_millis(442));
+
+    assert!(frame.element_with_id(ElementId::Assigned(50)).is_none());
+    assert!(frame.element_with_id(ElementId::Assigned(51)).is_some());
+}
+
+#[test]
+fn replace_brush_points_and_paint_new_points() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let new_points = Arc::new(vec![
+        BrushPoint { position: (10.0, 10.0), cp1: (10.0, 10.0), cp2: (10.0, 10.0), width: 0.5, angle: 0.0 },
+        BrushPoint { position: (15.0, 7.5), cp1: (12.0, 9.0), cp2: (13.0, 8.5), width: 0.5, angle: 0.0 }
+    ]);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()), 
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(50), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+        AnimationEdit::Element(ElementId::Assigned(50), Duration::from_millis(442), ElementEdit::SetBrushPoints(Arc::clone(&new_points))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushPoints(ElementId::Assigned(51), Arc::clone(&new_points))))
+    ]);
+    anim.panic_on_error();
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+    let frame = layer.get_frame_at_time(Duration::from_millis(442));
+
+    let points_for_element = |id| match frame.element_with_id(id) {
+        Some(Vector::BrushStroke(ref brush_stroke)) => Some(brush_stroke.points()),
+        _                                           => None
+    };
+
+    assert!(points_for_element(ElementId::Assigned(50)) == Some(Arc::clone(&new_points)));
+    assert!(points_for_element(ElementId::Assigned(51)) == Some(Arc::clone(&new_points)));
+}
+
+#[test]
+fn add_brush_points_above_existing_element() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let points = Arc::new(vec![
+        BrushPoint { position: (10.0, 10.0), cp1: (10.0, 10.0), cp2: (10.0, 10.0), width: 0.5, angle: 0.0 },
+        BrushPoint { position: (15.0, 7.5), cp1: (12.0, 9.0), cp2: (13.0, 8.5), width: 0.5, angle: 0.0 }
+    ]);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushPoints(ElementId::Assigned(50), Arc::clone(&points)))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushPoints(ElementId::Assigned(51), Arc::clone(&points)))),
+        AnimationEdit::Element(ElementId::Assigned(50), Duration::from_millis(442), ElementEdit::AddBrushPointsAbove(ElementId::Assigned(60), Arc::clone(&points))),
+        AnimationEdit::Element(ElementId::Assigned(50), Duration::from_millis(442), ElementEdit::AddBrushPointsAbove(ElementId::Assigned(61), Arc::clone(&points))),
+        AnimationEdit::Element(ElementId::Assigned(61), Duration::from_millis(442), ElementEdit::AddBrushPointsAbove(ElementId::Assigned(62), Arc::clone(&points))),
+        AnimationEdit::Element(ElementId::Assigned(51), Duration::from_millis(442), ElementEdit::AddBrushPointsAbove(ElementId::Assigned(63), Arc::clone(&points)))
+    ]);
+    anim.panic_on_error();
+
+    let layer       = anim.get_layer_with_id(2).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(442));
+    let element_ids = frame.vector_elements().unwrap()
+        .filter_map(|element| match element {
+            Vector::BrushStroke(brush_stroke)   => Some(brush_stroke.id()),
+            _                                   => None
+        })
+        .collect::<Vec<_>>();
+
+    assert!(element_ids == vec![50, 61, 62, 60, 51, 63].into_iter().map(|id| ElementId::Assigned(id)).collect::<Vec<_>>());
+}
+
+#[test]
+fn read_add_brush_points_above_from_edit_log() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let points = Arc::new(vec![
+        BrushPoint { position: (10.0, 10.0), cp1: (10.0, 10.0), cp2: (10.0, 10.0), width: 0.5, angle: 0.0 },
+        BrushPoint { position: (15.0, 7.5), cp1: (12.0, 9.0), cp2: (13.0, 8.5), width: 0.5, angle: 0.0 }
+    ]);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushPoints(ElementId::Assigned(50), Arc::clone(&points)))),
+        AnimationEdit::Element(ElementId::Assigned(50), Duration::from_millis(442), ElementEdit::AddBrushPointsAbove(ElementId::Unassigned, Arc::clone(&points)))
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(3..4);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits.len() == 1);
+    assert!(match edits[0] {
+        AnimationEdit::Element(ElementId::Assigned(50), when, ElementEdit::AddBrushPointsAbove(ElementId::Assigned(_), ref new_points))
+                => new_points == &points && when == Duration::from_millis(442),
+        _       => false
+    });
+}
+
+#[test]
+fn paint_calligraphy_brush_points_with_nib_angles() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let nib_points = Arc::new(vec![
+        BrushPoint { position: (10.0, 10.0), cp1: (10.0, 10.0), cp2: (10.0, 10.0), width: 0.5, angle: 0.25 },
+        BrushPoint { position: (15.0, 7.5), cp1: (12.0, 9.0), cp2: (13.0, 8.5), width: 0.75, angle: 1.5 }
+    ]);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Calligraphy(CalligraphyDefinition::default()), 
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushPoints(ElementId::Assigned(50), Arc::clone(&nib_points))))
+    ]);
+    anim.panic_on_error();
+
+    // Brush points read from the frame should keep their nib angles
+    let layer = anim.get_layer_with_id(2).unwrap();
+    let frame = layer.get_frame_at_time(Duration::from_millis(442));
+
+    assert!(match frame.element_with_id(ElementId::Assigned(50)) {
+        Some(Vector::BrushStroke(ref brush_stroke)) => brush_stroke.points() == nib_points,
+        _                                           => false
+    });
+
+    // The edit log should retain the calligraphy brush and the angles
+    let edit_log        = anim.read_edit_log(2..4);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(match edits[0] {
+        AnimationEdit::Layer(2, LayerEdit::Paint(_, PaintEdit::SelectBrush(_, BrushDefinition::Calligraphy(ref defn), BrushDrawingStyle::Draw)))
+            => defn == &CalligraphyDefinition::default(),
+        _   => false
+    });
+    assert!(match edits[1] {
+        AnimationEdit::Layer(2, LayerEdit::Paint(_, PaintEdit::BrushPoints(_, ref points)))
+            => points == &nib_points,
+        _   => false
+    });
+}
@@ -40,6 +40,24 @@ impl AnimationDb {
         }).unwrap()
     }
 
+    ///
+    /// Queries the brush presets stored with this animation
+    /// 
+    pub fn brush_presets(&self) -> Vec<BrushPreset> {
+        self.core.sync(|core| {
+            AnimationDbCore::get_brush_presets(&mut core.db)
+        }).unwrap()
+    }
+
+    ///
+    /// Queries the colour palette stored with this animation
+    /// 
+    pub fn palette(&self) -> Vec<Swatch> {
+        self.core.sync(|core| {
+            AnimationDbCore::get_swatches(&mut core.db)
+        }).unwrap()
+    }
+
     ///
     /// Assigns an unique element ID
     /// 
@@ -59,6 +59,12 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             Layer(layer_id, Paint(when, BrushStroke(ElementId::Unassigned, points))) =>
                 Layer(layer_id, Paint(when, BrushStroke(ElementId::Assigned(self.next_element_id()), points))),
 
+            Layer(layer_id, Paint(when, BrushPoints(ElementId::Unassigned, points))) =>
+                Layer(layer_id, Paint(when, BrushPoints(ElementId::Assigned(self.next_element_id()), points))),
+
+            Element(element_id, when, ElementEdit::AddBrushPointsAbove(ElementId::Unassigned, points)) =>
+                Element(element_id, when, ElementEdit::AddBrushPointsAbove(ElementId::Assigned(self.next_element_id()), points)),
+
             other => other
         }
     }
@@ -184,6 +190,7 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             SelectBrush(_id, brush_definition, drawing_style)   => Self::create_brush_definition(&mut self.db, brush_definition, drawing_style)?,
             BrushProperties(_id, brush_properties)              => Self::create_brush_properties(&mut self.db, brush_properties)?,
             BrushStroke(_id, brush_stroke)                      => self.create_brush_stroke(layer_id, when, brush_stroke)?,
+            BrushPoints(_id, brush_points)                      => self.db.update(vec![DatabaseUpdate::PopBrushPoints(brush_points)])?,
         }
 
         // create_new_element pushes an element ID, a key frame ID and a time. The various element actions pop the element ID so we need to pop the frame ID and time
@@ -262,6 +269,45 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
         Ok(())
     }
 
+    ///
+    /// Performs an editing action on an existing element
+    /// 
+    fn edit_element(&mut self, element_id: ElementId, edit: ElementEdit) -> Result<()> {
+        use self::ElementEdit::*;
+
+        if let ElementId::Assigned(element_id) = element_id {
+            match edit {
+                Delete => {
+                    self.db.update(vec![
+                        DatabaseUpdate::PushElementIdForAssignedId(element_id),
+                        DatabaseUpdate::PopDeleteElement
+                    ])?;
+                },
+
+                SetBrushPoints(points) => {
+                    self.db.update(vec![
+                        DatabaseUpdate::PushElementIdForAssignedId(element_id),
+                        DatabaseUpdate::PushDeleteBrushPoints,
+                        DatabaseUpdate::PopBrushPoints(points)
+                    ])?;
+                },
+
+                AddBrushPointsAbove(ElementId::Assigned(new_element_id), points) => {
+                    self.db.update(vec![
+                        DatabaseUpdate::PushElementIdForAssignedId(element_id),
+                        DatabaseUpdate::PopVectorElementAbove(VectorElementType::BrushStroke),
+                        DatabaseUpdate::PushElementAssignId(new_element_id),
+                        DatabaseUpdate::PopBrushPoints(points)
+                    ])?;
+                },
+
+                AddBrushPointsAbove(ElementId::Unassigned, _) => { }
+            }
+        }
+
+        Ok(())
+    }
+
     ///
     /// Performs a layer edit to a vector layer
     /// 
@@ -338,13 +384,39 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 self.edit_vector_layer(layer_id, layer_edit)?;
             },
 
-            Element(id, when, edit) => {
-                // TODO!
-                // unimplemented!()
+            Element(id, _when, edit) => {
+                self.edit_element(id, edit)?;
             },
 
             Motion(motion_id, motion_edit) => {
                 self.edit_motion(motion_id, motion_edit)?;
+            },
+
+            SetBrushPreset(preset) => {
+                Self::insert_brush(&mut self.db, &preset.definition)?;
+                Self::insert_brush_properties(&mut self.db, &preset.properties)?;
+                self.db.update(vec![
+                    DatabaseUpdate::PopBrushPreset(preset.name, DrawingStyleType::from(&preset.drawing_style))
+                ])?;
+            },
+
+            RemoveBrushPreset(name) => {
+                self.db.update(vec![
+                    DatabaseUpdate::DeleteBrushPreset(name)
+                ])?;
+            },
+
+            SetSwatch(swatch) => {
+                Self::insert_color(&mut self.db, &swatch.color)?;
+                self.db.update(vec![
+                    DatabaseUpdate::PopSwatch(swatch.name)
+                ])?;
+            },
+
+            RemoveSwatch(name) => {
+                self.db.update(vec![
+                    DatabaseUpdate::DeleteSwatch(name)
+                ])?;
             }
         }
 
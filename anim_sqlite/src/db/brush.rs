@@ -19,7 +19,23 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             &BrushDefinition::Ink(ref ink_defn) => {
                 db.update(vec![
                     PushBrushType(BrushDefinitionType::from(brush_definition)),
-                    PushInkBrush(ink_defn.min_width, ink_defn.max_width, ink_defn.scale_up_distance)
+                    PushInkBrush(ink_defn.min_width, ink_defn.max_width, ink_defn.scale_up_distance, ink_defn.velocity_width)
+                ])?;
+
+                // Pressure curves with no points don't need to store anything
+                if ink_defn.pressure_curve.points.len() > 0 {
+                    db.update(vec![
+                        PushInkPressureCurve(ink_defn.pressure_curve.points.clone())
+                    ])?;
+                }
+
+                Ok(())
+            },
+
+            &BrushDefinition::Calligraphy(ref calligraphy_defn) => {
+                db.update(vec![
+                    PushBrushType(BrushDefinitionType::from(brush_definition)),
+                    PushCalligraphyBrush(calligraphy_defn.nib_width, calligraphy_defn.nib_thickness, calligraphy_defn.nib_angle, calligraphy_defn.tilt_sensitivity, calligraphy_defn.pressure_sensitivity)
                 ])
             }
         }
@@ -37,6 +53,14 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             PushBrushProperties(brush_properties.size, brush_properties.opacity)
         ])?;
 
+        if let Some(gradient) = brush_properties.gradient {
+            Self::insert_color(db, &gradient.end_color)?;
+
+            db.update(vec![
+                PopBrushGradient(GradientType::from(gradient.gradient_type))
+            ])?;
+        }
+
         Ok(())
     }
 
@@ -55,12 +79,34 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 let min_width           = min_width as f32;
                 let max_width           = max_width as f32;
                 let scale_up_distance   = scale_up_distance as f32;
+                let velocity_width      = brush_entry.ink_velocity.map(|velocity| velocity as f32);
+                let pressure_curve      = PressureCurve::new(db.query_ink_pressure_curve(brush_id)?);
 
                 Ok(BrushDefinition::Ink(InkDefinition {
-                    min_width, max_width, scale_up_distance
+                    min_width, max_width, scale_up_distance, pressure_curve, velocity_width
                 }))
-            }
+            },
+
+            Calligraphy => Ok(BrushDefinition::Calligraphy(db.query_calligraphy_brush(brush_id)?))
+        }
+    }
+
+    ///
+    /// Retrieves the brush presets stored in the database
+    /// 
+    pub fn get_brush_presets(db: &mut TFile) -> Result<Vec<BrushPreset>> {
+        let entries = db.query_brush_presets()?;
+        let mut presets = vec![];
+
+        for entry in entries {
+            let (brush_id, drawing_style)   = entry.brush;
+            let definition                  = Self::get_brush_definition(db, brush_id)?;
+            let properties                  = Self::get_brush_properties(db, entry.brush_properties_id)?;
+
+            presets.push(BrushPreset::new(&entry.name, definition, BrushDrawingStyle::from(drawing_style), properties));
         }
+
+        Ok(presets)
     }
 
     ///
@@ -69,11 +115,20 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
     pub fn get_brush_properties(db: &mut TFile, brush_properties_id: i64) -> Result<BrushProperties> {
         let brush_properties_entry  = db.query_brush_properties(brush_properties_id)?;
         let color                   = Self::get_color(db, brush_properties_entry.color_id)?;
+        let gradient                = match brush_properties_entry.gradient {
+            Some((gradient_type, end_color_id)) => Some(BrushGradient {
+                gradient_type:  BrushGradientType::from(gradient_type),
+                end_color:      Self::get_color(db, end_color_id)?
+            }),
+
+            None => None
+        };
 
         Ok(BrushProperties {
             size:       brush_properties_entry.size as f32,
             opacity:    brush_properties_entry.opacity as f32,
-            color:      color
+            color:      color,
+            gradient:   gradient
         })
     }
 }
@@ -48,4 +48,18 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             }
         }
     }
+
+    ///
+    /// Retrieves the swatches in the palette stored in the database
+    /// 
+    pub fn get_swatches(db: &mut TFile) -> Result<Vec<Swatch>> {
+        let entries     = db.query_swatches()?;
+        let mut palette = vec![];
+
+        for (name, color_id) in entries {
+            palette.push(Swatch::new(&name, Self::get_color(db, color_id)?));
+        }
+
+        Ok(palette)
+    }
 }
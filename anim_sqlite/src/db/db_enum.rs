@@ -14,12 +14,23 @@ pub enum EditLogType {
     AddNewLayer,
     RemoveLayer,
 
+    SetBrushPreset,
+    RemoveBrushPreset,
+
+    SetSwatch,
+    RemoveSwatch,
+
     LayerAddKeyFrame,
     LayerRemoveKeyFrame,
 
     LayerPaintSelectBrush,
     LayerPaintBrushProperties,
     LayerPaintBrushStroke,
+    LayerPaintBrushPoints,
+
+    ElementDelete,
+    ElementSetBrushPoints,
+    ElementAddBrushPointsAbove,
 
     MotionCreate,
     MotionDelete,
@@ -45,7 +56,8 @@ pub enum DrawingStyleType {
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum BrushDefinitionType {
     Simple,
-    Ink
+    Ink,
+    Calligraphy
 }
 
 ///
@@ -57,6 +69,15 @@ pub enum ColorType {
     Hsluv
 }
 
+///
+/// Types of gradient fill
+/// 
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum GradientType {
+    Linear,
+    Radial
+}
+
 ///
 /// Types of player
 /// 
@@ -84,6 +105,7 @@ pub enum DbEnum {
     DrawingStyle(DrawingStyleType),
     BrushDefinition(BrushDefinitionType),
     Color(ColorType),
+    Gradient(GradientType),
     Layer(LayerType),
     MotionType(MotionType),
     MotionPathType(MotionPathType),
@@ -131,6 +153,14 @@ impl DbEnum {
         }
     }
 
+    /// Returns the Gradient value for this enum (if there is one)
+    pub fn gradient(self) -> Option<GradientType> {
+        match self {
+            DbEnum::Gradient(res)   => Some(res),
+            _                       => None
+        }
+    }
+
     /// Returns the VectorElement value for this enum (if there is one)
     pub fn vector_element(self) -> Option<VectorElementType> {
         match self {
@@ -157,6 +187,7 @@ pub enum DbEnumType {
     DrawingStyle,
     BrushDefinition,
     Color,
+    Gradient,
     Layer,
     VectorElement,
     MotionType
@@ -174,12 +205,23 @@ impl From<DbEnumType> for Vec<DbEnum> {
                     DbEnum::EditLog(AddNewLayer),
                     DbEnum::EditLog(RemoveLayer),
 
+                    DbEnum::EditLog(SetBrushPreset),
+                    DbEnum::EditLog(RemoveBrushPreset),
+
+                    DbEnum::EditLog(SetSwatch),
+                    DbEnum::EditLog(RemoveSwatch),
+
                     DbEnum::EditLog(LayerAddKeyFrame),
                     DbEnum::EditLog(LayerRemoveKeyFrame),
 
                     DbEnum::EditLog(LayerPaintSelectBrush),
                     DbEnum::EditLog(LayerPaintBrushProperties),
-                    DbEnum::EditLog(LayerPaintBrushStroke)
+                    DbEnum::EditLog(LayerPaintBrushStroke),
+                    DbEnum::EditLog(LayerPaintBrushPoints),
+
+                    DbEnum::EditLog(ElementDelete),
+                    DbEnum::EditLog(ElementSetBrushPoints),
+                    DbEnum::EditLog(ElementAddBrushPointsAbove)
                 ]
             },
 
@@ -195,7 +237,8 @@ impl From<DbEnumType> for Vec<DbEnum> {
                 use self::BrushDefinitionType::*;
                 vec![
                     DbEnum::BrushDefinition(Simple),
-                    DbEnum::BrushDefinition(Ink)
+                    DbEnum::BrushDefinition(Ink),
+                    DbEnum::BrushDefinition(Calligraphy)
                 ]
             },
 
@@ -207,6 +250,14 @@ impl From<DbEnumType> for Vec<DbEnum> {
                 ]
             },
 
+            Gradient => {
+                use self::GradientType::*;
+                vec![
+                    DbEnum::Gradient(Linear),
+                    DbEnum::Gradient(Radial)
+                ]
+            },
+
             Layer => {
                 use self::LayerType::*;
                 vec![
@@ -228,7 +279,9 @@ impl From<DbEnumType> for Vec<DbEnum> {
 
                 vec![
                     DbEnum::MotionType(None),
-                    DbEnum::MotionType(Translate)
+                    DbEnum::MotionType(Translate),
+                    DbEnum::MotionType(Scale),
+                    DbEnum::MotionType(Rotate)
                 ]
             }
         }
@@ -246,12 +299,17 @@ impl<'a> From<&'a AnimationEdit> for EditLogType {
             SetSize(_, _)                               => EditLogType::SetSize,
             AddNewLayer(_)                              => EditLogType::AddNewLayer,
             RemoveLayer(_)                              => EditLogType::RemoveLayer,
+            SetBrushPreset(_)                           => EditLogType::SetBrushPreset,
+            RemoveBrushPreset(_)                        => EditLogType::RemoveBrushPreset,
+            SetSwatch(_)                                => EditLogType::SetSwatch,
+            RemoveSwatch(_)                             => EditLogType::RemoveSwatch,
             
             Layer(_, AddKeyFrame(_))                    => EditLogType::LayerAddKeyFrame,
             Layer(_, RemoveKeyFrame(_))                 => EditLogType::LayerRemoveKeyFrame,
             Layer(_, Paint(_, SelectBrush(_, _, _)))    => EditLogType::LayerPaintSelectBrush,
             Layer(_, Paint(_, BrushProperties(_, _)))   => EditLogType::LayerPaintBrushProperties,
             Layer(_, Paint(_, BrushStroke(_,_)))        => EditLogType::LayerPaintBrushStroke,
+            Layer(_, Paint(_, BrushPoints(_,_)))        => EditLogType::LayerPaintBrushPoints,
 
             Motion(_, Create)                           => EditLogType::MotionCreate,
             Motion(_, Delete)                           => EditLogType::MotionDelete,
@@ -261,7 +319,9 @@ impl<'a> From<&'a AnimationEdit> for EditLogType {
             Motion(_, Attach(_))                        => EditLogType::MotionAttach,
             Motion(_, Detach(_))                        => EditLogType::MotionDetach,
 
-            Element(_, _, _)                            => unimplemented!() // No element edits exist yet
+            Element(_, _, ElementEdit::Delete)                      => EditLogType::ElementDelete,
+            Element(_, _, ElementEdit::SetBrushPoints(_))           => EditLogType::ElementSetBrushPoints,
+            Element(_, _, ElementEdit::AddBrushPointsAbove(_, _))   => EditLogType::ElementAddBrushPointsAbove
         }
     }
 }
@@ -277,6 +337,33 @@ impl<'a> From<&'a BrushDrawingStyle> for DrawingStyleType {
     }
 }
 
+impl From<DrawingStyleType> for BrushDrawingStyle {
+    fn from(t: DrawingStyleType) -> BrushDrawingStyle {
+        match t {
+            DrawingStyleType::Draw  => BrushDrawingStyle::Draw,
+            DrawingStyleType::Erase => BrushDrawingStyle::Erase
+        }
+    }
+}
+
+impl From<BrushGradientType> for GradientType {
+    fn from(t: BrushGradientType) -> GradientType {
+        match t {
+            BrushGradientType::Linear   => GradientType::Linear,
+            BrushGradientType::Radial   => GradientType::Radial
+        }
+    }
+}
+
+impl From<GradientType> for BrushGradientType {
+    fn from(t: GradientType) -> BrushGradientType {
+        match t {
+            GradientType::Linear    => BrushGradientType::Linear,
+            GradientType::Radial    => BrushGradientType::Radial
+        }
+    }
+}
+
 impl<'a> From<&'a PaintEdit> for VectorElementType {
     fn from(t: &PaintEdit) -> VectorElementType {
         use self::PaintEdit::*;
@@ -284,7 +371,8 @@ impl<'a> From<&'a PaintEdit> for VectorElementType {
         match t {
             SelectBrush(_, _, _)    => VectorElementType::BrushDefinition,
             BrushProperties(_, _)   => VectorElementType::BrushProperties,
-            BrushStroke(_, _)       => VectorElementType::BrushStroke
+            BrushStroke(_, _)       => VectorElementType::BrushStroke,
+            BrushPoints(_, _)       => VectorElementType::BrushStroke
         }
     }
 }
@@ -295,7 +383,8 @@ impl<'a> From<&'a BrushDefinition> for BrushDefinitionType {
 
         match t {
             &Simple     => BrushDefinitionType::Simple,
-            &Ink(_)     => BrushDefinitionType::Ink
+            &Ink(_)         => BrushDefinitionType::Ink,
+            &Calligraphy(_) => BrushDefinitionType::Calligraphy
         }
     }
 }
@@ -310,12 +399,23 @@ impl From<EditLogType> for DbEnumName {
             AddNewLayer                 => DbEnumName("Edit", "AddNewLayer"),
             RemoveLayer                 => DbEnumName("Edit", "RemoveLayer"),
 
+            SetBrushPreset              => DbEnumName("Edit", "SetBrushPreset"),
+            RemoveBrushPreset           => DbEnumName("Edit", "RemoveBrushPreset"),
+
+            SetSwatch                   => DbEnumName("Edit", "SetSwatch"),
+            RemoveSwatch                => DbEnumName("Edit", "RemoveSwatch"),
+
             LayerAddKeyFrame            => DbEnumName("Edit", "Layer::AddKeyFrame"),
             LayerRemoveKeyFrame         => DbEnumName("Edit", "Layer::RemoveKeyFrame"),
 
             LayerPaintSelectBrush       => DbEnumName("Edit", "Layer::Paint::SelectBrush"),
             LayerPaintBrushProperties   => DbEnumName("Edit", "Layer::Paint::BrushProperties"),
             LayerPaintBrushStroke       => DbEnumName("Edit", "Layer::Paint::BrushStroke"),
+            LayerPaintBrushPoints       => DbEnumName("Edit", "Layer::Paint::BrushPoints"),
+
+            ElementDelete               => DbEnumName("Edit", "Element::Delete"),
+            ElementSetBrushPoints       => DbEnumName("Edit", "Element::SetBrushPoints"),
+            ElementAddBrushPointsAbove  => DbEnumName("Edit", "Element::AddBrushPointsAbove"),
 
             MotionCreate                => DbEnumName("Edit", "Motion::Create"),
             MotionDelete                => DbEnumName("Edit", "Motion::Delete"),
@@ -344,8 +444,9 @@ impl From<BrushDefinitionType> for DbEnumName {
         use self::BrushDefinitionType::*;
 
         match t {
-            Simple  => DbEnumName("BrushType", "Simple"),
-            Ink     => DbEnumName("BrushType", "Ink")
+            Simple      => DbEnumName("BrushType", "Simple"),
+            Ink         => DbEnumName("BrushType", "Ink"),
+            Calligraphy => DbEnumName("BrushType", "Calligraphy")
         }
     }
 }
@@ -361,6 +462,17 @@ impl From<ColorType> for DbEnumName {
     }
 }
 
+impl From<GradientType> for DbEnumName {
+    fn from(t: GradientType) -> DbEnumName {
+        use self::GradientType::*;
+
+        match t {
+            Linear  => DbEnumName("GradientType", "Linear"),
+            Radial  => DbEnumName("GradientType", "Radial")
+        }
+    }
+}
+
 impl From<LayerType> for DbEnumName {
     fn from(t: LayerType) -> DbEnumName {
         use self::LayerType::*;
@@ -389,7 +501,9 @@ impl From<MotionType> for DbEnumName {
 
         match t {
             None        => DbEnumName("MotionType", "None"),
-            Translate   => DbEnumName("MotionType", "Translate")
+            Translate   => DbEnumName("MotionType", "Translate"),
+            Scale       => DbEnumName("MotionType", "Scale"),
+            Rotate      => DbEnumName("MotionType", "Rotate")
         }
     }
 }
@@ -413,6 +527,7 @@ impl From<DbEnum> for DbEnumName {
             DrawingStyle(dst)       => DbEnumName::from(dst),
             BrushDefinition(bdt)    => DbEnumName::from(bdt),
             Color(ct)               => DbEnumName::from(ct),
+            Gradient(gt)            => DbEnumName::from(gt),
             Layer(lt)               => DbEnumName::from(lt),
             VectorElement(vet)      => DbEnumName::from(vet),
             MotionType(mot)         => DbEnumName::from(mot),
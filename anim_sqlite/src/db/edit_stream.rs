@@ -2,6 +2,7 @@ use super::*;
 use super::db_enum::*;
 use super::flo_query::*;
 
+use canvas::*;
 use futures::task;
 
 use std::ops::Range;
@@ -109,6 +110,51 @@ impl<TFile: FloFile+Send> EditStream<TFile> {
         LayerEdit::Paint(when, PaintEdit::BrushProperties(element_id, brush_properties))
     }
 
+    ///
+    /// Generates a SetBrushPreset entry
+    /// 
+    fn set_brush_preset_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> AnimationEdit {
+        let name                        = core.db.query_edit_log_string(entry.edit_id).unwrap_or_else(|_err| String::new());
+        let (definition, drawing_style) = entry.brush
+            .map(|(brush_id, drawing_style)| (AnimationDbCore::get_brush_definition(&mut core.db, brush_id).unwrap_or(BrushDefinition::Simple), drawing_style))
+            .unwrap_or((BrushDefinition::Simple, DrawingStyleType::Draw));
+        let properties                  = entry.brush_properties_id
+            .map(|brush_properties_id| AnimationDbCore::get_brush_properties(&mut core.db, brush_properties_id).unwrap_or(BrushProperties::new()))
+            .unwrap_or(BrushProperties::new());
+
+        AnimationEdit::SetBrushPreset(BrushPreset::new(&name, definition, BrushDrawingStyle::from(drawing_style), properties))
+    }
+
+    ///
+    /// Generates a RemoveBrushPreset entry
+    /// 
+    fn remove_brush_preset_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> AnimationEdit {
+        let name = core.db.query_edit_log_string(entry.edit_id).unwrap_or_else(|_err| String::new());
+
+        AnimationEdit::RemoveBrushPreset(name)
+    }
+
+    ///
+    /// Generates a SetSwatch entry
+    /// 
+    fn set_swatch_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> AnimationEdit {
+        let name    = core.db.query_edit_log_string(entry.edit_id).unwrap_or_else(|_err| String::new());
+        let color   = core.db.query_edit_log_color(entry.edit_id)
+            .and_then(|color_id| AnimationDbCore::get_color(&mut core.db, color_id))
+            .unwrap_or(Color::Rgba(0.0, 0.0, 0.0, 1.0));
+
+        AnimationEdit::SetSwatch(Swatch::new(&name, color))
+    }
+
+    ///
+    /// Generates a RemoveSwatch entry
+    /// 
+    fn remove_swatch_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> AnimationEdit {
+        let name = core.db.query_edit_log_string(entry.edit_id).unwrap_or_else(|_err| String::new());
+
+        AnimationEdit::RemoveSwatch(name)
+    }
+
     ///
     /// Retrieves the raw points associated with an entry 
     /// 
@@ -136,6 +182,45 @@ impl<TFile: FloFile+Send> EditStream<TFile> {
         LayerEdit::Paint(when, PaintEdit::BrushStroke(element_id, points))
     }
 
+    ///
+    /// Retrieves the brush points associated with an entry
+    /// 
+    fn brush_points_for_entry_id(core: &mut AnimationDbCore<TFile>, edit_id: i64) -> Arc<Vec<BrushPoint>> {
+        let points = core.db.query_edit_log_brush_points(edit_id).unwrap_or_else(|_err| vec![]);
+
+        Arc::new(points)
+    }
+
+    ///
+    /// Decodes a brush points entry
+    /// 
+    fn brush_points_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> LayerEdit {
+        let points      = Self::brush_points_for_entry_id(core, entry.edit_id);
+        let when        = entry.when.unwrap_or(Duration::from_millis(0));
+        let element_id  = ElementId::from(entry.element_id);
+
+        LayerEdit::Paint(when, PaintEdit::BrushPoints(element_id, points))
+    }
+
+    ///
+    /// Decodes an edit to an existing element
+    /// 
+    fn element_edit_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> AnimationEdit {
+        let when        = entry.when.unwrap_or(Duration::from_millis(0));
+        let element_id  = ElementId::from(entry.element_id);
+
+        let edit        = match entry.edit_type {
+            EditLogType::ElementSetBrushPoints  => ElementEdit::SetBrushPoints(Self::brush_points_for_entry_id(core, entry.edit_id)),
+            EditLogType::ElementAddBrushPointsAbove => {
+                let new_element_id = core.db.query_edit_log_new_element_id(entry.edit_id).ok();
+                ElementEdit::AddBrushPointsAbove(ElementId::from(new_element_id), Self::brush_points_for_entry_id(core, entry.edit_id))
+            },
+            _                                   => ElementEdit::Delete
+        };
+
+        AnimationEdit::Element(element_id, when, edit)
+    }
+
     ///
     /// Turns an edit log entry into an animation edit
     /// 
@@ -147,12 +232,22 @@ impl<TFile: FloFile+Send> EditStream<TFile> {
             AddNewLayer                 => AnimationEdit::AddNewLayer(entry.layer_id.unwrap_or(INVALID_LAYER)),
             RemoveLayer                 => AnimationEdit::RemoveLayer(entry.layer_id.unwrap_or(INVALID_LAYER)),
 
+            SetBrushPreset              => Self::set_brush_preset_for_entry(core, entry),
+            RemoveBrushPreset           => Self::remove_brush_preset_for_entry(core, entry),
+            SetSwatch                   => Self::set_swatch_for_entry(core, entry),
+            RemoveSwatch                => Self::remove_swatch_for_entry(core, entry),
+
             LayerAddKeyFrame            => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::AddKeyFrame(entry.when.unwrap_or(Duration::from_millis(0)))),
             LayerRemoveKeyFrame         => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::RemoveKeyFrame(entry.when.unwrap_or(Duration::from_millis(0)))),
 
             LayerPaintSelectBrush       => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::select_brush_for_entry(core, entry)),
             LayerPaintBrushProperties   => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::brush_properties_for_entry(core, entry)),
             LayerPaintBrushStroke       => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::brush_stroke_for_entry(core, entry)),
+            LayerPaintBrushPoints       => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::brush_points_for_entry(core, entry)),
+
+            ElementDelete               => Self::element_edit_for_entry(core, entry),
+            ElementSetBrushPoints       => Self::element_edit_for_entry(core, entry),
+            ElementAddBrushPointsAbove  => Self::element_edit_for_entry(core, entry),
 
             MotionCreate                => unimplemented!(),
             MotionDelete                => unimplemented!(),
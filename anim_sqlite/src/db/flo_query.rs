@@ -33,8 +33,9 @@ pub struct ColorEntry {
 /// Entry read from the brush table
 /// 
 pub struct BrushEntry {
-    pub brush_type: BrushDefinitionType,
-    pub ink_defn:   Option<(f64, f64, f64)>
+    pub brush_type:     BrushDefinitionType,
+    pub ink_defn:       Option<(f64, f64, f64)>,
+    pub ink_velocity:   Option<f64>
 }
 
 ///
@@ -43,7 +44,17 @@ pub struct BrushEntry {
 pub struct BrushPropertiesEntry {
     pub size:       f64,
     pub opacity:    f64,
-    pub color_id:   i64
+    pub color_id:   i64,
+    pub gradient:   Option<(GradientType, i64)>
+}
+
+///
+/// Entry read from the brush preset table
+/// 
+pub struct BrushPresetEntry {
+    pub name:                   String,
+    pub brush:                  (i64, DrawingStyleType),
+    pub brush_properties_id:    i64
 }
 
 ///
@@ -136,6 +147,26 @@ pub trait FloQuery {
     /// 
     fn query_edit_log_raw_points(&mut self, edit_id: i64) -> Result<Vec<RawPoint>>;
 
+    ///
+    /// Retrieves the brush points associated with a particular edit ID
+    /// 
+    fn query_edit_log_brush_points(&mut self, edit_id: i64) -> Result<Vec<BrushPoint>>;
+
+    ///
+    /// Retrieves the string associated with a particular edit ID
+    /// 
+    fn query_edit_log_string(&mut self, edit_id: i64) -> Result<String>;
+
+    ///
+    /// Retrieves the ID of the colour associated with a particular edit ID
+    /// 
+    fn query_edit_log_color(&mut self, edit_id: i64) -> Result<i64>;
+
+    ///
+    /// Retrieves the ID of the element created by a particular edit ID
+    /// 
+    fn query_edit_log_new_element_id(&mut self, edit_id: i64) -> Result<i64>;
+
     ///
     /// Retrieves a colour with the specified ID
     /// 
@@ -146,11 +177,31 @@ pub trait FloQuery {
     /// 
     fn query_brush(&mut self, brush_id: i64) -> Result<BrushEntry>;
 
+    ///
+    /// Retrieves the definition of the calligraphy brush with the specified ID
+    /// 
+    fn query_calligraphy_brush(&mut self, brush_id: i64) -> Result<CalligraphyDefinition>;
+
+    ///
+    /// Retrieves the pressure curve control points for the ink brush with the specified ID
+    /// 
+    fn query_ink_pressure_curve(&mut self, brush_id: i64) -> Result<Vec<(f32, f32)>>;
+
     ///
     /// Retrieves the brush properties with the specified ID
     /// 
     fn query_brush_properties(&mut self, brush_properties_id: i64) -> Result<BrushPropertiesEntry>;
 
+    ///
+    /// Retrieves the brush presets stored with the animation
+    /// 
+    fn query_brush_presets(&mut self) -> Result<Vec<BrushPresetEntry>>;
+
+    ///
+    /// Retrieves the names and colour IDs of the swatches in the palette for the animation
+    /// 
+    fn query_swatches(&mut self) -> Result<Vec<(String, i64)>>;
+
     ///
     /// Queries the vector elements that appear before a certain time in the specified keyframe
     /// 
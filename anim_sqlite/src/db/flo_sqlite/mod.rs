@@ -14,6 +14,7 @@ use std::time::Duration;
 use std::mem;
 
 const V1_DEFINITION: &[u8]      = include_bytes!["../../../sql/flo_v1.sqlite"];
+const V1_UPGRADE: &[u8]         = include_bytes!["../../../sql/flo_v1_upgrade.sqlite"];
 const PACKAGE_NAME: &str        = env!("CARGO_PKG_NAME");
 const PACKAGE_VERSION: &str     = env!("CARGO_PKG_VERSION");
 
@@ -63,11 +64,22 @@ enum FloStatement {
     SelectEditLogValues,
     SelectEditLogSize,
     SelectEditLogRawPoints,
+    SelectEditLogBrushPoints,
+    SelectEditLogString,
+    SelectEditLogColor,
+    SelectEditLogNewElementId,
     SelectColor,
     SelectBrushDefinition,
+    SelectInkPressureCurve,
+    SelectCalligraphyBrush,
     SelectBrushProperties,
+    SelectBrushPresets,
+    SelectSwatches,
     SelectVectorElementsBefore,
+    SelectVectorElementOrdering,
+    SelectNextVectorElementOrdering,
     SelectBrushPoints,
+    SelectElementIdForAssignedId,
     SelectMotionsForElement,
     SelectElementsForMotion,
     SelectMotion,
@@ -75,6 +87,8 @@ enum FloStatement {
 
     UpdateAnimationSize,
     UpdateMotionType,
+    UpdateBrushPreset,
+    UpdateSwatch,
 
     InsertEnumValue,
     InsertEditType,
@@ -85,6 +99,10 @@ enum FloStatement {
     InsertELBrushProperties,
     InsertELElementId,
     InsertELRawPoints,
+    InsertELBrushPoint,
+    InsertELString,
+    InsertELColor,
+    InsertELNewElementId,
     InsertELMotionOrigin,
     InsertELMotionType,
     InsertELMotionElement,
@@ -92,7 +110,12 @@ enum FloStatement {
     InsertTimePoint,
     InsertBrushType,
     InsertInkBrush,
+    InsertInkPressurePoint,
+    InsertCalligraphyBrush,
     InsertBrushProperties,
+    InsertBrushGradient,
+    InsertBrushPreset,
+    InsertSwatch,
     InsertColorType,
     InsertRgb,
     InsertHsluv,
@@ -101,6 +124,7 @@ enum FloStatement {
     InsertKeyFrame,
     InsertVectorElementType,
     InsertElementAssignedId,
+    InsertVectorElementOrdering,
     InsertBrushDefinitionElement,
     InsertBrushPropertiesElement,
     InsertBrushPoint,
@@ -111,9 +135,13 @@ enum FloStatement {
 
     DeleteKeyFrame,
     DeleteLayer,
+    DeleteVectorElement,
+    DeleteBrushPoints,
     DeleteMotion,
     DeleteMotionPoints,
-    DeleteMotionAttachedElement
+    DeleteMotionAttachedElement,
+    DeleteBrushPreset,
+    DeleteSwatch
 }
 
 impl FloSqlite {
@@ -159,6 +187,48 @@ impl FloSqlite {
         Ok(())
     }
 
+    ///
+    /// Adds any tables and columns that are missing from a database created by an earlier version
+    /// 
+    /// Returns true if the database had no brush presets table (so it needs the default presets adding)
+    /// 
+    pub fn upgrade(sqlite: &Connection) -> Result<bool> {
+        let missing_presets = !Self::has_table(sqlite, "Flo_BrushPreset")?;
+
+        // Columns added to existing tables
+        Self::add_missing_column(sqlite, "Flo_Brush_Ink", "VelocityWidth", "REAL")?;
+        Self::add_missing_column(sqlite, "Flo_BrushPoint", "Angle", "REAL NOT NULL DEFAULT 0.0")?;
+
+        // New tables, indexes and triggers
+        let v1_upgrade = String::from_utf8_lossy(V1_UPGRADE);
+        sqlite.execute_batch(&v1_upgrade)?;
+
+        Ok(missing_presets)
+    }
+
+    ///
+    /// True if the database contains a table with the specified name
+    /// 
+    fn has_table(sqlite: &Connection, table: &str) -> Result<bool> {
+        let num_tables: i64 = sqlite.query_row("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?", &[&table], |row| row.get(0))?;
+
+        Ok(num_tables > 0)
+    }
+
+    ///
+    /// Adds a column to a table if it doesn't already have it
+    /// 
+    fn add_missing_column(sqlite: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+        let mut table_info  = sqlite.prepare(&format!("PRAGMA table_info({})", table))?;
+        let columns         = table_info.query_map(&[], |row| row.get::<_, String>(1))?.collect::<Result<Vec<_>>>()?;
+
+        if !columns.iter().any(|existing_column| existing_column == column) {
+            sqlite.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {};", table, column, definition))?;
+        }
+
+        Ok(())
+    }
+
     ///
     /// Turns a microsecond count into a duration
     /// 
@@ -210,21 +280,39 @@ impl FloSqlite {
                                                     LIMIT ? OFFSET ?",
             SelectEditLogSize               => "SELECT X, Y FROM Flo_EL_Size WHERE EditId = ?",
             SelectEditLogRawPoints          => "SELECT Points FROM Flo_EL_RawPoints WHERE EditId = ?",
+            SelectEditLogBrushPoints        => "SELECT X1, Y1, X2, Y2, X3, Y3, Width, Angle FROM Flo_EL_BrushPoint WHERE EditId = ? ORDER BY PointId ASC",
+            SelectEditLogString             => "SELECT String FROM Flo_EL_String WHERE EditId = ?",
+            SelectEditLogColor              => "SELECT Color FROM Flo_EL_Color WHERE EditId = ?",
+            SelectEditLogNewElementId       => "SELECT ElementId FROM Flo_EL_NewElementId WHERE EditId = ?",
             SelectColor                     => "SELECT Col.ColorType, Rgb.R, Rgb.G, Rgb.B, Hsluv.H, Hsluv.S, Hsluv.L FROM Flo_Color_Type AS Col \
                                                     LEFT OUTER JOIN Flo_Color_Rgb   AS Rgb      ON Col.Color = Rgb.Color \
                                                     LEFT OUTER JOIN Flo_Color_Hsluv AS Hsluv    ON Col.Color = Hsluv.Color \
                                                     WHERE Col.Color = ?",
-            SelectBrushDefinition           => "SELECT Brush.BrushType, Ink.MinWidth, Ink.MaxWidth, Ink.ScaleUpDistance FROM Flo_Brush_Type AS Brush \
+            SelectBrushDefinition           => "SELECT Brush.BrushType, Ink.MinWidth, Ink.MaxWidth, Ink.ScaleUpDistance, Ink.VelocityWidth FROM Flo_Brush_Type AS Brush \
                                                     LEFT OUTER JOIN Flo_Brush_Ink AS Ink ON Brush.Brush = Ink.Brush \
                                                     WHERE Brush.Brush = ?",
-            SelectBrushProperties           => "SELECT Size, Opacity, Color FROM Flo_BrushProperties WHERE BrushProperties = ?",
+            SelectInkPressureCurve          => "SELECT InputPressure, OutputPressure FROM Flo_Brush_InkPressureCurve WHERE Brush = ? ORDER BY PointIndex ASC",
+            SelectCalligraphyBrush          => "SELECT NibWidth, NibThickness, NibAngle, TiltSensitivity, PressureSensitivity FROM Flo_Brush_Calligraphy WHERE Brush = ?",
+            SelectBrushProperties           => "SELECT Props.Size, Props.Opacity, Props.Color, Grad.GradientType, Grad.EndColor FROM Flo_BrushProperties AS Props \
+                                                    LEFT OUTER JOIN Flo_BrushGradient AS Grad ON Props.BrushProperties = Grad.BrushProperties \
+                                                    WHERE Props.BrushProperties = ?",
+            SelectBrushPresets              => "SELECT Name, Brush, DrawingStyle, BrushProperties FROM Flo_BrushPreset WHERE AnimationId = ? ORDER BY PresetId ASC",
+            SelectSwatches                  => "SELECT Name, Color FROM Flo_Swatch WHERE AnimationId = ? ORDER BY SwatchId ASC",
             SelectVectorElementsBefore      => "SELECT Elem.ElementId, Elem.VectorElementType, Elem.AtTime, Brush.Brush, Brush.DrawingStyle, Props.BrushProperties, Assgn.AssignedId FROM Flo_VectorElement AS Elem \
                                                     LEFT OUTER JOIN Flo_BrushElement            AS Brush ON Elem.ElementId = Brush.ElementId \
                                                     LEFT OUTER JOIN Flo_BrushPropertiesElement  AS Props ON Elem.ElementId = Props.ElementId \
                                                     LEFT OUTER JOIN Flo_AssignedElementId       AS Assgn ON Elem.ElementId = Assgn.ElementId \
+                                                    LEFT OUTER JOIN Flo_VectorElementOrdering   AS Ord   ON Elem.ElementId = Ord.ElementId \
                                                     WHERE Elem.KeyFrameId = ? AND Elem.AtTime <= ? \
-                                                    ORDER BY Elem.ElementId ASC",
-            SelectBrushPoints               => "SELECT X1, Y1, X2, Y2, X3, Y3, Width FROM Flo_BrushPoint WHERE ElementId = ? ORDER BY PointId ASC",
+                                                    ORDER BY COALESCE(Ord.Ordering, Elem.ElementId) ASC, Elem.ElementId ASC",
+            SelectVectorElementOrdering     => "SELECT Elem.KeyFrameId, Elem.AtTime, COALESCE(Ord.Ordering, Elem.ElementId) FROM Flo_VectorElement AS Elem \
+                                                    LEFT OUTER JOIN Flo_VectorElementOrdering AS Ord ON Elem.ElementId = Ord.ElementId \
+                                                    WHERE Elem.ElementId = ?",
+            SelectNextVectorElementOrdering => "SELECT MIN(COALESCE(Ord.Ordering, Elem.ElementId)) FROM Flo_VectorElement AS Elem \
+                                                    LEFT OUTER JOIN Flo_VectorElementOrdering AS Ord ON Elem.ElementId = Ord.ElementId \
+                                                    WHERE Elem.KeyFrameId = ? AND COALESCE(Ord.Ordering, Elem.ElementId) > ?",
+            SelectBrushPoints               => "SELECT X1, Y1, X2, Y2, X3, Y3, Width, Angle FROM Flo_BrushPoint WHERE ElementId = ? ORDER BY PointId ASC",
+            SelectElementIdForAssignedId    => "SELECT ElementId FROM Flo_AssignedElementId WHERE AssignedId = ?",
             SelectMotionsForElement         => "SELECT MotionId FROM Flo_MotionAttached WHERE ElementId = ?",
             SelectElementsForMotion         => "SELECT ElementId FROM Flo_MotionAttached WHERE MotionId = ?",
             SelectMotion                    => "SELECT Mot.MotionType, Origin.X, Origin.Y FROM Flo_Motion AS Mot
@@ -237,6 +325,8 @@ impl FloSqlite {
 
             UpdateAnimationSize             => "UPDATE Flo_Animation SET SizeX = ?, SizeY = ? WHERE AnimationId = ?",
             UpdateMotionType                => "UPDATE Flo_Motion SET MotionType = ? WHERE MotionId = ?",
+            UpdateBrushPreset               => "UPDATE Flo_BrushPreset SET DrawingStyle = ?, Brush = ?, BrushProperties = ? WHERE AnimationId = ? AND Name = ?",
+            UpdateSwatch                    => "UPDATE Flo_Swatch SET Color = ? WHERE AnimationId = ? AND Name = ?",
 
             InsertEnumValue                 => "INSERT INTO Flo_EnumerationDescriptions (FieldName, Value, ApiName, Comment) SELECT ?, (SELECT IFNULL(Max(Value)+1, 0) FROM Flo_EnumerationDescriptions WHERE FieldName = ?), ?, ?",
             InsertEditType                  => "INSERT INTO Flo_EditLog (Edit) VALUES (?)",
@@ -247,14 +337,23 @@ impl FloSqlite {
             InsertELBrushProperties         => "INSERT INTO Flo_EL_BrushProperties (EditId, BrushProperties) VALUES (?, ?)",
             InsertELElementId               => "INSERT INTO Flo_EL_ElementId (EditId, ElementId) VALUES (?, ?)",
             InsertELRawPoints               => "INSERT INTO Flo_EL_RawPoints (EditId, Points) VALUES (?, ?)",
+            InsertELBrushPoint              => "INSERT INTO Flo_EL_BrushPoint (EditId, PointId, X1, Y1, X2, Y2, X3, Y3, Width, Angle) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            InsertELString                  => "INSERT INTO Flo_EL_String (EditId, String) VALUES (?, ?)",
+            InsertELColor                   => "INSERT INTO Flo_EL_Color (EditId, Color) VALUES (?, ?)",
+            InsertELNewElementId            => "INSERT INTO Flo_EL_NewElementId (EditId, ElementId) VALUES (?, ?)",
             InsertELMotionOrigin            => "INSERT INTO Flo_EL_MotionOrigin (EditId, X, Y) VALUES (?, ?, ?)",
             InsertELMotionType              => "INSERT INTO Flo_EL_MotionType (EditId, MotionType) VALUES (?, ?)",
             InsertELMotionElement           => "INSERT INTO Flo_EL_MotionAttach (EditId, AttachedElement) VALUES (?, ?)",
             InsertELMotionTimePoint         => "INSERT INTO Flo_EL_MotionPath (EditId, PointIndex, TimePointId) VALUES (?, ?, ?)",
             InsertTimePoint                 => "INSERT INTO Flo_TimePoint (X, Y, Milliseconds) VALUES (?, ?, ?)",
             InsertBrushType                 => "INSERT INTO Flo_Brush_Type (BrushType) VALUES (?)",
-            InsertInkBrush                  => "INSERT INTO Flo_Brush_Ink (Brush, MinWidth, MaxWidth, ScaleUpDistance) VALUES (?, ?, ?, ?)",
+            InsertInkBrush                  => "INSERT INTO Flo_Brush_Ink (Brush, MinWidth, MaxWidth, ScaleUpDistance, VelocityWidth) VALUES (?, ?, ?, ?, ?)",
+            InsertInkPressurePoint          => "INSERT INTO Flo_Brush_InkPressureCurve (Brush, PointIndex, InputPressure, OutputPressure) VALUES (?, ?, ?, ?)",
+            InsertCalligraphyBrush          => "INSERT INTO Flo_Brush_Calligraphy (Brush, NibWidth, NibThickness, NibAngle, TiltSensitivity, PressureSensitivity) VALUES (?, ?, ?, ?, ?, ?)",
             InsertBrushProperties           => "INSERT INTO Flo_BrushProperties (Size, Opacity, Color) VALUES (?, ?, ?)",
+            InsertBrushGradient             => "INSERT INTO Flo_BrushGradient (BrushProperties, GradientType, EndColor) VALUES (?, ?, ?)",
+            InsertBrushPreset               => "INSERT INTO Flo_BrushPreset (AnimationId, Name, DrawingStyle, Brush, BrushProperties) VALUES (?, ?, ?, ?, ?)",
+            InsertSwatch                    => "INSERT INTO Flo_Swatch (AnimationId, Name, Color) VALUES (?, ?, ?)",
             InsertColorType                 => "INSERT INTO Flo_Color_Type (ColorType) VALUES (?)",
             InsertRgb                       => "INSERT INTO Flo_Color_Rgb (Color, R, G, B) VALUES (?, ?, ?, ?)",
             InsertHsluv                     => "INSERT INTO Flo_Color_Hsluv (Color, H, S, L) VALUES (?, ?, ?, ?)",
@@ -262,9 +361,10 @@ impl FloSqlite {
             InsertAssignLayer               => "INSERT INTO Flo_AnimationLayers (AnimationId, LayerId, AssignedLayerId) VALUES (?, ?, ?)",
             InsertKeyFrame                  => "INSERT INTO Flo_LayerKeyFrame (LayerId, AtTime) VALUES (?, ?)",
             InsertVectorElementType         => "INSERT INTO Flo_VectorElement (KeyFrameId, VectorElementType, AtTime) VALUES (?, ?, ?)",
+            InsertVectorElementOrdering     => "INSERT INTO Flo_VectorElementOrdering (ElementId, Ordering) VALUES (?, ?)",
             InsertBrushDefinitionElement    => "INSERT INTO Flo_BrushElement (ElementId, Brush, DrawingStyle) VALUES (?, ?, ?)",
             InsertBrushPropertiesElement    => "INSERT INTO Flo_BrushPropertiesElement (ElementId, BrushProperties) VALUES (?, ?)",
-            InsertBrushPoint                => "INSERT INTO Flo_BrushPoint (ElementId, PointId, X1, Y1, X2, Y2, X3, Y3, Width) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            InsertBrushPoint                => "INSERT INTO Flo_BrushPoint (ElementId, PointId, X1, Y1, X2, Y2, X3, Y3, Width, Angle) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             InsertElementAssignedId         => "INSERT INTO Flo_AssignedElementId (ElementId, AssignedId) VALUES (?, ?)",
             InsertMotion                    => "INSERT INTO Flo_Motion (MotionId, MotionType) VALUES (?, ?)",
             InsertOrReplaceMotionOrigin     => "INSERT OR REPLACE INTO Flo_MotionOrigin (MotionId, X, Y) VALUES (?, ?, ?)",
@@ -273,9 +373,13 @@ impl FloSqlite {
 
             DeleteKeyFrame                  => "DELETE FROM Flo_LayerKeyFrame WHERE LayerId = ? AND AtTime = ?",
             DeleteLayer                     => "DELETE FROM Flo_LayerType WHERE LayerId = ?",
+            DeleteVectorElement             => "DELETE FROM Flo_VectorElement WHERE ElementId = ?",
+            DeleteBrushPoints               => "DELETE FROM Flo_BrushPoint WHERE ElementId = ?",
             DeleteMotion                    => "DELETE FROM Flo_Motion WHERE MotionId = ?",
             DeleteMotionPoints              => "DELETE FROM Flo_MotionPath WHERE MotionId = ? AND PathType = ?",
-            DeleteMotionAttachedElement     => "DELETE FROM Flo_MotionAttached WHERE MotionId = ? AND ElementId = ?"
+            DeleteMotionAttachedElement     => "DELETE FROM Flo_MotionAttached WHERE MotionId = ? AND ElementId = ?",
+            DeleteBrushPreset               => "DELETE FROM Flo_BrushPreset WHERE AnimationId = ? AND Name = ?",
+            DeleteSwatch                    => "DELETE FROM Flo_Swatch WHERE AnimationId = ? AND Name = ?"
         }
     }
 
@@ -374,4 +478,32 @@ mod test {
         // They're independent for different enum types
         assert!(db.enum_value(DbEnum::DrawingStyle(DrawingStyleType::Draw)) == 0);
     }
+
+    #[test]
+    fn upgrade_adds_missing_tables() {
+        let conn = Connection::open_in_memory().unwrap();
+        FloSqlite::setup(&conn).unwrap();
+
+        // Make the database look like one created before brush presets were stored
+        conn.execute_batch("DROP TABLE Flo_BrushPreset;").unwrap();
+
+        assert!(FloSqlite::upgrade(&conn).unwrap());
+        assert!(FloSqlite::has_table(&conn, "Flo_BrushPreset").unwrap());
+
+        // Upgrading an up to date database changes nothing
+        assert!(!FloSqlite::upgrade(&conn).unwrap());
+    }
+
+    #[test]
+    fn add_missing_column_only_adds_once() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE Flo_Test (Width REAL NOT NULL);").unwrap();
+
+        FloSqlite::add_missing_column(&conn, "Flo_Test", "Angle", "REAL NOT NULL DEFAULT 0.0").unwrap();
+        FloSqlite::add_missing_column(&conn, "Flo_Test", "Angle", "REAL NOT NULL DEFAULT 0.0").unwrap();
+
+        conn.execute_batch("INSERT INTO Flo_Test (Width) VALUES (1.0);").unwrap();
+        let angle: f64 = conn.query_row("SELECT Angle FROM Flo_Test", &[], |row| row.get(0)).unwrap();
+        assert!(angle == 0.0);
+    }
 }
\ No newline at end of file
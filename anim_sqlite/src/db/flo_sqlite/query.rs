@@ -178,6 +178,32 @@ impl FloQuery for FloSqlite {
         })
     }
 
+    ///
+    /// Retrieves the brush points associated with a particular edit ID
+    /// 
+    fn query_edit_log_brush_points(&mut self, edit_id: i64) -> Result<Vec<BrushPoint>> {
+        self.query_map(FloStatement::SelectEditLogBrushPoints, &[&edit_id],
+            |row| {
+                let x1:     f64 = row.get(0);
+                let y1:     f64 = row.get(1);
+                let x2:     f64 = row.get(2);
+                let y2:     f64 = row.get(3);
+                let x3:     f64 = row.get(4);
+                let y3:     f64 = row.get(5);
+                let width:  f64 = row.get(6);
+                let angle:  f64 = row.get(7);
+
+                BrushPoint {
+                    cp1:        (x1 as f32, y1 as f32),
+                    cp2:        (x2 as f32, y2 as f32),
+                    position:   (x3 as f32, y3 as f32),
+                    width:      width as f32,
+                    angle:      angle as f32
+                }
+            })
+            .map(|rows_with_errors| rows_with_errors.map(|row_with_error| row_with_error.unwrap()).collect())
+    }
+
     ///
     /// Retrieves a colour with the specified ID
     /// 
@@ -205,33 +231,131 @@ impl FloQuery for FloSqlite {
     /// Retrieves the brush with the specified ID
     /// 
     fn query_brush(&mut self, brush_id: i64) -> Result<BrushEntry> {
-        self.query_row(FloStatement::SelectBrushDefinition, &[&brush_id], |row| (row.get(0), row.get(1), row.get(2), row.get(3)))
-            .map(|(brush_type, min_width, max_width, scale_up_distance)| {
+        self.query_row(FloStatement::SelectBrushDefinition, &[&brush_id], |row| (row.get(0), row.get(1), row.get(2), row.get(3), row.get(4)))
+            .map(|(brush_type, min_width, max_width, scale_up_distance, velocity)| {
                 let min_width: Option<f64>          = min_width;
                 let max_width: Option<f64>          = max_width;
                 let scale_up_distance: Option<f64>  = scale_up_distance;
+                let velocity: Option<f64>           = velocity;
                 let brush_type                      = self.value_for_enum(DbEnumType::BrushDefinition, Some(brush_type)).and_then(|brush_type| brush_type.brush_definition());
 
                 BrushEntry {
                     brush_type: brush_type.unwrap(),
-                    ink_defn:   min_width.and_then(|min_width| max_width.map(|max_width| (min_width, max_width))).and_then(|(min_width, max_width)| scale_up_distance.map(|scale_up| (min_width, max_width, scale_up)))
+                    ink_defn:       min_width.and_then(|min_width| max_width.map(|max_width| (min_width, max_width))).and_then(|(min_width, max_width)| scale_up_distance.map(|scale_up| (min_width, max_width, scale_up))),
+                    ink_velocity:   velocity
                 }
             })
     }
 
     ///
-    /// Retrieves the brush properties with the specified ID
+    /// Retrieves the definition of the calligraphy brush with the specified ID
     /// 
-    fn query_brush_properties(&mut self, brush_properties_id: i64) -> Result<BrushPropertiesEntry> {
-        self.query_row(FloStatement::SelectBrushProperties, &[&brush_properties_id], |row| {
-            BrushPropertiesEntry {
-                size:       row.get(0),
-                opacity:    row.get(1),
-                color_id:   row.get(2)
+    fn query_calligraphy_brush(&mut self, brush_id: i64) -> Result<CalligraphyDefinition> {
+        self.query_row(FloStatement::SelectCalligraphyBrush, &[&brush_id], |row| {
+            let nib_width:              f64 = row.get(0);
+            let nib_thickness:          f64 = row.get(1);
+            let nib_angle:              f64 = row.get(2);
+            let tilt_sensitivity:       f64 = row.get(3);
+            let pressure_sensitivity:   f64 = row.get(4);
+
+            CalligraphyDefinition {
+                nib_width:              nib_width as f32,
+                nib_thickness:          nib_thickness as f32,
+                nib_angle:              nib_angle as f32,
+                tilt_sensitivity:       tilt_sensitivity as f32,
+                pressure_sensitivity:   pressure_sensitivity as f32
             }
         })
     }
 
+    ///
+    /// Retrieves the pressure curve control points for the ink brush with the specified ID
+    /// 
+    fn query_ink_pressure_curve(&mut self, brush_id: i64) -> Result<Vec<(f32, f32)>> {
+        self.query_map(FloStatement::SelectInkPressureCurve, &[&brush_id],
+            |row| {
+                let input:  f64 = row.get(0);
+                let output: f64 = row.get(1);
+
+                (input as f32, output as f32)
+            })
+            .map(|rows_with_errors| rows_with_errors.map(|row_with_error| row_with_error.unwrap()).collect())
+    }
+
+    ///
+    /// Retrieves the string associated with a particular edit ID
+    /// 
+    fn query_edit_log_string(&mut self, edit_id: i64) -> Result<String> {
+        self.query_row(FloStatement::SelectEditLogString, &[&edit_id], |row| row.get(0))
+    }
+
+    ///
+    /// Retrieves the ID of the colour associated with a particular edit ID
+    /// 
+    fn query_edit_log_color(&mut self, edit_id: i64) -> Result<i64> {
+        self.query_row(FloStatement::SelectEditLogColor, &[&edit_id], |row| row.get(0))
+    }
+
+    ///
+    /// Retrieves the ID of the element created by a particular edit ID
+    /// 
+    fn query_edit_log_new_element_id(&mut self, edit_id: i64) -> Result<i64> {
+        self.query_row(FloStatement::SelectEditLogNewElementId, &[&edit_id], |row| row.get(0))
+    }
+
+    ///
+    /// Retrieves the brush presets stored with the animation
+    /// 
+    fn query_brush_presets(&mut self) -> Result<Vec<BrushPresetEntry>> {
+        let animation_id = self.animation_id;
+
+        // As for the other queries, value_for_enum can't be called from inside query_map
+        self.query_map(FloStatement::SelectBrushPresets, &[&animation_id], |row| (row.get(0), row.get(1), row.get(2), row.get(3)))
+            .map(|rows_with_errors| rows_with_errors
+                .map(|row_with_error| row_with_error.unwrap())
+                .map(|(name, brush_id, drawing_style, brush_properties_id)| {
+                    let name: String                = name;
+                    let brush_id: i64               = brush_id;
+                    let brush_properties_id: i64    = brush_properties_id;
+                    let drawing_style               = self.value_for_enum(DbEnumType::DrawingStyle, Some(drawing_style)).and_then(|drawing_style| drawing_style.drawing_style());
+
+                    BrushPresetEntry {
+                        name:                   name,
+                        brush:                  (brush_id, drawing_style.unwrap_or(DrawingStyleType::Draw)),
+                        brush_properties_id:    brush_properties_id
+                    }
+                })
+                .collect())
+    }
+
+    ///
+    /// Retrieves the names and colour IDs of the swatches in the palette for the animation
+    /// 
+    fn query_swatches(&mut self) -> Result<Vec<(String, i64)>> {
+        let animation_id = self.animation_id;
+
+        self.query_map(FloStatement::SelectSwatches, &[&animation_id], |row| (row.get(0), row.get(1)))
+            .map(|rows_with_errors| rows_with_errors.map(|row_with_error| row_with_error.unwrap()).collect())
+    }
+
+    ///
+    /// Retrieves the brush properties with the specified ID
+    /// 
+    fn query_brush_properties(&mut self, brush_properties_id: i64) -> Result<BrushPropertiesEntry> {
+        self.query_row(FloStatement::SelectBrushProperties, &[&brush_properties_id], |row| (row.get(0), row.get(1), row.get(2), row.get(3), row.get(4)))
+            .map(|(size, opacity, color_id, gradient_type, end_color_id)| {
+                let end_color_id: Option<i64>   = end_color_id;
+                let gradient_type               = self.value_for_enum(DbEnumType::Gradient, gradient_type).and_then(|gradient_type| gradient_type.gradient());
+
+                BrushPropertiesEntry {
+                    size:       size,
+                    opacity:    opacity,
+                    color_id:   color_id,
+                    gradient:   gradient_type.and_then(|gradient_type| end_color_id.map(|end_color_id| (gradient_type, end_color_id)))
+                }
+            })
+    }
+
     ///
     /// Queries the vector elements that appear before a certain time in the specified keyframe
     /// 
@@ -278,12 +402,14 @@ impl FloQuery for FloSqlite {
                 let x3:     f64 = row.get(4);
                 let y3:     f64 = row.get(5);
                 let width:  f64 = row.get(6);
+                let angle:  f64 = row.get(7);
 
                 BrushPoint {
                     cp1:        (x1 as f32, y1 as f32),
                     cp2:        (x2 as f32, y2 as f32),
                     position:   (x3 as f32, y3 as f32),
-                    width:      width as f32
+                    width:      width as f32,
+                    angle:      angle as f32
                 }
             })
             .map(|rows_with_errors| rows_with_errors.map(|row_with_error| row_with_error.unwrap()).collect())
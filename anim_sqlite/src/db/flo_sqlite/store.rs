@@ -67,6 +67,30 @@ impl FloSqlite {
                 set_brush_props.insert(&[&edit_log_id, &brush_props_id])?;
             },
 
+            PopEditLogBrushPreset(drawing_style)                            => {
+                let brush_props_id      = self.stack.pop().unwrap();
+                let brush_id            = self.stack.pop().unwrap();
+                let edit_log_id         = self.stack.pop().unwrap();
+                let drawing_style       = self.enum_value(DbEnum::DrawingStyle(drawing_style));
+                let mut set_brush       = Self::prepare(&self.sqlite, FloStatement::InsertELBrush)?;
+                set_brush.insert(&[&edit_log_id, &drawing_style, &brush_id])?;
+                let mut set_brush_props = Self::prepare(&self.sqlite, FloStatement::InsertELBrushProperties)?;
+                set_brush_props.insert(&[&edit_log_id, &brush_props_id])?;
+            },
+
+            PushEditLogString(string)                                       => {
+                let edit_log_id         = self.stack.last().unwrap();
+                let mut set_string      = Self::prepare(&self.sqlite, FloStatement::InsertELString)?;
+                set_string.insert(&[edit_log_id, &string])?;
+            },
+
+            PopEditLogColor                                                 => {
+                let color_id            = self.stack.pop().unwrap();
+                let edit_log_id         = self.stack.pop().unwrap();
+                let mut set_color       = Self::prepare(&self.sqlite, FloStatement::InsertELColor)?;
+                set_color.insert(&[&edit_log_id, &color_id])?;
+            },
+
             PushEditLogElementId(element_id)                                => {
                 let edit_log_id         = self.stack.last().unwrap();
                 let mut add_element_id  = Self::prepare(&self.sqlite, FloStatement::InsertELElementId)?;
@@ -83,6 +107,21 @@ impl FloSqlite {
                 add_raw_point.insert(&[edit_log_id, &point_bytes])?;
             },
 
+            PushEditLogBrushPoints(points)                                  => {
+                let edit_log_id             = self.stack.last().unwrap();
+                let mut add_brush_point     = Self::prepare(&self.sqlite, FloStatement::InsertELBrushPoint)?;
+
+                for (index, point) in points.iter().enumerate() {
+                    add_brush_point.insert(&[
+                        edit_log_id, &(index as i64),
+                        &(point.cp1.0 as f64), &(point.cp1.1 as f64),
+                        &(point.cp2.0 as f64), &(point.cp2.1 as f64),
+                        &(point.position.0 as f64), &(point.position.1 as f64),
+                        &(point.width as f64), &(point.angle as f64)
+                    ])?;
+                }
+            },
+
             PushEditLogMotionOrigin(x, y) => {
                 let (x, y)          = (x as f64, y as f64);
                 let edit_log_id     = self.stack.last().unwrap();
@@ -99,6 +138,13 @@ impl FloSqlite {
                 add_type.insert(&[edit_log_id, &motion_type])?;
             },
 
+            PushEditLogNewElementId(new_element_id) => {
+                let edit_log_id             = self.stack.last().unwrap();
+                let mut add_new_element_id  = Self::prepare(&self.sqlite, FloStatement::InsertELNewElementId)?;
+
+                add_new_element_id.insert(&[edit_log_id, &new_element_id])?;
+            },
+
             PushEditLogMotionElement(attach_element) => {
                 let edit_log_id     = self.stack.last().unwrap();
                 let mut add_type    = Self::prepare(&self.sqlite, FloStatement::InsertELMotionElement)?;
@@ -140,10 +186,26 @@ impl FloSqlite {
                 self.stack.push(brush_id);
             },
 
-            PushInkBrush(min_width, max_width, scale_up_distance)           => {
+            PushInkBrush(min_width, max_width, scale_up_distance, velocity) => {
                 let brush_id                = self.stack.last().unwrap();
                 let mut insert_ink_brush    = Self::prepare(&self.sqlite, FloStatement::InsertInkBrush)?;
-                insert_ink_brush.insert(&[brush_id, &(min_width as f64), &(max_width as f64), &(scale_up_distance as f64)])?;
+                let velocity                = velocity.map(|velocity| velocity as f64);
+                insert_ink_brush.insert(&[brush_id, &(min_width as f64), &(max_width as f64), &(scale_up_distance as f64), &velocity])?;
+            },
+
+            PushCalligraphyBrush(nib_width, nib_thickness, nib_angle, tilt_sensitivity, pressure_sensitivity) => {
+                let brush_id                = self.stack.last().unwrap();
+                let mut insert_calligraphy  = Self::prepare(&self.sqlite, FloStatement::InsertCalligraphyBrush)?;
+                insert_calligraphy.insert(&[brush_id, &(nib_width as f64), &(nib_thickness as f64), &(nib_angle as f64), &(tilt_sensitivity as f64), &(pressure_sensitivity as f64)])?;
+            },
+
+            PushInkPressureCurve(points)                                    => {
+                let brush_id                = self.stack.last().unwrap();
+                let mut insert_point        = Self::prepare(&self.sqlite, FloStatement::InsertInkPressurePoint)?;
+
+                for (index, (input, output)) in points.into_iter().enumerate() {
+                    insert_point.insert(&[brush_id, &(index as i64), &(input as f64), &(output as f64)])?;
+                }
             },
 
             PushBrushProperties(size, opacity)                              => {
@@ -153,6 +215,14 @@ impl FloSqlite {
                 self.stack.push(brush_props_id);
             },
 
+            PopBrushGradient(gradient_type)                                 => {
+                let end_color_id                = self.stack.pop().unwrap();
+                let brush_props_id              = self.stack.last().unwrap();
+                let gradient_type               = self.enum_value(DbEnum::Gradient(gradient_type));
+                let mut insert_brush_gradient   = Self::prepare(&self.sqlite, FloStatement::InsertBrushGradient)?;
+                insert_brush_gradient.insert(&[brush_props_id, &gradient_type, &end_color_id])?;
+            },
+
             PushColorType(color_type)                                       => {
                 let color_type              = self.enum_value(DbEnum::Color(color_type));
                 let mut insert_color_type   = Self::prepare(&self.sqlite, FloStatement::InsertColorType)?;
@@ -233,12 +303,53 @@ impl FloSqlite {
                 self.stack.push(element_id);
             },
 
+            PopVectorElementAbove(element_type)                             => {
+                let below_element_id                = self.stack.pop().unwrap();
+                let mut select_ordering             = Self::prepare(&self.sqlite, FloStatement::SelectVectorElementOrdering)?;
+                let (keyframe_id, when, below): (i64, i64, f64) = select_ordering.query_row(&[&below_element_id], |row| (row.get(0), row.get(1), row.get(2)))?;
+                let mut select_next_ordering        = Self::prepare(&self.sqlite, FloStatement::SelectNextVectorElementOrdering)?;
+                let next: Option<f64>               = select_next_ordering.query_row(&[&keyframe_id, &below], |row| row.get(0))?;
+
+                let element_type                    = self.enum_value(DbEnum::VectorElement(element_type));
+                let mut insert_vector_element_type  = Self::prepare(&self.sqlite, FloStatement::InsertVectorElementType)?;
+                let element_id                      = insert_vector_element_type.insert(&[&keyframe_id, &element_type, &when])?;
+
+                // New elements are already ordered after everything else, so we only need an ordering if there's an element above the one we're inserting after
+                if let Some(next) = next {
+                    let mut insert_ordering = Self::prepare(&self.sqlite, FloStatement::InsertVectorElementOrdering)?;
+                    insert_ordering.insert(&[&element_id, &((below + next) / 2.0)])?;
+                }
+
+                self.stack.push(element_id);
+            },
+
             PushElementAssignId(assigned_id)                                => {
                 let element_id                      = self.stack.last().unwrap();
                 let mut insert_element_assigned_id  = Self::prepare(&self.sqlite, FloStatement::InsertElementAssignedId)?;
                 insert_element_assigned_id.insert(&[element_id, &assigned_id])?;
             },
 
+            PushElementIdForAssignedId(assigned_id)                         => {
+                let mut select_element_id   = Self::prepare(&self.sqlite, FloStatement::SelectElementIdForAssignedId)?;
+                let element_id              = match select_element_id.query_row(&[&assigned_id], |row| row.get(0)) {
+                    Err(Error::QueryReturnedNoRows) => -1,
+                    other                           => other?
+                };
+                self.stack.push(element_id);
+            },
+
+            PopDeleteElement                                                => {
+                let element_id              = self.stack.pop().unwrap();
+                let mut delete_element      = Self::prepare(&self.sqlite, FloStatement::DeleteVectorElement)?;
+                delete_element.execute(&[&element_id])?;
+            },
+
+            PushDeleteBrushPoints                                           => {
+                let element_id              = self.stack.last().unwrap();
+                let mut delete_brush_points = Self::prepare(&self.sqlite, FloStatement::DeleteBrushPoints)?;
+                delete_brush_points.execute(&[element_id])?;
+            },
+
             PopVectorBrushElement(drawing_style)                            => {
                 let brush_id                            = self.stack.pop().unwrap();
                 let element_id                          = self.stack.pop().unwrap();
@@ -265,7 +376,7 @@ impl FloSqlite {
                         &(point.cp1.0 as f64), &(point.cp1.1 as f64),
                         &(point.cp2.0 as f64), &(point.cp2.1 as f64),
                         &(point.position.0 as f64), &(point.position.1 as f64),
-                        &(point.width as f64)
+                        &(point.width as f64), &(point.angle as f64)
                     ])?;
                 }
             },
@@ -317,6 +428,44 @@ impl FloSqlite {
                 insert_attached_element.insert(&[&motion_id, &element_id])?;
             },
 
+            PopBrushPreset(name, drawing_style)                             => {
+                let brush_props_id      = self.stack.pop().unwrap();
+                let brush_id            = self.stack.pop().unwrap();
+                let drawing_style       = self.enum_value(DbEnum::DrawingStyle(drawing_style));
+
+                // Replace the existing preset if there is one so the presets stay in the order they were created in
+                let mut update_preset   = Self::prepare(&self.sqlite, FloStatement::UpdateBrushPreset)?;
+                let num_updated         = update_preset.execute(&[&drawing_style, &brush_id, &brush_props_id, &self.animation_id, &name])?;
+
+                if num_updated == 0 {
+                    let mut insert_preset = Self::prepare(&self.sqlite, FloStatement::InsertBrushPreset)?;
+                    insert_preset.insert(&[&self.animation_id, &name, &drawing_style, &brush_id, &brush_props_id])?;
+                }
+            },
+
+            DeleteBrushPreset(name)                                         => {
+                let mut delete_preset = Self::prepare(&self.sqlite, FloStatement::DeleteBrushPreset)?;
+                delete_preset.execute(&[&self.animation_id, &name])?;
+            },
+
+            PopSwatch(name)                                                 => {
+                let color_id            = self.stack.pop().unwrap();
+
+                // As for presets, swatches are updated in place so the palette keeps its order
+                let mut update_swatch   = Self::prepare(&self.sqlite, FloStatement::UpdateSwatch)?;
+                let num_updated         = update_swatch.execute(&[&color_id, &self.animation_id, &name])?;
+
+                if num_updated == 0 {
+                    let mut insert_swatch = Self::prepare(&self.sqlite, FloStatement::InsertSwatch)?;
+                    insert_swatch.insert(&[&self.animation_id, &name, &color_id])?;
+                }
+            },
+
+            DeleteSwatch(name)                                              => {
+                let mut delete_swatch = Self::prepare(&self.sqlite, FloStatement::DeleteSwatch)?;
+                delete_swatch.execute(&[&self.animation_id, &name])?;
+            },
+
             DeleteMotion(motion_id)                                         => {
                 let mut delete_motion = Self::prepare(&self.sqlite, FloStatement::DeleteMotion)?;
                 delete_motion.execute(&[&motion_id])?;
@@ -46,15 +46,30 @@ pub enum DatabaseUpdate {
     /// Pops two things from the stack: a brush properties ID and an edit ID and inserts a brush properties edit
     PopEditLogBrushProperties,
 
+    /// Pops three things from the stack: a brush properties ID, a brush ID and an edit ID and inserts both into the edit log with the specified drawing style
+    PopEditLogBrushPreset(DrawingStyleType),
+
+    /// Uses the edit ID on top of the stack and associates a string with it
+    PushEditLogString(String),
+
+    /// Pops two things from the stack: a colour ID and an edit ID and associates the colour with the edit
+    PopEditLogColor,
+
     /// Uses the edit ID on top of the stack and inserts a raw point for it (index, position, pressure, tilt)
     PushRawPoints(Arc<Vec<RawPoint>>),
 
+    /// Uses the edit ID on top of the stack and inserts the brush points for it
+    PushEditLogBrushPoints(Arc<Vec<BrushPoint>>),
+
     /// Uses the edit ID on top of the stack and associates a motion origin with it
     PushEditLogMotionOrigin(f32, f32),
 
     /// Uses the edit ID on top of the stack and associates a motion type with it
     PushEditLogMotionType(MotionType),
 
+    /// Uses the edit ID on top of the stack and sets the ID of the element that the edit creates
+    PushEditLogNewElementId(i64),
+
     /// Uses the edit ID on top of the stack sets the attached element ID
     PushEditLogMotionElement(i64),
 
@@ -67,12 +82,33 @@ pub enum DatabaseUpdate {
     /// Inserts a new brush type definition, pushing the new brush's ID to the stack
     PushBrushType(BrushDefinitionType),
 
-    /// Inserts an ink brush, leaving the brush ID on the stack
-    PushInkBrush(f32, f32, f32),
+    /// Inserts an ink brush (min width, max width, scale up distance, velocity width), leaving the brush ID on the stack
+    PushInkBrush(f32, f32, f32, Option<f32>),
+
+    /// Sets the pressure curve control points for the ink brush with the ID on top of the stack, leaving the ID on the stack
+    PushInkPressureCurve(Vec<(f32, f32)>),
+
+    /// Inserts a calligraphy brush (nib width, nib thickness, nib angle, tilt sensitivity, pressure sensitivity), leaving the brush ID on the stack
+    PushCalligraphyBrush(f32, f32, f32, f32, f32),
 
     /// Pops a colour ID and pushes brush properties with that colour and the specified size and opacity
     PushBrushProperties(f32, f32),
 
+    /// Pops a colour ID and makes it the end colour of a gradient fill for the brush properties with the ID on top of the stack, leaving the ID on the stack
+    PopBrushGradient(GradientType),
+
+    /// Pops a brush properties ID and a brush ID and stores them as the brush preset with the specified name (replacing any existing preset with that name)
+    PopBrushPreset(String, DrawingStyleType),
+
+    /// Removes the brush preset with the specified name
+    DeleteBrushPreset(String),
+
+    /// Pops a colour ID and stores it as the palette swatch with the specified name (replacing any existing swatch with that name)
+    PopSwatch(String),
+
+    /// Removes the palette swatch with the specified name
+    DeleteSwatch(String),
+
     /// Pushes a colour ID of the specified type
     PushColorType(ColorType),
 
@@ -110,9 +146,21 @@ pub enum DatabaseUpdate {
     /// (Stack has the element ID, the key frame ID and the time left afterwards)
     PushVectorElementType(VectorElementType, Duration),
 
+    /// Pops a vector element ID and creates a new vector element of the specified type directly above it (in the same keyframe and at the same time), and pushes its ID
+    PopVectorElementAbove(VectorElementType),
+
     /// Uses the element ID on top of the stack and sets its assigned ID, leaving it on top of the stack
     PushElementAssignId(i64),
 
+    /// Looks up a vector element with an assigned ID and pushes its real ID (or -1 if there's no such element)
+    PushElementIdForAssignedId(i64),
+
+    /// Removes the vector element with the ID on top of the stack
+    PopDeleteElement,
+
+    /// Removes any brush points attached to the vector element ID on top of the stack, leaving the ID on the stack
+    PushDeleteBrushPoints,
+
     /// Pops a brush ID and a vector element ID and creates a vector brush element from them
     PopVectorBrushElement(DrawingStyleType),
 
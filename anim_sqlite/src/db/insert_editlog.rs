@@ -70,6 +70,27 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 self.db.update(vec![PushEditLogLayer(layer_id), Pop])?;
             },
 
+            &SetBrushPreset(ref preset)                     => {
+                self.db.update(vec![PushEditLogString(preset.name.clone())])?;
+                Self::insert_brush(&mut self.db, &preset.definition)?;
+                Self::insert_brush_properties(&mut self.db, &preset.properties)?;
+                self.db.update(vec![PopEditLogBrushPreset(DrawingStyleType::from(&preset.drawing_style))])?;
+            },
+
+            &RemoveBrushPreset(ref name)                    => {
+                self.db.update(vec![PushEditLogString(name.clone()), Pop])?;
+            },
+
+            &SetSwatch(ref swatch)                          => {
+                self.db.update(vec![PushEditLogString(swatch.name.clone())])?;
+                Self::insert_color(&mut self.db, &swatch.color)?;
+                self.db.update(vec![PopEditLogColor])?;
+            },
+
+            &RemoveSwatch(ref name)                         => {
+                self.db.update(vec![PushEditLogString(name.clone()), Pop])?;
+            },
+
             &Element(element_id, when, ref element_edit)    => {
                 Self::insert_element_id(&mut self.db, &element_id)?;
                 self.db.update(vec![PushEditLogWhen(when)])?;
@@ -89,6 +110,25 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
     /// Inserts the parameters for an element edit into the edit log
     /// 
     fn insert_element_edit(&mut self, edit: &ElementEdit) -> Result<()> {
+        use animation::ElementEdit::*;
+
+        match edit {
+            Delete                  => {
+                self.db.update(vec![Pop])?;
+            },
+
+            SetBrushPoints(points)  => {
+                self.db.update(vec![PushEditLogBrushPoints(Arc::clone(points)), Pop])?;
+            },
+
+            AddBrushPointsAbove(new_element_id, points) => {
+                if let &ElementId::Assigned(new_element_id) = new_element_id {
+                    self.db.update(vec![PushEditLogNewElementId(new_element_id)])?;
+                }
+                self.db.update(vec![PushEditLogBrushPoints(Arc::clone(points)), Pop])?;
+            }
+        }
+
         Ok(())
     }
 
@@ -192,6 +232,11 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             &BrushStroke(ref id, ref points)                        => {
                 Self::insert_element_id(&mut self.db, id)?;
                 self.db.update(vec![PushRawPoints(Arc::clone(points)), Pop])?;
+            },
+
+            &BrushPoints(ref id, ref points)                        => {
+                Self::insert_element_id(&mut self.db, id)?;
+                self.db.update(vec![PushEditLogBrushPoints(Arc::clone(points)), Pop])?;
             }
         }
 
@@ -63,6 +63,9 @@ impl AnimationDb {
 
         let core    = Arc::new(Desync::new(AnimationDbCore::new(connection)));
 
+        // New animations start with the built-in brush presets
+        Self::add_default_presets(&core);
+
         let db      = AnimationDb {
             core:   core
         };
@@ -74,8 +77,16 @@ impl AnimationDb {
     /// Creates an animation database that uses an existing database already set up in a SQLite connection
     /// 
     pub fn from_connection(connection: Connection) -> AnimationDb {
+        // Files created by earlier versions might be missing some tables
+        let missing_presets = FloSqlite::upgrade(&connection).unwrap();
+
         let core    = Arc::new(Desync::new(AnimationDbCore::new(connection)));
 
+        // Files from before brush presets were stored get the built-in presets
+        if missing_presets {
+            Self::add_default_presets(&core);
+        }
+
         let db = AnimationDb {
             core:   core,
         };
@@ -83,6 +94,19 @@ impl AnimationDb {
         db
     }
 
+    ///
+    /// Adds the built-in brush presets to the animation (these aren't edits so they're not in the edit log)
+    /// 
+    fn add_default_presets(core: &Desync<AnimationDbCore<FloSqlite>>) {
+        core.sync(|core| -> Result<()> {
+            for preset in BrushPreset::default_presets() {
+                core.perform_edit(AnimationEdit::SetBrushPreset(preset))?;
+            }
+
+            Ok(())
+        }).unwrap();
+    }
+
     ///
     /// If there has been an error, retrieves what it is and clears the condition
     /// 
@@ -31,13 +31,43 @@ impl AnimationDb {
         }))
     }
 
+    ///
+    /// Interprets a motion entry as a scale motion
+    /// 
+    fn get_scale_motion<TFile: FloFile>(core: &mut TFile, motion_id: i64, entry: MotionEntry) -> Result<Motion> {
+        // The origin is the point that the element is scaled around, and the path gives the scale factors
+        let origin      = entry.origin.unwrap_or((0.0, 0.0));
+        let motion_path = Self::get_motion_path(core, motion_id, MotionPathType::Position)?;
+
+        Ok(Motion::Scale(ScaleMotion {
+            origin: origin,
+            scale:  motion_path
+        }))
+    }
+
+    ///
+    /// Interprets a motion entry as a rotate motion
+    /// 
+    fn get_rotate_motion<TFile: FloFile>(core: &mut TFile, motion_id: i64, entry: MotionEntry) -> Result<Motion> {
+        // The origin is the point that the element rotates around, and the path gives the angle
+        let origin      = entry.origin.unwrap_or((0.0, 0.0));
+        let motion_path = Self::get_motion_path(core, motion_id, MotionPathType::Position)?;
+
+        Ok(Motion::Rotate(RotateMotion {
+            origin: origin,
+            rotate: motion_path
+        }))
+    }
+
     ///
     /// Turns a motion entry into a motion
     /// 
     pub fn motion_for_entry<TFile: FloFile>(core: &mut TFile, motion_id: i64, motion_entry: MotionEntry) -> Result<Motion> {
         match motion_entry.motion_type {
             MotionType::None        => Ok(Motion::None),
-            MotionType::Translate   => Ok(Self::get_translate_motion(core, motion_id, motion_entry)?)
+            MotionType::Translate   => Ok(Self::get_translate_motion(core, motion_id, motion_entry)?),
+            MotionType::Scale       => Ok(Self::get_scale_motion(core, motion_id, motion_entry)?),
+            MotionType::Rotate      => Ok(Self::get_rotate_motion(core, motion_id, motion_entry)?)
         }
     }
     
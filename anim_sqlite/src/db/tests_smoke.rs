@@ -278,7 +278,7 @@ fn smoke_push_brush_type() {
 fn smoke_push_ink_brush() {
     test_updates(vec![
         DatabaseUpdate::PushBrushType(BrushDefinitionType::Ink),
-        DatabaseUpdate::PushInkBrush(1.0, 2.0, 3.0),
+        DatabaseUpdate::PushInkBrush(1.0, 2.0, 3.0, None),
         DatabaseUpdate::Pop
     ])
 }
@@ -336,7 +336,7 @@ fn smoke_editlog_element_id() {
         DatabaseUpdate::PushEditType(EditLogType::LayerPaintSelectBrush),
         DatabaseUpdate::PushEditLogElementId(3),
         DatabaseUpdate::PushBrushType(BrushDefinitionType::Ink),
-        DatabaseUpdate::PushInkBrush(1.0, 2.0, 3.0),
+        DatabaseUpdate::PushInkBrush(1.0, 2.0, 3.0, None),
         DatabaseUpdate::PopEditLogBrush(DrawingStyleType::Erase)
     ])
 }
@@ -346,7 +346,7 @@ fn smoke_editlog_brush() {
     test_updates(vec![
         DatabaseUpdate::PushEditType(EditLogType::LayerPaintSelectBrush),
         DatabaseUpdate::PushBrushType(BrushDefinitionType::Ink),
-        DatabaseUpdate::PushInkBrush(1.0, 2.0, 3.0),
+        DatabaseUpdate::PushInkBrush(1.0, 2.0, 3.0, None),
         DatabaseUpdate::PopEditLogBrush(DrawingStyleType::Erase)
     ])
 }
@@ -461,7 +461,7 @@ fn smoke_pop_vector_brush_element() {
         DatabaseUpdate::PushNearestKeyFrame(Duration::from_millis(2000)),
         DatabaseUpdate::PushVectorElementType(VectorElementType::BrushDefinition, Duration::from_millis(2500)),
         DatabaseUpdate::PushBrushType(BrushDefinitionType::Ink),
-        DatabaseUpdate::PushInkBrush(1.0, 2.0, 3.0),
+        DatabaseUpdate::PushInkBrush(1.0, 2.0, 3.0, None),
         DatabaseUpdate::PopVectorBrushElement(DrawingStyleType::Draw),
         DatabaseUpdate::Pop,
         DatabaseUpdate::Pop
@@ -495,7 +495,7 @@ fn smoke_pop_brush_points() {
         DatabaseUpdate::PushLayerForAssignedId(24),
         DatabaseUpdate::PushNearestKeyFrame(Duration::from_millis(2000)),
         DatabaseUpdate::PushVectorElementType(VectorElementType::BrushStroke, Duration::from_millis(2500)),
-        DatabaseUpdate::PopBrushPoints(Arc::new(vec![BrushPoint { position: (10.0, 5.0), cp1: (20.0, 20.0), cp2: (30.0, 30.0), width: 10.0 }])),
+        DatabaseUpdate::PopBrushPoints(Arc::new(vec![BrushPoint { position: (10.0, 5.0), cp1: (20.0, 20.0), cp2: (30.0, 30.0), width: 10.0, angle: 0.0 }])),
         DatabaseUpdate::Pop,
         DatabaseUpdate::Pop
     ])
@@ -0,0 +1,278 @@
+use super::gradient::*;
+use super::super::traits::*;
+
+use curves::*;
+use curves::bezier;
+use canvas::*;
+
+use std::f32;
+use std::iter;
+
+// Minimum distance between points to use to fit to a curve
+const MIN_DISTANCE: f64 = 2.0;
+
+// Tilt (in degrees) at which the direction of the pen fully determines the nib angle
+const FULL_TILT: f32 = 30.0;
+
+// Tilt (in degrees) below which the pen is treated as upright (the tilt direction is unreliable for upright pens)
+const MIN_TILT: f32 = 1.0;
+
+// Distance between the samples used to generate the outline of the stroke
+const SAMPLE_DISTANCE: f64 = 2.0;
+
+///
+/// The calligraphy brush draws a stroke using a flat nib, which is turned by the tilt of the pen
+///
+pub struct CalligraphyBrush {
+    /// The blend mode that this brush will use
+    blend_mode: BlendMode,
+
+    /// The definition for this brush
+    definition: CalligraphyDefinition
+}
+
+impl CalligraphyBrush {
+    ///
+    /// Creates a new calligraphy brush
+    ///
+    pub fn new(definition: &CalligraphyDefinition, drawing_style: BrushDrawingStyle) -> CalligraphyBrush {
+        use BrushDrawingStyle::*;
+
+        let blend_mode = match drawing_style {
+            Draw    => BlendMode::SourceOver,
+            Erase   => BlendMode::DestinationOut
+        };
+
+        CalligraphyBrush {
+            blend_mode: blend_mode,
+            definition: definition.clone()
+        }
+    }
+
+    ///
+    /// Works out the width of the nib for a raw point (as a proportion of the full width)
+    ///
+    fn width_for_point(&self, point: &RawPoint) -> f32 {
+        let pressure = point.pressure.max(0.0).min(1.0);
+
+        1.0 - self.definition.pressure_sensitivity*(1.0-pressure)
+    }
+
+    ///
+    /// Works out the angle of the nib for a raw point
+    ///
+    fn angle_for_point(&self, point: &RawPoint) -> f32 {
+        let (tilt_x, tilt_y)    = point.tilt;
+        let tilt                = (tilt_x*tilt_x + tilt_y*tilt_y).sqrt();
+
+        if tilt < MIN_TILT {
+            // Upright pens (and mice) use the fixed nib angle
+            self.definition.nib_angle
+        } else {
+            // The nib is held across the direction the pen is leaning in
+            let pen_angle   = tilt_y.atan2(tilt_x) + f32::consts::PI/2.0;
+            let influence   = self.definition.tilt_sensitivity * (tilt/FULL_TILT).min(1.0);
+
+            interpolate_nib_angle(self.definition.nib_angle, pen_angle, influence)
+        }
+    }
+
+    ///
+    /// Samples a set of brush points, returning the position, width and nib angle at each sample
+    ///
+    fn sample_points(points: &[BrushPoint]) -> Vec<(Coord2, f32, f32)> {
+        let mut samples = vec![];
+        samples.push((Coord2(points[0].position.0 as f64, points[0].position.1 as f64), points[0].width, points[0].angle));
+
+        for (last, next) in points.iter().zip(points.iter().skip(1)) {
+            let curve = bezier::Curve::from_points(Coord2(last.position.0 as f64, last.position.1 as f64), Coord2(next.position.0 as f64, next.position.1 as f64),
+                Coord2(next.cp1.0 as f64, next.cp1.1 as f64), Coord2(next.cp2.0 as f64, next.cp2.1 as f64));
+            let num_samples = ((curve.estimate_length(1.0) / SAMPLE_DISTANCE).ceil() as usize).max(1).min(256);
+
+            for sample in 1..(num_samples+1) {
+                let t = (sample as f64) / (num_samples as f64);
+
+                samples.push((
+                    curve.point_at_pos(t),
+                    last.width + (next.width-last.width)*(t as f32),
+                    interpolate_nib_angle(last.angle, next.angle, t as f32)
+                ));
+            }
+        }
+
+        samples
+    }
+}
+
+impl Brush for CalligraphyBrush {
+    fn brush_points_for_raw_points(&self, points: &[RawPoint]) -> Vec<BrushPoint> {
+        // Nothing to draw if there are too few points in the brush stroke to fit a curve to
+        if points.len() <= 2 {
+            return vec![];
+        }
+
+        // Pick points that are a certain distance apart to use for fitting, keeping the raw point they came from
+        let mut distant_points  = vec![&points[0]];
+        let mut last_point      = Coord2(points[0].position.0 as f64, points[0].position.1 as f64);
+
+        for point in points.iter().skip(1) {
+            let coord = Coord2(point.position.0 as f64, point.position.1 as f64);
+
+            if last_point.distance_to(&coord) >= MIN_DISTANCE {
+                last_point = coord;
+                distant_points.push(point);
+            }
+        }
+
+        let coords: Vec<_>  = distant_points.iter().map(|point| Coord2(point.position.0 as f64, point.position.1 as f64)).collect();
+        let widths: Vec<_>  = distant_points.iter().map(|point| self.width_for_point(point)).collect();
+        let angles: Vec<_>  = distant_points.iter().map(|point| self.angle_for_point(point)).collect();
+
+        // Fit these points to a curve
+        let curve = bezier::Curve::fit_from_points(&coords, 1.0);
+
+        // Turn into brush points, using the width and angle of the nearest input point to the end of each segment
+        let mut brush_points    = vec![];
+        let mut nearest         = 0;
+
+        if let Some(curve) = curve {
+            let start = curve[0].start_point();
+            brush_points.push(BrushPoint {
+                position:   (start.x() as f32, start.y() as f32),
+                cp1:        (0.0, 0.0),
+                cp2:        (0.0, 0.0),
+                width:      widths[0],
+                angle:      angles[0]
+            });
+
+            for segment in curve {
+                let end             = segment.end_point();
+                let (cp1, cp2)      = segment.control_points();
+
+                // Segments are in order along the stroke, so we only need to search forward for the nearest point
+                while nearest+1 < coords.len() && coords[nearest+1].distance_to(&end) <= coords[nearest].distance_to(&end) {
+                    nearest += 1;
+                }
+
+                brush_points.push(BrushPoint {
+                    position:   (end.x() as f32, end.y() as f32),
+                    cp1:        (cp1.x() as f32, cp1.y() as f32),
+                    cp2:        (cp2.x() as f32, cp2.y() as f32),
+                    width:      widths[nearest],
+                    angle:      angles[nearest]
+                });
+            }
+        }
+
+        brush_points
+    }
+
+    fn prepare_to_render<'a>(&'a self, properties: &BrushProperties) -> Box<dyn 'a+Iterator<Item=Draw>> {
+        Box::new(vec![
+            Draw::BlendMode(self.blend_mode),
+            Draw::FillColor(properties.color.with_alpha(properties.opacity))
+        ].into_iter())
+    }
+
+    fn render_brush<'a>(&'a self, properties: &'a BrushProperties, points: &'a Vec<BrushPoint>) -> Box<dyn 'a+Iterator<Item=Draw>> {
+        // Nothing to do if there are too few points
+        if points.len() < 2 {
+            return Box::new(iter::empty());
+        }
+
+        // The brush size sets the width of the nib, and the thickness scales along with it
+        let nib_width   = properties.size as f64;
+        let thickness   = nib_width * ((self.definition.nib_thickness / self.definition.nib_width) as f64);
+        let samples     = Self::sample_points(points);
+
+        // Work out the edges of the stroke swept out by the nib at each sample
+        let mut upper = vec![];
+        let mut lower = vec![];
+
+        for index in 0..samples.len() {
+            let (pos, width, angle) = samples[index];
+
+            // Direction of travel at this point
+            let before      = samples[if index > 0 { index-1 } else { 0 }].0;
+            let after       = samples[if index+1 < samples.len() { index+1 } else { index }].0;
+            let direction   = after - before;
+            let length      = direction.magnitude();
+            let direction   = if length > 0.0 { direction * (1.0/length) } else { Coord2(1.0, 0.0) };
+            let normal      = Coord2(-direction.y(), direction.x());
+
+            // The nib always points to the same side of the stroke so that the outline doesn't twist
+            let angle       = angle as f64;
+            let mut nib     = Coord2(angle.cos(), angle.sin()) * (nib_width*(width as f64)/2.0);
+            if direction.x()*nib.y() - direction.y()*nib.x() < 0.0 {
+                nib = nib * -1.0;
+            }
+
+            let edge        = nib + normal*(thickness/2.0);
+
+            upper.push(pos + edge);
+            lower.push(pos - edge);
+        }
+
+        // Draw the outline
+        let mut preamble = gradient_fill_for_stroke(properties, points);
+        preamble.extend(vec![
+            Draw::NewPath,
+            Draw::Move(upper[0].x() as f32, upper[0].y() as f32)
+        ]);
+
+        let outline = upper.into_iter().skip(1)
+            .chain(lower.into_iter().rev())
+            .map(|point| Draw::Line(point.x() as f32, point.y() as f32));
+
+        Box::new(preamble.into_iter()
+            .chain(outline)
+            .chain(vec![Draw::ClosePath, Draw::Fill]))
+    }
+
+    ///
+    /// Retrieves the definition for this brush
+    ///
+    fn to_definition(&self) -> (BrushDefinition, BrushDrawingStyle) {
+        let drawing_style = match self.blend_mode {
+            BlendMode::DestinationOut   => BrushDrawingStyle::Erase,
+            _                           => BrushDrawingStyle::Draw
+        };
+
+        (BrushDefinition::Calligraphy(self.definition.clone()), drawing_style)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stroke(tilt: (f32, f32)) -> Vec<RawPoint> {
+        (0..50).map(|x| RawPoint { position: ((x as f32)*4.0, 0.0), pressure: 1.0, tilt: tilt }).collect()
+    }
+
+    #[test]
+    fn untilted_stroke_uses_nib_angle() {
+        let brush   = CalligraphyBrush::new(&CalligraphyDefinition::default(), BrushDrawingStyle::Draw);
+        let points  = brush.brush_points_for_raw_points(&stroke((0.0, 0.0)));
+
+        assert!(points.len() >= 2);
+        assert!(points.iter().all(|point| (point.angle - CalligraphyDefinition::default().nib_angle).abs() < 0.001));
+    }
+
+    #[test]
+    fn tilt_turns_the_nib() {
+        let brush   = CalligraphyBrush::new(&CalligraphyDefinition::default(), BrushDrawingStyle::Draw);
+        let points  = brush.brush_points_for_raw_points(&stroke((45.0, 0.0)));
+
+        // Leaning the pen along the x axis holds the nib across it
+        assert!(points.len() >= 2);
+        assert!(points.iter().all(|point| (point.angle - f32::consts::PI/2.0).abs() < 0.001));
+    }
+
+    #[test]
+    fn interpolate_angle_across_half_turn() {
+        let angle = interpolate_nib_angle(0.1, f32::consts::PI-0.1, 0.5);
+
+        assert!(angle.abs() < 0.001);
+    }
+}
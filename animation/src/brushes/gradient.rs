@@ -0,0 +1,104 @@
+use super::super::traits::*;
+
+use canvas::*;
+
+use std::f32;
+
+///
+/// Returns the instructions that set up the gradient fill for a brush stroke
+///
+/// This is empty if the brush properties don't specify a gradient. Otherwise, a linear gradient
+/// runs from the first to the last point of the stroke and a radial gradient spreads out from
+/// the centre of its bounding box.
+///
+pub fn gradient_fill_for_stroke(properties: &BrushProperties, points: &Vec<BrushPoint>) -> Vec<Draw> {
+    // Nothing to do if there's no gradient or nothing to fill
+    let gradient = match properties.gradient {
+        Some(gradient)  => gradient,
+        None            => { return vec![]; }
+    };
+
+    if points.len() == 0 {
+        return vec![];
+    }
+
+    // Define the gradient
+    let mut draw = match gradient.gradient_type {
+        BrushGradientType::Linear => {
+            let start   = points[0].position;
+            let end     = points[points.len()-1].position;
+
+            vec![Draw::NewLinearGradient(start, end)]
+        },
+
+        BrushGradientType::Radial => {
+            let (min_x, min_y, max_x, max_y) = points.iter()
+                .fold((f32::MAX, f32::MAX, f32::MIN, f32::MIN), |(min_x, min_y, max_x, max_y), point| {
+                    let (x, y) = point.position;
+                    (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+                });
+
+            let center  = ((min_x+max_x)/2.0, (min_y+max_y)/2.0);
+            let (w, h)  = (max_x-min_x, max_y-min_y);
+            let radius  = (w*w + h*h).sqrt()/2.0 + properties.size/2.0;
+
+            vec![Draw::NewRadialGradient(center, 0.0, center, radius)]
+        }
+    };
+
+    // Blend from the brush colour to the end colour
+    draw.extend(vec![
+        Draw::GradientStop(0.0, properties.color.with_alpha(properties.opacity)),
+        Draw::GradientStop(1.0, gradient.end_color.with_alpha(properties.opacity)),
+        Draw::FillGradient
+    ]);
+
+    draw
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn points() -> Vec<BrushPoint> {
+        vec![(0.0, 0.0), (10.0, 0.0), (10.0, 20.0)].into_iter()
+            .map(|position| BrushPoint { position: position, cp1: position, cp2: position, width: 1.0, angle: 0.0 })
+            .collect()
+    }
+
+    #[test]
+    fn no_gradient_by_default() {
+        assert!(gradient_fill_for_stroke(&BrushProperties::new(), &points()).len() == 0);
+    }
+
+    #[test]
+    fn linear_gradient_follows_stroke() {
+        let mut properties  = BrushProperties::new();
+        properties.gradient = Some(BrushGradient { gradient_type: BrushGradientType::Linear, end_color: Color::Rgba(1.0, 0.0, 0.0, 1.0) });
+
+        let draw = gradient_fill_for_stroke(&properties, &points());
+
+        assert!(draw[0] == Draw::NewLinearGradient((0.0, 0.0), (10.0, 20.0)));
+        assert!(draw[1] == Draw::GradientStop(0.0, Color::Rgba(0.0, 0.0, 0.0, 1.0)));
+        assert!(draw[2] == Draw::GradientStop(1.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)));
+        assert!(draw[3] == Draw::FillGradient);
+    }
+
+    #[test]
+    fn radial_gradient_is_centered_on_stroke() {
+        let mut properties  = BrushProperties::new();
+        properties.gradient = Some(BrushGradient { gradient_type: BrushGradientType::Radial, end_color: Color::Rgba(1.0, 0.0, 0.0, 1.0) });
+
+        let draw = gradient_fill_for_stroke(&properties, &points());
+
+        match draw[0] {
+            Draw::NewRadialGradient(start, start_radius, end, _) => {
+                assert!(start == (5.0, 10.0));
+                assert!(end == (5.0, 10.0));
+                assert!(start_radius == 0.0);
+            },
+
+            _ => assert!(false)
+        }
+    }
+}
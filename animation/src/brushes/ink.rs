@@ -1,3 +1,4 @@
+use super::gradient::*;
 use super::super::traits::*;
 
 use std::iter;
@@ -14,6 +15,9 @@ const MIN_DISTANCE: f64 = 2.0;
 // (As we fit to a distance of 1.0, having a pressure of 0-1 means the curve fitter can use essentially any pressure)
 const INK_PRESSURE_SCALE: f64 = 50.0;
 
+// How much of the previous pressure is kept when working out the pressure from the velocity of the brush stroke
+const VELOCITY_SMOOTHING: f32 = 0.7;
+
 ///
 /// The ink brush draws a solid line with width based on pressure
 /// 
@@ -28,7 +32,13 @@ pub struct InkBrush {
     max_width: f32,
 
     // Distance to scale up at the start of the brush stroke
-    scale_up_distance: f32
+    scale_up_distance: f32,
+
+    /// Maps input pressures to the pressure used for the width of the brush stroke
+    pressure_curve: PressureCurve,
+
+    /// If set, the distance between points where the brush stroke reaches its minimum width
+    velocity_width: Option<f32>
 }
 
 impl InkBrush {
@@ -47,9 +57,44 @@ impl InkBrush {
             blend_mode:         blend_mode,
             min_width:          definition.min_width,
             max_width:          definition.max_width,
-            scale_up_distance:  definition.scale_up_distance
+            scale_up_distance:  definition.scale_up_distance,
+            pressure_curve:     definition.pressure_curve.clone(),
+            velocity_width:     definition.velocity_width
         }
     }
+
+    ///
+    /// Works out the pressure to use for each of a set of raw points
+    /// 
+    fn pressure_for_raw_points(&self, points: &[RawPoint]) -> Vec<f32> {
+        let input_pressure: Vec<f32> = match self.velocity_width {
+            None                    => points.iter().map(|point| point.pressure).collect(),
+
+            Some(velocity_width)    => {
+                // Faster movements produce thinner lines (smoothed so the width doesn't jitter with the input events)
+                let velocity_width  = velocity_width.max(0.001);
+                let mut last_pos    = points.first().map(|point| point.position).unwrap_or((0.0, 0.0));
+                let mut pressure    = 1.0;
+
+                points.iter()
+                    .map(|point| {
+                        let (dx, dy)    = (point.position.0-last_pos.0, point.position.1-last_pos.1);
+                        let distance    = (dx*dx + dy*dy).sqrt();
+                        let target      = 1.0 - (distance/velocity_width).min(1.0);
+
+                        pressure        = pressure*VELOCITY_SMOOTHING + target*(1.0-VELOCITY_SMOOTHING);
+                        last_pos        = point.position;
+
+                        pressure
+                    })
+                    .collect()
+            }
+        };
+
+        input_pressure.into_iter()
+            .map(|pressure| self.pressure_curve.pressure(pressure))
+            .collect()
+    }
 }
 
 ///
@@ -270,8 +315,11 @@ impl Brush for InkBrush {
             return vec![];
         }
 
-        // Convert points to ink points
-        let ink_points: Vec<_> = points.iter().map(|point| InkCoord::from(point)).collect();
+        // Convert points to ink points, using the pressure curve to work out the pressure at each point
+        let ink_points: Vec<_> = points.iter()
+            .zip(self.pressure_for_raw_points(points))
+            .map(|(point, pressure)| InkCoord::from(&RawPoint { position: point.position, tilt: point.tilt, pressure: pressure }))
+            .collect();
 
         // Average points that are very close together so we don't overdo 
         // the curve fitting
@@ -356,7 +404,8 @@ impl Brush for InkBrush {
                 position:   (start.x as f32, start.y as f32),
                 cp1:        (0.0, 0.0),
                 cp2:        (0.0, 0.0),
-                width:      (start.pressure/INK_PRESSURE_SCALE) as f32
+                width:      (start.pressure/INK_PRESSURE_SCALE) as f32,
+                angle:      0.0
             });
 
             // Convert the remaining curve segments
@@ -368,7 +417,8 @@ impl Brush for InkBrush {
                     position:   (end.x as f32, end.y as f32),
                     cp1:        (cp1.x as f32, cp1.y as f32),
                     cp2:        (cp2.x as f32, cp2.y as f32),
-                    width:      (end.pressure/INK_PRESSURE_SCALE) as f32
+                    width:      (end.pressure/INK_PRESSURE_SCALE) as f32,
+                    angle:      0.0
                 });
             }
         }
@@ -407,10 +457,11 @@ impl Brush for InkBrush {
 
         // Upper portion
         let Coord2(x, y) = upper_curves[0][0].start_point();
-        let preamble = vec![
+        let mut preamble = gradient_fill_for_stroke(properties, points);
+        preamble.extend(vec![
             Draw::NewPath,
             Draw::Move(x as f32, y as f32)
-        ];
+        ]);
 
         let upper_curves = upper_curves.into_iter()
             .flat_map(|curve_list|  curve_list.into_iter())
@@ -450,7 +501,9 @@ impl Brush for InkBrush {
         let definition = BrushDefinition::Ink(InkDefinition {
             min_width:          self.min_width,
             max_width:          self.max_width,
-            scale_up_distance:  self.scale_up_distance
+            scale_up_distance:  self.scale_up_distance,
+            pressure_curve:     self.pressure_curve.clone(),
+            velocity_width:     self.velocity_width
         });
         
         let drawing_style = match self.blend_mode {
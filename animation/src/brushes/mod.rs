@@ -1,10 +1,14 @@
 mod simple;
 mod ink;
+mod calligraphy;
 mod preview;
+mod gradient;
 
 pub use self::simple::*;
 pub use self::ink::*;
+pub use self::calligraphy::*;
 pub use self::preview::*;
+pub use self::gradient::*;
 
 use super::traits::*;
 use std::sync::*;
@@ -16,7 +20,8 @@ pub fn create_brush_from_definition(definition: &BrushDefinition, drawing_style:
     use BrushDefinition::*;
 
     match definition {
-        &Simple                     => Arc::new(SimpleBrush::new()),
-        &Ink(ref ink_definition)    => Arc::new(InkBrush::new(ink_definition, drawing_style))
+        &Simple                             => Arc::new(SimpleBrush::new()),
+        &Ink(ref ink_definition)            => Arc::new(InkBrush::new(ink_definition, drawing_style)),
+        &Calligraphy(ref calligraphy_defn)  => Arc::new(CalligraphyBrush::new(calligraphy_defn, drawing_style))
     }
 }
@@ -66,7 +66,8 @@ impl Brush for SimpleBrush {
                 position:   (start.x() as f32, start.y() as f32),
                 cp1:        (0.0, 0.0),
                 cp2:        (0.0, 0.0),
-                width:      0.0
+                width:      0.0,
+                angle:      0.0
             });
 
             // Convert the remaining curve segments
@@ -78,7 +79,8 @@ impl Brush for SimpleBrush {
                     position:   (end.x() as f32, end.y() as f32),
                     cp1:        (cp1.x() as f32, cp1.y() as f32),
                     cp2:        (cp2.x() as f32, cp2.y() as f32),
-                    width:      1.0
+                    width:      1.0,
+                    angle:      0.0
                 });
             }
         }
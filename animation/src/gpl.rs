@@ -0,0 +1,127 @@
+//!
+//! Reads and writes GIMP palette (`.gpl`) files
+//!
+//! A GIMP palette is a text file starting with a `GIMP Palette` header, followed by some optional
+//! `Name:` and `Columns:` lines, `#` comments and then one line per colour of the form `R G B Name`
+//! where the components are integers from 0-255.
+//!
+
+use super::traits::*;
+
+use canvas::*;
+
+use std::io;
+use std::io::{BufRead, Write};
+
+///
+/// Reads the swatches from a GIMP palette file
+/// 
+/// Colours without a name are named after their hex value
+/// 
+pub fn read_gpl<Input: BufRead>(input: Input) -> io::Result<Vec<Swatch>> {
+    let mut lines       = input.lines();
+    let mut swatches    = vec![];
+
+    // The first line identifies the file as a GIMP palette
+    let header = lines.next().unwrap_or_else(|| Ok(String::new()))?;
+    if header.trim() != "GIMP Palette" {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a GIMP palette file"));
+    }
+
+    for line in lines {
+        let line = line?;
+        let line = line.trim();
+
+        // Skip blank lines, comments and the header fields
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Name:") || line.starts_with("Columns:") {
+            continue;
+        }
+
+        // Colour lines are 'R G B Name'
+        let mut fields  = line.split_whitespace();
+        let mut rgb     = vec![];
+
+        for _ in 0..3 {
+            let component = fields.next().and_then(|field| field.parse::<u8>().ok());
+            let component = component.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("Bad colour in GIMP palette: '{}'", line)))?;
+
+            rgb.push(component);
+        }
+
+        let name    = fields.collect::<Vec<_>>().join(" ");
+        let name    = if name.is_empty() { format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2]) } else { name };
+        let color   = Color::Rgba((rgb[0] as f32)/255.0, (rgb[1] as f32)/255.0, (rgb[2] as f32)/255.0, 1.0);
+
+        swatches.push(Swatch::new(&name, color));
+    }
+
+    Ok(swatches)
+}
+
+///
+/// Writes a set of swatches as a GIMP palette file
+/// 
+/// Alpha values are not stored in GIMP palettes so are discarded
+/// 
+pub fn write_gpl<Output: Write>(palette_name: &str, swatches: &[Swatch], output: &mut Output) -> io::Result<()> {
+    writeln!(output, "GIMP Palette")?;
+    writeln!(output, "Name: {}", palette_name)?;
+    writeln!(output, "#")?;
+
+    for swatch in swatches.iter() {
+        let (r, g, b, _a)   = swatch.color.to_rgba_components();
+        let to_byte         = |component: f32| (component.max(0.0).min(1.0) * 255.0).round() as u8;
+
+        writeln!(output, "{:3} {:3} {:3}\t{}", to_byte(r), to_byte(g), to_byte(b), swatch.name)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::io::Cursor;
+
+    #[test]
+    fn read_simple_palette() {
+        let palette = "GIMP Palette\nName: Test\nColumns: 4\n#\n255   0   0\tRed\n  0 128 255 Sky blue\n\n 0 0 0\n";
+        let swatches = read_gpl(Cursor::new(palette)).unwrap();
+
+        assert!(swatches.len() == 3);
+        assert!(swatches[0] == Swatch::new("Red", Color::Rgba(1.0, 0.0, 0.0, 1.0)));
+        assert!(swatches[1].name == "Sky blue");
+        assert!(swatches[2].name == "#000000");
+    }
+
+    #[test]
+    fn reject_missing_header() {
+        assert!(read_gpl(Cursor::new("255 0 0 Red\n")).is_err());
+    }
+
+    #[test]
+    fn reject_bad_colour() {
+        assert!(read_gpl(Cursor::new("GIMP Palette\n255 zero 0 Red\n")).is_err());
+    }
+
+    #[test]
+    fn write_and_read_palette() {
+        let swatches = vec![
+            Swatch::new("Red", Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            Swatch::new("Dark grey", Color::Rgba(0.2, 0.2, 0.2, 1.0))
+        ];
+
+        let mut output = vec![];
+        write_gpl("Test", &swatches, &mut output).unwrap();
+
+        let read_back = read_gpl(Cursor::new(output)).unwrap();
+
+        assert!(read_back.len() == 2);
+        assert!(read_back[0] == swatches[0]);
+        assert!(read_back[1].name == "Dark grey");
+
+        let (r, g, b, _) = read_back[1].color.to_rgba_components();
+        assert!((r-0.2).abs() < 0.01 && (g-0.2).abs() < 0.01 && (b-0.2).abs() < 0.01);
+    }
+}
@@ -31,7 +31,9 @@ impl InMemoryAnimation {
             next_element_id:        0,
             vector_layers:          HashMap::new(),
             motions:                HashMap::new(),
-            motions_for_element:    HashMap::new()
+            motions_for_element:    HashMap::new(),
+            brush_presets:          BrushPreset::default_presets(),
+            palette:                vec![]
         };
 
         // Create the final animation
@@ -101,6 +103,14 @@ impl Animation for InMemoryAnimation {
         self.core.lock().unwrap().edit_log.len()
     }
 
+    fn brush_presets(&self) -> Vec<BrushPreset> {
+        self.core.lock().unwrap().brush_presets.clone()
+    }
+
+    fn palette(&self) -> Vec<Swatch> {
+        self.core.lock().unwrap().palette.clone()
+    }
+
     fn read_edit_log<'a>(&'a self, range: Range<usize>) -> Box<dyn 'a+Stream<Item=AnimationEdit, Error=()>> {
         let core        = self.core.lock().unwrap();
         let log_items   = range.into_iter().map(move |index| core.edit_log[index].clone());
@@ -184,6 +194,7 @@ mod test {
     use super::*;
     use std::time::Duration;
     use futures::executor;
+    use canvas::*;
 
     #[test]
     fn can_set_size() {
@@ -240,6 +251,47 @@ mod test {
         assert!(ids == vec![keep1, keep2, keep3]);
     }
 
+    #[test]
+    fn starts_with_default_brush_presets() {
+        let animation = InMemoryAnimation::new();
+
+        assert!(animation.brush_presets() == BrushPreset::default_presets());
+    }
+
+    #[test]
+    fn can_replace_and_remove_brush_presets() {
+        let animation   = InMemoryAnimation::new();
+        let mut thick   = BrushProperties::new();
+        thick.size      = 30.0;
+
+        animation.perform_edits(vec![
+            AnimationEdit::SetBrushPreset(BrushPreset::new("Ink", BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw, thick)),
+            AnimationEdit::SetBrushPreset(BrushPreset::new("Nib", BrushDefinition::Calligraphy(CalligraphyDefinition::default()), BrushDrawingStyle::Draw, BrushProperties::new())),
+            AnimationEdit::RemoveBrushPreset("Eraser".to_string())
+        ]);
+
+        let presets = animation.brush_presets();
+
+        assert!(presets.len() == 2);
+        assert!(presets[0].name == "Ink");
+        assert!(presets[0].properties.size == 30.0);
+        assert!(presets[1].name == "Nib");
+    }
+
+    #[test]
+    fn can_replace_and_remove_swatches() {
+        let animation = InMemoryAnimation::new();
+
+        animation.perform_edits(vec![
+            AnimationEdit::SetSwatch(Swatch::new("Sky", Color::Rgba(0.5, 0.7, 1.0, 1.0))),
+            AnimationEdit::SetSwatch(Swatch::new("Grass", Color::Rgba(0.2, 0.8, 0.2, 1.0))),
+            AnimationEdit::SetSwatch(Swatch::new("Sky", Color::Rgba(0.2, 0.4, 1.0, 1.0))),
+            AnimationEdit::RemoveSwatch("Grass".to_string())
+        ]);
+
+        assert!(animation.palette() == vec![Swatch::new("Sky", Color::Rgba(0.2, 0.4, 1.0, 1.0))]);
+    }
+
     #[test]
     fn will_assign_element_ids() {
         let animation = InMemoryAnimation::new();
@@ -407,4 +459,71 @@ mod test {
 
         assert!(animation.motion().get_motions_for_element(ElementId::Assigned(10)) == vec![ElementId::Assigned(0), ElementId::Assigned(1), ElementId::Assigned(3), ElementId::Assigned(4)])
     }
+
+    #[test]
+    fn can_erase_through_brush_stroke() {
+        let animation   = InMemoryAnimation::new();
+        let points      = vec![
+            BrushPoint { position: (0.0, 0.0), cp1: (0.0, 0.0), cp2: (0.0, 0.0), width: 0.5, angle: 0.0 },
+            BrushPoint { position: (200.0, 0.0), cp1: (66.0, 0.0), cp2: (133.0, 0.0), width: 0.5, angle: 0.0 }
+        ];
+
+        animation.perform_edits(vec![
+            AnimationEdit::AddNewLayer(0),
+            AnimationEdit::Layer(0, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushPoints(ElementId::Assigned(10), Arc::new(points))))
+        ]);
+
+        let erase = EraseEditAction::ErasePath(0, Duration::from_millis(0), Arc::new(vec![(100.0, -10.0), (100.0, 10.0)]), 5.0);
+        animation.perform_edits(erase.to_animation_edits(&animation));
+
+        let layer               = animation.get_layer_with_id(0).unwrap();
+        let frame               = layer.get_frame_at_time(Duration::from_millis(0));
+        let strokes: Vec<_>     = frame.vector_elements().unwrap()
+            .filter_map(|element| match element {
+                Vector::BrushStroke(brush_stroke)   => Some(brush_stroke),
+                _                                   => None
+            })
+            .collect();
+
+        assert!(strokes.len() == 2);
+        assert!(strokes[0].id() == ElementId::Assigned(10));
+        assert!((strokes[0].points().last().unwrap().position.0 - 95.0).abs() < 0.1);
+        assert!((strokes[1].points()[0].position.0 - 105.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn erased_pieces_keep_their_place_in_the_frame() {
+        let animation   = InMemoryAnimation::new();
+        let stroke      = |y| Arc::new(vec![
+            BrushPoint { position: (0.0, y), cp1: (0.0, y), cp2: (0.0, y), width: 0.5, angle: 0.0 },
+            BrushPoint { position: (300.0, y), cp1: (100.0, y), cp2: (200.0, y), width: 0.5, angle: 0.0 }
+        ]);
+
+        animation.perform_edits(vec![
+            AnimationEdit::AddNewLayer(0),
+            AnimationEdit::Layer(0, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushPoints(ElementId::Assigned(10), stroke(0.0)))),
+            AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushPoints(ElementId::Assigned(11), stroke(50.0))))
+        ]);
+
+        // Split the lower stroke into three pieces
+        let erase = EraseEditAction::ErasePath(0, Duration::from_millis(0), Arc::new(vec![(100.0, -10.0), (100.0, 10.0), (200.0, 10.0), (200.0, -10.0)]), 5.0);
+        animation.perform_edits(erase.to_animation_edits(&animation));
+
+        let layer               = animation.get_layer_with_id(0).unwrap();
+        let frame               = layer.get_frame_at_time(Duration::from_millis(0));
+        let strokes: Vec<_>     = frame.vector_elements().unwrap()
+            .filter_map(|element| match element {
+                Vector::BrushStroke(brush_stroke)   => Some(brush_stroke),
+                _                                   => None
+            })
+            .collect();
+
+        assert!(strokes.len() == 4);
+        assert!(strokes[0].id() == ElementId::Assigned(10));
+        assert!((strokes[1].points()[0].position.0 - 105.0).abs() < 0.1);
+        assert!((strokes[2].points()[0].position.0 - 205.0).abs() < 0.1);
+        assert!(strokes[3].id() == ElementId::Assigned(11));
+    }
 }
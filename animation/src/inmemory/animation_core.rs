@@ -23,7 +23,13 @@ pub struct AnimationCore {
     pub motions: HashMap<ElementId, Motion>,
 
     /// Maps element IDs to the attached motions
-    pub motions_for_element: HashMap<ElementId, Vec<ElementId>>
+    pub motions_for_element: HashMap<ElementId, Vec<ElementId>>,
+
+    /// The brush presets stored in this animation
+    pub brush_presets: Vec<BrushPreset>,
+
+    /// The swatches in the colour palette for this animation
+    pub palette: Vec<Swatch>
 }
 
 impl AnimationCore {
@@ -60,6 +66,34 @@ impl AnimationCore {
                 // We don't know which layer owns the element, so we just tell all of them to perform the edit (layers without the element will ignore the instruction)
                 self.vector_layers.values()
                     .for_each(move |layer| layer.edit_element(*element_id, *when, element_edit));
+            },
+
+            SetBrushPreset(preset) => {
+                let existing = self.brush_presets.iter_mut().find(|existing| existing.name == preset.name);
+
+                if let Some(existing) = existing {
+                    *existing = preset.clone();
+                } else {
+                    self.brush_presets.push(preset.clone());
+                }
+            },
+
+            RemoveBrushPreset(name) => {
+                self.brush_presets.retain(|preset| &preset.name != name);
+            },
+
+            SetSwatch(swatch) => {
+                let existing = self.palette.iter_mut().find(|existing| existing.name == swatch.name);
+
+                if let Some(existing) = existing {
+                    *existing = swatch.clone();
+                } else {
+                    self.palette.push(swatch.clone());
+                }
+            },
+
+            RemoveSwatch(name) => {
+                self.palette.retain(|swatch| &swatch.name != name);
             }
         }
     }
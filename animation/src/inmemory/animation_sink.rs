@@ -73,6 +73,12 @@ impl AnimationSink {
                 Layer(layer_id, Paint(when, BrushStroke(ElementId::Unassigned, points))) =>
                     Layer(layer_id, Paint(when, BrushStroke(ElementId::Assigned(assign_id()), points))),
 
+                Layer(layer_id, Paint(when, BrushPoints(ElementId::Unassigned, points))) =>
+                    Layer(layer_id, Paint(when, BrushPoints(ElementId::Assigned(assign_id()), points))),
+
+                Element(element_id, when, ElementEdit::AddBrushPointsAbove(ElementId::Unassigned, points)) =>
+                    Element(element_id, when, ElementEdit::AddBrushPointsAbove(ElementId::Assigned(assign_id()), points)),
+
                 other => other
             };
 
@@ -36,6 +36,27 @@ impl VectorKeyFrame {
         self.core.lock().unwrap().add_element(when, new_element);
     }
 
+    ///
+    /// Adds a new element directly above an existing element (or to the front if the existing element can't be found)
+    /// 
+    pub fn add_element_above(&self, existing_element_id: ElementId, new_element: Vector) {
+        self.core.lock().unwrap().add_element_above(existing_element_id, new_element);
+    }
+
+    ///
+    /// Removes the element with the specified ID from this keyframe
+    /// 
+    pub fn remove_element(&self, element_id: ElementId) {
+        self.core.lock().unwrap().remove_element(element_id);
+    }
+
+    ///
+    /// Replaces the element with the specified ID with the result of a function
+    /// 
+    pub fn replace_element<ReplaceFn: FnOnce(&Vector) -> Vector>(&self, element_id: ElementId, replace: ReplaceFn) {
+        self.core.lock().unwrap().replace_element(element_id, replace);
+    }
+
     ///
     /// Retrieves the elements in this keyframe
     /// 
@@ -118,4 +139,50 @@ impl VectorKeyFrameCore {
         self.active_properties = new_element.update_properties(Arc::clone(&self.active_properties));
         self.elements.push((when, new_element));
     }
+
+    ///
+    /// Adds a new element directly above an existing element (so it's drawn with the same properties)
+    /// 
+    pub fn add_element_above(&mut self, existing_element_id: ElementId, new_element: Vector) {
+        match self.elements.iter().position(|&(_, ref element)| element.id() == existing_element_id) {
+            Some(index) => {
+                let when = self.elements[index].0;
+                self.elements.insert(index+1, (when, new_element));
+                self.update_active_properties();
+            },
+
+            None        => {
+                let when = self.start_time;
+                self.add_element(when, new_element);
+            }
+        }
+    }
+
+    ///
+    /// Removes an element from this keyframe
+    /// 
+    pub fn remove_element(&mut self, element_id: ElementId) {
+        self.elements.retain(|&(_, ref element)| element.id() != element_id);
+        self.update_active_properties();
+    }
+
+    ///
+    /// Replaces an element in this keyframe
+    /// 
+    pub fn replace_element<ReplaceFn: FnOnce(&Vector) -> Vector>(&mut self, element_id: ElementId, replace: ReplaceFn) {
+        if let Some(index) = self.elements.iter().position(|&(_, ref element)| element.id() == element_id) {
+            let new_element         = replace(&self.elements[index].1);
+            self.elements[index].1  = new_element;
+
+            self.update_active_properties();
+        }
+    }
+
+    ///
+    /// Recalculates the active properties from the elements in this keyframe
+    /// 
+    fn update_active_properties(&mut self) {
+        self.active_properties = self.elements.iter()
+            .fold(Arc::new(VectorProperties::default()), |properties, &(_, ref element)| element.update_properties(properties));
+    }
 }
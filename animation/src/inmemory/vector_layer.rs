@@ -38,8 +38,8 @@ impl InMemoryVectorLayer {
     ///
     /// Performs an edit on an element contained within this animation
     /// 
-    pub fn edit_element(&self, element_id: ElementId, when: Duration, edit: &ElementEdit) {
-        // TODO
+    pub fn edit_element(&self, element_id: ElementId, _when: Duration, edit: &ElementEdit) {
+        self.core.lock().unwrap().edit_element(element_id, edit);
     }
 }
 
@@ -68,6 +68,42 @@ impl VectorLayerCore {
                 let brush_stroke    = Vector::new(BrushElement::new(*id, Arc::new(brush_points)));
 
                 self.add_element(when, brush_stroke);
+            },
+
+            BrushPoints(id, brush_points)           => {
+                let brush_stroke    = Vector::new(BrushElement::new(*id, Arc::clone(brush_points)));
+
+                self.add_element(when, brush_stroke);
+            }
+        }
+    }
+
+    ///
+    /// Performs an edit on an element in this layer (has no effect if the element is not in this layer)
+    /// 
+    pub fn edit_element(&mut self, element_id: ElementId, edit: &ElementEdit) {
+        use self::ElementEdit::*;
+
+        // Element IDs are unique, so the element can only be in one of the keyframes
+        let keyframe = self.keyframes.iter()
+            .filter(|keyframe| keyframe.element_with_id(element_id).is_some())
+            .nth(0);
+
+        if let Some(keyframe) = keyframe {
+            match edit {
+                Delete                  => keyframe.remove_element(element_id),
+
+                SetBrushPoints(points)  => keyframe.replace_element(element_id, |element| {
+                    match element {
+                        Vector::BrushStroke(_)  => Vector::new(BrushElement::new(element_id, Arc::clone(points))),
+                        other                   => other.clone()
+                    }
+                }),
+
+                AddBrushPointsAbove(new_element_id, points) => {
+                    let new_element = Vector::new(BrushElement::new(*new_element_id, Arc::clone(points)));
+                    keyframe.add_element_above(element_id, new_element);
+                }
             }
         }
     }
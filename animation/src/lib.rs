@@ -17,6 +17,7 @@ extern crate itertools;
 mod traits;
 pub mod inmemory;
 pub mod brushes;
+pub mod gpl;
 
 mod deref_map;
 
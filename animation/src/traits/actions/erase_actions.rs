@@ -0,0 +1,337 @@
+use super::edit_action::*;
+use super::super::edit::*;
+use super::super::brush::*;
+use super::super::vector::*;
+use super::super::animation::*;
+
+use curves::*;
+use curves::bezier::*;
+use itertools::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+/// Number of bisection steps used to find where the eraser crosses a curve
+const EDGE_REFINE_STEPS: usize = 10;
+
+/// Pieces of a brush stroke shorter than this are removed rather than being left behind after erasing
+const MIN_PIECE_LENGTH: f64 = 0.5;
+
+///
+/// Edit actions that remove parts of the elements in a frame
+///
+pub enum EraseEditAction {
+    /// Erases the parts of the brush strokes in a layer that lie within a radius of a path
+    ///
+    /// This is the action performed by the vector eraser: brush strokes that are completely covered
+    /// by the eraser are deleted, strokes with an erased end are trimmed, and strokes erased through the
+    /// middle are split. The first surviving piece of a split stroke keeps its original element, while
+    /// the remaining pieces are added as new brush strokes directly above it, so they keep the same
+    /// place in the frame and are drawn with the same brush.
+    ///
+    /// Only the centreline of each brush stroke is considered. Elements that have motions attached are
+    /// left alone, as the path is in frame coordinates and won't match the element's own coordinates.
+    ErasePath(u64, Duration, Arc<Vec<(f32, f32)>>, f32)
+}
+
+impl EditAction for EraseEditAction {
+    ///
+    /// Converts this edit action into a set of animation edits for a particular animation
+    ///
+    fn to_animation_edits<Anim: Animation>(&self, animation: &Anim) -> Vec<AnimationEdit> {
+        use self::EraseEditAction::*;
+
+        match self {
+            ErasePath(layer_id, when, path, radius) => erase_path_edit(animation, *layer_id, *when, path, *radius)
+        }
+    }
+}
+
+///
+/// Generates the edits required to erase along a path in a particular layer
+///
+fn erase_path_edit<Anim: Animation>(animation: &Anim, layer_id: u64, when: Duration, path: &Vec<(f32, f32)>, radius: f32) -> Vec<AnimationEdit> {
+    if path.len() == 0 { return vec![]; }
+
+    // Fetch the frame that is being erased from
+    let frame = match animation.get_layer_with_id(layer_id) {
+        Some(layer) => layer.get_frame_at_time(when),
+        None        => return vec![]
+    };
+    let elements = frame.vector_elements()
+        .map(|elements| elements.collect::<Vec<_>>())
+        .unwrap_or(vec![]);
+
+    // Erase from each brush stroke in turn
+    let mut edits = vec![];
+
+    for element in elements {
+        if let Vector::BrushStroke(ref brush_stroke) = element {
+            let element_id = brush_stroke.id();
+
+            if element_id.is_unassigned()                                               { continue; }
+            if animation.motion().get_motions_for_element(element_id).len() > 0     { continue; }
+
+            if let Some(pieces) = erase_brush_points(&*brush_stroke.points(), path, radius) {
+                let mut pieces = pieces.into_iter();
+
+                match pieces.next() {
+                    None        => edits.push(AnimationEdit::Element(element_id, when, ElementEdit::Delete)),
+                    Some(first) => {
+                        edits.push(AnimationEdit::Element(element_id, when, ElementEdit::SetBrushPoints(Arc::new(first))));
+
+                        // Each piece goes directly above the original, so adding them in reverse keeps them in order
+                        let remaining = pieces.collect::<Vec<_>>();
+                        edits.extend(remaining.into_iter().rev()
+                            .map(|piece| AnimationEdit::Element(element_id, when, ElementEdit::AddBrushPointsAbove(ElementId::Unassigned, Arc::new(piece)))));
+                    }
+                }
+            }
+        }
+    }
+
+    edits
+}
+
+///
+/// Returns the distance from a point to the closest point on a path
+///
+fn distance_to_path(point: &Coord2, path: &[(f32, f32)]) -> f64 {
+    if path.len() == 1 {
+        return point.distance_to(&Coord2(path[0].0 as f64, path[0].1 as f64));
+    }
+
+    path.iter()
+        .tuple_windows()
+        .map(|(start, end)| {
+            let start   = Coord2(start.0 as f64, start.1 as f64);
+            let end     = Coord2(end.0 as f64, end.1 as f64);
+            let line    = end - start;
+            let len_sq  = line.dot(&line);
+
+            if len_sq <= 0.0 {
+                point.distance_to(&start)
+            } else {
+                let t = ((*point - start).dot(&line) / len_sq).max(0.0).min(1.0);
+                point.distance_to(&(start + line*t))
+            }
+        })
+        .fold(f64::MAX, |a, b| a.min(b))
+}
+
+///
+/// Finds the t value where a curve crosses the edge of the eraser, given two t values on either side of the edge
+///
+fn find_eraser_edge(curve: &Curve<Coord2>, mut from_t: f64, mut to_t: f64, path: &[(f32, f32)], radius: f64) -> f64 {
+    let from_erased = distance_to_path(&curve.point_at_pos(from_t), path) <= radius;
+
+    for _ in 0..EDGE_REFINE_STEPS {
+        let mid_t = (from_t + to_t) / 2.0;
+
+        if (distance_to_path(&curve.point_at_pos(mid_t), path) <= radius) == from_erased {
+            from_t = mid_t;
+        } else {
+            to_t = mid_t;
+        }
+    }
+
+    (from_t + to_t) / 2.0
+}
+
+///
+/// Creates the brush point for the section of a curve between two t values
+///
+fn section_brush_point(curve: &Curve<Coord2>, start_t: f64, end_t: f64, last: &BrushPoint, next: &BrushPoint) -> BrushPoint {
+    // Cut the curve down to the section between the two points
+    let section = if start_t > 0.0 { curve.subdivide(start_t).1 } else { curve.clone() };
+    let section = if start_t < 1.0 && end_t < 1.0 { section.subdivide((end_t - start_t) / (1.0 - start_t)).0 } else { section };
+
+    let (cp1, cp2)  = section.control_points();
+    let end_point   = section.end_point();
+    let width       = last.width + (next.width - last.width) * (end_t as f32);
+    let angle       = interpolate_nib_angle(last.angle, next.angle, end_t as f32);
+
+    BrushPoint {
+        position:   (end_point.x() as f32, end_point.y() as f32),
+        cp1:        (cp1.x() as f32, cp1.y() as f32),
+        cp2:        (cp2.x() as f32, cp2.y() as f32),
+        width:      width,
+        angle:      angle
+    }
+}
+
+///
+/// Creates the brush point that starts a new piece of a brush stroke
+///
+fn start_brush_point(curve: &Curve<Coord2>, t: f64, last: &BrushPoint, next: &BrushPoint) -> BrushPoint {
+    let position    = curve.point_at_pos(t);
+    let position    = (position.x() as f32, position.y() as f32);
+
+    BrushPoint {
+        position:   position,
+        cp1:        position,
+        cp2:        position,
+        width:      last.width + (next.width - last.width) * (t as f32),
+        angle:      interpolate_nib_angle(last.angle, next.angle, t as f32)
+    }
+}
+
+///
+/// Returns the length of the centreline of a set of brush points
+///
+fn brush_points_length(points: &[BrushPoint]) -> f64 {
+    points.iter()
+        .tuple_windows()
+        .map(|(last, next)| {
+            let curve = Curve::from_points(Coord2(last.position.0 as f64, last.position.1 as f64), Coord2(next.position.0 as f64, next.position.1 as f64),
+                Coord2(next.cp1.0 as f64, next.cp1.1 as f64), Coord2(next.cp2.0 as f64, next.cp2.1 as f64));
+            curve.estimate_length(1.0)
+        })
+        .sum()
+}
+
+///
+/// Erases the parts of a brush stroke that are within a radius of a path
+///
+/// Returns None if the eraser doesn't touch the brush stroke, or the pieces of the brush stroke that are
+/// left behind if it does (which will be empty if the brush stroke has been completely erased)
+///
+pub fn erase_brush_points(points: &[BrushPoint], path: &[(f32, f32)], radius: f32) -> Option<Vec<Vec<BrushPoint>>> {
+    if points.len() < 2 || path.len() == 0 { return None; }
+
+    let radius = radius.max(0.01) as f64;
+
+    // Quick rejection using the bounds of the control polygon, which always contains the curve
+    let (stroke_min, stroke_max) = points.iter().enumerate()
+        .flat_map(|(index, point)| if index == 0 { vec![point.position] } else { vec![point.position, point.cp1, point.cp2] })
+        .fold(((f32::MAX, f32::MAX), (f32::MIN, f32::MIN)), |(min, max), (x, y)| ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y))));
+    let (path_min, path_max) = path.iter()
+        .fold(((f32::MAX, f32::MAX), (f32::MIN, f32::MIN)), |(min, max), &(x, y)| ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y))));
+    let radius_f32 = radius as f32;
+
+    if stroke_max.0 < path_min.0-radius_f32 || stroke_min.0 > path_max.0+radius_f32
+        || stroke_max.1 < path_min.1-radius_f32 || stroke_min.1 > path_max.1+radius_f32 {
+        return None;
+    }
+
+    // Walk the curve sections, building up the pieces that lie outside of the eraser
+    let is_erased       = |point: &Coord2| distance_to_path(point, path) <= radius;
+    let mut pieces      = vec![];
+    let mut current     = None;
+    let mut erased_any  = false;
+
+    for (last, next) in points.iter().tuple_windows() {
+        let curve = Curve::from_points(Coord2(last.position.0 as f64, last.position.1 as f64), Coord2(next.position.0 as f64, next.position.1 as f64),
+            Coord2(next.cp1.0 as f64, next.cp1.1 as f64), Coord2(next.cp2.0 as f64, next.cp2.1 as f64));
+
+        // Sample often enough that the eraser can't slip between two samples
+        let num_samples = ((curve.estimate_length(1.0) / (radius * 0.5)).ceil() as usize).max(4).min(1024);
+
+        let mut last_t      = 0.0;
+        let mut last_erased = is_erased(&curve.point_at_pos(0.0));
+        let mut span_start  = 0.0;
+
+        if last_erased {
+            erased_any = true;
+            if let Some(piece) = current.take() { pieces.push(piece); }
+        } else if current.is_none() {
+            current = Some(vec![start_brush_point(&curve, 0.0, last, next)]);
+        }
+
+        for sample in 1..(num_samples+1) {
+            let t       = (sample as f64) / (num_samples as f64);
+            let erased  = is_erased(&curve.point_at_pos(t));
+
+            if erased != last_erased {
+                let edge_t = find_eraser_edge(&curve, last_t, t, path, radius);
+
+                if erased {
+                    // Entering the eraser: finish the piece we were building
+                    if let Some(mut piece) = current.take() {
+                        piece.push(section_brush_point(&curve, span_start, edge_t, last, next));
+                        pieces.push(piece);
+                    }
+                    erased_any = true;
+                } else {
+                    // Leaving the eraser: start a new piece
+                    current     = Some(vec![start_brush_point(&curve, edge_t, last, next)]);
+                    span_start  = edge_t;
+                }
+            }
+
+            last_t      = t;
+            last_erased = erased;
+        }
+
+        // Any remaining part of this section belongs to the current piece
+        if !last_erased {
+            if let Some(ref mut piece) = current {
+                piece.push(section_brush_point(&curve, span_start, 1.0, last, next));
+            }
+        }
+    }
+
+    if let Some(piece) = current.take() { pieces.push(piece); }
+
+    if !erased_any {
+        None
+    } else {
+        Some(pieces.into_iter()
+            .filter(|piece| piece.len() >= 2 && brush_points_length(piece) >= MIN_PIECE_LENGTH)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn straight_stroke() -> Vec<BrushPoint> {
+        vec![
+            BrushPoint { position: (0.0, 0.0), cp1: (0.0, 0.0), cp2: (0.0, 0.0), width: 0.5, angle: 0.0 },
+            BrushPoint { position: (100.0, 0.0), cp1: (33.0, 0.0), cp2: (66.0, 0.0), width: 0.5, angle: 0.0 },
+            BrushPoint { position: (200.0, 0.0), cp1: (133.0, 0.0), cp2: (166.0, 0.0), width: 0.5, angle: 0.0 }
+        ]
+    }
+
+    #[test]
+    fn eraser_away_from_stroke_does_nothing() {
+        let erased = erase_brush_points(&straight_stroke(), &[(100.0, 50.0), (150.0, 50.0)], 5.0);
+
+        assert!(erased.is_none());
+    }
+
+    #[test]
+    fn erase_entire_stroke() {
+        let erased = erase_brush_points(&straight_stroke(), &[(-10.0, 0.0), (210.0, 0.0)], 5.0);
+
+        assert!(erased == Some(vec![]));
+    }
+
+    #[test]
+    fn trim_end_of_stroke() {
+        let erased = erase_brush_points(&straight_stroke(), &[(180.0, 0.0), (220.0, 0.0)], 5.0).unwrap();
+
+        assert!(erased.len() == 1);
+
+        let piece = &erased[0];
+        assert!(piece[0].position == (0.0, 0.0));
+        assert!((piece[piece.len()-1].position.0 - 175.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn split_stroke_in_middle() {
+        let erased = erase_brush_points(&straight_stroke(), &[(50.0, -10.0), (50.0, 10.0)], 5.0).unwrap();
+
+        assert!(erased.len() == 2);
+
+        let first   = &erased[0];
+        let second  = &erased[1];
+
+        assert!(first[0].position == (0.0, 0.0));
+        assert!((first[first.len()-1].position.0 - 45.0).abs() < 0.1);
+        assert!((second[0].position.0 - 55.0).abs() < 0.1);
+        assert!(second[second.len()-1].position == (200.0, 0.0));
+        assert!(second.len() == 3);
+    }
+}
@@ -5,6 +5,10 @@
 
 mod edit_action;
 mod motion_actions;
+mod erase_actions;
+mod transform_actions;
 
 pub use self::edit_action::*;
 pub use self::motion_actions::*;
+pub use self::erase_actions::*;
+pub use self::transform_actions::*;
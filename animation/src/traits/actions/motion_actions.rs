@@ -26,7 +26,19 @@ pub enum MotionEditAction {
     /// 
     /// If a translation that is being updated is attached to an element outside of the set
     /// that is being changed, the attached translation is changed to a new ID.
-    MoveElements(Vec<ElementId>, Duration, (f32, f32), (f32, f32))
+    MoveElements(Vec<ElementId>, Duration, (f32, f32), (f32, f32)),
+
+    /// Scales a set of elements around a point (the scale factors along the x and y axes are the last parameter)
+    /// 
+    /// A new scale motion is created and attached to the elements, so the scaling is applied after any
+    /// motions that are already attached to them.
+    ScaleElements(Vec<ElementId>, Duration, (f32, f32), (f32, f32)),
+
+    /// Rotates a set of elements around a point by an angle in radians
+    /// 
+    /// A new rotate motion is created and attached to the elements, so the rotation is applied after any
+    /// motions that are already attached to them.
+    RotateElements(Vec<ElementId>, Duration, (f32, f32), f32)
 }
 
 impl EditAction for MotionEditAction {
@@ -37,7 +49,15 @@ impl EditAction for MotionEditAction {
         use self::MotionEditAction::*;
 
         match self {
-            MoveElements(elements, when, from, to)  => move_elements_edit(animation, elements, when, from, to)
+            MoveElements(elements, when, from, to)          => move_elements_edit(animation, elements, when, from, to),
+            ScaleElements(elements, when, origin, scale)    => {
+                let scale_point = TimePoint::new(scale.0, scale.1, *when);
+                attach_motion_edit(animation, elements, MotionType::Scale, origin, TimeCurve::new(scale_point, scale_point))
+            },
+            RotateElements(elements, when, origin, angle)   => {
+                let rotate_point = TimePoint::new(*angle, 0.0, *when);
+                attach_motion_edit(animation, elements, MotionType::Rotate, origin, TimeCurve::new(rotate_point, rotate_point))
+            }
         }
     }
 }
@@ -47,30 +67,39 @@ impl EditAction for MotionEditAction {
 /// that attaches a suitable motion that just translates them instantly at a point in time
 /// 
 fn static_move_edit<Anim: Animation>(animation: &Anim, elements: &HashSet<ElementId>, when: &Duration, from: &(f32, f32), to: &(f32, f32)) -> Vec<AnimationEdit> {
+    // Creates a motion that instantaneously moves from the 'from' point to the 'to' point 
+    let target_point        = TimePoint::new(to.0, to.1, when.clone());
+    let elements: Vec<_>    = elements.iter().cloned().collect();
+
+    attach_motion_edit(animation, &elements, MotionType::Translate, from, TimeCurve::new(target_point, target_point))
+}
+
+///
+/// Generates an edit that creates a new motion and attaches it to a set of elements
+/// 
+fn attach_motion_edit<Anim: Animation>(animation: &Anim, elements: &[ElementId], motion_type: MotionType, origin: &(f32, f32), path: TimeCurve) -> Vec<AnimationEdit> {
     if elements.len() > 0 {
-        // Create a new motion, then attach it to the static elements
-        let static_motion_id    = animation.motion().assign_motion_id();
-        let target_point        = TimePoint::new(to.0, to.1, when.clone());
+        // Create a new motion, then attach it to the elements
+        let motion_id       = animation.motion().assign_motion_id();
         
-        // Creates a motion that instantaneously moves from the 'from' point to the 'to' point 
-        let create_motion       = vec![
+        let create_motion   = vec![
             MotionEdit::Create,
-            MotionEdit::SetType(MotionType::Translate),
-            MotionEdit::SetOrigin(from.0, from.1),
-            MotionEdit::SetPath(TimeCurve::new(target_point, target_point))
+            MotionEdit::SetType(motion_type),
+            MotionEdit::SetOrigin(origin.0, origin.1),
+            MotionEdit::SetPath(path)
         ];
 
-        // Attach the static elements
-        let attach_elements     = elements.iter()
+        // Attach the elements
+        let attach_elements = elements.iter()
             .map(|element_id| MotionEdit::Attach(*element_id));
 
         // Turn into a series of animation edits
         create_motion.into_iter()
             .chain(attach_elements)
-            .map(|motion_edit| AnimationEdit::Motion(static_motion_id, motion_edit))
+            .map(|motion_edit| AnimationEdit::Motion(motion_id, motion_edit))
             .collect()
     } else {
-        // No static elements = no static element translation
+        // No elements = no motion
         vec![]
     }
 }
@@ -196,6 +225,8 @@ mod test {
             fn get_layer_ids(&self) -> Vec<u64> { unimplemented!() }
             fn get_layer_with_id<'a>(&'a self, layer_id: u64) -> Option<Box<'a+Deref<Target='a+Layer>>> { unimplemented!() }
             fn get_num_edits(&self) -> usize { unimplemented!() }
+            fn brush_presets(&self) -> Vec<BrushPreset> { unimplemented!() }
+            fn palette(&self) -> Vec<Swatch> { unimplemented!() }
             fn read_edit_log<'a>(&'a self, range: Range<usize>) -> Box<'a+Stream<Item=AnimationEdit, Error=()>> { unimplemented!() }
             fn motion<'a>(&'a self) -> &'a AnimationMotion { self }
         }
@@ -255,6 +286,8 @@ mod test {
             fn get_layer_ids(&self) -> Vec<u64> { unimplemented!() }
             fn get_layer_with_id<'a>(&'a self, layer_id: u64) -> Option<Box<'a+Deref<Target='a+Layer>>> { unimplemented!() }
             fn get_num_edits(&self) -> usize { unimplemented!() }
+            fn brush_presets(&self) -> Vec<BrushPreset> { unimplemented!() }
+            fn palette(&self) -> Vec<Swatch> { unimplemented!() }
             fn read_edit_log<'a>(&'a self, range: Range<usize>) -> Box<'a+Stream<Item=AnimationEdit, Error=()>> { unimplemented!() }
             fn motion<'a>(&'a self) -> &'a AnimationMotion { self }
         }
@@ -290,4 +323,58 @@ mod test {
 
         assert!(dynamic_move.len() == 1);
     }
+
+    #[test]
+    fn rotate_elements() {
+        // Test animation that has no attached motions
+        struct TestAnimation;
+
+        impl Animation for TestAnimation {
+            fn size(&self) -> (f64, f64) { unimplemented!() }
+            fn duration(&self) -> Duration { unimplemented!() }
+            fn frame_length(&self) -> Duration { unimplemented!() }
+            fn get_layer_ids(&self) -> Vec<u64> { unimplemented!() }
+            fn get_layer_with_id<'a>(&'a self, _layer_id: u64) -> Option<Box<'a+Deref<Target='a+Layer>>> { unimplemented!() }
+            fn get_num_edits(&self) -> usize { unimplemented!() }
+            fn brush_presets(&self) -> Vec<BrushPreset> { unimplemented!() }
+            fn palette(&self) -> Vec<Swatch> { unimplemented!() }
+            fn read_edit_log<'a>(&'a self, _range: Range<usize>) -> Box<'a+Stream<Item=AnimationEdit, Error=()>> { unimplemented!() }
+            fn motion<'a>(&'a self) -> &'a AnimationMotion { self }
+        }
+
+        impl AnimationMotion for TestAnimation {
+            fn get_motion_ids(&self, _when: Range<Duration>) -> Box<Stream<Item=ElementId, Error=()>> { unimplemented!() }
+
+            fn assign_motion_id(&self) -> ElementId {
+                ElementId::Assigned(42)
+            }
+
+            fn get_motions_for_element(&self, _element_id: ElementId) -> Vec<ElementId> {
+                vec![ElementId::Assigned(10)]
+            }
+
+            fn get_elements_for_motion(&self, _motion_id: ElementId) -> Vec<ElementId> {
+                vec![]
+            }
+
+            fn get_motion(&self, _motion_id: ElementId) -> Option<Motion> {
+                None
+            }
+        }
+
+        // Rotating always creates a new motion (even if the element already has a motion attached)
+        let animation   = TestAnimation;
+        let rotate      = MotionEditAction::RotateElements(vec![ElementId::Assigned(1)], Duration::from_millis(442), (100.0, 200.0), 0.5)
+            .to_animation_edits(&animation);
+
+        let rotate_point = TimePoint::new(0.5, 0.0, Duration::from_millis(442));
+
+        assert!(rotate == vec![
+            AnimationEdit::Motion(ElementId::Assigned(42), MotionEdit::Create),
+            AnimationEdit::Motion(ElementId::Assigned(42), MotionEdit::SetType(MotionType::Rotate)),
+            AnimationEdit::Motion(ElementId::Assigned(42), MotionEdit::SetOrigin(100.0, 200.0)),
+            AnimationEdit::Motion(ElementId::Assigned(42), MotionEdit::SetPath(TimeCurve::new(rotate_point, rotate_point))),
+            AnimationEdit::Motion(ElementId::Assigned(42), MotionEdit::Attach(ElementId::Assigned(1)))
+        ]);
+    }
 }
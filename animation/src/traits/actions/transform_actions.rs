@@ -0,0 +1,190 @@
+use super::edit_action::*;
+use super::super::edit::*;
+use super::super::motion::*;
+use super::super::vector::*;
+use super::super::animation::*;
+
+use canvas::Transform2D;
+
+use std::sync::*;
+use std::time::Duration;
+use std::collections::HashSet;
+
+///
+/// Edit actions that scale, rotate or flip elements
+///
+pub enum TransformEditAction {
+    /// Applies a transformation (in frame coordinates) to a set of elements in a layer at a particular time
+    ///
+    /// The points of each brush stroke are rewritten. Elements that have motions attached keep them: the
+    /// transformation is conjugated with the transformation the motions apply at the specified time, so the
+    /// element appears transformed at that time.
+    ///
+    /// To animate a transformation instead, use `MotionEditAction::ScaleElements` or `MotionEditAction::RotateElements`.
+    TransformElements(u64, Duration, Vec<ElementId>, Transform2D)
+}
+
+impl EditAction for TransformEditAction {
+    ///
+    /// Converts this edit action into a set of animation edits for a particular animation
+    ///
+    fn to_animation_edits<Anim: Animation>(&self, animation: &Anim) -> Vec<AnimationEdit> {
+        use self::TransformEditAction::*;
+
+        match self {
+            TransformElements(layer_id, when, elements, transform) => transform_elements_edit(animation, *layer_id, *when, elements, transform)
+        }
+    }
+}
+
+///
+/// Returns the transformation that the motions attached to an element apply at a particular time
+///
+fn motion_transform<Anim: Animation>(animation: &Anim, element_id: ElementId, when: Duration) -> Transform2D {
+    // Motions are applied in the order they're attached, so later motions are applied to the result of the earlier ones
+    animation.motion().get_motions_for_element(element_id)
+        .into_iter()
+        .filter_map(|motion_id| animation.motion().get_motion(motion_id))
+        .fold(Transform2D::identity(), |transform, motion| motion.transform_at_time(when) * transform)
+}
+
+///
+/// Generates the edits required to transform a set of elements
+///
+fn transform_elements_edit<Anim: Animation>(animation: &Anim, layer_id: u64, when: Duration, elements: &[ElementId], transform: &Transform2D) -> Vec<AnimationEdit> {
+    // Fetch the frame containing the elements
+    let frame = match animation.get_layer_with_id(layer_id) {
+        Some(layer) => layer.get_frame_at_time(when),
+        None        => return vec![]
+    };
+    let elements: HashSet<_> = elements.iter().cloned().collect();
+
+    frame.vector_elements()
+        .map(|vector_elements| vector_elements.collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|element| elements.contains(&element.id()))
+        .filter_map(|element| {
+            if let Vector::BrushStroke(ref brush_stroke) = element {
+                // The element is displayed with its motions applied, so the new points are the ones that end up
+                // transformed once the motions are applied to them (elements whose motions can't be undone are left alone)
+                let element_id          = brush_stroke.id();
+                let motion              = motion_transform(animation, element_id, when);
+                let inverse_motion      = motion.invert()?;
+                let element_transform   = inverse_motion * *transform * motion;
+
+                let points              = transform_brush_points(&brush_stroke.points(), &element_transform);
+                Some(AnimationEdit::Element(element_id, when, ElementEdit::SetBrushPoints(Arc::new(points))))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::super::*;
+    use super::super::super::super::inmemory::*;
+
+    use std::f32;
+
+    ///
+    /// Creates an animation containing a brush stroke (element 10) that is moved by (50, 20) at 442ms
+    ///
+    fn translated_element() -> InMemoryAnimation {
+        let animation   = InMemoryAnimation::new();
+        let when        = Duration::from_millis(442);
+        let moved_to    = TimePoint::new(50.0, 20.0, when);
+
+        animation.perform_edits(vec![
+            AnimationEdit::AddNewLayer(0),
+            AnimationEdit::Layer(0, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(0, LayerEdit::Paint(when, PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(0, LayerEdit::Paint(when, PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(0, LayerEdit::Paint(when, PaintEdit::BrushStroke(ElementId::Assigned(10), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0)),
+                    RawPoint::from((30.0, 15.0))
+                ])))),
+
+            AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+            AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Translate)),
+            AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(0.0, 0.0)),
+            AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(TimeCurve::new(moved_to, moved_to))),
+            AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Attach(ElementId::Assigned(10)))
+        ]);
+
+        animation
+    }
+
+    ///
+    /// Reads the points of element 10 from the animation
+    ///
+    fn element_points(animation: &InMemoryAnimation) -> Vec<BrushPoint> {
+        let frame = animation.get_layer_with_id(0).unwrap().get_frame_at_time(Duration::from_millis(442));
+
+        match frame.element_with_id(ElementId::Assigned(10)) {
+            Some(Vector::BrushStroke(brush_stroke)) => brush_stroke.points().iter().cloned().collect(),
+            _                                       => panic!("Element 10 is not a brush stroke")
+        }
+    }
+
+    ///
+    /// Checks that transforming element 10 moves where it's displayed by the transformation
+    ///
+    fn check_transform_of_translated_element(transform: Transform2D) {
+        let animation   = translated_element();
+        let motion      = Transform2D::translate(50.0, 20.0);
+        let old_points  = element_points(&animation);
+
+        let edits       = TransformEditAction::TransformElements(0, Duration::from_millis(442), vec![ElementId::Assigned(10)], transform)
+            .to_animation_edits(&animation);
+        assert!(edits.len() == 1);
+        animation.perform_edits(edits);
+
+        let new_points  = element_points(&animation);
+        assert!(new_points.len() == old_points.len());
+
+        for (old_point, new_point) in old_points.iter().zip(new_points.iter()) {
+            let expected    = transform_brush_point(&transform_brush_point(old_point, &motion), &transform);
+            let displayed   = transform_brush_point(new_point, &motion);
+
+            assert!((expected.position.0-displayed.position.0).abs() < 0.01 && (expected.position.1-displayed.position.1).abs() < 0.01);
+            assert!((expected.cp1.0-displayed.cp1.0).abs() < 0.01 && (expected.cp1.1-displayed.cp1.1).abs() < 0.01);
+            assert!((expected.cp2.0-displayed.cp2.0).abs() < 0.01 && (expected.cp2.1-displayed.cp2.1).abs() < 0.01);
+            assert!((expected.width-displayed.width).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn rotate_translated_element_around_pivot() {
+        let pivot = (70.0, 30.0);
+
+        check_transform_of_translated_element(Transform2D::translate(pivot.0, pivot.1) * Transform2D::rotate(f32::consts::PI/3.0) * Transform2D::translate(-pivot.0, -pivot.1));
+    }
+
+    #[test]
+    fn scale_translated_element_around_pivot() {
+        let pivot = (70.0, 30.0);
+
+        check_transform_of_translated_element(Transform2D::translate(pivot.0, pivot.1) * Transform2D::scale(2.0, 0.5) * Transform2D::translate(-pivot.0, -pivot.1));
+    }
+
+    #[test]
+    fn transform_element_without_motions() {
+        let animation   = translated_element();
+        let transform   = Transform2D::scale(2.0, 2.0);
+
+        animation.perform_edits(vec![AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Detach(ElementId::Assigned(10)))]);
+        let old_points  = element_points(&animation);
+
+        let edits       = TransformEditAction::TransformElements(0, Duration::from_millis(442), vec![ElementId::Assigned(10)], transform)
+            .to_animation_edits(&animation);
+        animation.perform_edits(edits);
+
+        let new_points  = element_points(&animation);
+        assert!(new_points == transform_brush_points(&old_points, &transform));
+    }
+}
@@ -1,6 +1,8 @@
 use super::edit::*;
 use super::layer::*;
 use super::animation_motion::*;
+use super::brush_preset::*;
+use super::swatch::*;
 
 use futures::*;
 
@@ -42,6 +44,16 @@ pub trait Animation :
     /// 
     fn get_num_edits(&self) -> usize;
 
+    ///
+    /// Retrieves the brush presets stored in this animation
+    /// 
+    fn brush_presets(&self) -> Vec<BrushPreset>;
+
+    ///
+    /// Retrieves the swatches in the colour palette for this animation
+    /// 
+    fn palette(&self) -> Vec<Swatch>;
+
     ///
     /// Reads from the edit log for this animation
     /// 
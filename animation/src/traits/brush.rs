@@ -20,7 +20,27 @@ pub struct BrushPoint {
     pub cp2: (f32, f32),
 
     /// Width of this segment
-    pub width: f32
+    pub width: f32,
+
+    /// Angle of the nib at the end of this segment, in radians (only used by brushes with a flat nib)
+    #[serde(default)]
+    pub angle: f32
+}
+
+///
+/// Interpolates between two nib angles
+/// 
+/// A flat nib looks the same when it's turned by half a circle, so this takes the shortest route between the two angles
+/// taking that into account.
+/// 
+pub fn interpolate_nib_angle(from: f32, to: f32, t: f32) -> f32 {
+    use std::f32::consts::PI;
+
+    let mut difference = (to - from) % PI;
+    if difference > PI/2.0          { difference -= PI; }
+    else if difference < -PI/2.0    { difference += PI; }
+
+    from + difference*t
 }
 
 ///
@@ -0,0 +1,39 @@
+use std::f32;
+
+///
+/// Calligraphy brushes draw with a flat nib: the width of the stroke depends on the angle of the nib
+/// relative to the direction the stroke is travelling in. The actual behaviour is implemented by the
+/// `CalligraphyBrush` structure.
+/// 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct CalligraphyDefinition {
+    /// Width of the nib at pressure 100%
+    pub nib_width: f32,
+
+    /// Thickness of the nib (the width of the stroke when moving along the edge of the nib)
+    pub nib_thickness: f32,
+
+    /// Angle of the nib in radians when the pen is not tilted
+    pub nib_angle: f32,
+
+    /// How much the tilt of the pen turns the nib (0 = the nib angle is fixed, 1 = the nib follows the pen)
+    pub tilt_sensitivity: f32,
+
+    /// How much the pressure of the pen changes the width of the nib (0 = the width is fixed)
+    pub pressure_sensitivity: f32
+}
+
+impl CalligraphyDefinition {
+    ///
+    /// Creates the default calligraphy definition
+    /// 
+    pub fn default() -> CalligraphyDefinition {
+        CalligraphyDefinition {
+            nib_width:              8.0,
+            nib_thickness:          0.75,
+            nib_angle:              f32::consts::PI/4.0,
+            tilt_sensitivity:       1.0,
+            pressure_sensitivity:   0.5
+        }
+    }
+}
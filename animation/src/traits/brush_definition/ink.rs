@@ -1,3 +1,4 @@
+use super::pressure_curve::*;
 
 ///
 /// Ink brushes are solid lines of varying width. This defines how they behave.
@@ -12,7 +13,16 @@ pub struct InkDefinition {
     pub max_width: f32,
 
     // Distance to scale up at the start of the brush stroke
-    pub scale_up_distance: f32
+    pub scale_up_distance: f32,
+
+    /// Maps the pressure from the input device to the pressure used to determine the width of the stroke
+    #[serde(default)]
+    pub pressure_curve: PressureCurve,
+
+    /// If set, the width comes from the speed of the stroke instead of the input device: this is the distance
+    /// between input points at which the stroke reaches its minimum width (useful for devices without pressure)
+    #[serde(default)]
+    pub velocity_width: Option<f32>
 }
 
 impl InkDefinition {
@@ -23,7 +33,9 @@ impl InkDefinition {
         InkDefinition {
             min_width:          0.25,
             max_width:          5.0,
-            scale_up_distance:  40.0
+            scale_up_distance:  40.0,
+            pressure_curve:     PressureCurve::linear(),
+            velocity_width:     None
         }
     }
 
@@ -34,7 +46,9 @@ impl InkDefinition {
         InkDefinition {
             min_width:          3.0,
             max_width:          20.0,
-            scale_up_distance:  5.0
+            scale_up_distance:  5.0,
+            pressure_curve:     PressureCurve::linear(),
+            velocity_width:     None
         }
     }
 }
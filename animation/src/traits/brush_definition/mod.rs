@@ -1,6 +1,10 @@
 mod ink;
+mod calligraphy;
+mod pressure_curve;
 
 pub use self::ink::*;
+pub use self::calligraphy::*;
+pub use self::pressure_curve::*;
 
 ///
 /// Stores the definition of a particular brush
@@ -11,5 +15,8 @@ pub enum BrushDefinition {
     Simple,
 
     /// An ink brush with a particular definition
-    Ink(InkDefinition)
+    Ink(InkDefinition),
+
+    /// A flat-nib brush whose stroke depends on the tilt of the pen and the direction of the stroke
+    Calligraphy(CalligraphyDefinition)
 }
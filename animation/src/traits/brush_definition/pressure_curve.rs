@@ -0,0 +1,134 @@
+///
+/// Maps the pressure reported by an input device to the pressure used to work out the width of a brush stroke
+///
+/// The curve is made up of control points that map an input pressure to an output pressure, with the
+/// pressures in between being linearly interpolated. Both pressures are in the range 0-1. The curve
+/// starts at (0, 0) and ends at (1, 1) unless control points are supplied for those input pressures.
+/// 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct PressureCurve {
+    /// The control points for this curve, as (input pressure, output pressure) pairs ordered by input pressure
+    pub points: Vec<(f32, f32)>
+}
+
+impl PressureCurve {
+    ///
+    /// Creates a pressure curve where the output pressure is the same as the input pressure
+    /// 
+    pub fn linear() -> PressureCurve {
+        PressureCurve {
+            points: vec![]
+        }
+    }
+
+    ///
+    /// Creates a pressure curve from a set of control points
+    /// 
+    pub fn new(points: Vec<(f32, f32)>) -> PressureCurve {
+        let mut points: Vec<_> = points.into_iter()
+            .map(|(input, output)| (input.max(0.0).min(1.0), output.max(0.0).min(1.0)))
+            .collect();
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(::std::cmp::Ordering::Equal));
+
+        PressureCurve {
+            points: points
+        }
+    }
+
+    ///
+    /// True if this curve doesn't change the input pressure
+    /// 
+    pub fn is_linear(&self) -> bool {
+        self.points.iter().all(|&(input, output)| (input-output).abs() < 0.001)
+    }
+
+    ///
+    /// Returns the control points for this curve, including the implicit start and end points
+    /// 
+    pub fn control_points(&self) -> Vec<(f32, f32)> {
+        let mut control_points = vec![];
+
+        if self.points.first().map(|&(input, _)| input > 0.0).unwrap_or(true) {
+            control_points.push((0.0, 0.0));
+        }
+
+        control_points.extend(self.points.iter().cloned());
+
+        if self.points.last().map(|&(input, _)| input < 1.0).unwrap_or(true) {
+            control_points.push((1.0, 1.0));
+        }
+
+        control_points
+    }
+
+    ///
+    /// Returns the output pressure for a particular input pressure
+    /// 
+    pub fn pressure(&self, input: f32) -> f32 {
+        let input           = input.max(0.0).min(1.0);
+        let control_points  = self.control_points();
+
+        for index in 1..control_points.len() {
+            let (start_input, start_output) = control_points[index-1];
+            let (end_input, end_output)     = control_points[index];
+
+            if input <= end_input {
+                let range = end_input - start_input;
+
+                return if range <= 0.0 {
+                    end_output
+                } else {
+                    start_output + (end_output - start_output) * ((input - start_input) / range)
+                };
+            }
+        }
+
+        control_points.last().map(|&(_, output)| output).unwrap_or(input)
+    }
+}
+
+impl Default for PressureCurve {
+    fn default() -> PressureCurve {
+        PressureCurve::linear()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn linear_curve_does_not_change_pressure() {
+        let curve = PressureCurve::linear();
+
+        assert!(curve.is_linear());
+        assert!((curve.pressure(0.0) - 0.0).abs() < 0.001);
+        assert!((curve.pressure(0.3) - 0.3).abs() < 0.001);
+        assert!((curve.pressure(1.0) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn interpolates_between_control_points() {
+        let curve = PressureCurve::new(vec![(0.5, 0.8)]);
+
+        assert!(!curve.is_linear());
+        assert!((curve.pressure(0.25) - 0.4).abs() < 0.001);
+        assert!((curve.pressure(0.5) - 0.8).abs() < 0.001);
+        assert!((curve.pressure(0.75) - 0.9).abs() < 0.001);
+    }
+
+    #[test]
+    fn control_points_are_sorted() {
+        let curve = PressureCurve::new(vec![(0.7, 0.9), (0.2, 0.1)]);
+
+        assert!(curve.control_points() == vec![(0.0, 0.0), (0.2, 0.1), (0.7, 0.9), (1.0, 1.0)]);
+    }
+
+    #[test]
+    fn explicit_end_points_replace_implicit_ones() {
+        let curve = PressureCurve::new(vec![(0.0, 0.2), (1.0, 0.6)]);
+
+        assert!(curve.control_points() == vec![(0.0, 0.2), (1.0, 0.6)]);
+        assert!((curve.pressure(0.5) - 0.4).abs() < 0.001);
+    }
+}
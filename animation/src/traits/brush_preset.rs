@@ -0,0 +1,48 @@
+use super::brush_properties::*;
+use super::brush_definition::*;
+use super::brush_drawing_style::*;
+
+///
+/// A named set of brush settings that can be stored alongside an animation
+/// 
+#[derive(Clone, PartialEq, Debug)]
+pub struct BrushPreset {
+    /// The name of this preset (presets are identified by their name)
+    pub name: String,
+
+    /// The brush that this preset selects
+    pub definition: BrushDefinition,
+
+    /// The drawing style to use with the brush
+    pub drawing_style: BrushDrawingStyle,
+
+    /// The size, opacity and colour to use with the brush
+    pub properties: BrushProperties
+}
+
+impl BrushPreset {
+    ///
+    /// Creates a new brush preset
+    /// 
+    pub fn new(name: &str, definition: BrushDefinition, drawing_style: BrushDrawingStyle, properties: BrushProperties) -> BrushPreset {
+        BrushPreset {
+            name:           name.to_string(),
+            definition:     definition,
+            drawing_style:  drawing_style,
+            properties:     properties
+        }
+    }
+
+    ///
+    /// Returns the presets that a new animation starts with
+    /// 
+    pub fn default_presets() -> Vec<BrushPreset> {
+        let mut eraser_properties = BrushProperties::new();
+        eraser_properties.size = 10.0;
+
+        vec![
+            BrushPreset::new("Ink", BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw, BrushProperties::new()),
+            BrushPreset::new("Eraser", BrushDefinition::Ink(InkDefinition::default_eraser()), BrushDrawingStyle::Erase, eraser_properties)
+        ]
+    }
+}
@@ -1,5 +1,31 @@
 use canvas::*;
 
+///
+/// The shape of a gradient used to fill a brush stroke
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BrushGradientType {
+    /// Blends along the length of the stroke, from its first point to its last
+    Linear,
+
+    /// Blends outwards from the centre of the stroke
+    Radial
+}
+
+///
+/// Describes a gradient that a brush stroke is filled with instead of its solid colour
+///
+/// The gradient runs from the colour in the brush properties to the end colour.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BrushGradient {
+    /// The shape of this gradient
+    pub gradient_type: BrushGradientType,
+
+    /// The colour at the end of the gradient
+    pub end_color: Color
+}
+
 ///
 /// Standard properties for a brush stroke
 /// 
@@ -15,7 +41,10 @@ pub struct BrushProperties {
     pub opacity: f32,
 
     /// The colour of the brush stroke
-    pub color: Color
+    pub color: Color,
+
+    /// If set, the stroke is filled with a gradient starting at the colour above
+    pub gradient: Option<BrushGradient>
 }
 
 impl BrushProperties {
@@ -26,7 +55,8 @@ impl BrushProperties {
         BrushProperties {
             size:       5.0,
             opacity:    1.0,
-            color:      Color::Rgba(0.0, 0.0, 0.0, 1.0)
+            color:      Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            gradient:   None
         }
     }
 }
\ No newline at end of file
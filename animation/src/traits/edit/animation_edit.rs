@@ -2,6 +2,8 @@ use super::element_id::*;
 use super::layer_edit::*;
 use super::motion_edit::*;
 use super::element_edit::*;
+use super::super::brush_preset::*;
+use super::super::swatch::*;
 
 use std::time::Duration;
 
@@ -28,7 +30,19 @@ pub enum AnimationEdit {
     AddNewLayer(u64),
 
     /// Removes the layer with the specified ID
-    RemoveLayer(u64)
+    RemoveLayer(u64),
+
+    /// Stores a brush preset in this animation, replacing any existing preset with the same name
+    SetBrushPreset(BrushPreset),
+
+    /// Removes the brush preset with the specified name
+    RemoveBrushPreset(String),
+
+    /// Stores a swatch in the palette for this animation, replacing any existing swatch with the same name
+    SetSwatch(Swatch),
+
+    /// Removes the swatch with the specified name from the palette
+    RemoveSwatch(String)
 }
 
 impl AnimationEdit {
@@ -40,8 +54,9 @@ impl AnimationEdit {
         use self::AnimationEdit::*;
 
         match self {
-            Layer(layer_id, layer_edit) => Layer(layer_id, layer_edit.assign_element_id(assign_element_id)),
-            other                       => other
+            Layer(layer_id, layer_edit)         => Layer(layer_id, layer_edit.assign_element_id(assign_element_id)),
+            Element(element_id, when, edit)     => Element(element_id, when, edit.assign_element_id(assign_element_id)),
+            other                               => other
         }
     }
 }
\ No newline at end of file
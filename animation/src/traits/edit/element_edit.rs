@@ -1,6 +1,34 @@
+use super::element_id::*;
+use super::super::brush::*;
+
+use std::sync::*;
+
 ///
 /// Represents an edit to an element within a frame
 /// 
 #[derive(Clone, PartialEq, Debug)]
 pub enum ElementEdit {
+    /// Removes the element from its frame
+    Delete,
+
+    /// Replaces the points in a brush stroke element (has no effect on other types of element)
+    SetBrushPoints(Arc<Vec<BrushPoint>>),
+
+    /// Adds a new brush stroke with the specified ID directly above this element (so it's drawn with the same brush and properties)
+    AddBrushPointsAbove(ElementId, Arc<Vec<BrushPoint>>)
+}
+
+impl ElementEdit {
+    ///
+    /// If this edit creates a new element with an unassigned ID, calls the specified function to supply a new
+    /// element ID. If the edit already has an ID, leaves it unchanged.
+    /// 
+    pub fn assign_element_id<AssignFn: FnOnce() -> i64>(self, assign_element_id: AssignFn) -> ElementEdit {
+        use self::ElementEdit::*;
+
+        match self {
+            AddBrushPointsAbove(ElementId::Unassigned, points)  => AddBrushPointsAbove(ElementId::Assigned(assign_element_id()), points),
+            other                                               => other
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use super::element_id::*;
 
+use super::super::brush::*;
 use super::super::raw_point::*;
 use super::super::brush_properties::*;
 use super::super::brush_definition::*;
@@ -19,7 +20,10 @@ pub enum PaintEdit {
     BrushProperties(ElementId, BrushProperties),
 
     /// Draws a brush stroke using the current brush and the specified set of input points
-    BrushStroke(ElementId, Arc<Vec<RawPoint>>)
+    BrushStroke(ElementId, Arc<Vec<RawPoint>>),
+
+    /// Draws a brush stroke using the current brush from a set of points that have already been fitted to a curve
+    BrushPoints(ElementId, Arc<Vec<BrushPoint>>)
 }
 
 impl PaintEdit {
@@ -32,7 +36,8 @@ impl PaintEdit {
         match self {
             SelectBrush(id, _, _)   => *id,
             BrushProperties(id, _)  => *id,
-            BrushStroke(id, _)      => *id
+            BrushStroke(id, _)      => *id,
+            BrushPoints(id, _)      => *id
         }
     }
 
@@ -48,6 +53,7 @@ impl PaintEdit {
             SelectBrush(Unassigned, brush_def, brush_style) => SelectBrush(Assigned(assign_element_id()), brush_def, brush_style),
             BrushProperties(Unassigned, brush_props)        => BrushProperties(Assigned(assign_element_id()), brush_props),
             BrushStroke(Unassigned, points)                 => BrushStroke(Assigned(assign_element_id()), points),
+            BrushPoints(Unassigned, points)                 => BrushPoints(Assigned(assign_element_id()), points),
             
             assigned => assigned
         }
@@ -9,6 +9,8 @@ mod brush;
 mod brush_properties;
 mod brush_definition;
 mod brush_drawing_style;
+mod brush_preset;
+mod swatch;
 mod vector;
 mod path;
 mod time_path;
@@ -25,6 +27,8 @@ pub use self::brush::*;
 pub use self::brush_properties::*;
 pub use self::brush_definition::*;
 pub use self::brush_drawing_style::*;
+pub use self::brush_preset::*;
+pub use self::swatch::*;
 pub use self::vector::*;
 pub use self::path::*;
 pub use self::time_path::*;
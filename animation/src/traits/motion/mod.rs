@@ -1,9 +1,13 @@
 mod transform;
 mod motion;
 mod translate;
+mod scale;
+mod rotate;
 mod motion_type;
 
 pub use self::transform::*;
 pub use self::motion::*;
 pub use self::translate::*;
+pub use self::scale::*;
+pub use self::rotate::*;
 pub use self::motion_type::*;
@@ -1,9 +1,13 @@
 use super::translate::*;
+use super::scale::*;
+use super::rotate::*;
 use super::transform::*;
 use super::motion_type::*;
 use super::super::brush::*;
 use super::super::time_path::*;
 
+use canvas::Transform2D;
+
 use std::ops::Range;
 use std::time::Duration;
 
@@ -18,7 +22,13 @@ pub enum Motion {
     None,
 
     /// Describes how an element is translated over time
-    Translate(TranslateMotion)
+    Translate(TranslateMotion),
+
+    /// Describes how an element is scaled around a point over time
+    Scale(ScaleMotion),
+
+    /// Describes how an element is rotated around a point over time
+    Rotate(RotateMotion)
 }
 
 impl Motion {
@@ -30,7 +40,9 @@ impl Motion {
 
         match self {
             None            => MotionType::None,
-            Translate(_)    => MotionType::Translate
+            Translate(_)    => MotionType::Translate,
+            Scale(_)        => MotionType::Scale,
+            Rotate(_)       => MotionType::Rotate
         }
     }
 
@@ -42,7 +54,9 @@ impl Motion {
 
         match motion_type {
             None        => { *self = Motion::None; },
-            Translate   => { *self = Motion::Translate(TranslateMotion::default()); }
+            Translate   => { *self = Motion::Translate(TranslateMotion::default()); },
+            Scale       => { *self = Motion::Scale(ScaleMotion::default()); },
+            Rotate      => { *self = Motion::Rotate(RotateMotion::default()); }
         }
     }
 
@@ -54,7 +68,9 @@ impl Motion {
 
         match self {
             None                    => { }
-            Translate(translate)    => { translate.set_origin(new_origin); }
+            Translate(translate)    => { translate.set_origin(new_origin); },
+            Scale(scale)            => { scale.set_origin(new_origin); },
+            Rotate(rotate)          => { rotate.set_origin(new_origin); }
         }
     }
 
@@ -66,7 +82,9 @@ impl Motion {
 
         match self {
             None                    => { }
-            Translate(translate)    => { translate.set_path(new_path); }
+            Translate(translate)    => { translate.set_path(new_path); },
+            Scale(scale)            => { scale.set_path(new_path); },
+            Rotate(rotate)          => { rotate.set_path(new_path); }
         }
     }
 }
@@ -77,7 +95,20 @@ impl MotionTransform for Motion {
 
         match self {
             None                    => 0.0..0.0,
-            Translate(translate)    => translate.range_millis()
+            Translate(translate)    => translate.range_millis(),
+            Scale(scale)            => scale.range_millis(),
+            Rotate(rotate)          => rotate.range_millis()
+        }
+    }
+
+    fn transform_at_time(&self, time: Duration) -> Transform2D {
+        use self::Motion::*;
+
+        match self {
+            None                    => Transform2D::identity(),
+            Translate(translate)    => translate.transform_at_time(time),
+            Scale(scale)            => scale.transform_at_time(time),
+            Rotate(rotate)          => rotate.transform_at_time(time)
         }
     }
 
@@ -86,7 +117,9 @@ impl MotionTransform for Motion {
 
         match self {
             None                    => Box::new(points.cloned()),
-            Translate(translate)    => translate.transform_points(time, points)
+            Translate(translate)    => translate.transform_points(time, points),
+            Scale(scale)            => scale.transform_points(time, points),
+            Rotate(rotate)          => rotate.transform_points(time, points)
         }
     }
 }
@@ -4,5 +4,7 @@
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum MotionType {
     None,
-    Translate
+    Translate,
+    Scale,
+    Rotate
 }
\ No newline at end of file
@@ -0,0 +1,121 @@
+use super::transform::*;
+use super::super::brush::*;
+use super::super::time_path::*;
+
+use canvas::Transform2D;
+
+use std::ops::Range;
+use std::time::Duration;
+
+///
+/// Describes how an element is rotated over time
+/// 
+#[derive(Clone, PartialEq, Debug)]
+pub struct RotateMotion {
+    /// The point that the element rotates around
+    pub origin: (f32, f32),
+
+    /// Curve describing the rotation (the x coordinate is the angle in radians, the y coordinate is unused)
+    pub rotate: TimeCurve
+}
+
+impl RotateMotion {
+    ///
+    /// Creates a rotate motion that just instantaneously rotates something around a point
+    /// 
+    pub fn rotate_at(when: Duration, origin: (f32, f32), radians: f32) -> RotateMotion {
+        let rotate_point = TimePoint::new(radians, 0.0, when);
+
+        RotateMotion {
+            origin: origin,
+            rotate: TimeCurve::new(rotate_point, rotate_point)
+        }
+    }
+
+    ///
+    /// Sets the origin of this motion
+    ///
+    #[inline]
+    pub fn set_origin(&mut self, new_origin: (f32, f32)) {
+        self.origin = new_origin;
+    }
+
+    ///
+    /// Sets the path of this motion
+    /// 
+    #[inline]
+    pub fn set_path(&mut self, new_path: TimeCurve) {
+        self.rotate = new_path;
+    }
+}
+
+impl Default for RotateMotion {
+    ///
+    /// Creates a default rotate motion (which doesn't turn the element)
+    /// 
+    fn default() -> RotateMotion {
+        RotateMotion {
+            origin: (0.0, 0.0),
+            rotate: TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(0.0, 0.0, Duration::from_millis(0)))
+        }
+    }
+}
+
+impl MotionTransform for RotateMotion {
+    fn range_millis(&self) -> Range<f32> {
+        if self.rotate.points.len() == 0 {
+            0.0..0.0
+        } else {
+            let start   = self.rotate.points[0].point.milliseconds();
+            let end     = self.rotate.points.last().unwrap().point.milliseconds();
+
+            start..end
+        }
+    }
+
+    fn transform_at_time(&self, time: Duration) -> Transform2D {
+        let time_millis = ((time.as_secs() as f32) * 1_000.0) + ((time.subsec_nanos() as f32) / 1_000_000.0);
+        let origin      = self.origin;
+        let rotate      = self.rotate.point_at_time(time_millis);
+
+        if let Some(rotate) = rotate {
+            // Points rotated around the origin
+            Transform2D::translate(origin.0, origin.1) * Transform2D::rotate(rotate.0) * Transform2D::translate(-origin.0, -origin.1)
+        } else {
+            // Points unchanged if we can't find a time
+            Transform2D::identity()
+        }
+    }
+
+    fn transform_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>> {
+        let transform = self.transform_at_time(time);
+
+        Box::new(points.map(move |point| transform_brush_point(point, &transform)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::f32;
+
+    #[test]
+    fn rotate_around_origin() {
+        let motion  = RotateMotion::rotate_at(Duration::from_millis(442), (10.0, 10.0), f32::consts::PI/2.0);
+        let points  = vec![BrushPoint { position: (20.0, 10.0), cp1: (10.0, 10.0), cp2: (15.0, 10.0), width: 0.5, angle: 0.0 }];
+        let rotated = motion.transform_points(Duration::from_millis(442), points.iter()).collect::<Vec<_>>();
+
+        assert!((rotated[0].position.0-10.0).abs() < 0.0001 && (rotated[0].position.1-20.0).abs() < 0.0001);
+        assert!((rotated[0].angle-f32::consts::PI/2.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn no_rotation_at_other_times() {
+        let motion  = RotateMotion::rotate_at(Duration::from_millis(442), (10.0, 10.0), f32::consts::PI/2.0);
+        let points  = vec![BrushPoint { position: (20.0, 10.0), cp1: (10.0, 10.0), cp2: (15.0, 10.0), width: 0.5, angle: 0.0 }];
+        let rotated = motion.transform_points(Duration::from_millis(1000), points.iter()).collect::<Vec<_>>();
+
+        assert!(rotated == points);
+    }
+}
@@ -0,0 +1,110 @@
+use super::transform::*;
+use super::super::brush::*;
+use super::super::time_path::*;
+
+use canvas::Transform2D;
+
+use std::ops::Range;
+use std::time::Duration;
+
+///
+/// Describes how an element is scaled over time
+/// 
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScaleMotion {
+    /// The point that the element is scaled around
+    pub origin: (f32, f32),
+
+    /// Curve describing the scale factors along the x and y axes (negative factors flip the element)
+    pub scale: TimeCurve
+}
+
+impl ScaleMotion {
+    ///
+    /// Creates a scale motion that just instantaneously scales something around a point
+    /// 
+    pub fn scale_at(when: Duration, origin: (f32, f32), scale: (f32, f32)) -> ScaleMotion {
+        let scale_point = TimePoint::new(scale.0, scale.1, when);
+
+        ScaleMotion {
+            origin: origin,
+            scale:  TimeCurve::new(scale_point, scale_point)
+        }
+    }
+
+    ///
+    /// Sets the origin of this motion
+    ///
+    #[inline]
+    pub fn set_origin(&mut self, new_origin: (f32, f32)) {
+        self.origin = new_origin;
+    }
+
+    ///
+    /// Sets the path of this motion
+    /// 
+    #[inline]
+    pub fn set_path(&mut self, new_path: TimeCurve) {
+        self.scale = new_path;
+    }
+}
+
+impl Default for ScaleMotion {
+    ///
+    /// Creates a default scale motion (which leaves the element at its original size)
+    /// 
+    fn default() -> ScaleMotion {
+        ScaleMotion {
+            origin: (0.0, 0.0),
+            scale:  TimeCurve::new(TimePoint::new(1.0, 1.0, Duration::from_millis(0)), TimePoint::new(1.0, 1.0, Duration::from_millis(0)))
+        }
+    }
+}
+
+impl MotionTransform for ScaleMotion {
+    fn range_millis(&self) -> Range<f32> {
+        if self.scale.points.len() == 0 {
+            0.0..0.0
+        } else {
+            let start   = self.scale.points[0].point.milliseconds();
+            let end     = self.scale.points.last().unwrap().point.milliseconds();
+
+            start..end
+        }
+    }
+
+    fn transform_at_time(&self, time: Duration) -> Transform2D {
+        let time_millis = ((time.as_secs() as f32) * 1_000.0) + ((time.subsec_nanos() as f32) / 1_000_000.0);
+        let origin      = self.origin;
+        let scale       = self.scale.point_at_time(time_millis);
+
+        if let Some(scale) = scale {
+            // Points scaled around the origin
+            Transform2D::translate(origin.0, origin.1) * Transform2D::scale(scale.0, scale.1) * Transform2D::translate(-origin.0, -origin.1)
+        } else {
+            // Points unchanged if we can't find a time
+            Transform2D::identity()
+        }
+    }
+
+    fn transform_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>> {
+        let transform = self.transform_at_time(time);
+
+        Box::new(points.map(move |point| transform_brush_point(point, &transform)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scale_around_origin() {
+        let motion  = ScaleMotion::scale_at(Duration::from_millis(442), (10.0, 10.0), (2.0, -1.0));
+        let points  = vec![BrushPoint { position: (20.0, 20.0), cp1: (10.0, 10.0), cp2: (15.0, 15.0), width: 0.5, angle: 0.0 }];
+        let scaled  = motion.transform_points(Duration::from_millis(442), points.iter()).collect::<Vec<_>>();
+
+        assert!((scaled[0].position.0-30.0).abs() < 0.0001 && (scaled[0].position.1-0.0).abs() < 0.0001);
+        assert!((scaled[0].cp1.0-10.0).abs() < 0.0001 && (scaled[0].cp1.1-10.0).abs() < 0.0001);
+    }
+}
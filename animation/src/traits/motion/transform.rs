@@ -1,5 +1,7 @@
 use super::super::brush::*;
 
+use canvas::Transform2D;
+
 use std::ops::Range;
 use std::time::Duration;
 
@@ -12,8 +14,90 @@ pub trait MotionTransform {
     /// 
     fn range_millis(&self) -> Range<f32>;
 
+    ///
+    /// Returns the transformation that this motion applies at the specified time
+    ///
+    fn transform_at_time(&self, time: Duration) -> Transform2D;
+
     ///
     /// Returns a transformed set of points at the specified time
     /// 
     fn transform_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>>;
 }
+
+///
+/// Applies a transformation to a set of brush points
+///
+/// The nib angles are transformed along with the points, so calligraphy strokes keep their shape when rotated or flipped.
+/// Widths are scaled by the average amount the transformation scales by, so strokes stay in proportion when resized.
+///
+pub fn transform_brush_points(points: &[BrushPoint], transform: &Transform2D) -> Vec<BrushPoint> {
+    points.iter()
+        .map(|point| transform_brush_point(point, transform))
+        .collect()
+}
+
+///
+/// Applies a transformation to a single brush point
+///
+pub fn transform_brush_point(point: &BrushPoint, transform: &Transform2D) -> BrushPoint {
+    let Transform2D(row1, row2, _)  = *transform;
+    let (nib_x, nib_y)              = (point.angle.cos(), point.angle.sin());
+    let width_scale                 = (row1.0*row2.1 - row1.1*row2.0).abs().sqrt();
+
+    BrushPoint {
+        position:   transform.transform_point(point.position.0, point.position.1),
+        cp1:        transform.transform_point(point.cp1.0, point.cp1.1),
+        cp2:        transform.transform_point(point.cp2.0, point.cp2.1),
+        width:      point.width * width_scale,
+        angle:      (row2.0*nib_x + row2.1*nib_y).atan2(row1.0*nib_x + row1.1*nib_y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::f32;
+
+    fn is_close(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0-b.0).abs() < 0.0001 && (a.1-b.1).abs() < 0.0001
+    }
+
+    #[test]
+    fn scale_brush_points() {
+        let points      = vec![BrushPoint { position: (10.0, 20.0), cp1: (2.0, 4.0), cp2: (6.0, 8.0), width: 0.5, angle: 0.0 }];
+        let scaled      = transform_brush_points(&points, &Transform2D::scale(2.0, 3.0));
+
+        assert!(is_close(scaled[0].position, (20.0, 60.0)));
+        assert!(is_close(scaled[0].cp1, (4.0, 12.0)));
+        assert!(is_close(scaled[0].cp2, (12.0, 24.0)));
+        assert!((scaled[0].width - 0.5*6.0f32.sqrt()).abs() < 0.0001);
+    }
+
+    #[test]
+    fn rotation_keeps_the_width() {
+        let points      = vec![BrushPoint { position: (10.0, 0.0), cp1: (0.0, 0.0), cp2: (5.0, 0.0), width: 0.5, angle: 0.0 }];
+        let rotated     = transform_brush_points(&points, &Transform2D::rotate(1.0));
+
+        assert!((rotated[0].width - 0.5).abs() < 0.0001);
+    }
+
+    #[test]
+    fn rotation_turns_the_nib() {
+        let points      = vec![BrushPoint { position: (10.0, 0.0), cp1: (0.0, 0.0), cp2: (5.0, 0.0), width: 0.5, angle: 0.0 }];
+        let rotated     = transform_brush_points(&points, &Transform2D::rotate(f32::consts::PI/2.0));
+
+        assert!(is_close(rotated[0].position, (0.0, 10.0)));
+        assert!((rotated[0].angle - f32::consts::PI/2.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn flip_mirrors_the_nib() {
+        let points      = vec![BrushPoint { position: (10.0, 0.0), cp1: (0.0, 0.0), cp2: (5.0, 0.0), width: 0.5, angle: f32::consts::PI/4.0 }];
+        let flipped     = transform_brush_points(&points, &Transform2D::scale(-1.0, 1.0));
+
+        assert!(is_close(flipped[0].position, (-10.0, 0.0)));
+        assert!((flipped[0].angle - f32::consts::PI*3.0/4.0).abs() < 0.0001);
+    }
+}
@@ -2,6 +2,8 @@ use super::transform::*;
 use super::super::brush::*;
 use super::super::time_path::*;
 
+use canvas::Transform2D;
+
 use std::ops::Range;
 use std::time::Duration;
 
@@ -71,6 +73,20 @@ impl MotionTransform for TranslateMotion {
         }
     }
 
+    fn transform_at_time(&self, time: Duration) -> Transform2D {
+        let time_millis = ((time.as_secs() as f32) * 1_000.0) + ((time.subsec_nanos() as f32) / 1_000_000.0);
+        let origin      = self.origin;
+        let position    = self.translate.point_at_time(time_millis);
+
+        if let Some(position) = position {
+            // Points moved by the offset from the origin
+            Transform2D::translate(position.0 - origin.0, position.1 - origin.1)
+        } else {
+            // Points unchanged if we can't find a time
+            Transform2D::identity()
+        }
+    }
+
     fn transform_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>> {
         let time_millis = ((time.as_secs() as f32) * 1_000.0) + ((time.subsec_nanos() as f32) / 1_000_000.0);
         let origin      = self.origin;
@@ -86,6 +102,7 @@ impl MotionTransform for TranslateMotion {
                     cp1:        (point.cp1.0 + offset.0, point.cp1.1 + offset.1),
                     cp2:        (point.cp2.0 + offset.0, point.cp2.1 + offset.1),
                     width:      point.width,
+                    angle:      point.angle
                 }
             }))
         } else {
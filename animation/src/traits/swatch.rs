@@ -0,0 +1,25 @@
+use canvas::*;
+
+///
+/// A named colour in the palette stored alongside an animation
+/// 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Swatch {
+    /// The name of this swatch (swatches are identified by their name)
+    pub name: String,
+
+    /// The colour of this swatch
+    pub color: Color
+}
+
+impl Swatch {
+    ///
+    /// Creates a new swatch
+    /// 
+    pub fn new(name: &str, color: Color) -> Swatch {
+        Swatch {
+            name:   name.to_string(),
+            color:  color
+        }
+    }
+}
@@ -70,7 +70,8 @@ impl BrushElement {
                 position:   transform(point.position),
                 cp1:        transform(point.cp1),
                 cp2:        transform(point.cp2),
-                width:      point.width
+                width:      point.width,
+                angle:      point.angle
             }
         };
 
@@ -137,8 +138,8 @@ impl VectorElement for BrushElement {
     /// Creates a new vector element from this one with the control points updated to the specified set of new values
     ///
     fn with_adjusted_control_points(&self, new_positions: Vec<(f32, f32)>) -> Vector {
-        // The widths are kept the same as they are in this element
-        let widths = self.points.iter().map(|point| point.width);
+        // The widths and nib angles are kept the same as they are in this element
+        let widths = self.points.iter().map(|point| (point.width, point.angle));
 
         // The first element still has two control points, but we only actually care about its position. Generate two fake control points here.
         let initial_pos             = new_positions[0];
@@ -151,11 +152,12 @@ impl VectorElement for BrushElement {
         let brush_elements          = initial_control_points.into_iter().chain(new_positions.into_iter())
             .tuples()
             .zip(widths)
-            .map(|((cp1, cp2, pos), width)| BrushPoint {
+            .map(|((cp1, cp2, pos), (width, angle))| BrushPoint {
                 position:   pos,
                 cp1:        cp1,
                 cp2:        cp2,
-                width:      width
+                width:      width,
+                angle:      angle
             });
 
         // Create a new brush element
@@ -185,21 +187,24 @@ mod test {
                 position: (1.0, 2.0),
                 cp1: (3.0, 4.0),
                 cp2: (5.0, 6.0),
-                width: 0.5
+                width: 0.5,
+                angle: 0.0
             },
 
             BrushPoint {
                 position: (7.0, 8.0),
                 cp1: (9.0, 10.0),
                 cp2: (11.0, 12.0),
-                width: 0.6
+                width: 0.6,
+                angle: 0.0
             },
             
             BrushPoint {
                 position: (13.0, 14.0),
                 cp1: (15.0, 16.0),
                 cp2: (17.0, 18.0),
-                width: 0.7
+                width: 0.7,
+                angle: 0.0
             },
         ];
         let element = BrushElement::new(ElementId::Assigned(5), Arc::new(points));
@@ -223,21 +228,24 @@ mod test {
                 position: (1.0, 2.0),
                 cp1: (3.0, 4.0),
                 cp2: (5.0, 6.0),
-                width: 0.5
+                width: 0.5,
+                angle: 0.0
             },
 
             BrushPoint {
                 position: (7.0, 8.0),
                 cp1: (9.0, 10.0),
                 cp2: (11.0, 12.0),
-                width: 0.6
+                width: 0.6,
+                angle: 0.0
             },
             
             BrushPoint {
                 position: (13.0, 14.0),
                 cp1: (15.0, 16.0),
                 cp2: (17.0, 18.0),
-                width: 0.7
+                width: 0.7,
+                angle: 0.0
             },
         ];
         let element = BrushElement::new(ElementId::Assigned(5), Arc::new(points));
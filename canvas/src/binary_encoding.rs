@@ -0,0 +1,187 @@
+use super::draw::*;
+use super::encoding::*;
+
+use float_encoder::*;
+
+use std::mem;
+
+/// The first bytes of a binary canvas encoding
+pub const BINARY_ENCODING_MAGIC: [u8; 2] = [b'f', b'c'];
+
+/// The version of the binary encoding generated by this library
+pub const BINARY_ENCODING_VERSION: u8 = 1;
+
+//
+// The binary encoding uses the same instruction characters as the string encoding, but
+// writes numbers as little-endian bytes. Coordinates along paths are written as squished
+// floats relative to the previous coordinate, so dense strokes take 2 bytes per value
+// rather than 6.
+//
+
+impl CanvasEncoding<Vec<u8>> for char {
+    #[inline]
+    fn encode_canvas(&self, append_to: &mut Vec<u8>) {
+        // Only the ASCII characters used to identify instructions are written as characters
+        debug_assert!(self.is_ascii());
+        append_to.push(*self as u8)
+    }
+}
+
+impl CanvasEncoding<Vec<u8>> for u32 {
+    #[inline]
+    fn encode_canvas(&self, append_to: &mut Vec<u8>) {
+        let value = *self;
+
+        append_to.extend([
+            (value&0xff) as u8,
+            ((value>>8)&0xff) as u8,
+            ((value>>16)&0xff) as u8,
+            ((value>>24)&0xff) as u8
+        ].iter());
+    }
+}
+
+impl CanvasEncoding<Vec<u8>> for f32 {
+    #[inline]
+    fn encode_canvas(&self, append_to: &mut Vec<u8>) {
+        let transmuted: u32 = unsafe { mem::transmute(*self) };
+        transmuted.encode_canvas(append_to)
+    }
+}
+
+///
+/// Writes the coordinates of drawing instructions in the binary encoding
+///
+struct BinaryEncoder {
+    /// The last x coordinate, as it will be seen by the decoder
+    last_x: f64,
+
+    /// The last y coordinate, as it will be seen by the decoder
+    last_y: f64
+}
+
+impl BinaryEncoder {
+    fn new() -> BinaryEncoder {
+        BinaryEncoder {
+            last_x: 0.0,
+            last_y: 0.0
+        }
+    }
+
+    ///
+    /// Writes a coordinate relative to the previous coordinate
+    ///
+    fn coordinate(last: &mut f64, next: f32, append_to: &mut Vec<u8>) {
+        let start = append_to.len();
+        squish_float(append_to, *last, next as f64).unwrap();
+
+        // Squished floats lose precision: continue from the value the decoder will read so errors don't accumulate
+        *last = unsquish_float(&mut &append_to[start..], *last).unwrap();
+    }
+
+    ///
+    /// Writes a point relative to the previous point
+    ///
+    fn point(&mut self, (x, y): (f32, f32), append_to: &mut Vec<u8>) {
+        Self::coordinate(&mut self.last_x, x, append_to);
+        Self::coordinate(&mut self.last_y, y, append_to);
+    }
+}
+
+impl CanvasEncoding<Vec<u8>> for Vec<Draw> {
+    ///
+    /// Writes a drawing in the binary encoding, starting with a version header
+    ///
+    /// Coordinates are relative to the previous instruction, so a drawing must be decoded as a
+    /// whole rather than instruction by instruction.
+    ///
+    fn encode_canvas(&self, append_to: &mut Vec<u8>) {
+        append_to.extend(BINARY_ENCODING_MAGIC.iter());
+        append_to.push(BINARY_ENCODING_VERSION);
+
+        let mut encoder = BinaryEncoder::new();
+        self.iter().for_each(|item| encode_draw_instruction(item, append_to, &mut |point, append_to| encoder.point(point, append_to)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::color::*;
+    use super::super::transform2d::*;
+
+    fn encode_binary(drawing: Vec<Draw>) -> Vec<u8> {
+        let mut result = vec![];
+        drawing.encode_canvas(&mut result);
+        result
+    }
+
+    #[test]
+    fn starts_with_header() {
+        assert!(encode_binary(vec![]) == vec![b'f', b'c', BINARY_ENCODING_VERSION]);
+    }
+
+    #[test]
+    fn can_encode_u32() {
+        let mut encoded = vec![];
+        0xabcd1234u32.encode_canvas(&mut encoded);
+
+        assert!(encoded == vec![0x34, 0x12, 0xcd, 0xab]);
+    }
+
+    #[test]
+    fn can_encode_color() {
+        let mut encoded = vec![];
+        ('C', 'f', Color::Rgba(1.0, 0.0, 0.0, 1.0)).encode_canvas(&mut encoded);
+
+        assert!(encoded == vec![b'C', b'f', b'R', 0x00, 0x00, 0x80, 0x3f, 0, 0, 0, 0, 0, 0, 0, 0, 0x00, 0x00, 0x80, 0x3f]);
+    }
+
+    #[test]
+    fn small_moves_are_two_bytes_per_coordinate() {
+        let encoded = encode_binary(vec![Draw::Move(1.0, 2.0), Draw::Line(1.5, 2.5)]);
+
+        assert!(encoded.len() == 3 + 5 + 5);
+        assert!(encoded[3] == b'm');
+        assert!(encoded[8] == b'l');
+    }
+
+    #[test]
+    fn large_moves_are_stored_exactly() {
+        let encoded = encode_binary(vec![Draw::Move(1000.0, 0.0)]);
+
+        assert!(encoded.len() == 3 + 1 + 6 + 2);
+        assert!(unsquish_float(&mut &encoded[4..], 0.0).unwrap() == 1000.0);
+    }
+
+    #[test]
+    fn rounding_errors_do_not_accumulate() {
+        // Each step is smaller than the precision of a squished float
+        let drawing: Vec<_> = (0..1000).map(|step| Draw::Line((step as f32)*0.001, 0.0)).collect();
+        let encoded = encode_binary(drawing);
+
+        // Decode the x coordinates from the stream
+        let mut src     = &encoded[3..];
+        let mut last_x  = 0.0;
+        let mut last_y  = 0.0;
+
+        for step in 0..1000 {
+            assert!(src[0] == b'l');
+            src = &src[1..];
+
+            last_x = unsquish_float(&mut src, last_x).unwrap();
+            last_y = unsquish_float(&mut src, last_y).unwrap();
+
+            assert!((last_x - (step as f64)*0.001).abs() <= 1.0/256.0);
+            assert!(last_y == 0.0);
+        }
+    }
+
+    #[test]
+    fn transforms_are_stored_exactly() {
+        let encoded = encode_binary(vec![Draw::MultiplyTransform(Transform2D::identity())]);
+
+        assert!(encoded.len() == 3 + 2 + 9*4);
+        assert!(&encoded[3..9] == &[b'T', b'm', 0x00, 0x00, 0x80, 0x3f]);
+    }
+}
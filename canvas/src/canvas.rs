@@ -1,9 +1,11 @@
 use super::gc::*;
 use super::draw::*;
 use super::color::*;
+use super::font::*;
 use super::transform2d::*;
 
 use std::collections::vec_deque::*;
+use std::collections::HashMap;
 use std::sync::*;
 use std::mem;
 
@@ -12,16 +14,28 @@ use futures::task::Task;
 use futures::task;
 use futures::{Stream,Poll,Async};
 
+///
+/// Where an instruction in the canvas was drawn
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum DrawTarget {
+    Layer(u32),
+    Sprite(u32)
+}
+
 ///
 /// The core structure used to store details of a canvas 
 ///
 struct CanvasCore {
-    /// What was drawn since the last clear command was sent to this canvas (and the layer that it's on)
-    drawing_since_last_clear: Vec<(u32, Draw)>,
+    /// What was drawn since the last clear command was sent to this canvas (and the layer or sprite that it's on)
+    drawing_since_last_clear: Vec<(DrawTarget, Draw)>,
 
     /// The current layer that we're drawing on
     current_layer: u32,
 
+    /// The sprite that's being defined, if there is one
+    current_sprite: Option<u32>,
+
     // Tasks to notify next time we add to the canvas
     pending_streams: Vec<Arc<CanvasStream>>,
 }
@@ -52,6 +66,10 @@ impl CanvasCore {
                 (_, Draw::Clip)         => break,
                 (_, Draw::Unclip)       => break,
 
+                // Sprites are defined for the whole canvas, so can't be rewound
+                (_, Draw::StartSprite(_)) => break,
+                (_, Draw::FreeSprite(_))  => break,
+
                 // If the state stack has a pop for every push then we can remove these requests too
                 // TODO: this has a bug in that if the final event is a 'push' instead of a 'pop'
                 // then it will mistakenly believe the states can be removed
@@ -79,7 +97,7 @@ impl CanvasCore {
     ///
     /// Removes all of the drawing for the specified layer
     /// 
-    /// (Except for ClearCanvas)
+    /// (Except for ClearCanvas, and sprite definitions that might still be drawn)
     /// 
     fn clear_layer(&mut self, layer_id: u32) {
         // Take the old drawing from this object
@@ -90,15 +108,82 @@ impl CanvasCore {
         let new_drawing = old_drawing.into_iter()
             .filter(|drawing| {
                 match drawing {
-                    &(_, Draw::ClearCanvas)         => true,
-                    &(_, Draw::LayerBlend(_, _))    => true,
-                    &(layer, _)                     => layer != layer_id
+                    &(_, Draw::ClearCanvas)                 => true,
+                    &(_, Draw::LayerBlend(_, _))            => true,
+                    &(_, Draw::FreeSprite(_))               => true,
+                    &(DrawTarget::Sprite(_), _)             => true,
+                    &(DrawTarget::Layer(layer), _)          => layer != layer_id
                 }
             })
             .collect();
         
         // This becomes the new drawing for this layer
         self.drawing_since_last_clear = new_drawing;
+
+        // Sprites that were freed but kept because the layer still drew them can now be released
+        self.release_freed_sprites();
+    }
+
+    ///
+    /// Removes the definitions of any sprites that have been freed and are no longer drawn
+    ///
+    fn release_freed_sprites(&mut self) {
+        // Find the sprites whose most recent definition has been freed
+        let mut freed = HashMap::new();
+
+        for drawing in self.drawing_since_last_clear.iter() {
+            match drawing {
+                &(_, Draw::StartSprite(sprite_id))  => { freed.insert(sprite_id, false); },
+                &(_, Draw::FreeSprite(sprite_id))   => { freed.insert(sprite_id, true); },
+                _                                   => { }
+            }
+        }
+
+        let mut freed: Vec<_> = freed.into_iter()
+            .filter(|&(_, is_freed)| is_freed)
+            .map(|(sprite_id, _)| sprite_id)
+            .collect();
+
+        // Remove the definition and the free instruction for any of these sprites that are unused (releasing a sprite can make the sprites it draws unused)
+        loop {
+            let unused: Vec<_> = freed.iter().cloned().filter(|sprite_id| self.remove_unused_sprite(*sprite_id)).collect();
+            if unused.len() == 0 {
+                break;
+            }
+
+            self.drawing_since_last_clear.retain(|drawing| match drawing {
+                &(_, Draw::FreeSprite(sprite_id))   => !unused.contains(&sprite_id),
+                _                                   => true
+            });
+            freed.retain(|sprite_id| !unused.contains(sprite_id));
+        }
+    }
+
+    ///
+    /// The layer or sprite that instructions are currently being drawn on
+    ///
+    fn current_target(&self) -> DrawTarget {
+        match self.current_sprite {
+            Some(sprite_id) => DrawTarget::Sprite(sprite_id),
+            None            => DrawTarget::Layer(self.current_layer)
+        }
+    }
+
+    ///
+    /// Removes the definition of a sprite if it's not drawn anywhere, returning true if it was removed
+    ///
+    fn remove_unused_sprite(&mut self, sprite_id: u32) -> bool {
+        let is_used = self.drawing_since_last_clear.iter()
+            .any(|drawing| match drawing {
+                &(_, Draw::DrawSprite(drawn_id, _)) => drawn_id == sprite_id,
+                _                                   => false
+            });
+
+        if !is_used {
+            self.drawing_since_last_clear.retain(|drawing| drawing.0 != DrawTarget::Sprite(sprite_id));
+        }
+
+        !is_used
     }
 
     ///
@@ -116,17 +201,46 @@ impl CanvasCore {
                     // Clearing the canvas empties the command list and updates the clear count
                     self.drawing_since_last_clear   = vec![];
                     self.current_layer              = 0;
+                    self.current_sprite             = None;
                     clear_pending                   = true;
 
                     new_drawing = vec![];
 
                     // Start the new drawing with the 'clear' command
-                    self.drawing_since_last_clear.push((0, *draw));
+                    self.drawing_since_last_clear.push((DrawTarget::Layer(0), *draw));
+                },
+
+                &Draw::StartSprite(sprite_id) => {
+                    // A previous definition can be replaced if nothing has drawn it yet
+                    self.remove_unused_sprite(sprite_id);
+
+                    self.current_sprite = Some(sprite_id);
+                    self.drawing_since_last_clear.push((DrawTarget::Sprite(sprite_id), *draw));
+                },
+
+                &Draw::EndSprite => {
+                    let target = self.current_target();
+                    self.current_sprite = None;
+                    self.drawing_since_last_clear.push((target, *draw));
+                },
+
+                &Draw::FreeSprite(sprite_id) => {
+                    // The free only needs to be kept if the sprite is still used by the drawing
+                    if !self.remove_unused_sprite(sprite_id) {
+                        let target = self.current_target();
+                        self.drawing_since_last_clear.push((target, *draw));
+                    }
+                },
+
+                // Everything else that's drawn while defining a sprite is just recorded as part of that sprite
+                _ if self.current_sprite.is_some() => {
+                    let target = self.current_target();
+                    self.drawing_since_last_clear.push((target, *draw));
                 },
 
                 &Draw::Restore => {
                     // Have to push the restore in case it can't be cleared
-                    self.drawing_since_last_clear.push((self.current_layer, *draw));
+                    self.drawing_since_last_clear.push((DrawTarget::Layer(self.current_layer), *draw));
 
                     // On a 'restore' command we clear out everything since the 'store' if we can (so we don't build a backlog)
                     self.rewind_to_last_store();
@@ -139,24 +253,24 @@ impl CanvasCore {
                         self.drawing_since_last_clear.pop();
                     } else {
                         // Something else: the free becomes part of the drawing log (this is often inefficient)
-                        self.drawing_since_last_clear.push((self.current_layer, *draw));
+                        self.drawing_since_last_clear.push((DrawTarget::Layer(self.current_layer), *draw));
                     }
                 },
 
                 &Draw::Layer(new_layer) => {
                     self.current_layer = new_layer;
-                    self.drawing_since_last_clear.push((new_layer, *draw));
+                    self.drawing_since_last_clear.push((DrawTarget::Layer(new_layer), *draw));
                 },
 
                 &Draw::ClearLayer => {
                     // Remove all of the commands for the current layer, replacing them with just a switch to this layer
                     let current_layer = self.current_layer;
                     self.clear_layer(current_layer);
-                    self.drawing_since_last_clear.push((current_layer, Draw::Layer(current_layer)));
+                    self.drawing_since_last_clear.push((DrawTarget::Layer(current_layer), Draw::Layer(current_layer)));
                 },
 
                 // Default is to add to the current drawing
-                _ => self.drawing_since_last_clear.push((self.current_layer, *draw))
+                _ => self.drawing_since_last_clear.push((DrawTarget::Layer(self.current_layer), *draw))
             }
 
             // Send everything to the streams
@@ -188,8 +302,9 @@ impl Canvas {
     pub fn new() -> Canvas {
         // A canvas is initially just a clear command
         let core = CanvasCore { 
-            drawing_since_last_clear:   vec![ (0, Draw::ClearCanvas) ],
+            drawing_since_last_clear:   vec![ (DrawTarget::Layer(0), Draw::ClearCanvas) ],
             current_layer:              0,
+            current_sprite:             None,
             pending_streams:            vec![ ]
         };
 
@@ -290,6 +405,19 @@ impl<'a> GraphicsContext for CoreContext<'a> {
     fn dash_offset(&mut self, offset: f32)          { self.pending.push(Draw::DashOffset(offset)); }
     fn fill_color(&mut self, col: Color)            { self.pending.push(Draw::FillColor(col)); }
     fn stroke_color(&mut self, col: Color)          { self.pending.push(Draw::StrokeColor(col)); }
+    fn new_linear_gradient(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) { self.pending.push(Draw::NewLinearGradient((x1, y1), (x2, y2))); }
+    fn new_radial_gradient(&mut self, x1: f32, y1: f32, r1: f32, x2: f32, y2: f32, r2: f32) { self.pending.push(Draw::NewRadialGradient((x1, y1), r1, (x2, y2), r2)); }
+    fn gradient_stop(&mut self, pos: f32, col: Color) { self.pending.push(Draw::GradientStop(pos, col)); }
+    fn gradient_transform(&mut self, transform: Transform2D) { self.pending.push(Draw::GradientTransform(transform)); }
+    fn fill_gradient(&mut self)                     { self.pending.push(Draw::FillGradient); }
+    fn stroke_gradient(&mut self)                   { self.pending.push(Draw::StrokeGradient); }
+    fn font(&mut self, family: FontFamily, weight: CanvasFontWeight) { self.pending.push(Draw::Font(family, weight)); }
+    fn font_size(&mut self, size: f32)              { self.pending.push(Draw::FontSize(size)); }
+    fn text_align(&mut self, alignment: TextAlignment) { self.pending.push(Draw::TextAlign(alignment)); }
+    fn new_text(&mut self)                          { self.pending.push(Draw::NewText); }
+    fn text_char(&mut self, c: char)                { self.pending.push(Draw::TextChar(c)); }
+    fn fill_text(&mut self, x: f32, y: f32)         { self.pending.push(Draw::FillText(x, y)); }
+    fn stroke_text(&mut self, x: f32, y: f32)       { self.pending.push(Draw::StrokeText(x, y)); }
     fn blend_mode(&mut self, mode: BlendMode)       { self.pending.push(Draw::BlendMode(mode)); }
     fn identity_transform(&mut self)                { self.pending.push(Draw::IdentityTransform); }
     fn canvas_height(&mut self, height: f32)        { self.pending.push(Draw::CanvasHeight(height)); }
@@ -306,6 +434,10 @@ impl<'a> GraphicsContext for CoreContext<'a> {
     fn layer(&mut self, layer_id: u32)              { self.pending.push(Draw::Layer(layer_id)); }
     fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode) { self.pending.push(Draw::LayerBlend(layer_id, blend_mode)); }
     fn clear_layer(&mut self)                       { self.pending.push(Draw::ClearLayer); }
+    fn start_sprite(&mut self, sprite_id: u32)      { self.pending.push(Draw::StartSprite(sprite_id)); }
+    fn end_sprite(&mut self)                        { self.pending.push(Draw::EndSprite); }
+    fn draw_sprite(&mut self, sprite_id: u32, transform: Transform2D) { self.pending.push(Draw::DrawSprite(sprite_id, transform)); }
+    fn free_sprite(&mut self, sprite_id: u32)       { self.pending.push(Draw::FreeSprite(sprite_id)); }
 
     fn draw(&mut self, d: Draw)                     { self.pending.push(d); }
     fn draw_list<'b>(&'b mut self, drawing: Box<dyn 'b+Iterator<Item=Draw>>) {
@@ -783,4 +915,100 @@ mod test {
         assert!(stream.wait_stream() == Some(Ok(Draw::Move(10.0, 10.0))));
         assert!(stream.wait_stream() == Some(Ok(Draw::Fill)));
     }
+
+    #[test]
+    fn clear_layer_keeps_sprite_definitions() {
+        let canvas = Canvas::new();
+
+        canvas.draw(|gc| {
+            gc.start_sprite(1);
+            gc.new_path();
+            gc.fill();
+            gc.end_sprite();
+
+            gc.draw_sprite(1, Transform2D::identity());
+            gc.clear_layer();
+        });
+
+        assert!(canvas.get_drawing() == vec![Draw::ClearCanvas, Draw::StartSprite(1), Draw::NewPath, Draw::Fill, Draw::EndSprite, Draw::Layer(0)]);
+    }
+
+    #[test]
+    fn clear_layer_releases_freed_sprites() {
+        let canvas = Canvas::new();
+
+        canvas.draw(|gc| {
+            gc.start_sprite(1);
+            gc.new_path();
+            gc.fill();
+            gc.end_sprite();
+
+            gc.draw_sprite(1, Transform2D::identity());
+            gc.free_sprite(1);
+            gc.clear_layer();
+        });
+
+        assert!(canvas.get_drawing() == vec![Draw::ClearCanvas, Draw::Layer(0)]);
+    }
+
+    #[test]
+    fn clear_layer_keeps_freed_sprites_drawn_on_other_layers() {
+        let canvas = Canvas::new();
+
+        canvas.draw(|gc| {
+            gc.start_sprite(1);
+            gc.fill();
+            gc.end_sprite();
+
+            gc.layer(1);
+            gc.draw_sprite(1, Transform2D::identity());
+            gc.layer(0);
+            gc.draw_sprite(1, Transform2D::identity());
+            gc.free_sprite(1);
+            gc.clear_layer();
+        });
+
+        assert!(canvas.get_drawing() == vec![Draw::ClearCanvas, Draw::StartSprite(1), Draw::Fill, Draw::EndSprite, Draw::Layer(1), Draw::DrawSprite(1, Transform2D::identity()), Draw::FreeSprite(1), Draw::Layer(0)]);
+    }
+
+    #[test]
+    fn redefining_unused_sprite_replaces_it() {
+        let canvas = Canvas::new();
+
+        canvas.draw(|gc| {
+            gc.start_sprite(1);
+            gc.fill();
+            gc.end_sprite();
+
+            gc.start_sprite(1);
+            gc.stroke();
+            gc.end_sprite();
+        });
+
+        assert!(canvas.get_drawing() == vec![Draw::ClearCanvas, Draw::StartSprite(1), Draw::Stroke, Draw::EndSprite]);
+    }
+
+    #[test]
+    fn redefining_drawn_sprite_keeps_old_definition() {
+        let canvas = Canvas::new();
+
+        canvas.draw(|gc| {
+            gc.start_sprite(1);
+            gc.fill();
+            gc.end_sprite();
+            gc.draw_sprite(1, Transform2D::identity());
+
+            gc.start_sprite(1);
+            gc.stroke();
+            gc.end_sprite();
+            gc.free_sprite(1);
+        });
+
+        assert!(canvas.get_drawing() == vec![
+            Draw::ClearCanvas, 
+            Draw::StartSprite(1), Draw::Fill, Draw::EndSprite, 
+            Draw::DrawSprite(1, Transform2D::identity()),
+            Draw::StartSprite(1), Draw::Stroke, Draw::EndSprite,
+            Draw::FreeSprite(1)]);
+    }
 }
@@ -47,6 +47,76 @@ impl Color {
         }
     }
 
+    ///
+    /// Returns this colour as HSV components (hue is in degrees, saturation and value are in the range 0-1)
+    /// 
+    pub fn to_hsv_components(&self) -> (f32, f32, f32, f32) {
+        let (r, g, b, a)    = self.to_rgba_components();
+        let max             = f32::max(r, f32::max(g, b));
+        let min             = f32::min(r, f32::min(g, b));
+        let chroma          = max - min;
+
+        let h = if chroma <= 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g-b)/chroma)
+        } else if max == g {
+            60.0 * ((b-r)/chroma + 2.0)
+        } else {
+            60.0 * ((r-g)/chroma + 4.0)
+        };
+        let s = if max <= 0.0 { 0.0 } else { chroma/max };
+
+        (h.rem_euclid(360.0), s, max, a)
+    }
+
+    ///
+    /// Creates an RGBA colour from HSV components (hue is in degrees, saturation and value are in the range 0-1)
+    /// 
+    pub fn from_hsv(h: f32, s: f32, v: f32, a: f32) -> Color {
+        let h       = h.rem_euclid(360.0)/60.0;
+        let chroma  = v * s;
+        let x       = chroma * (1.0 - ((h % 2.0) - 1.0).abs());
+        let m       = v - chroma;
+
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x)
+        };
+
+        Color::Rgba(r+m, g+m, b+m, a)
+    }
+
+    ///
+    /// Returns the RGB components of this colour as a hex string in the form '#rrggbb'
+    /// 
+    pub fn to_hex_string(&self) -> String {
+        let (r, g, b, _)    = self.to_rgba_components();
+        let to_byte         = |component: f32| (component.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        format!("#{:02x}{:02x}{:02x}", to_byte(r), to_byte(g), to_byte(b))
+    }
+
+    ///
+    /// Parses a hex string in the form '#rrggbb' (the '#' is optional) into an opaque RGBA colour
+    /// 
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        let hex = hex.trim();
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let component = |idx: usize| u8::from_str_radix(&hex[idx..idx+2], 16).ok().map(|byte| (byte as f32)/255.0);
+
+        Some(Color::Rgba(component(0)?, component(2)?, component(4)?, 1.0))
+    }
+
     ///
     /// Converts this colour to another format
     /// 
@@ -118,4 +188,41 @@ mod test {
         assert!((b-0.38) < 0.1);
         assert!(a == 0.8);
     }
+
+    #[test]
+    fn can_convert_hsv_to_rgba() {
+        let (r, g, b, a) = Color::from_hsv(120.0, 0.5, 0.8, 0.7).to_rgba_components();
+
+        assert!((r-0.4).abs() < 0.001);
+        assert!((g-0.8).abs() < 0.001);
+        assert!((b-0.4).abs() < 0.001);
+        assert!(a == 0.7);
+    }
+
+    #[test]
+    fn hsv_round_trips() {
+        let rgb             = Color::Rgba(0.5, 0.7, 0.2, 0.9);
+        let (h, s, v, a)    = rgb.to_hsv_components();
+        let (r, g, b, a)    = Color::from_hsv(h, s, v, a).to_rgba_components();
+
+        assert!((r-0.5).abs() < 0.001);
+        assert!((g-0.7).abs() < 0.001);
+        assert!((b-0.2).abs() < 0.001);
+        assert!(a == 0.9);
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let hex = Color::Rgba(1.0, 0.5, 0.0, 1.0).to_hex_string();
+
+        assert!(hex == "#ff8000");
+        assert!(Color::from_hex(&hex).map(|col| col.to_hex_string()) == Some(hex));
+    }
+
+    #[test]
+    fn invalid_hex_is_rejected() {
+        assert!(Color::from_hex("#ff80").is_none());
+        assert!(Color::from_hex("#gg8000").is_none());
+        assert!(Color::from_hex("ff8000").is_some());
+    }
 }
\ No newline at end of file
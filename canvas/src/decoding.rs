@@ -0,0 +1,684 @@
+use super::draw::*;
+use super::font::*;
+use super::color::*;
+use super::transform2d::*;
+
+use std::mem;
+use std::char;
+
+///
+/// Errors that can occur while decoding a canvas stream
+///
+/// The position is the index of the character in the stream where the error was detected.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DecoderError {
+    /// An instruction started with a character that isn't a known instruction
+    UnknownInstruction(char, usize),
+
+    /// A character that selects an option (such as a line join or a colour type) wasn't recognised
+    UnknownOption(char, usize),
+
+    /// A character in a number wasn't part of the encoding character set
+    InvalidNumberCharacter(char, usize),
+
+    /// A text character was not a valid unicode code point
+    InvalidTextCharacter(u32, usize),
+
+    /// The stream finished partway through an instruction (the position is where the instruction started)
+    UnfinishedInstruction(usize)
+}
+
+///
+/// Result of trying to decode part of an instruction: None means that more characters are needed
+///
+type PartialResult<T> = Result<Option<T>, DecoderError>;
+
+///
+/// Reads the value from a partial result, or returns from the current function if more characters are needed
+///
+macro_rules! need {
+    ($partial: expr) => {
+        match $partial? {
+            Some(value) => value,
+            None        => { return Ok(None); }
+        }
+    }
+}
+
+///
+/// Reads the parts of an instruction from the characters received so far
+///
+/// Numbers are only decoded once: the values read by earlier attempts at reading the instruction
+/// are stored in the decoder and are replayed when the reader reaches them again.
+///
+struct InstructionReader<'a> {
+    /// The characters making up the instruction so far
+    chars: &'a [char],
+
+    /// The next character to read
+    pos: usize,
+
+    /// The position in the stream of the first character in the instruction
+    start: usize,
+
+    /// The numbers decoded so far for this instruction, along with the position of the character following each one
+    numbers: &'a mut Vec<(u32, usize)>,
+
+    /// The index in the numbers of the next number to read
+    next_number: usize,
+
+    /// If the instruction is incomplete, the number of characters needed before the reader can make progress
+    wanted: usize
+}
+
+impl<'a> InstructionReader<'a> {
+    ///
+    /// Reads the next character from the instruction
+    ///
+    fn read_char(&mut self) -> PartialResult<char> {
+        if self.pos < self.chars.len() {
+            self.pos += 1;
+            Ok(Some(self.chars[self.pos-1]))
+        } else {
+            self.wanted = self.pos + 1;
+            Ok(None)
+        }
+    }
+
+    ///
+    /// The position in the stream of the character that was just read
+    ///
+    fn last_pos(&self) -> usize {
+        self.start + self.pos - 1
+    }
+
+    ///
+    /// Reads a u32 value (6 base-64 characters, least significant first)
+    ///
+    fn read_u32(&mut self) -> PartialResult<u32> {
+        // Use the value from an earlier attempt if this number has already been read
+        if self.next_number < self.numbers.len() {
+            let (value, next_pos) = self.numbers[self.next_number];
+
+            self.next_number    += 1;
+            self.pos            = next_pos;
+
+            return Ok(Some(value));
+        }
+
+        // Wait until all of the characters in the number have arrived
+        if self.chars.len() < self.pos + 6 {
+            self.wanted = self.pos + 6;
+            return Ok(None);
+        }
+
+        let mut result = 0;
+
+        for index in 0..6 {
+            let c       = need!(self.read_char());
+            let value   = match c {
+                'A'..='Z'   => (c as u32) - ('A' as u32),
+                'a'..='z'   => (c as u32) - ('a' as u32) + 26,
+                '0'..='9'   => (c as u32) - ('0' as u32) + 52,
+                '+'         => 62,
+                '/'         => 63,
+                _           => { return Err(DecoderError::InvalidNumberCharacter(c, self.last_pos())); }
+            };
+
+            result |= value << (index*6);
+        }
+
+        self.numbers.push((result, self.pos));
+        self.next_number += 1;
+
+        Ok(Some(result))
+    }
+
+    ///
+    /// Reads an f32 value
+    ///
+    fn read_f32(&mut self) -> PartialResult<f32> {
+        let bits        = need!(self.read_u32());
+        let value: f32  = unsafe { mem::transmute(bits) };
+
+        Ok(Some(value))
+    }
+
+    ///
+    /// Reads a coordinate
+    ///
+    fn read_point(&mut self) -> PartialResult<(f32, f32)> {
+        let x = need!(self.read_f32());
+        let y = need!(self.read_f32());
+
+        Ok(Some((x, y)))
+    }
+
+    ///
+    /// Reads a colour
+    ///
+    fn read_color(&mut self) -> PartialResult<Color> {
+        match need!(self.read_char()) {
+            'R' => {
+                let r = need!(self.read_f32());
+                let g = need!(self.read_f32());
+                let b = need!(self.read_f32());
+                let a = need!(self.read_f32());
+
+                Ok(Some(Color::Rgba(r, g, b, a)))
+            },
+
+            other => Err(DecoderError::UnknownOption(other, self.last_pos()))
+        }
+    }
+
+    ///
+    /// Reads a transformation matrix
+    ///
+    fn read_transform(&mut self) -> PartialResult<Transform2D> {
+        let mut values = [0.0; 9];
+
+        for value in values.iter_mut() {
+            *value = need!(self.read_f32());
+        }
+
+        Ok(Some(Transform2D((values[0], values[1], values[2]), (values[3], values[4], values[5]), (values[6], values[7], values[8]))))
+    }
+
+    ///
+    /// Reads a blend mode
+    ///
+    fn read_blend_mode(&mut self) -> PartialResult<BlendMode> {
+        use self::BlendMode::*;
+
+        let group   = need!(self.read_char());
+        let mode    = need!(self.read_char());
+
+        match (group, mode) {
+            ('S', 'V')  => Ok(Some(SourceOver)),
+            ('S', 'I')  => Ok(Some(SourceIn)),
+            ('S', 'O')  => Ok(Some(SourceOut)),
+            ('D', 'V')  => Ok(Some(DestinationOver)),
+            ('D', 'I')  => Ok(Some(DestinationIn)),
+            ('D', 'O')  => Ok(Some(DestinationOut)),
+            ('S', 'A')  => Ok(Some(SourceAtop)),
+            ('D', 'A')  => Ok(Some(DestinationAtop)),
+
+            ('E', 'M')  => Ok(Some(Multiply)),
+            ('E', 'S')  => Ok(Some(Screen)),
+            ('E', 'D')  => Ok(Some(Darken)),
+            ('E', 'L')  => Ok(Some(Lighten)),
+
+            ('S', _)    |
+            ('D', _)    |
+            ('E', _)    => Err(DecoderError::UnknownOption(mode, self.last_pos())),
+            _           => Err(DecoderError::UnknownOption(group, self.last_pos()-1))
+        }
+    }
+
+    ///
+    /// Reads an option character and converts it to a value
+    ///
+    fn read_option<T, ConvertFn: Fn(char) -> Option<T>>(&mut self, convert: ConvertFn) -> PartialResult<T> {
+        let c = need!(self.read_char());
+
+        match convert(c) {
+            Some(value) => Ok(Some(value)),
+            None        => Err(DecoderError::UnknownOption(c, self.last_pos()))
+        }
+    }
+
+    ///
+    /// Reads a complete instruction
+    ///
+    fn read_draw(&mut self) -> PartialResult<Draw> {
+        use self::Draw::*;
+
+        let instruction = need!(self.read_char());
+
+        match instruction {
+            'N' => {
+                match need!(self.read_char()) {
+                    'p'     => Ok(Some(NewPath)),
+                    'A'     => Ok(Some(ClearCanvas)),
+                    'C'     => Ok(Some(ClearLayer)),
+                    'l'     => Ok(Some(Layer(need!(self.read_u32())))),
+                    'b'     => {
+                        let layer_id    = need!(self.read_u32());
+                        let blend_mode  = need!(self.read_blend_mode());
+                        Ok(Some(LayerBlend(layer_id, blend_mode)))
+                    },
+                    other   => Err(DecoderError::UnknownOption(other, self.last_pos()))
+                }
+            },
+
+            'm' => Ok(Some(Move(need!(self.read_f32()), need!(self.read_f32())))),
+            'l' => Ok(Some(Line(need!(self.read_f32()), need!(self.read_f32())))),
+            'c' => {
+                let p1 = need!(self.read_point());
+                let p2 = need!(self.read_point());
+                let p3 = need!(self.read_point());
+                Ok(Some(BezierCurve(p1, p2, p3)))
+            },
+            '.' => Ok(Some(ClosePath)),
+            'F' => Ok(Some(Fill)),
+            'S' => Ok(Some(Stroke)),
+
+            'L' => {
+                match need!(self.read_char()) {
+                    'w'     => Ok(Some(LineWidth(need!(self.read_f32())))),
+                    'p'     => Ok(Some(LineWidthPixels(need!(self.read_f32())))),
+                    'j'     => Ok(Some(LineJoin(need!(self.read_option(|c| match c {
+                        'M' => Some(self::LineJoin::Miter),
+                        'R' => Some(self::LineJoin::Round),
+                        'B' => Some(self::LineJoin::Bevel),
+                        _   => None
+                    }))))),
+                    'c'     => Ok(Some(LineCap(need!(self.read_option(|c| match c {
+                        'B' => Some(self::LineCap::Butt),
+                        'R' => Some(self::LineCap::Round),
+                        'S' => Some(self::LineCap::Square),
+                        _   => None
+                    }))))),
+                    other   => Err(DecoderError::UnknownOption(other, self.last_pos()))
+                }
+            },
+
+            'D' => {
+                match need!(self.read_char()) {
+                    'n'     => Ok(Some(NewDashPattern)),
+                    'l'     => Ok(Some(DashLength(need!(self.read_f32())))),
+                    'o'     => Ok(Some(DashOffset(need!(self.read_f32())))),
+                    other   => Err(DecoderError::UnknownOption(other, self.last_pos()))
+                }
+            },
+
+            'C' => {
+                match need!(self.read_char()) {
+                    's'     => Ok(Some(StrokeColor(need!(self.read_color())))),
+                    'f'     => Ok(Some(FillColor(need!(self.read_color())))),
+                    other   => Err(DecoderError::UnknownOption(other, self.last_pos()))
+                }
+            },
+
+            'G' => {
+                match need!(self.read_char()) {
+                    'l'     => {
+                        let start   = need!(self.read_point());
+                        let end     = need!(self.read_point());
+                        Ok(Some(NewLinearGradient(start, end)))
+                    },
+                    'r'     => {
+                        let start   = need!(self.read_point());
+                        let r1      = need!(self.read_f32());
+                        let end     = need!(self.read_point());
+                        let r2      = need!(self.read_f32());
+                        Ok(Some(NewRadialGradient(start, r1, end, r2)))
+                    },
+                    's'     => {
+                        let pos     = need!(self.read_f32());
+                        let color   = need!(self.read_color());
+                        Ok(Some(GradientStop(pos, color)))
+                    },
+                    't'     => Ok(Some(GradientTransform(need!(self.read_transform())))),
+                    'F'     => Ok(Some(FillGradient)),
+                    'S'     => Ok(Some(StrokeGradient)),
+                    other   => Err(DecoderError::UnknownOption(other, self.last_pos()))
+                }
+            },
+
+            't' => {
+                match need!(self.read_char()) {
+                    'f'     => {
+                        let family = need!(self.read_option(|c| match c {
+                            'A' => Some(FontFamily::SansSerif),
+                            'R' => Some(FontFamily::Serif),
+                            'M' => Some(FontFamily::Monospace),
+                            _   => None
+                        }));
+                        let weight = need!(self.read_option(|c| match c {
+                            'N' => Some(CanvasFontWeight::Normal),
+                            'B' => Some(CanvasFontWeight::Bold),
+                            _   => None
+                        }));
+                        Ok(Some(Font(family, weight)))
+                    },
+                    's'     => Ok(Some(FontSize(need!(self.read_f32())))),
+                    'a'     => Ok(Some(TextAlign(need!(self.read_option(|c| match c {
+                        'L' => Some(TextAlignment::Left),
+                        'C' => Some(TextAlignment::Center),
+                        'R' => Some(TextAlignment::Right),
+                        _   => None
+                    }))))),
+                    'n'     => Ok(Some(NewText)),
+                    'c'     => {
+                        let code = need!(self.read_u32());
+                        match char::from_u32(code) {
+                            Some(c) => Ok(Some(TextChar(c))),
+                            None    => Err(DecoderError::InvalidTextCharacter(code, self.last_pos()))
+                        }
+                    },
+                    'F'     => Ok(Some(FillText(need!(self.read_f32()), need!(self.read_f32())))),
+                    'S'     => Ok(Some(StrokeText(need!(self.read_f32()), need!(self.read_f32())))),
+                    other   => Err(DecoderError::UnknownOption(other, self.last_pos()))
+                }
+            },
+
+            'M' => Ok(Some(BlendMode(need!(self.read_blend_mode())))),
+
+            'T' => {
+                match need!(self.read_char()) {
+                    'i'     => Ok(Some(IdentityTransform)),
+                    'h'     => Ok(Some(CanvasHeight(need!(self.read_f32())))),
+                    'c'     => {
+                        let min = need!(self.read_point());
+                        let max = need!(self.read_point());
+                        Ok(Some(CenterRegion(min, max)))
+                    },
+                    'm'     => Ok(Some(MultiplyTransform(need!(self.read_transform())))),
+                    other   => Err(DecoderError::UnknownOption(other, self.last_pos()))
+                }
+            },
+
+            'Z' => {
+                match need!(self.read_char()) {
+                    'n'     => Ok(Some(Unclip)),
+                    'c'     => Ok(Some(Clip)),
+                    's'     => Ok(Some(Store)),
+                    'r'     => Ok(Some(Restore)),
+                    'f'     => Ok(Some(FreeStoredBuffer)),
+                    other   => Err(DecoderError::UnknownOption(other, self.last_pos()))
+                }
+            },
+
+            'X' => {
+                match need!(self.read_char()) {
+                    's'     => Ok(Some(StartSprite(need!(self.read_u32())))),
+                    'e'     => Ok(Some(EndSprite)),
+                    'd'     => {
+                        let sprite_id   = need!(self.read_u32());
+                        let transform   = need!(self.read_transform());
+                        Ok(Some(DrawSprite(sprite_id, transform)))
+                    },
+                    'f'     => Ok(Some(FreeSprite(need!(self.read_u32())))),
+                    other   => Err(DecoderError::UnknownOption(other, self.last_pos()))
+                }
+            },
+
+            'P' => Ok(Some(PushState)),
+            'p' => Ok(Some(PopState)),
+
+            other => Err(DecoderError::UnknownInstruction(other, self.last_pos()))
+        }
+    }
+}
+
+///
+/// Decodes a canvas stream (as generated by `CanvasEncoding`) back into drawing instructions
+///
+/// Characters can be supplied as they arrive: an instruction is returned as soon as all of
+/// its characters have been received.
+///
+pub struct CanvasDecoder {
+    /// The characters received for the instruction that's being decoded
+    buffer: Vec<char>,
+
+    /// The numbers read so far from the instruction that's being decoded
+    numbers: Vec<(u32, usize)>,
+
+    /// The number of characters needed in the buffer before the instruction can be read any further
+    wanted: usize,
+
+    /// The position in the stream of the first character in the buffer
+    start: usize,
+
+    /// The position in the stream of the next character
+    pos: usize
+}
+
+impl CanvasDecoder {
+    ///
+    /// Creates a new canvas decoder
+    ///
+    pub fn new() -> CanvasDecoder {
+        CanvasDecoder {
+            buffer:     vec![],
+            numbers:    vec![],
+            wanted:     0,
+            start:      0,
+            pos:        0
+        }
+    }
+
+    ///
+    /// Adds the next character from the stream, returning an instruction if it completes one
+    ///
+    pub fn decode(&mut self, next_char: char) -> Result<Option<Draw>, DecoderError> {
+        let pos = self.pos;
+        self.pos += 1;
+
+        // Whitespace separates instructions
+        if self.buffer.is_empty() {
+            if next_char.is_whitespace() {
+                return Ok(None);
+            }
+
+            self.start = pos;
+        }
+
+        self.buffer.push(next_char);
+
+        // Nothing more can be read until the characters the reader is waiting for have arrived
+        if self.buffer.len() < self.wanted {
+            return Ok(None);
+        }
+
+        // Try to read the instruction from the characters received so far
+        let (result, wanted) = {
+            let mut reader = InstructionReader { chars: &self.buffer, pos: 0, start: self.start, numbers: &mut self.numbers, next_number: 0, wanted: 0 };
+            let result      = reader.read_draw();
+
+            (result, reader.wanted)
+        };
+
+        // Start a new instruction unless more characters are needed for this one
+        match result {
+            Ok(None)    => { self.wanted = wanted; },
+            _           => {
+                self.buffer.clear();
+                self.numbers.clear();
+                self.wanted = 0;
+            }
+        }
+
+        result
+    }
+
+    ///
+    /// Indicates that the stream has finished, returning an error if it stopped partway through an instruction
+    ///
+    pub fn finish(self) -> Result<(), DecoderError> {
+        if self.buffer.is_empty() {
+            Ok(())
+        } else {
+            Err(DecoderError::UnfinishedInstruction(self.start))
+        }
+    }
+}
+
+impl Default for CanvasDecoder {
+    fn default() -> CanvasDecoder {
+        CanvasDecoder::new()
+    }
+}
+
+///
+/// Decodes a complete canvas stream into the drawing instructions it contains
+///
+pub fn decode_drawing<In: IntoIterator<Item=char>>(source: In) -> Result<Vec<Draw>, DecoderError> {
+    let mut decoder = CanvasDecoder::new();
+    let mut result  = vec![];
+
+    for c in source {
+        if let Some(draw) = decoder.decode(c)? {
+            result.push(draw);
+        }
+    }
+
+    decoder.finish()?;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::encoding::*;
+
+    fn check_round_trip(drawing: Vec<Draw>) {
+        let mut encoded = String::new();
+        drawing.encode_canvas(&mut encoded);
+
+        let decoded = decode_drawing(encoded.chars());
+
+        assert!(decoded == Ok(drawing.clone()), "{:?} decoded as {:?}", drawing, decoded);
+    }
+
+    #[test]
+    fn round_trip_every_instruction() {
+        use self::Draw::*;
+
+        let transform = Transform2D((1.0, 2.0, 3.0), (4.0, 5.0, 6.0), (7.0, 8.0, 9.0));
+
+        check_round_trip(vec![
+            NewPath,
+            Move(10.0, -20.0),
+            Line(0.5, 0.25),
+            BezierCurve((1.0, 2.0), (3.0, 4.0), (5.0, 6.0)),
+            ClosePath,
+            Fill,
+            Stroke,
+            LineWidth(2.5),
+            LineWidthPixels(1.0),
+            LineJoin(self::LineJoin::Miter),
+            LineJoin(self::LineJoin::Round),
+            LineJoin(self::LineJoin::Bevel),
+            LineCap(self::LineCap::Butt),
+            LineCap(self::LineCap::Round),
+            LineCap(self::LineCap::Square),
+            NewDashPattern,
+            DashLength(4.0),
+            DashOffset(2.0),
+            FillColor(Color::Rgba(0.1, 0.2, 0.3, 0.4)),
+            StrokeColor(Color::Rgba(1.0, 0.0, 0.5, 1.0)),
+            NewLinearGradient((0.0, 1.0), (2.0, 3.0)),
+            NewRadialGradient((0.0, 1.0), 2.0, (3.0, 4.0), 5.0),
+            GradientStop(0.5, Color::Rgba(0.25, 0.5, 0.75, 1.0)),
+            GradientTransform(transform),
+            FillGradient,
+            StrokeGradient,
+            Font(FontFamily::SansSerif, CanvasFontWeight::Normal),
+            Font(FontFamily::Serif, CanvasFontWeight::Bold),
+            Font(FontFamily::Monospace, CanvasFontWeight::Normal),
+            FontSize(14.0),
+            TextAlign(TextAlignment::Left),
+            TextAlign(TextAlignment::Center),
+            TextAlign(TextAlignment::Right),
+            NewText,
+            TextChar('a'),
+            TextChar('\n'),
+            TextChar('ü'),
+            TextChar('\u{1F600}'),
+            FillText(1.0, 2.0),
+            StrokeText(3.0, 4.0),
+            IdentityTransform,
+            CanvasHeight(1080.0),
+            CanvasHeight(-768.0),
+            CenterRegion((0.0, 0.0), (1920.0, 1080.0)),
+            MultiplyTransform(transform),
+            Unclip,
+            Clip,
+            Store,
+            Restore,
+            FreeStoredBuffer,
+            PushState,
+            PopState,
+            ClearCanvas,
+            Layer(0),
+            Layer(0xffffffff),
+            LayerBlend(3, self::BlendMode::Multiply),
+            ClearLayer,
+            StartSprite(42),
+            EndSprite,
+            DrawSprite(42, transform),
+            FreeSprite(42)
+        ]);
+    }
+
+    #[test]
+    fn round_trip_every_blend_mode() {
+        use self::BlendMode::*;
+
+        let modes = vec![SourceOver, SourceIn, SourceOut, DestinationOver, DestinationIn, DestinationOut, SourceAtop, DestinationAtop, Multiply, Screen, Darken, Lighten];
+
+        check_round_trip(modes.into_iter().map(Draw::BlendMode).collect());
+    }
+
+    #[test]
+    fn decode_one_character_at_a_time() {
+        let mut encoded = String::new();
+        Draw::Move(20.0, 20.0).encode_canvas(&mut encoded);
+
+        let mut decoder = CanvasDecoder::new();
+        let chars: Vec<_> = encoded.chars().collect();
+
+        for c in chars.iter().take(chars.len()-1) {
+            assert!(decoder.decode(*c) == Ok(None));
+        }
+
+        assert!(decoder.decode(chars[chars.len()-1]) == Ok(Some(Draw::Move(20.0, 20.0))));
+        assert!(decoder.finish() == Ok(()));
+    }
+
+    #[test]
+    fn whitespace_is_skipped() {
+        assert!(decode_drawing("Np\n  F\nS\n".chars()) == Ok(vec![Draw::NewPath, Draw::Fill, Draw::Stroke]));
+    }
+
+    #[test]
+    fn unknown_instruction_reports_position() {
+        assert!(decode_drawing("Np\nQ".chars()) == Err(DecoderError::UnknownInstruction('Q', 3)));
+    }
+
+    #[test]
+    fn unknown_option_reports_position() {
+        assert!(decode_drawing("FLx".chars()) == Err(DecoderError::UnknownOption('x', 2)));
+    }
+
+    #[test]
+    fn invalid_number_reports_position() {
+        assert!(decode_drawing("LwAA*AAA".chars()) == Err(DecoderError::InvalidNumberCharacter('*', 4)));
+    }
+
+    #[test]
+    fn invalid_text_character() {
+        let mut encoded = String::from("tc");
+        0xd800u32.encode_canvas(&mut encoded);
+
+        assert!(decode_drawing(encoded.chars()) == Err(DecoderError::InvalidTextCharacter(0xd800, 7)));
+    }
+
+    #[test]
+    fn truncated_stream_is_unfinished() {
+        assert!(decode_drawing("Np\nmAAAoBB".chars()) == Err(DecoderError::UnfinishedInstruction(3)));
+    }
+
+    #[test]
+    fn decoder_recovers_after_error() {
+        let mut decoder = CanvasDecoder::new();
+
+        assert!(decoder.decode('Q') == Err(DecoderError::UnknownInstruction('Q', 0)));
+        assert!(decoder.decode('F') == Ok(Some(Draw::Fill)));
+    }
+}
@@ -4,6 +4,9 @@
 
 use super::transform2d::*;
 use super::color::*;
+use super::font::*;
+
+use curves::bezier::path::{StrokeJoin, StrokeCap};
 
 ///
 /// Possible way to join lines 
@@ -25,6 +28,26 @@ pub enum LineCap {
     Square
 }
 
+impl From<LineJoin> for StrokeJoin {
+    fn from(join: LineJoin) -> StrokeJoin {
+        match join {
+            LineJoin::Miter => StrokeJoin::Miter,
+            LineJoin::Round => StrokeJoin::Round,
+            LineJoin::Bevel => StrokeJoin::Bevel
+        }
+    }
+}
+
+impl From<LineCap> for StrokeCap {
+    fn from(cap: LineCap) -> StrokeCap {
+        match cap {
+            LineCap::Butt   => StrokeCap::Butt,
+            LineCap::Round  => StrokeCap::Round,
+            LineCap::Square => StrokeCap::Square
+        }
+    }
+}
+
 ///
 /// Blend mode to use when drawing
 /// 
@@ -98,6 +121,45 @@ pub enum Draw {
     /// Set the line color
     StrokeColor(Color),
 
+    /// Starts defining a new linear gradient running between two points
+    NewLinearGradient((f32, f32), (f32, f32)),
+
+    /// Starts defining a new radial gradient between a start circle and an end circle (center, radius)
+    NewRadialGradient((f32, f32), f32, (f32, f32), f32),
+
+    /// Adds a colour stop (position from 0-1) to the gradient that's being defined
+    GradientStop(f32, Color),
+
+    /// Sets the transformation applied to the gradient that's being defined
+    GradientTransform(Transform2D),
+
+    /// Fills future paths using the most recently defined gradient
+    FillGradient,
+
+    /// Strokes future paths using the most recently defined gradient
+    StrokeGradient,
+
+    /// Selects the font family and weight used for text
+    Font(FontFamily, CanvasFontWeight),
+
+    /// Sets the size of the font used for text (in canvas units)
+    FontSize(f32),
+
+    /// Sets how text is aligned relative to the point it's drawn at
+    TextAlign(TextAlignment),
+
+    /// Resets the text run to empty
+    NewText,
+
+    /// Adds a character to the current text run
+    TextChar(char),
+
+    /// Fills the current text run with its baseline starting at the specified point
+    FillText(f32, f32),
+
+    /// Draws the outline of the current text run with its baseline starting at the specified point
+    StrokeText(f32, f32),
+
     /// Set how future renderings are blended with one another
     BlendMode(BlendMode),
 
@@ -156,5 +218,21 @@ pub enum Draw {
     LayerBlend(u32, BlendMode),
 
     /// Clears the current layer
-    ClearLayer
+    ClearLayer,
+
+    /// Starts defining the sprite with the specified ID, replacing any existing definition
+    /// 
+    /// Instructions up to the next EndSprite are recorded into the sprite instead of being drawn.
+    StartSprite(u32),
+
+    /// Finishes defining a sprite
+    EndSprite,
+
+    /// Draws a sprite on the current layer, with a transformation applied to its coordinates
+    /// 
+    /// Renderers may cache the rendered sprite, so a sprite should set any colours or line styles that it uses
+    DrawSprite(u32, Transform2D),
+
+    /// Releases the definition of a sprite
+    FreeSprite(u32)
 }
@@ -1,5 +1,6 @@
 use super::draw::*;
 use super::color::*;
+use super::font::*;
 use super::transform2d::*;
 
 use std::mem;
@@ -55,23 +56,23 @@ impl CanvasEncoding<String> for f32 {
 // Some convenience encodings for implementing the main canvas encoding
 //
 
-impl<A: CanvasEncoding<String>, B: CanvasEncoding<String>> CanvasEncoding<String> for (A, B) {
-    fn encode_canvas(&self, append_to: &mut String) {
+impl<Buffer, A: CanvasEncoding<Buffer>, B: CanvasEncoding<Buffer>> CanvasEncoding<Buffer> for (A, B) {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
         self.0.encode_canvas(append_to);
         self.1.encode_canvas(append_to);
     }
 }
 
-impl<A: CanvasEncoding<String>, B: CanvasEncoding<String>, C: CanvasEncoding<String>> CanvasEncoding<String> for (A, B, C) {
-    fn encode_canvas(&self, append_to: &mut String) {
+impl<Buffer, A: CanvasEncoding<Buffer>, B: CanvasEncoding<Buffer>, C: CanvasEncoding<Buffer>> CanvasEncoding<Buffer> for (A, B, C) {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
         self.0.encode_canvas(append_to);
         self.1.encode_canvas(append_to);
         self.2.encode_canvas(append_to);
     }
 }
 
-impl<A: CanvasEncoding<String>, B: CanvasEncoding<String>, C: CanvasEncoding<String>, D: CanvasEncoding<String>> CanvasEncoding<String> for (A, B, C, D) {
-    fn encode_canvas(&self, append_to: &mut String) {
+impl<Buffer, A: CanvasEncoding<Buffer>, B: CanvasEncoding<Buffer>, C: CanvasEncoding<Buffer>, D: CanvasEncoding<Buffer>> CanvasEncoding<Buffer> for (A, B, C, D) {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
         self.0.encode_canvas(append_to);
         self.1.encode_canvas(append_to);
         self.2.encode_canvas(append_to);
@@ -79,8 +80,8 @@ impl<A: CanvasEncoding<String>, B: CanvasEncoding<String>, C: CanvasEncoding<Str
     }
 }
 
-impl<A: CanvasEncoding<String>, B: CanvasEncoding<String>, C: CanvasEncoding<String>, D: CanvasEncoding<String>, E: CanvasEncoding<String>> CanvasEncoding<String> for (A, B, C, D, E) {
-    fn encode_canvas(&self, append_to: &mut String) {
+impl<Buffer, A: CanvasEncoding<Buffer>, B: CanvasEncoding<Buffer>, C: CanvasEncoding<Buffer>, D: CanvasEncoding<Buffer>, E: CanvasEncoding<Buffer>> CanvasEncoding<Buffer> for (A, B, C, D, E) {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
         self.0.encode_canvas(append_to);
         self.1.encode_canvas(append_to);
         self.2.encode_canvas(append_to);
@@ -93,8 +94,8 @@ impl<A: CanvasEncoding<String>, B: CanvasEncoding<String>, C: CanvasEncoding<Str
 // Main canvas encoding
 //
 
-impl CanvasEncoding<String> for Color {
-    fn encode_canvas(&self, append_to: &mut String) {
+impl<Buffer> CanvasEncoding<Buffer> for Color where char: CanvasEncoding<Buffer>, f32: CanvasEncoding<Buffer> {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
         match self {
             &Color::Rgba(r,g,b,a) => ('R', r, g, b, a),
             
@@ -106,8 +107,8 @@ impl CanvasEncoding<String> for Color {
     }
 }
 
-impl CanvasEncoding<String> for LineJoin {
-    fn encode_canvas(&self, append_to: &mut String) {
+impl<Buffer> CanvasEncoding<Buffer> for LineJoin where char: CanvasEncoding<Buffer> {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
         use self::LineJoin::*;
 
         match self {
@@ -118,8 +119,8 @@ impl CanvasEncoding<String> for LineJoin {
     }
 }
 
-impl CanvasEncoding<String> for LineCap {
-    fn encode_canvas(&self, append_to: &mut String) {
+impl<Buffer> CanvasEncoding<Buffer> for LineCap where char: CanvasEncoding<Buffer> {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
         use self::LineCap::*;
 
         match self {
@@ -130,8 +131,8 @@ impl CanvasEncoding<String> for LineCap {
     }
 }
 
-impl CanvasEncoding<String> for BlendMode {
-    fn encode_canvas(&self, append_to: &mut String) {
+impl<Buffer> CanvasEncoding<Buffer> for BlendMode where char: CanvasEncoding<Buffer> {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
         use self::BlendMode::*;
 
         match self {
@@ -152,8 +153,43 @@ impl CanvasEncoding<String> for BlendMode {
     }
 }
 
-impl CanvasEncoding<String> for Transform2D {
-    fn encode_canvas(&self, append_to: &mut String) {
+impl<Buffer> CanvasEncoding<Buffer> for FontFamily where char: CanvasEncoding<Buffer> {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
+        use self::FontFamily::*;
+
+        match self {
+            &SansSerif  => 'A',
+            &Serif      => 'R',
+            &Monospace  => 'M'
+        }.encode_canvas(append_to)
+    }
+}
+
+impl<Buffer> CanvasEncoding<Buffer> for CanvasFontWeight where char: CanvasEncoding<Buffer> {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
+        use self::CanvasFontWeight::*;
+
+        match self {
+            &Normal => 'N',
+            &Bold   => 'B'
+        }.encode_canvas(append_to)
+    }
+}
+
+impl<Buffer> CanvasEncoding<Buffer> for TextAlignment where char: CanvasEncoding<Buffer> {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
+        use self::TextAlignment::*;
+
+        match self {
+            &Left   => 'L',
+            &Center => 'C',
+            &Right  => 'R'
+        }.encode_canvas(append_to)
+    }
+}
+
+impl<Buffer> CanvasEncoding<Buffer> for Transform2D where f32: CanvasEncoding<Buffer> {
+    fn encode_canvas(&self, append_to: &mut Buffer) {
         let Transform2D(a, b, c) = *self;
         a.encode_canvas(append_to);
         b.encode_canvas(append_to);
@@ -161,44 +197,70 @@ impl CanvasEncoding<String> for Transform2D {
     }
 }
 
+///
+/// Encodes a drawing instruction, using a function to write the coordinates along paths (so encodings can store them
+/// relative to each other)
+///
+pub fn encode_draw_instruction<Buffer, EncodePoint>(draw: &Draw, append_to: &mut Buffer, encode_point: &mut EncodePoint)
+where EncodePoint: FnMut((f32, f32), &mut Buffer), char: CanvasEncoding<Buffer>, u32: CanvasEncoding<Buffer>, f32: CanvasEncoding<Buffer> {
+    use self::Draw::*;
+
+    match draw {
+        &NewPath                                => ('N', 'p').encode_canvas(append_to),
+        &Move(x, y)                             => { 'm'.encode_canvas(append_to); encode_point((x, y), append_to); },
+        &Line(x, y)                             => { 'l'.encode_canvas(append_to); encode_point((x, y), append_to); },
+        &BezierCurve(p1, p2, p3)                => { 'c'.encode_canvas(append_to); encode_point(p1, append_to); encode_point(p2, append_to); encode_point(p3, append_to); },
+        &ClosePath                              => ('.').encode_canvas(append_to),
+        &Fill                                   => 'F'.encode_canvas(append_to),
+        &Stroke                                 => 'S'.encode_canvas(append_to),
+        &LineWidth(width)                       => ('L', 'w', width).encode_canvas(append_to),
+        &LineWidthPixels(width)                 => ('L', 'p', width).encode_canvas(append_to),
+        &LineJoin(join)                         => ('L', 'j', join).encode_canvas(append_to),
+        &LineCap(cap)                           => ('L', 'c', cap).encode_canvas(append_to),
+        &NewDashPattern                         => ('D', 'n').encode_canvas(append_to),
+        &DashLength(length)                     => ('D', 'l', length).encode_canvas(append_to),
+        &DashOffset(offset)                     => ('D', 'o', offset).encode_canvas(append_to),
+        &StrokeColor(col)                       => ('C', 's', col).encode_canvas(append_to),
+        &FillColor(col)                         => ('C', 'f', col).encode_canvas(append_to),
+        &NewLinearGradient(start, end)          => ('G', 'l', start, end).encode_canvas(append_to),
+        &NewRadialGradient(start, r1, end, r2)  => ('G', 'r', (start, r1), (end, r2)).encode_canvas(append_to),
+        &GradientStop(pos, col)                 => ('G', 's', pos, col).encode_canvas(append_to),
+        &GradientTransform(transform)           => ('G', 't', transform).encode_canvas(append_to),
+        &FillGradient                           => ('G', 'F').encode_canvas(append_to),
+        &StrokeGradient                         => ('G', 'S').encode_canvas(append_to),
+        &Font(family, weight)                   => ('t', 'f', family, weight).encode_canvas(append_to),
+        &FontSize(size)                         => ('t', 's', size).encode_canvas(append_to),
+        &TextAlign(alignment)                   => ('t', 'a', alignment).encode_canvas(append_to),
+        &NewText                                => ('t', 'n').encode_canvas(append_to),
+        &TextChar(c)                            => ('t', 'c', c as u32).encode_canvas(append_to),
+        &FillText(x, y)                         => { ('t', 'F').encode_canvas(append_to); encode_point((x, y), append_to); },
+        &StrokeText(x, y)                       => { ('t', 'S').encode_canvas(append_to); encode_point((x, y), append_to); },
+        &BlendMode(mode)                        => ('M', mode).encode_canvas(append_to),
+        &IdentityTransform                      => ('T', 'i').encode_canvas(append_to),
+        &CanvasHeight(height)                   => ('T', 'h', height).encode_canvas(append_to),
+        &CenterRegion(min, max)                 => ('T', 'c', min, max).encode_canvas(append_to),
+        &MultiplyTransform(transform)           => ('T', 'm', transform).encode_canvas(append_to),
+        &Unclip                                 => ('Z', 'n').encode_canvas(append_to),
+        &Clip                                   => ('Z', 'c').encode_canvas(append_to),
+        &Store                                  => ('Z', 's').encode_canvas(append_to),
+        &Restore                                => ('Z', 'r').encode_canvas(append_to),
+        &FreeStoredBuffer                       => ('Z', 'f').encode_canvas(append_to),
+        &PushState                              => 'P'.encode_canvas(append_to),
+        &PopState                               => 'p'.encode_canvas(append_to),
+        &ClearCanvas                            => ('N', 'A').encode_canvas(append_to),
+        &Layer(layer_id)                        => ('N', 'l', layer_id).encode_canvas(append_to),
+        &LayerBlend(layer_id, blend_mode)       => ('N', 'b', layer_id, blend_mode).encode_canvas(append_to),
+        &ClearLayer                             => ('N', 'C').encode_canvas(append_to),
+        &StartSprite(sprite_id)                 => ('X', 's', sprite_id).encode_canvas(append_to),
+        &EndSprite                              => ('X', 'e').encode_canvas(append_to),
+        &DrawSprite(sprite_id, transform)       => ('X', 'd', sprite_id, transform).encode_canvas(append_to),
+        &FreeSprite(sprite_id)                  => ('X', 'f', sprite_id).encode_canvas(append_to)
+    }
+}
+
 impl CanvasEncoding<String> for Draw {
     fn encode_canvas(&self, append_to: &mut String) {
-        use self::Draw::*;
-
-        match self {
-            &NewPath                                => ('N', 'p').encode_canvas(append_to),
-            &Move(x, y)                             => ('m', x, y).encode_canvas(append_to),
-            &Line(x, y)                             => ('l', x, y).encode_canvas(append_to),
-            &BezierCurve(p1, p2, p3)                => ('c', p1, p2, p3).encode_canvas(append_to),
-            &ClosePath                              => ('.').encode_canvas(append_to),
-            &Fill                                   => 'F'.encode_canvas(append_to),
-            &Stroke                                 => 'S'.encode_canvas(append_to),
-            &LineWidth(width)                       => ('L', 'w', width).encode_canvas(append_to),
-            &LineWidthPixels(width)                 => ('L', 'p', width).encode_canvas(append_to),
-            &LineJoin(join)                         => ('L', 'j', join).encode_canvas(append_to),
-            &LineCap(cap)                           => ('L', 'c', cap).encode_canvas(append_to),
-            &NewDashPattern                         => ('D', 'n').encode_canvas(append_to),
-            &DashLength(length)                     => ('D', 'l', length).encode_canvas(append_to),
-            &DashOffset(offset)                     => ('D', 'o', offset).encode_canvas(append_to),
-            &StrokeColor(col)                       => ('C', 's', col).encode_canvas(append_to),
-            &FillColor(col)                         => ('C', 'f', col).encode_canvas(append_to),
-            &BlendMode(mode)                        => ('M', mode).encode_canvas(append_to),
-            &IdentityTransform                      => ('T', 'i').encode_canvas(append_to),
-            &CanvasHeight(height)                   => ('T', 'h', height).encode_canvas(append_to),
-            &CenterRegion(min, max)                 => ('T', 'c', min, max).encode_canvas(append_to),
-            &MultiplyTransform(transform)           => ('T', 'm', transform).encode_canvas(append_to),
-            &Unclip                                 => ('Z', 'n').encode_canvas(append_to),
-            &Clip                                   => ('Z', 'c').encode_canvas(append_to),
-            &Store                                  => ('Z', 's').encode_canvas(append_to),
-            &Restore                                => ('Z', 'r').encode_canvas(append_to),
-            &FreeStoredBuffer                       => ('Z', 'f').encode_canvas(append_to),
-            &PushState                              => 'P'.encode_canvas(append_to),
-            &PopState                               => 'p'.encode_canvas(append_to),
-            &ClearCanvas                            => ('N', 'A').encode_canvas(append_to),
-            &Layer(layer_id)                        => ('N', 'l', layer_id).encode_canvas(append_to),
-            &LayerBlend(layer_id, blend_mode)       => ('N', 'b', layer_id, blend_mode).encode_canvas(append_to),
-            &ClearLayer                             => ('N', 'C').encode_canvas(append_to)
-        }
+        encode_draw_instruction(self, append_to, &mut |point, append_to| point.encode_canvas(append_to));
     }
 }
 
@@ -271,6 +333,32 @@ mod test {
     #[test]
     fn can_encode_fillcolor() { assert!(&encode_draw(Draw::FillColor(Color::Rgba(1.0, 1.0, 1.0, 1.0))) == "CfRAAAg/AAAAg/AAAAg/AAAAg/A") }
     #[test]
+    fn can_encode_new_linear_gradient() { assert!(&encode_draw(Draw::NewLinearGradient((20.0, 20.0), (1.0, 1.0))) == "GlAAAoBBAAAoBBAAAg/AAAAg/A") }
+    #[test]
+    fn can_encode_new_radial_gradient() { assert!(&encode_draw(Draw::NewRadialGradient((20.0, 20.0), 0.0, (20.0, 20.0), 1.0)) == "GrAAAoBBAAAoBBAAAAAAAAAoBBAAAoBBAAAg/A") }
+    #[test]
+    fn can_encode_gradient_stop() { assert!(&encode_draw(Draw::GradientStop(1.0, Color::Rgba(1.0, 1.0, 1.0, 1.0))) == "GsAAAg/ARAAAg/AAAAg/AAAAg/AAAAg/A") }
+    #[test]
+    fn can_encode_gradient_transform() { assert!(&encode_draw(Draw::GradientTransform(Transform2D((1.0, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 0.0, 0.0)))) == "GtAAAg/AAAAAAAAAAAAAAAAg/AAAAAAAAAAAAAAAAg/AAAAAAAAAAAAA") }
+    #[test]
+    fn can_encode_fill_gradient() { assert!(&encode_draw(Draw::FillGradient) == "GF") }
+    #[test]
+    fn can_encode_stroke_gradient() { assert!(&encode_draw(Draw::StrokeGradient) == "GS") }
+    #[test]
+    fn can_encode_font() { assert!(&encode_draw(Draw::Font(FontFamily::Serif, CanvasFontWeight::Bold)) == "tfRB") }
+    #[test]
+    fn can_encode_font_size() { assert!(&encode_draw(Draw::FontSize(20.0)) == "tsAAAoBB") }
+    #[test]
+    fn can_encode_text_align() { assert!(&encode_draw(Draw::TextAlign(TextAlignment::Center)) == "taC") }
+    #[test]
+    fn can_encode_new_text() { assert!(&encode_draw(Draw::NewText) == "tn") }
+    #[test]
+    fn can_encode_text_char() { assert!(&encode_draw(Draw::TextChar('A')) == "tcBBAAAA") }
+    #[test]
+    fn can_encode_fill_text() { assert!(&encode_draw(Draw::FillText(20.0, 20.0)) == "tFAAAoBBAAAoBB") }
+    #[test]
+    fn can_encode_stroke_text() { assert!(&encode_draw(Draw::StrokeText(20.0, 20.0)) == "tSAAAoBBAAAoBB") }
+    #[test]
     fn can_encode_blendmode() { assert!(&encode_draw(Draw::BlendMode(BlendMode::SourceOver)) == "MSV") }
     #[test]
     fn can_encode_identity_transform() { assert!(&encode_draw(Draw::IdentityTransform) == "Ti") }
@@ -296,4 +384,16 @@ mod test {
     fn can_encode_layer() { assert!(&encode_draw(Draw::Layer(2)) == "NlCAAAAA") }
     #[test]
     fn can_encode_clearlayer() { assert!(&encode_draw(Draw::ClearLayer) == "NC") }
+
+    #[test]
+    fn can_encode_start_sprite() { assert!(&encode_draw(Draw::StartSprite(1)) == "XsBAAAAA") }
+
+    #[test]
+    fn can_encode_end_sprite() { assert!(&encode_draw(Draw::EndSprite) == "Xe") }
+
+    #[test]
+    fn can_encode_draw_sprite() { assert!(&encode_draw(Draw::DrawSprite(1, Transform2D::identity())) == "XdBAAAAAAAAg/AAAAAAAAAAAAAAAAAAAAAAg/AAAAAAAAAAAAAAAAAAAAAAg/A") }
+
+    #[test]
+    fn can_encode_free_sprite() { assert!(&encode_draw(Draw::FreeSprite(1)) == "XfBAAAAA") }
 }
@@ -0,0 +1,93 @@
+///
+/// The generic font families that can be selected on a canvas
+///
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum FontFamily {
+    SansSerif,
+    Serif,
+    Monospace
+}
+
+///
+/// The weight of a font drawn on a canvas
+///
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum CanvasFontWeight {
+    Normal,
+    Bold
+}
+
+///
+/// How a text run is aligned relative to the position it's drawn at
+///
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum TextAlignment {
+    Left,
+    Center,
+    Right
+}
+
+///
+/// The size of a run of text (in canvas units)
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TextMetrics {
+    /// The distance from the start of the text to where the next character would be drawn
+    pub width: f32,
+
+    /// How far the font extends above the baseline
+    pub ascent: f32,
+
+    /// How far the font extends below the baseline
+    pub descent: f32
+}
+
+///
+/// Trait implemented by objects that can measure text in a particular font
+///
+pub trait FontMetrics {
+    ///
+    /// Measures a run of text when rendered in a particular font
+    ///
+    fn measure_text(&self, family: FontFamily, weight: CanvasFontWeight, size: f32, text: &str) -> TextMetrics;
+}
+
+///
+/// Font metrics that estimate the size of text without a renderer
+///
+/// Used where the text is laid out by a target that can't be queried, such as a browser.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ApproximateFontMetrics;
+
+impl FontMetrics for ApproximateFontMetrics {
+    fn measure_text(&self, family: FontFamily, weight: CanvasFontWeight, size: f32, text: &str) -> TextMetrics {
+        // Average advance of a character, as a proportion of the font size
+        let char_width = match (family, weight) {
+            (FontFamily::Monospace, _)      => 0.6,
+            (_, CanvasFontWeight::Normal)   => 0.5,
+            (_, CanvasFontWeight::Bold)     => 0.55
+        };
+
+        TextMetrics {
+            width:      (text.chars().count() as f32) * char_width * size,
+            ascent:     size * 0.8,
+            descent:    size * 0.2
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn approximate_width_scales_with_length() {
+        let short   = ApproximateFontMetrics.measure_text(FontFamily::Monospace, CanvasFontWeight::Normal, 10.0, "12");
+        let long    = ApproximateFontMetrics.measure_text(FontFamily::Monospace, CanvasFontWeight::Normal, 10.0, "1234");
+
+        assert!((short.width - 12.0).abs() < 0.01);
+        assert!((long.width - 24.0).abs() < 0.01);
+        assert!((long.ascent + long.descent - 10.0).abs() < 0.01);
+    }
+}
@@ -1,5 +1,6 @@
 use super::draw::*;
 use super::color::*;
+use super::font::*;
 use super::transform2d::*;
 
 use curves::*;
@@ -28,6 +29,19 @@ pub trait GraphicsContext {
     fn dash_offset(&mut self, offset: f32);
     fn fill_color(&mut self, col: Color);
     fn stroke_color(&mut self, col: Color);
+    fn new_linear_gradient(&mut self, x1: f32, y1: f32, x2: f32, y2: f32);
+    fn new_radial_gradient(&mut self, x1: f32, y1: f32, r1: f32, x2: f32, y2: f32, r2: f32);
+    fn gradient_stop(&mut self, pos: f32, col: Color);
+    fn gradient_transform(&mut self, transform: Transform2D);
+    fn fill_gradient(&mut self);
+    fn stroke_gradient(&mut self);
+    fn font(&mut self, family: FontFamily, weight: CanvasFontWeight);
+    fn font_size(&mut self, size: f32);
+    fn text_align(&mut self, alignment: TextAlignment);
+    fn new_text(&mut self);
+    fn text_char(&mut self, c: char);
+    fn fill_text(&mut self, x: f32, y: f32);
+    fn stroke_text(&mut self, x: f32, y: f32);
     fn blend_mode(&mut self, mode: BlendMode);
     fn identity_transform(&mut self);
     fn canvas_height(&mut self, height: f32);
@@ -44,6 +58,10 @@ pub trait GraphicsContext {
     fn layer(&mut self, layer_id: u32);
     fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode);
     fn clear_layer(&mut self);
+    fn start_sprite(&mut self, sprite_id: u32);
+    fn end_sprite(&mut self);
+    fn draw_sprite(&mut self, sprite_id: u32, transform: Transform2D);
+    fn free_sprite(&mut self, sprite_id: u32);
 
     fn draw(&mut self, d: Draw) {
         use self::Draw::*;
@@ -65,6 +83,19 @@ pub trait GraphicsContext {
             DashOffset(dash_offset)                     => self.dash_offset(dash_offset),
             FillColor(col)                              => self.fill_color(col),
             StrokeColor(col)                            => self.stroke_color(col),
+            NewLinearGradient((x1, y1), (x2, y2))       => self.new_linear_gradient(x1, y1, x2, y2),
+            NewRadialGradient((x1, y1), r1, (x2, y2), r2) => self.new_radial_gradient(x1, y1, r1, x2, y2, r2),
+            GradientStop(pos, col)                      => self.gradient_stop(pos, col),
+            GradientTransform(transform)                => self.gradient_transform(transform),
+            FillGradient                                => self.fill_gradient(),
+            StrokeGradient                              => self.stroke_gradient(),
+            Font(family, weight)                        => self.font(family, weight),
+            FontSize(size)                              => self.font_size(size),
+            TextAlign(alignment)                        => self.text_align(alignment),
+            NewText                                     => self.new_text(),
+            TextChar(c)                                 => self.text_char(c),
+            FillText(x, y)                              => self.fill_text(x, y),
+            StrokeText(x, y)                            => self.stroke_text(x, y),
             BlendMode(blendmode)                        => self.blend_mode(blendmode),
             IdentityTransform                           => self.identity_transform(),
             CanvasHeight(height)                        => self.canvas_height(height),
@@ -80,7 +111,11 @@ pub trait GraphicsContext {
             ClearCanvas                                 => self.clear_canvas(),
             Layer(layer_id)                             => self.layer(layer_id),
             LayerBlend(layer_id, blend_mode)            => self.layer_blend(layer_id, blend_mode),
-            ClearLayer                                  => self.clear_layer()
+            ClearLayer                                  => self.clear_layer(),
+            StartSprite(sprite_id)                      => self.start_sprite(sprite_id),
+            EndSprite                                   => self.end_sprite(),
+            DrawSprite(sprite_id, transform)            => self.draw_sprite(sprite_id, transform),
+            FreeSprite(sprite_id)                       => self.free_sprite(sprite_id)
         }
     }
 
@@ -112,6 +147,15 @@ pub trait GraphicsPrimitives : GraphicsContext {
             self.draw(d);
         }
     }
+
+    ///
+    /// Fills some text with its baseline starting at a particular point
+    /// 
+    fn text(&mut self, text: &str, x: f32, y: f32) {
+        for d in draw_text(text, x, y) {
+            self.draw(d);
+        }
+    }
 }
 
 ///
@@ -130,6 +174,18 @@ pub fn draw_rect(x1: f32, y1: f32, x2: f32, y2: f32) -> Vec<Draw> {
     ]
 }
 
+///
+/// Returns the drawing commands that fill a run of text
+/// 
+pub fn draw_text(text: &str, x: f32, y: f32) -> Vec<Draw> {
+    use self::Draw::*;
+
+    iter::once(NewText)
+        .chain(text.chars().map(|c| TextChar(c)))
+        .chain(iter::once(FillText(x, y)))
+        .collect()
+}
+
 ///
 /// Returns the drawing commands for a circle
 /// 
@@ -193,6 +249,19 @@ impl GraphicsContext for Vec<Draw> {
     #[inline] fn dash_offset(&mut self, offset: f32)                                    { self.push(Draw::DashOffset(offset)); }
     #[inline] fn fill_color(&mut self, col: Color)                                      { self.push(Draw::FillColor(col)); }
     #[inline] fn stroke_color(&mut self, col: Color)                                    { self.push(Draw::StrokeColor(col)); }
+    #[inline] fn new_linear_gradient(&mut self, x1: f32, y1: f32, x2: f32, y2: f32)     { self.push(Draw::NewLinearGradient((x1, y1), (x2, y2))); }
+    #[inline] fn new_radial_gradient(&mut self, x1: f32, y1: f32, r1: f32, x2: f32, y2: f32, r2: f32) { self.push(Draw::NewRadialGradient((x1, y1), r1, (x2, y2), r2)); }
+    #[inline] fn gradient_stop(&mut self, pos: f32, col: Color)                         { self.push(Draw::GradientStop(pos, col)); }
+    #[inline] fn gradient_transform(&mut self, transform: Transform2D)                  { self.push(Draw::GradientTransform(transform)); }
+    #[inline] fn fill_gradient(&mut self)                                               { self.push(Draw::FillGradient); }
+    #[inline] fn stroke_gradient(&mut self)                                             { self.push(Draw::StrokeGradient); }
+    #[inline] fn font(&mut self, family: FontFamily, weight: CanvasFontWeight)          { self.push(Draw::Font(family, weight)); }
+    #[inline] fn font_size(&mut self, size: f32)                                        { self.push(Draw::FontSize(size)); }
+    #[inline] fn text_align(&mut self, alignment: TextAlignment)                        { self.push(Draw::TextAlign(alignment)); }
+    #[inline] fn new_text(&mut self)                                                    { self.push(Draw::NewText); }
+    #[inline] fn text_char(&mut self, c: char)                                          { self.push(Draw::TextChar(c)); }
+    #[inline] fn fill_text(&mut self, x: f32, y: f32)                                   { self.push(Draw::FillText(x, y)); }
+    #[inline] fn stroke_text(&mut self, x: f32, y: f32)                                 { self.push(Draw::StrokeText(x, y)); }
     #[inline] fn blend_mode(&mut self, mode: BlendMode)                                 { self.push(Draw::BlendMode(mode)); }
     #[inline] fn identity_transform(&mut self)                                          { self.push(Draw::IdentityTransform); }
     #[inline] fn canvas_height(&mut self, height: f32)                                  { self.push(Draw::CanvasHeight(height)); }
@@ -209,6 +278,10 @@ impl GraphicsContext for Vec<Draw> {
     #[inline] fn layer(&mut self, layer_id: u32)                                        { self.push(Draw::Layer(layer_id)); }
     #[inline] fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode)           { self.push(Draw::LayerBlend(layer_id, blend_mode)); }
     #[inline] fn clear_layer(&mut self)                                                 { self.push(Draw::ClearLayer); }
+    #[inline] fn start_sprite(&mut self, sprite_id: u32)                                { self.push(Draw::StartSprite(sprite_id)); }
+    #[inline] fn end_sprite(&mut self)                                                  { self.push(Draw::EndSprite); }
+    #[inline] fn draw_sprite(&mut self, sprite_id: u32, transform: Transform2D)         { self.push(Draw::DrawSprite(sprite_id, transform)); }
+    #[inline] fn free_sprite(&mut self, sprite_id: u32)                                 { self.push(Draw::FreeSprite(sprite_id)); }
 
     #[inline]
     fn draw(&mut self, d: Draw) {
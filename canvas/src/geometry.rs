@@ -0,0 +1,599 @@
+use super::draw::*;
+use super::font::*;
+use super::sprite::*;
+use super::transform2d::*;
+
+use curves::*;
+use curves::bezier;
+
+/// The longest line segment used when approximating a curve (in canvas units)
+const MAX_SEGMENT_LENGTH: f64 = 2.0;
+
+/// The most line segments used when approximating a single curve
+const MAX_SEGMENTS_PER_CURVE: usize = 256;
+
+///
+/// How a path was rendered
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PathOperation {
+    /// The inside of the path was filled
+    Fill,
+
+    /// The outline of the path was drawn with a line of the specified width (in canvas units)
+    ///
+    /// Lines with widths set in pixels have a width of 0 here, as their size depends on the view.
+    Stroke(f32)
+}
+
+///
+/// A subpath, approximated as a series of line segments
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Subpath {
+    /// The points making up this subpath
+    pub points: Vec<Coord2>,
+
+    /// True if the subpath was closed with `ClosePath`
+    pub closed: bool
+}
+
+///
+/// A path that was rendered by a drawing
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct DrawnPath {
+    /// The layer that the path was drawn on
+    pub layer: u32,
+
+    /// The index of the instruction that rendered this path
+    pub instruction_index: usize,
+
+    /// How the path was rendered
+    pub operation: PathOperation,
+
+    /// The subpaths making up this path, in canvas coordinates
+    pub subpaths: Vec<Subpath>
+}
+
+///
+/// The state that affects how paths are drawn
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct GeometryState {
+    transform:      Transform2D,
+    line_width:     f32,
+    font:           (FontFamily, CanvasFontWeight),
+    font_size:      f32,
+    text_align:     TextAlignment
+}
+
+///
+/// Describes the shapes rendered by a list of drawing instructions
+///
+/// Coordinates are in canvas units: instructions that set up the view (`CanvasHeight` and
+/// `CenterRegion`) reset the transform, while `MultiplyTransform` moves what's drawn. Text is
+/// represented by a rectangle covering its approximate extent. Clipping is not taken into account.
+///
+pub struct DrawingGeometry {
+    /// The paths that have been drawn, in the order they were drawn
+    paths: Vec<DrawnPath>,
+
+    /// The current drawing state
+    state: GeometryState,
+
+    /// States saved by PushState
+    state_stack: Vec<GeometryState>,
+
+    /// The path that's being built up
+    current_path: Vec<Subpath>,
+
+    /// The text that's being built up
+    current_text: String,
+
+    /// The layer that's being drawn on
+    current_layer: u32,
+
+    /// The sprites that have been defined
+    sprites: SpriteCache
+}
+
+impl GeometryState {
+    fn new() -> GeometryState {
+        GeometryState {
+            transform:      Transform2D::identity(),
+            line_width:     1.0,
+            font:           (FontFamily::SansSerif, CanvasFontWeight::Normal),
+            font_size:      12.0,
+            text_align:     TextAlignment::Left
+        }
+    }
+}
+
+impl DrawingGeometry {
+    ///
+    /// Works out the geometry of a drawing
+    ///
+    pub fn new<In: IntoIterator<Item=Draw>>(drawing: In) -> DrawingGeometry {
+        let mut geometry = DrawingGeometry {
+            paths:          vec![],
+            state:          GeometryState::new(),
+            state_stack:    vec![],
+            current_path:   vec![],
+            current_text:   String::new(),
+            current_layer:  0,
+            sprites:        SpriteCache::new()
+        };
+
+        for (index, draw) in drawing.into_iter().enumerate() {
+            geometry.draw(index, draw);
+        }
+
+        geometry
+    }
+
+    ///
+    /// The paths in the drawing, in the order they were drawn
+    ///
+    pub fn paths(&self) -> &Vec<DrawnPath> {
+        &self.paths
+    }
+
+    ///
+    /// Retrieves the path drawn in a particular position in the drawing order
+    ///
+    pub fn path_at_index(&self, index: usize) -> Option<&DrawnPath> {
+        self.paths.get(index)
+    }
+
+    ///
+    /// Returns the paths that cover a point, topmost first
+    ///
+    /// The tolerance extends the area covered by each path, which is useful for picking thin lines.
+    ///
+    pub fn paths_at_point(&self, x: f32, y: f32, tolerance: f32) -> Vec<&DrawnPath> {
+        let point       = Coord2(x as f64, y as f64);
+        let tolerance   = tolerance as f64;
+
+        let mut result: Vec<_> = self.paths.iter()
+            .filter(|path| path.covers_point(point, tolerance))
+            .collect();
+
+        // Later layers are drawn over earlier ones (and later paths over earlier paths within a layer)
+        result.sort_by(|a, b| (b.layer, b.instruction_index).cmp(&(a.layer, a.instruction_index)));
+        result
+    }
+
+    ///
+    /// Returns the topmost layer that has a path covering a point
+    ///
+    pub fn layer_at_point(&self, x: f32, y: f32, tolerance: f32) -> Option<u32> {
+        self.paths_at_point(x, y, tolerance).first().map(|path| path.layer)
+    }
+
+    ///
+    /// The bounding box of everything in the drawing as (min, max), or None if nothing was drawn
+    ///
+    pub fn bounds(&self) -> Option<(Coord2, Coord2)> {
+        self.paths.iter()
+            .filter_map(|path| path.bounds())
+            .fold(None, |bounds, (min, max)| match bounds {
+                None                        => Some((min, max)),
+                Some((old_min, old_max))    => Some((Coord2::from_smallest_components(old_min, min), Coord2::from_biggest_components(old_max, max)))
+            })
+    }
+
+    ///
+    /// Adds a point to the current path
+    ///
+    fn add_point(&mut self, point: Coord2) {
+        if self.current_path.is_empty() {
+            self.current_path.push(Subpath { points: vec![], closed: false });
+        }
+
+        let subpath = self.current_path.last_mut().unwrap();
+        subpath.points.push(point);
+    }
+
+    ///
+    /// The last point in the current path
+    ///
+    fn last_point(&self) -> Coord2 {
+        self.current_path.last()
+            .and_then(|subpath| subpath.points.last().cloned())
+            .unwrap_or(Coord2(0.0, 0.0))
+    }
+
+    ///
+    /// Transforms a point using the current transformation
+    ///
+    fn transform_point(&self, x: f32, y: f32) -> Coord2 {
+        let (x, y) = self.state.transform.transform_point(x, y);
+        Coord2(x as f64, y as f64)
+    }
+
+    ///
+    /// Adds a bezier curve from the last point to the current path
+    ///
+    fn add_curve(&mut self, end: Coord2, cp1: Coord2, cp2: Coord2) {
+        let start   = self.last_point();
+        let curve   = bezier::Curve::from_points(start, end, cp1, cp2);
+
+        // Approximate the curve with line segments based on the length of its control polygon
+        let length      = start.distance_to(&cp1) + cp1.distance_to(&cp2) + cp2.distance_to(&end);
+        let segments    = ((length / MAX_SEGMENT_LENGTH).ceil() as usize).max(1).min(MAX_SEGMENTS_PER_CURVE);
+
+        for segment in 1..=segments {
+            let point = curve.point_at_pos((segment as f64)/(segments as f64));
+            self.add_point(point);
+        }
+    }
+
+    ///
+    /// Adds the current path to the drawing
+    ///
+    fn add_path(&mut self, instruction_index: usize, operation: PathOperation) {
+        self.paths.push(DrawnPath {
+            layer:              self.current_layer,
+            instruction_index:  instruction_index,
+            operation:          operation,
+            subpaths:           self.current_path.clone()
+        });
+    }
+
+    ///
+    /// Adds the current text to the drawing as a rectangle
+    ///
+    fn add_text(&mut self, instruction_index: usize, x: f32, y: f32, operation: PathOperation) {
+        let (family, weight)    = self.state.font;
+        let metrics             = ApproximateFontMetrics.measure_text(family, weight, self.state.font_size, &self.current_text);
+
+        let left = match self.state.text_align {
+            TextAlignment::Left     => x,
+            TextAlignment::Center   => x - metrics.width/2.0,
+            TextAlignment::Right    => x - metrics.width
+        };
+        let right = left + metrics.width;
+
+        let corners = vec![
+            self.transform_point(left, y - metrics.descent),
+            self.transform_point(right, y - metrics.descent),
+            self.transform_point(right, y + metrics.ascent),
+            self.transform_point(left, y + metrics.ascent)
+        ];
+
+        self.paths.push(DrawnPath {
+            layer:              self.current_layer,
+            instruction_index:  instruction_index,
+            operation:          operation,
+            subpaths:           vec![Subpath { points: corners, closed: true }]
+        });
+    }
+
+    ///
+    /// The current line width, in canvas units
+    ///
+    fn stroke_width(&self) -> f32 {
+        // Widths are scaled along with the transform
+        self.state.line_width * self.state.transform.determinant().abs().sqrt()
+    }
+
+    ///
+    /// Processes a drawing instruction
+    ///
+    fn draw(&mut self, index: usize, draw: Draw) {
+        use self::Draw::*;
+
+        if self.sprites.record(&draw) {
+            return;
+        }
+
+        match draw {
+            NewPath                                 => { self.current_path = vec![]; },
+            Move(x, y)                              => {
+                let point = self.transform_point(x, y);
+                self.current_path.push(Subpath { points: vec![point], closed: false });
+            },
+            Line(x, y)                              => {
+                let point = self.transform_point(x, y);
+                self.add_point(point);
+            },
+            BezierCurve((x, y), (cp1x, cp1y), (cp2x, cp2y)) => {
+                let end = self.transform_point(x, y);
+                let cp1 = self.transform_point(cp1x, cp1y);
+                let cp2 = self.transform_point(cp2x, cp2y);
+                self.add_curve(end, cp1, cp2);
+            },
+            ClosePath                               => {
+                if let Some(subpath) = self.current_path.last_mut() {
+                    subpath.closed = true;
+                }
+            },
+
+            Fill                                    => { self.add_path(index, PathOperation::Fill); },
+            Stroke                                  => {
+                let width = self.stroke_width();
+                self.add_path(index, PathOperation::Stroke(width));
+            },
+
+            LineWidth(width)                        => { self.state.line_width = width; },
+            LineWidthPixels(_)                      => { self.state.line_width = 0.0; },
+
+            Font(family, weight)                    => { self.state.font = (family, weight); },
+            FontSize(size)                          => { self.state.font_size = size; },
+            TextAlign(alignment)                    => { self.state.text_align = alignment; },
+            NewText                                 => { self.current_text = String::new(); },
+            TextChar(c)                             => { self.current_text.push(c); },
+            FillText(x, y)                          => { self.add_text(index, x, y, PathOperation::Fill); },
+            StrokeText(x, y)                        => {
+                let width = self.stroke_width();
+                self.add_text(index, x, y, PathOperation::Stroke(width));
+            },
+
+            IdentityTransform                       |
+            CanvasHeight(_)                         |
+            CenterRegion(_, _)                      => { self.state.transform = Transform2D::identity(); },
+            MultiplyTransform(transform)            => { self.state.transform = self.state.transform * transform; },
+
+            PushState                               => { self.state_stack.push(self.state); },
+            PopState                                => {
+                if let Some(state) = self.state_stack.pop() {
+                    self.state = state;
+                }
+            },
+
+            ClearCanvas                             => {
+                self.paths          = vec![];
+                self.state          = GeometryState::new();
+                self.state_stack    = vec![];
+                self.current_path   = vec![];
+                self.current_layer  = 0;
+            },
+            Layer(layer_id)                         => { self.current_layer = layer_id; },
+            ClearLayer                              => {
+                let current_layer = self.current_layer;
+                self.paths.retain(|path| path.layer != current_layer);
+            },
+
+            DrawSprite(sprite_id, transform)        => {
+                // Everything drawn by a sprite is attributed to the DrawSprite instruction
+                for sprite_draw in self.sprites.render_sprite(sprite_id, transform) {
+                    self.draw(index, sprite_draw);
+                }
+            },
+
+            _                                       => { }
+        }
+    }
+}
+
+impl DrawnPath {
+    ///
+    /// The bounding box of this path as (min, max), including the width of any line drawn around it
+    ///
+    pub fn bounds(&self) -> Option<(Coord2, Coord2)> {
+        let mut points = self.subpaths.iter().flat_map(|subpath| subpath.points.iter());
+
+        let first           = points.next()?;
+        let (min, max)      = points.fold((*first, *first), |(min, max), point| (Coord2::from_smallest_components(min, *point), Coord2::from_biggest_components(max, *point)));
+
+        match self.operation {
+            PathOperation::Fill             => Some((min, max)),
+            PathOperation::Stroke(width)    => {
+                let half_width = (width as f64)/2.0;
+                Some((min - Coord2(half_width, half_width), max + Coord2(half_width, half_width)))
+            }
+        }
+    }
+
+    ///
+    /// True if this path covers a point
+    ///
+    pub fn covers_point(&self, point: Coord2, tolerance: f64) -> bool {
+        match self.operation {
+            PathOperation::Fill             => self.winding_number(point) != 0 || self.distance_to_outline(point, true) <= tolerance,
+            PathOperation::Stroke(width)    => self.distance_to_outline(point, false) <= (width as f64)/2.0 + tolerance
+        }
+    }
+
+    ///
+    /// Computes the non-zero winding number of this path around a point (filled paths are always closed)
+    ///
+    fn winding_number(&self, point: Coord2) -> i32 {
+        let mut winding = 0;
+
+        for subpath in self.subpaths.iter() {
+            for (start, end) in Self::segments(subpath, true) {
+                if start.y() <= point.y() {
+                    if end.y() > point.y() && Self::side(start, end, point) > 0.0 {
+                        winding += 1;
+                    }
+                } else if end.y() <= point.y() && Self::side(start, end, point) < 0.0 {
+                    winding -= 1;
+                }
+            }
+        }
+
+        winding
+    }
+
+    ///
+    /// Finds the shortest distance from a point to the outline of this path
+    ///
+    fn distance_to_outline(&self, point: Coord2, close_all: bool) -> f64 {
+        let mut distance = f64::MAX;
+
+        for subpath in self.subpaths.iter() {
+            // A subpath with a single point is drawn as a dot
+            if subpath.points.len() == 1 {
+                distance = distance.min(subpath.points[0].distance_to(&point));
+            }
+
+            for (start, end) in Self::segments(subpath, close_all || subpath.closed) {
+                distance = distance.min(Self::distance_to_segment(start, end, point));
+            }
+        }
+
+        distance
+    }
+
+    ///
+    /// The line segments making up a subpath
+    ///
+    fn segments(subpath: &Subpath, closed: bool) -> Vec<(Coord2, Coord2)> {
+        let points          = &subpath.points;
+        let mut segments: Vec<_> = points.iter().zip(points.iter().skip(1)).map(|(a, b)| (*a, *b)).collect();
+
+        if closed && points.len() > 2 {
+            segments.push((points[points.len()-1], points[0]));
+        }
+
+        segments
+    }
+
+    ///
+    /// Which side of a line a point is on (positive for the left, negative for the right)
+    ///
+    #[inline]
+    fn side(start: Coord2, end: Coord2, point: Coord2) -> f64 {
+        (end.x()-start.x())*(point.y()-start.y()) - (point.x()-start.x())*(end.y()-start.y())
+    }
+
+    ///
+    /// The distance from a point to a line segment
+    ///
+    fn distance_to_segment(start: Coord2, end: Coord2, point: Coord2) -> f64 {
+        let line        = end - start;
+        let length_sq   = line.dot(&line);
+
+        if length_sq == 0.0 {
+            start.distance_to(&point)
+        } else {
+            let t       = ((point - start).dot(&line) / length_sq).max(0.0).min(1.0);
+            let nearest = start + line*t;
+
+            nearest.distance_to(&point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn square(x: f32, y: f32, size: f32) -> Vec<Draw> {
+        vec![Draw::NewPath, Draw::Move(x, y), Draw::Line(x+size, y), Draw::Line(x+size, y+size), Draw::Line(x, y+size), Draw::ClosePath]
+    }
+
+    #[test]
+    fn fill_covers_inside_point() {
+        let mut drawing = square(0.0, 0.0, 10.0);
+        drawing.push(Draw::Fill);
+
+        let geometry = DrawingGeometry::new(drawing);
+
+        assert!(geometry.paths_at_point(5.0, 5.0, 0.0).len() == 1);
+        assert!(geometry.paths_at_point(15.0, 5.0, 0.0).len() == 0);
+        assert!(geometry.paths_at_point(11.0, 5.0, 2.0).len() == 1);
+    }
+
+    #[test]
+    fn stroke_covers_outline_only() {
+        let mut drawing = vec![Draw::LineWidth(2.0)];
+        drawing.extend(square(0.0, 0.0, 10.0));
+        drawing.push(Draw::Stroke);
+
+        let geometry = DrawingGeometry::new(drawing);
+
+        assert!(geometry.paths_at_point(5.0, 5.0, 0.0).len() == 0);
+        assert!(geometry.paths_at_point(10.5, 5.0, 0.0).len() == 1);
+        assert!(geometry.path_at_index(0).unwrap().operation == PathOperation::Stroke(2.0));
+    }
+
+    #[test]
+    fn transform_moves_paths() {
+        let mut drawing = vec![Draw::CanvasHeight(1000.0), Draw::MultiplyTransform(Transform2D::translate(100.0, 0.0))];
+        drawing.extend(square(0.0, 0.0, 10.0));
+        drawing.push(Draw::Fill);
+
+        let geometry = DrawingGeometry::new(drawing);
+
+        assert!(geometry.paths_at_point(5.0, 5.0, 0.0).len() == 0);
+        assert!(geometry.paths_at_point(105.0, 5.0, 0.0).len() == 1);
+    }
+
+    #[test]
+    fn pop_state_restores_transform() {
+        let mut drawing = vec![Draw::PushState, Draw::MultiplyTransform(Transform2D::translate(100.0, 0.0)), Draw::PopState];
+        drawing.extend(square(0.0, 0.0, 10.0));
+        drawing.push(Draw::Fill);
+
+        let geometry = DrawingGeometry::new(drawing);
+
+        assert!(geometry.paths_at_point(5.0, 5.0, 0.0).len() == 1);
+    }
+
+    #[test]
+    fn topmost_layer_is_first() {
+        let mut drawing = vec![Draw::Layer(2)];
+        drawing.extend(square(0.0, 0.0, 10.0));
+        drawing.push(Draw::Fill);
+        drawing.push(Draw::Layer(1));
+        drawing.extend(square(0.0, 0.0, 10.0));
+        drawing.push(Draw::Fill);
+
+        let geometry = DrawingGeometry::new(drawing);
+
+        assert!(geometry.layer_at_point(5.0, 5.0, 0.0) == Some(2));
+        assert!(geometry.paths_at_point(5.0, 5.0, 0.0).len() == 2);
+    }
+
+    #[test]
+    fn clear_layer_removes_paths() {
+        let mut drawing = square(0.0, 0.0, 10.0);
+        drawing.push(Draw::Fill);
+        drawing.push(Draw::ClearLayer);
+
+        let geometry = DrawingGeometry::new(drawing);
+
+        assert!(geometry.paths().len() == 0);
+        assert!(geometry.bounds() == None);
+    }
+
+    #[test]
+    fn bounds_include_stroke_width() {
+        let mut drawing = square(0.0, 0.0, 10.0);
+        drawing.push(Draw::Fill);
+        drawing.push(Draw::LineWidth(4.0));
+        drawing.extend(square(20.0, 20.0, 10.0));
+        drawing.push(Draw::Stroke);
+
+        let geometry = DrawingGeometry::new(drawing);
+
+        assert!(geometry.bounds() == Some((Coord2(0.0, 0.0), Coord2(32.0, 32.0))));
+    }
+
+    #[test]
+    fn curve_is_followed() {
+        let drawing = vec![Draw::NewPath, Draw::Move(0.0, 0.0), Draw::BezierCurve((100.0, 0.0), (0.0, 100.0), (100.0, 100.0)), Draw::LineWidth(1.0), Draw::Stroke];
+        let geometry = DrawingGeometry::new(drawing);
+
+        // The midpoint of this curve is at (50, 75)
+        assert!(geometry.paths_at_point(50.0, 75.0, 0.5).len() == 1);
+        assert!(geometry.paths_at_point(50.0, 0.0, 0.5).len() == 0);
+    }
+
+    #[test]
+    fn sprites_are_attributed_to_draw_sprite() {
+        let mut drawing = vec![Draw::StartSprite(0)];
+        drawing.extend(square(0.0, 0.0, 10.0));
+        drawing.push(Draw::Fill);
+        drawing.push(Draw::EndSprite);
+        drawing.push(Draw::DrawSprite(0, Transform2D::translate(50.0, 0.0)));
+
+        let geometry = DrawingGeometry::new(drawing);
+
+        assert!(geometry.paths_at_point(5.0, 5.0, 0.0).len() == 0);
+        assert!(geometry.paths_at_point(55.0, 5.0, 0.0)[0].instruction_index == 9);
+    }
+}
@@ -8,19 +8,34 @@ extern crate serde_derive;
 
 extern crate futures;
 extern crate flo_curves as curves;
+extern crate flo_float_encoder as float_encoder;
 extern crate desync;
 extern crate hsluv;
 
 mod gc;
 mod draw;
+mod font;
+mod sprite;
 mod color;
 mod canvas;
 mod encoding;
+mod binary_encoding;
+mod decoding;
+mod optimise;
+mod geometry;
 mod transform2d;
+mod transform_context;
 
 pub use self::gc::*;
 pub use self::draw::*;
+pub use self::font::*;
+pub use self::sprite::*;
 pub use self::color::*;
 pub use self::canvas::*;
 pub use self::encoding::*;
+pub use self::binary_encoding::*;
+pub use self::decoding::*;
+pub use self::optimise::*;
+pub use self::geometry::*;
 pub use self::transform2d::*;
+pub use self::transform_context::*;
@@ -0,0 +1,370 @@
+use super::draw::*;
+
+///
+/// The items of drawing state that can be tracked by the optimiser
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum StateSlot {
+    FillStyle,
+    StrokeStyle,
+    LineWidth,
+    LineJoin,
+    LineCap,
+    BlendMode,
+    Font,
+    FontSize,
+    TextAlign
+}
+
+const NUM_SLOTS: usize = 9;
+
+///
+/// How a drawing instruction interacts with the state tracked by the optimiser
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum InstructionType {
+    /// Starts a new path
+    NewPath,
+
+    /// Adds to the current path without using or changing any state
+    Path,
+
+    /// Sets a state value
+    Setter(StateSlot),
+
+    /// Pushes the current state
+    PushState,
+
+    /// Pops the current state
+    PopState,
+
+    /// Resets the state to an unknown value (eg, by switching layers)
+    Reset,
+
+    /// Uses the current state, and changes the value of a state slot in a way that can't be compared
+    Invalidates(StateSlot),
+
+    /// Any other instruction (assumed to use the current state and path)
+    Other
+}
+
+impl InstructionType {
+    ///
+    /// Determines the type of a drawing instruction
+    ///
+    fn of(draw: &Draw) -> InstructionType {
+        use self::Draw::*;
+        use self::InstructionType::*;
+
+        match draw {
+            &Draw::NewPath              => InstructionType::NewPath,
+
+            &Move(_, _)                 |
+            &Line(_, _)                 |
+            &BezierCurve(_, _, _)       |
+            &ClosePath                  => Path,
+
+            &FillColor(_)               => Setter(StateSlot::FillStyle),
+            &StrokeColor(_)             => Setter(StateSlot::StrokeStyle),
+            &LineWidth(_)               |
+            &LineWidthPixels(_)         => Setter(StateSlot::LineWidth),
+            &Draw::LineJoin(_)          => Setter(StateSlot::LineJoin),
+            &Draw::LineCap(_)           => Setter(StateSlot::LineCap),
+            &Draw::BlendMode(_)         => Setter(StateSlot::BlendMode),
+            &Font(_, _)                 => Setter(StateSlot::Font),
+            &FontSize(_)                => Setter(StateSlot::FontSize),
+            &TextAlign(_)               => Setter(StateSlot::TextAlign),
+
+            &Draw::PushState            => InstructionType::PushState,
+            &Draw::PopState             => InstructionType::PopState,
+
+            &ClearCanvas                |
+            &Layer(_)                   |
+            &ClearLayer                 |
+            &StartSprite(_)             |
+            &EndSprite                  => Reset,
+
+            &FillGradient               => Invalidates(StateSlot::FillStyle),
+            &StrokeGradient             => Invalidates(StateSlot::StrokeStyle),
+
+            // Pixel line widths are calculated from the transform at the point they are set
+            &IdentityTransform          |
+            &CanvasHeight(_)            |
+            &CenterRegion(_, _)         |
+            &MultiplyTransform(_)       => Invalidates(StateSlot::LineWidth),
+
+            _                           => Other
+        }
+    }
+}
+
+///
+/// Removes redundant instructions from a drawing
+///
+struct DrawingOptimiser {
+    /// The optimised drawing (None for instructions that have been removed)
+    output: Vec<Option<Draw>>,
+
+    /// The known values of the state slots as of the last instruction that used them
+    committed: [Option<Draw>; NUM_SLOTS],
+
+    /// Indexes in the output of setters that have not been used yet
+    pending: [Option<usize>; NUM_SLOTS],
+
+    /// The output index of each PushState instruction along with the committed state it saved
+    state_stack: Vec<(usize, [Option<Draw>; NUM_SLOTS])>,
+
+    /// The output index of the NewPath instruction if the current path hasn't been used yet
+    path_start: Option<usize>
+}
+
+impl DrawingOptimiser {
+    ///
+    /// Creates a new optimiser
+    ///
+    fn new() -> DrawingOptimiser {
+        DrawingOptimiser {
+            output:         vec![],
+            committed:      [None; NUM_SLOTS],
+            pending:        [None; NUM_SLOTS],
+            state_stack:    vec![],
+            path_start:     None
+        }
+    }
+
+    ///
+    /// Marks any pending setters as used
+    ///
+    fn commit_pending(&mut self) {
+        for slot in 0..NUM_SLOTS {
+            if let Some(index) = self.pending[slot].take() {
+                self.committed[slot] = self.output[index];
+            }
+        }
+    }
+
+    ///
+    /// Removes any pending setters from the output
+    ///
+    fn discard_pending(&mut self) {
+        for slot in 0..NUM_SLOTS {
+            if let Some(index) = self.pending[slot].take() {
+                self.output[index] = None;
+            }
+        }
+    }
+
+    ///
+    /// Adds a drawing instruction to the output
+    ///
+    fn add(&mut self, draw: Draw) {
+        match InstructionType::of(&draw) {
+            InstructionType::NewPath => {
+                // A path that was never used is replaced by the new one
+                if let Some(path_start) = self.path_start {
+                    for index in path_start..self.output.len() {
+                        let is_path = self.output[index].map(|draw| match InstructionType::of(&draw) {
+                            InstructionType::NewPath | InstructionType::Path    => true,
+                            _                                                   => false
+                        }).unwrap_or(false);
+
+                        if is_path {
+                            self.output[index] = None;
+                        }
+                    }
+                }
+
+                self.path_start = Some(self.output.len());
+                self.output.push(Some(draw));
+            },
+
+            InstructionType::Path => {
+                self.output.push(Some(draw));
+            },
+
+            InstructionType::Setter(slot) => {
+                let slot = slot as usize;
+
+                // A setter that was never used is overridden by this one
+                if let Some(index) = self.pending[slot].take() {
+                    self.output[index] = None;
+                }
+
+                // Setting a value that's already set does nothing
+                if self.committed[slot] != Some(draw) {
+                    self.pending[slot] = Some(self.output.len());
+                    self.output.push(Some(draw));
+                }
+            },
+
+            InstructionType::PushState => {
+                self.commit_pending();
+                self.path_start = None;
+
+                self.state_stack.push((self.output.len(), self.committed));
+                self.output.push(Some(draw));
+            },
+
+            InstructionType::PopState => {
+                self.path_start = None;
+
+                if let Some((push_index, saved_state)) = self.state_stack.pop() {
+                    // Anything set but not used before the state was popped has no effect
+                    self.discard_pending();
+                    self.committed = saved_state;
+
+                    // Push/pop pairs with nothing in between can be removed entirely
+                    if self.output[(push_index+1)..].iter().all(|draw| draw.is_none()) {
+                        self.output[push_index] = None;
+                    } else {
+                        self.output.push(Some(draw));
+                    }
+                } else {
+                    // Popping a state from before this drawing started: the state is no longer known
+                    self.commit_pending();
+                    self.committed = [None; NUM_SLOTS];
+
+                    self.output.push(Some(draw));
+                }
+            },
+
+            InstructionType::Reset => {
+                self.commit_pending();
+                self.path_start = None;
+
+                self.committed = [None; NUM_SLOTS];
+                self.state_stack = vec![];
+
+                self.output.push(Some(draw));
+            },
+
+            InstructionType::Invalidates(slot) => {
+                self.commit_pending();
+                self.path_start = None;
+
+                self.committed[slot as usize] = None;
+
+                self.output.push(Some(draw));
+            },
+
+            InstructionType::Other => {
+                self.commit_pending();
+                self.path_start = None;
+
+                self.output.push(Some(draw));
+            }
+        }
+    }
+
+    ///
+    /// Retrieves the optimised drawing
+    ///
+    fn finish(self) -> Vec<Draw> {
+        self.output.into_iter()
+            .filter_map(|draw| draw)
+            .collect()
+    }
+}
+
+///
+/// Removes and coalesces redundant instructions in a drawing without changing what is rendered
+///
+/// This removes state changes that are immediately replaced or that set a value that's already
+/// set, push/pop pairs that enclose nothing, and paths that are replaced before they are used.
+/// The drawing is assumed to continue from an unknown state, so it's safe to optimise each
+/// part of a stream separately.
+///
+pub fn optimise_drawing<In: IntoIterator<Item=Draw>>(drawing: In) -> Vec<Draw> {
+    let mut optimiser = DrawingOptimiser::new();
+
+    for draw in drawing {
+        optimiser.add(draw);
+    }
+
+    optimiser.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::color::*;
+
+    #[test]
+    fn remove_repeated_fill_color() {
+        let red     = Color::Rgba(1.0, 0.0, 0.0, 1.0);
+        let drawing = vec![Draw::FillColor(red), Draw::Fill, Draw::FillColor(red), Draw::Fill];
+
+        assert!(optimise_drawing(drawing) == vec![Draw::FillColor(red), Draw::Fill, Draw::Fill]);
+    }
+
+    #[test]
+    fn coalesce_unused_line_widths() {
+        let drawing = vec![Draw::LineWidth(1.0), Draw::LineWidthPixels(2.0), Draw::LineWidth(3.0), Draw::Stroke];
+
+        assert!(optimise_drawing(drawing) == vec![Draw::LineWidth(3.0), Draw::Stroke]);
+    }
+
+    #[test]
+    fn keep_setters_used_by_paths() {
+        let drawing = vec![Draw::NewPath, Draw::Move(0.0, 0.0), Draw::LineWidth(1.0), Draw::Line(1.0, 1.0), Draw::LineWidth(2.0), Draw::Stroke];
+
+        assert!(optimise_drawing(drawing) == vec![Draw::NewPath, Draw::Move(0.0, 0.0), Draw::Line(1.0, 1.0), Draw::LineWidth(2.0), Draw::Stroke]);
+    }
+
+    #[test]
+    fn remove_empty_push_pop() {
+        let drawing = vec![Draw::Fill, Draw::PushState, Draw::PushState, Draw::LineWidth(2.0), Draw::PopState, Draw::PopState, Draw::Stroke];
+
+        assert!(optimise_drawing(drawing) == vec![Draw::Fill, Draw::Stroke]);
+    }
+
+    #[test]
+    fn keep_push_pop_with_drawing() {
+        let drawing = vec![Draw::PushState, Draw::LineWidth(2.0), Draw::Stroke, Draw::LineWidth(3.0), Draw::PopState];
+
+        assert!(optimise_drawing(drawing) == vec![Draw::PushState, Draw::LineWidth(2.0), Draw::Stroke, Draw::PopState]);
+    }
+
+    #[test]
+    fn pop_restores_known_state() {
+        let drawing = vec![Draw::LineWidth(1.0), Draw::Stroke, Draw::PushState, Draw::LineWidth(2.0), Draw::Stroke, Draw::PopState, Draw::LineWidth(1.0), Draw::Stroke];
+
+        assert!(optimise_drawing(drawing) == vec![Draw::LineWidth(1.0), Draw::Stroke, Draw::PushState, Draw::LineWidth(2.0), Draw::Stroke, Draw::PopState, Draw::Stroke]);
+    }
+
+    #[test]
+    fn unmatched_pop_forgets_state() {
+        let drawing = vec![Draw::LineWidth(1.0), Draw::Stroke, Draw::PopState, Draw::LineWidth(1.0), Draw::Stroke];
+
+        assert!(optimise_drawing(drawing.clone()) == drawing);
+    }
+
+    #[test]
+    fn remove_unused_paths() {
+        let drawing = vec![Draw::NewPath, Draw::Move(0.0, 0.0), Draw::Line(1.0, 1.0), Draw::NewPath, Draw::NewPath, Draw::Move(2.0, 2.0), Draw::Fill];
+
+        assert!(optimise_drawing(drawing) == vec![Draw::NewPath, Draw::Move(2.0, 2.0), Draw::Fill]);
+    }
+
+    #[test]
+    fn keep_used_paths() {
+        let drawing = vec![Draw::NewPath, Draw::Move(0.0, 0.0), Draw::Fill, Draw::NewPath, Draw::Move(2.0, 2.0), Draw::Clip];
+
+        assert!(optimise_drawing(drawing.clone()) == drawing);
+    }
+
+    #[test]
+    fn gradient_invalidates_fill_color() {
+        let red     = Color::Rgba(1.0, 0.0, 0.0, 1.0);
+        let drawing = vec![Draw::FillColor(red), Draw::Fill, Draw::FillGradient, Draw::Fill, Draw::FillColor(red), Draw::Fill];
+
+        assert!(optimise_drawing(drawing.clone()) == drawing);
+    }
+
+    #[test]
+    fn layer_switch_forgets_state() {
+        let drawing = vec![Draw::LineWidth(1.0), Draw::Stroke, Draw::Layer(1), Draw::LineWidth(1.0), Draw::Stroke];
+
+        assert!(optimise_drawing(drawing.clone()) == drawing);
+    }
+}
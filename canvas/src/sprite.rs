@@ -0,0 +1,202 @@
+use super::draw::*;
+use super::transform2d::*;
+
+use std::sync::*;
+use std::collections::HashMap;
+
+/// How many levels of sprites drawn within sprites will be expanded (prevents a sprite that draws itself from recursing forever)
+const MAX_SPRITE_DEPTH: usize = 16;
+
+///
+/// Stores the sprites defined by a drawing for a renderer
+///
+/// Renderers pass their instructions through `record()`, which stores sprite definitions as
+/// they arrive. `DrawSprite` instructions can then be expanded into the instructions that
+/// render the sprite without the drawing needing to send them again.
+///
+pub struct SpriteCache {
+    /// The sprites that have been defined
+    sprites: HashMap<u32, Arc<Vec<Draw>>>,
+
+    /// The ID and the instructions for the sprite that's being defined
+    recording: Option<(u32, Vec<Draw>)>
+}
+
+impl SpriteCache {
+    ///
+    /// Creates a new sprite cache with no sprites in it
+    ///
+    pub fn new() -> SpriteCache {
+        SpriteCache {
+            sprites:    HashMap::new(),
+            recording:  None
+        }
+    }
+
+    ///
+    /// True if a sprite is currently being defined
+    ///
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    ///
+    /// Processes an instruction that's about to be drawn, returning true if it was handled by the cache
+    ///
+    /// Sprite definition instructions and instructions that form part of a sprite are handled here.
+    /// Any other instruction (including `DrawSprite`) should be rendered as normal.
+    ///
+    pub fn record(&mut self, draw: &Draw) -> bool {
+        match draw {
+            &Draw::StartSprite(sprite_id) => {
+                self.finish_recording();
+                self.recording = Some((sprite_id, vec![]));
+                true
+            },
+
+            &Draw::EndSprite => {
+                self.finish_recording();
+                true
+            },
+
+            &Draw::FreeSprite(sprite_id) => {
+                self.sprites.remove(&sprite_id);
+                true
+            },
+
+            &Draw::ClearCanvas => {
+                // Clearing the canvas removes all of the sprites
+                self.sprites    = HashMap::new();
+                self.recording  = None;
+                false
+            },
+
+            other => {
+                if let Some((_, ref mut instructions)) = self.recording {
+                    instructions.push(*other);
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    ///
+    /// Stores the sprite that's being recorded, if there is one
+    ///
+    fn finish_recording(&mut self) {
+        if let Some((sprite_id, instructions)) = self.recording.take() {
+            self.sprites.insert(sprite_id, Arc::new(instructions));
+        }
+    }
+
+    ///
+    /// Returns the instructions needed to render a sprite with a particular transform
+    ///
+    /// Instructions that only make sense for the canvas as a whole, such as layer selection,
+    /// are left out. Sprites drawn by the sprite are expanded in place.
+    ///
+    pub fn render_sprite(&self, sprite_id: u32, transform: Transform2D) -> Vec<Draw> {
+        let mut result = vec![];
+        self.render_sprite_onto(sprite_id, transform, 0, &mut result);
+        result
+    }
+
+    ///
+    /// Adds the instructions to render a sprite to a list
+    ///
+    fn render_sprite_onto(&self, sprite_id: u32, transform: Transform2D, depth: usize, target: &mut Vec<Draw>) {
+        if depth >= MAX_SPRITE_DEPTH { return; }
+
+        if let Some(instructions) = self.sprites.get(&sprite_id) {
+            target.push(Draw::PushState);
+            target.push(Draw::MultiplyTransform(transform));
+
+            for draw in instructions.iter() {
+                match draw {
+                    &Draw::Layer(_)             |
+                    &Draw::LayerBlend(_, _)     |
+                    &Draw::ClearLayer           |
+                    &Draw::ClearCanvas          |
+                    &Draw::Store                |
+                    &Draw::Restore              |
+                    &Draw::FreeStoredBuffer     => { },
+
+                    &Draw::DrawSprite(sub_sprite_id, sub_transform) => {
+                        self.render_sprite_onto(sub_sprite_id, sub_transform, depth+1, target);
+                    },
+
+                    other                       => target.push(*other)
+                }
+            }
+
+            target.push(Draw::PopState);
+        }
+    }
+}
+
+impl Default for SpriteCache {
+    fn default() -> SpriteCache {
+        SpriteCache::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn records_sprite_definition() {
+        let mut cache = SpriteCache::new();
+
+        assert!(cache.record(&Draw::StartSprite(1)));
+        assert!(cache.record(&Draw::NewPath));
+        assert!(cache.record(&Draw::Fill));
+        assert!(cache.record(&Draw::EndSprite));
+        assert!(!cache.record(&Draw::Stroke));
+
+        let transform = Transform2D::translate(1.0, 2.0);
+        assert!(cache.render_sprite(1, transform) == vec![Draw::PushState, Draw::MultiplyTransform(transform), Draw::NewPath, Draw::Fill, Draw::PopState]);
+    }
+
+    #[test]
+    fn free_sprite_removes_definition() {
+        let mut cache = SpriteCache::new();
+
+        cache.record(&Draw::StartSprite(1));
+        cache.record(&Draw::Fill);
+        cache.record(&Draw::EndSprite);
+        cache.record(&Draw::FreeSprite(1));
+
+        assert!(cache.render_sprite(1, Transform2D::identity()) == vec![]);
+    }
+
+    #[test]
+    fn expands_nested_sprites() {
+        let mut cache   = SpriteCache::new();
+        let identity    = Transform2D::identity();
+
+        cache.record(&Draw::StartSprite(1));
+        cache.record(&Draw::Fill);
+        cache.record(&Draw::StartSprite(2));
+        cache.record(&Draw::DrawSprite(1, identity));
+        cache.record(&Draw::EndSprite);
+
+        assert!(cache.render_sprite(2, identity) == vec![
+            Draw::PushState, Draw::MultiplyTransform(identity),
+            Draw::PushState, Draw::MultiplyTransform(identity), Draw::Fill, Draw::PopState,
+            Draw::PopState]);
+    }
+
+    #[test]
+    fn recursive_sprite_is_limited() {
+        let mut cache = SpriteCache::new();
+
+        cache.record(&Draw::StartSprite(1));
+        cache.record(&Draw::DrawSprite(1, Transform2D::identity()));
+        cache.record(&Draw::EndSprite);
+
+        assert!(cache.render_sprite(1, Transform2D::identity()).len() == MAX_SPRITE_DEPTH*3);
+    }
+}
@@ -1,6 +1,13 @@
+use curves::*;
+use curves::bezier::BezierCurve;
+
+use std::ops::Mul;
+
 ///
 /// Represents a 2D affine transformation matrix
 ///
+/// Points are treated as column vectors, so the transformed x coordinate is `a0*x + a1*y + a2`.
+///
 #[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Transform2D(pub (f32, f32, f32), pub (f32, f32, f32), pub (f32, f32, f32));
 
@@ -12,4 +19,184 @@ impl Transform2D {
     pub fn translate(x: f32, y: f32) -> Transform2D {
         Transform2D((1.0, 0.0, x), (0.0, 1.0, y), (0.0, 0.0, 1.0))
     }
+
+    pub fn scale(x: f32, y: f32) -> Transform2D {
+        Transform2D((x, 0.0, 0.0), (0.0, y, 0.0), (0.0, 0.0, 1.0))
+    }
+
+    ///
+    /// A rotation about the origin (anticlockwise when the y axis points up)
+    ///
+    pub fn rotate(radians: f32) -> Transform2D {
+        let (sin, cos) = radians.sin_cos();
+
+        Transform2D((cos, -sin, 0.0), (sin, cos, 0.0), (0.0, 0.0, 1.0))
+    }
+
+    pub fn rotate_degrees(degrees: f32) -> Transform2D {
+        Self::rotate(degrees.to_radians())
+    }
+
+    ///
+    /// The transformation set by a canvas height instruction, mapping a canvas of the specified height onto the
+    /// view coordinates (where the visible area runs from -1 to 1 vertically)
+    ///
+    /// A negative height flips the y axis but not the x axis.
+    ///
+    pub fn canvas_height(height: f32) -> Transform2D {
+        Self::scale(2.0/height.abs(), 2.0/height)
+    }
+
+    ///
+    /// A skew by the specified angles along the x and y axes
+    ///
+    pub fn skew(x_radians: f32, y_radians: f32) -> Transform2D {
+        Transform2D((1.0, x_radians.tan(), 0.0), (y_radians.tan(), 1.0, 0.0), (0.0, 0.0, 1.0))
+    }
+
+    ///
+    /// Returns the transformation that applies `other` followed by this transformation
+    ///
+    pub fn multiply(&self, other: &Transform2D) -> Transform2D {
+        let Transform2D(a, b, c) = *self;
+        let Transform2D(d, e, f) = *other;
+
+        let row = |r: (f32, f32, f32)| {
+            (r.0*d.0 + r.1*e.0 + r.2*f.0,
+             r.0*d.1 + r.1*e.1 + r.2*f.1,
+             r.0*d.2 + r.1*e.2 + r.2*f.2)
+        };
+
+        Transform2D(row(a), row(b), row(c))
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let Transform2D(a, b, c) = *self;
+
+        a.0*(b.1*c.2 - b.2*c.1) - a.1*(b.0*c.2 - b.2*c.0) + a.2*(b.0*c.1 - b.1*c.0)
+    }
+
+    ///
+    /// Returns the inverse of this transformation, or None if it has no inverse (eg, a scale by 0)
+    ///
+    pub fn invert(&self) -> Option<Transform2D> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() { return None; }
+
+        let Transform2D(a, b, c) = *self;
+        let inv = 1.0/det;
+
+        Some(Transform2D(
+            ((b.1*c.2 - b.2*c.1)*inv, (a.2*c.1 - a.1*c.2)*inv, (a.1*b.2 - a.2*b.1)*inv),
+            ((b.2*c.0 - b.0*c.2)*inv, (a.0*c.2 - a.2*c.0)*inv, (a.2*b.0 - a.0*b.2)*inv),
+            ((b.0*c.1 - b.1*c.0)*inv, (a.1*c.0 - a.0*c.1)*inv, (a.0*b.1 - a.1*b.0)*inv)
+        ))
+    }
+
+    ///
+    /// The translation applied after this transformation by a center region instruction
+    ///
+    /// This moves the center of the region to the point that this transformation currently maps to `view_origin`
+    /// (there's no translation if this transformation can't be inverted).
+    ///
+    pub fn center_region(&self, view_origin: (f32, f32), min: (f32, f32), max: (f32, f32)) -> Transform2D {
+        match self.invert() {
+            Some(inverse) => {
+                let origin = inverse.transform_point(view_origin.0, view_origin.1);
+                let center = ((min.0+max.0)/2.0, (min.1+max.1)/2.0);
+
+                Transform2D::translate(origin.0-center.0, origin.1-center.1)
+            },
+
+            None => Transform2D::identity()
+        }
+    }
+
+    ///
+    /// Applies this transformation to a point
+    ///
+    pub fn transform_point(&self, x: f32, y: f32) -> (f32, f32) {
+        let Transform2D(a, b, _c) = *self;
+
+        (a.0*x + a.1*y + a.2, b.0*x + b.1*y + b.2)
+    }
+
+    ///
+    /// Applies this transformation to a bezier curve
+    ///
+    /// Affine transformations can be applied to a bezier curve by transforming its control points.
+    ///
+    pub fn transform_curve<Curve: BezierCurve>(&self, curve: &Curve) -> Curve
+    where Curve::Point: Coordinate2D {
+        let transform_coord = |point: Curve::Point| {
+            let (x, y) = self.transform_point(point.x() as f32, point.y() as f32);
+            Curve::Point::from_components(&[x as f64, y as f64])
+        };
+
+        let (cp1, cp2) = curve.control_points();
+
+        Curve::from_points(transform_coord(curve.start_point()), transform_coord(curve.end_point()), transform_coord(cp1), transform_coord(cp2))
+    }
+}
+
+impl Mul for Transform2D {
+    type Output = Transform2D;
+
+    fn mul(self, other: Transform2D) -> Transform2D {
+        self.multiply(&other)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use curves::bezier;
+
+    fn is_close(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0-b.0).abs() < 0.0001 && (a.1-b.1).abs() < 0.0001
+    }
+
+    #[test]
+    fn rotate_quarter_turn() {
+        assert!(is_close(Transform2D::rotate_degrees(90.0).transform_point(1.0, 0.0), (0.0, 1.0)));
+    }
+
+    #[test]
+    fn multiply_applies_right_hand_side_first() {
+        let transform = Transform2D::translate(10.0, 0.0) * Transform2D::scale(2.0, 3.0);
+
+        assert!(is_close(transform.transform_point(1.0, 1.0), (12.0, 3.0)));
+    }
+
+    #[test]
+    fn invert_undoes_transform() {
+        let transform   = Transform2D::translate(5.0, -3.0) * Transform2D::rotate(0.7) * Transform2D::skew(0.2, 0.1) * Transform2D::scale(2.0, 0.5);
+        let inverse     = transform.invert().unwrap();
+        let (x, y)      = transform.transform_point(3.0, 4.0);
+
+        assert!(is_close(inverse.transform_point(x, y), (3.0, 4.0)));
+    }
+
+    #[test]
+    fn cannot_invert_zero_scale() {
+        assert!(Transform2D::scale(0.0, 1.0).invert().is_none());
+    }
+
+    #[test]
+    fn center_region_moves_center_to_view_origin() {
+        let transform   = Transform2D::translate(400.0, 300.0) * Transform2D::scale(150.0, -150.0);
+        let transform   = transform * transform.center_region((300.0, 200.0), (2.0, 3.0), (3.0, 4.0));
+
+        assert!(is_close(transform.transform_point(2.5, 3.5), (300.0, 200.0)));
+    }
+
+    #[test]
+    fn transform_curve_control_points() {
+        let curve       = bezier::Curve::from_points(Coord2(0.0, 0.0), Coord2(1.0, 0.0), Coord2(0.0, 1.0), Coord2(1.0, 1.0));
+        let transformed = Transform2D::translate(1.0, 2.0).transform_curve(&curve);
+
+        assert!(transformed.start_point() == Coord2(1.0, 2.0));
+        assert!(transformed.end_point() == Coord2(2.0, 2.0));
+        assert!(transformed.control_points() == (Coord2(1.0, 3.0), Coord2(2.0, 3.0)));
+    }
 }
@@ -0,0 +1,258 @@
+use super::gc::*;
+use super::draw::*;
+use super::color::*;
+use super::font::*;
+use super::transform2d::*;
+
+///
+/// A graphics context that passes its instructions on to another context while tracking the
+/// current transformation and state stack
+///
+/// Transformations are tracked in view coordinates, where the origin is the center of the
+/// canvas and the visible area runs from -1 to 1 vertically with the y axis pointing up.
+/// This makes it possible to map coordinates (such as pointer positions) between the canvas
+/// and the view without depending on the renderer.
+///
+pub struct TransformTrackingContext<Target: GraphicsContext> {
+    /// The context that instructions are sent to
+    target: Target,
+
+    /// The current transformation from canvas to view coordinates
+    transform: Transform2D,
+
+    /// The transformations saved by push_state
+    state_stack: Vec<Transform2D>,
+
+    /// While a sprite is being defined, the transform and state stack from outside of the sprite
+    outside_sprite: Option<(Transform2D, Vec<Transform2D>)>
+}
+
+impl<Target: GraphicsContext> TransformTrackingContext<Target> {
+    ///
+    /// Creates a new tracking context that sends its instructions to a target context
+    ///
+    pub fn new(target: Target) -> TransformTrackingContext<Target> {
+        TransformTrackingContext {
+            target:         target,
+            transform:      Transform2D::identity(),
+            state_stack:    vec![],
+            outside_sprite: None
+        }
+    }
+
+    ///
+    /// The context that this is sending instructions to
+    ///
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+
+    ///
+    /// Retrieves the target context from this object
+    ///
+    pub fn into_target(self) -> Target {
+        self.target
+    }
+
+    ///
+    /// The transformation from canvas coordinates to view coordinates
+    ///
+    pub fn current_transform(&self) -> Transform2D {
+        self.transform
+    }
+
+    ///
+    /// The number of states that have been pushed and not popped
+    ///
+    pub fn state_depth(&self) -> usize {
+        self.state_stack.len()
+    }
+
+    ///
+    /// Maps a point in canvas coordinates to view coordinates
+    ///
+    pub fn canvas_to_view(&self, x: f32, y: f32) -> (f32, f32) {
+        self.transform.transform_point(x, y)
+    }
+
+    ///
+    /// Maps a point in view coordinates to canvas coordinates (None if the transform can't be inverted)
+    ///
+    pub fn view_to_canvas(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+        self.transform.invert().map(|inverse| inverse.transform_point(x, y))
+    }
+}
+
+impl<Target: GraphicsContext> GraphicsContext for TransformTrackingContext<Target> {
+    fn new_path(&mut self)                                                  { self.target.new_path(); }
+    fn move_to(&mut self, x: f32, y: f32)                                   { self.target.move_to(x, y); }
+    fn line_to(&mut self, x: f32, y: f32)                                   { self.target.line_to(x, y); }
+    fn bezier_curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32) { self.target.bezier_curve_to(x1, y1, x2, y2, x3, y3); }
+    fn close_path(&mut self)                                                { self.target.close_path(); }
+    fn fill(&mut self)                                                      { self.target.fill(); }
+    fn stroke(&mut self)                                                    { self.target.stroke(); }
+    fn line_width(&mut self, width: f32)                                    { self.target.line_width(width); }
+    fn line_width_pixels(&mut self, width: f32)                             { self.target.line_width_pixels(width); }
+    fn line_join(&mut self, join: LineJoin)                                 { self.target.line_join(join); }
+    fn line_cap(&mut self, cap: LineCap)                                    { self.target.line_cap(cap); }
+    fn new_dash_pattern(&mut self)                                          { self.target.new_dash_pattern(); }
+    fn dash_length(&mut self, length: f32)                                  { self.target.dash_length(length); }
+    fn dash_offset(&mut self, offset: f32)                                  { self.target.dash_offset(offset); }
+    fn fill_color(&mut self, col: Color)                                    { self.target.fill_color(col); }
+    fn stroke_color(&mut self, col: Color)                                  { self.target.stroke_color(col); }
+    fn new_linear_gradient(&mut self, x1: f32, y1: f32, x2: f32, y2: f32)   { self.target.new_linear_gradient(x1, y1, x2, y2); }
+    fn new_radial_gradient(&mut self, x1: f32, y1: f32, r1: f32, x2: f32, y2: f32, r2: f32) { self.target.new_radial_gradient(x1, y1, r1, x2, y2, r2); }
+    fn gradient_stop(&mut self, pos: f32, col: Color)                       { self.target.gradient_stop(pos, col); }
+    fn gradient_transform(&mut self, transform: Transform2D)                { self.target.gradient_transform(transform); }
+    fn fill_gradient(&mut self)                                             { self.target.fill_gradient(); }
+    fn stroke_gradient(&mut self)                                           { self.target.stroke_gradient(); }
+    fn font(&mut self, family: FontFamily, weight: CanvasFontWeight)        { self.target.font(family, weight); }
+    fn font_size(&mut self, size: f32)                                      { self.target.font_size(size); }
+    fn text_align(&mut self, alignment: TextAlignment)                      { self.target.text_align(alignment); }
+    fn new_text(&mut self)                                                  { self.target.new_text(); }
+    fn text_char(&mut self, c: char)                                        { self.target.text_char(c); }
+    fn fill_text(&mut self, x: f32, y: f32)                                 { self.target.fill_text(x, y); }
+    fn stroke_text(&mut self, x: f32, y: f32)                               { self.target.stroke_text(x, y); }
+    fn blend_mode(&mut self, mode: BlendMode)                               { self.target.blend_mode(mode); }
+    fn unclip(&mut self)                                                    { self.target.unclip(); }
+    fn clip(&mut self)                                                      { self.target.clip(); }
+    fn store(&mut self)                                                     { self.target.store(); }
+    fn restore(&mut self)                                                   { self.target.restore(); }
+    fn free_stored_buffer(&mut self)                                        { self.target.free_stored_buffer(); }
+    fn layer(&mut self, layer_id: u32)                                      { self.target.layer(layer_id); }
+    fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode)         { self.target.layer_blend(layer_id, blend_mode); }
+    fn clear_layer(&mut self)                                               { self.target.clear_layer(); }
+    fn draw_sprite(&mut self, sprite_id: u32, transform: Transform2D)       { self.target.draw_sprite(sprite_id, transform); }
+    fn free_sprite(&mut self, sprite_id: u32)                               { self.target.free_sprite(sprite_id); }
+
+    fn identity_transform(&mut self) {
+        self.transform = Transform2D::identity();
+        self.target.identity_transform();
+    }
+
+    fn canvas_height(&mut self, height: f32) {
+        self.transform = Transform2D::canvas_height(height);
+        self.target.canvas_height(height);
+    }
+
+    fn center_region(&mut self, minx: f32, miny: f32, maxx: f32, maxy: f32) {
+        // Move the center of the region to where the view origin currently is
+        self.transform = self.transform * self.transform.center_region((0.0, 0.0), (minx, miny), (maxx, maxy));
+
+        self.target.center_region(minx, miny, maxx, maxy);
+    }
+
+    fn transform(&mut self, transform: Transform2D) {
+        self.transform = self.transform * transform;
+        self.target.transform(transform);
+    }
+
+    fn push_state(&mut self) {
+        self.state_stack.push(self.transform);
+        self.target.push_state();
+    }
+
+    fn pop_state(&mut self) {
+        if let Some(transform) = self.state_stack.pop() {
+            self.transform = transform;
+        }
+
+        self.target.pop_state();
+    }
+
+    fn clear_canvas(&mut self) {
+        self.transform      = Transform2D::identity();
+        self.state_stack    = vec![];
+        self.outside_sprite = None;
+        self.target.clear_canvas();
+    }
+
+    fn start_sprite(&mut self, sprite_id: u32) {
+        // Sprites are recorded rather than drawn, so they don't affect the transform of the canvas
+        if self.outside_sprite.is_none() {
+            self.outside_sprite = Some((self.transform, self.state_stack.clone()));
+        }
+
+        self.transform      = Transform2D::identity();
+        self.state_stack    = vec![];
+        self.target.start_sprite(sprite_id);
+    }
+
+    fn end_sprite(&mut self) {
+        if let Some((transform, state_stack)) = self.outside_sprite.take() {
+            self.transform      = transform;
+            self.state_stack    = state_stack;
+        }
+
+        self.target.end_sprite();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn is_close(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0-b.0).abs() < 0.0001 && (a.1-b.1).abs() < 0.0001
+    }
+
+    #[test]
+    fn passes_instructions_to_target() {
+        let mut gc = TransformTrackingContext::new(Vec::<Draw>::new());
+
+        gc.canvas_height(1000.0);
+        gc.new_path();
+        gc.move_to(10.0, 20.0);
+
+        assert!(gc.into_target() == vec![Draw::CanvasHeight(1000.0), Draw::NewPath, Draw::Move(10.0, 20.0)]);
+    }
+
+    #[test]
+    fn map_point_through_canvas_height() {
+        let mut gc = TransformTrackingContext::new(Vec::<Draw>::new());
+
+        gc.canvas_height(1000.0);
+
+        assert!(is_close(gc.canvas_to_view(500.0, 250.0), (1.0, 0.5)));
+        assert!(is_close(gc.view_to_canvas(1.0, 0.5).unwrap(), (500.0, 250.0)));
+    }
+
+    #[test]
+    fn center_region_moves_center_to_origin() {
+        let mut gc = TransformTrackingContext::new(Vec::<Draw>::new());
+
+        gc.canvas_height(1000.0);
+        gc.center_region(0.0, 0.0, 1920.0, 1080.0);
+
+        assert!(is_close(gc.canvas_to_view(960.0, 540.0), (0.0, 0.0)));
+    }
+
+    #[test]
+    fn pop_state_restores_transform() {
+        let mut gc = TransformTrackingContext::new(Vec::<Draw>::new());
+
+        gc.canvas_height(2.0);
+        gc.push_state();
+        gc.transform(Transform2D::translate(10.0, 10.0));
+
+        assert!(is_close(gc.canvas_to_view(0.0, 0.0), (10.0, 10.0)));
+        assert!(gc.state_depth() == 1);
+
+        gc.pop_state();
+
+        assert!(is_close(gc.canvas_to_view(0.0, 0.0), (0.0, 0.0)));
+        assert!(gc.state_depth() == 0);
+    }
+
+    #[test]
+    fn sprite_definition_does_not_change_transform() {
+        let mut gc = TransformTrackingContext::new(Vec::<Draw>::new());
+
+        gc.canvas_height(2.0);
+        gc.start_sprite(0);
+        gc.transform(Transform2D::translate(10.0, 10.0));
+        gc.end_sprite();
+
+        assert!(is_close(gc.canvas_to_view(0.0, 0.0), (0.0, 0.0)));
+    }
+}
@@ -0,0 +1,167 @@
+use super::super::coordinate::*;
+use super::super::bezier::*;
+
+use std::f64;
+
+///
+/// Represents an ellipse in 2 dimensions
+///
+#[derive(Clone, Copy)]
+pub struct Ellipse<Coord: Coordinate2D+Coordinate> {
+    /// The center of this ellipse
+    pub center: Coord,
+
+    /// The radius of this ellipse along its x axis
+    pub radius_x: f64,
+
+    /// The radius of this ellipse along its y axis
+    pub radius_y: f64,
+
+    /// The angle that the axes of this ellipse are rotated by, in radians
+    pub rotation: f64
+}
+
+///
+/// Represents an arc of an ellipse in 2 dimensions
+///
+/// Angles are measured from the ellipse's x axis towards its y axis, the same way as SVG measures them.
+/// The arc runs backwards if the end angle is less than the start angle.
+///
+#[derive(Clone, Copy)]
+pub struct EllipticalArc<'a, Coord: 'a+Coordinate2D+Coordinate> {
+    /// The ellipse that this is an arc of
+    ellipse: &'a Ellipse<Coord>,
+
+    /// The start point of this arc, in radians
+    start_radians: f64,
+
+    /// The end point of this arc, in radians
+    end_radians: f64
+}
+
+impl<Coord: Coordinate2D+Coordinate> Ellipse<Coord> {
+    ///
+    /// Creates a new ellipse with a center, radii and rotation
+    ///
+    pub fn new(center: Coord, radius_x: f64, radius_y: f64, rotation: f64) -> Ellipse<Coord> {
+        Ellipse {
+            center:     center,
+            radius_x:   radius_x,
+            radius_y:   radius_y,
+            rotation:   rotation
+        }
+    }
+
+    ///
+    /// Returns an object representing an arc from this ellipse
+    ///
+    pub fn arc<'a>(&'a self, start_radians: f64, end_radians: f64) -> EllipticalArc<'a, Coord> {
+        EllipticalArc {
+            ellipse:        self,
+            start_radians:  start_radians,
+            end_radians:    end_radians
+        }
+    }
+
+    ///
+    /// Returns the point at a particular angle around this ellipse
+    ///
+    pub fn point_at_angle(&self, radians: f64) -> Coord {
+        self.transform_point(radians.cos(), radians.sin())
+    }
+
+    ///
+    /// Maps a point on the unit circle onto this ellipse
+    ///
+    fn transform_point(&self, x: f64, y: f64) -> Coord {
+        let (x, y)                  = (x*self.radius_x, y*self.radius_y);
+        let (cos_theta, sin_theta)  = (self.rotation.cos(), self.rotation.sin());
+
+        Coord::from_components(&[
+            x*cos_theta - y*sin_theta + self.center.x(),
+            x*sin_theta + y*cos_theta + self.center.y()
+        ])
+    }
+}
+
+impl<'a, Coord: Coordinate2D+Coordinate> EllipticalArc<'a, Coord> {
+    ///
+    /// Converts this arc to a series of bezier curves
+    ///
+    /// The arc is divided into sections of 90 degrees or less so the approximation stays accurate.
+    ///
+    pub fn to_bezier_curves<Curve: BezierCurve<Point=Coord>>(&self) -> Vec<Curve> {
+        let sweep           = self.end_radians - self.start_radians;
+        let num_sections    = ((sweep.abs() / (f64::consts::PI/2.0)).ceil() as usize).max(1);
+        let section_sweep   = sweep / (num_sections as f64);
+
+        // Control points for an arc of the unit circle are along the tangents at each end (this scales with the arc in an affine transform)
+        let tangent_length  = 4.0/3.0 * (section_sweep/4.0).tan();
+
+        (0..num_sections)
+            .map(|section| {
+                let start_angle = self.start_radians + (section as f64)*section_sweep;
+                let end_angle   = start_angle + section_sweep;
+
+                let (x0, y0)    = (start_angle.cos(), start_angle.sin());
+                let (x3, y3)    = (end_angle.cos(), end_angle.sin());
+                let (x1, y1)    = (x0 - y0*tangent_length, y0 + x0*tangent_length);
+                let (x2, y2)    = (x3 + y3*tangent_length, y3 - x3*tangent_length);
+
+                Curve::from_points(
+                    self.ellipse.transform_point(x0, y0),
+                    self.ellipse.transform_point(x3, y3),
+                    self.ellipse.transform_point(x1, y1),
+                    self.ellipse.transform_point(x2, y2))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::f64;
+
+    #[test]
+    fn quarter_arc_ends_on_axes() {
+        let ellipse                 = Ellipse::new(Coord2(0.0, 0.0), 2.0, 1.0, 0.0);
+        let curves: Vec<Curve<_>>   = ellipse.arc(0.0, f64::consts::PI/2.0).to_bezier_curves();
+
+        assert!(curves.len() == 1);
+        assert!(curves[0].start_point().distance_to(&Coord2(2.0, 0.0)) < 0.0001);
+        assert!(curves[0].end_point().distance_to(&Coord2(0.0, 1.0)) < 0.0001);
+    }
+
+    #[test]
+    fn rotated_ellipse_is_roughly_elliptical() {
+        let ellipse                 = Ellipse::new(Coord2(5.0, 5.0), 4.0, 2.0, f64::consts::PI/6.0);
+        let curves: Vec<Curve<_>>   = ellipse.arc(0.0, f64::consts::PI*2.0).to_bezier_curves();
+
+        assert!(curves.len() == 4);
+
+        for curve in curves {
+            for t in 0..=10 {
+                let t = (t as f64)/10.0;
+                let p = curve.point_at_pos(t) - Coord2(5.0, 5.0);
+
+                // Rotate back to the ellipse's axes and check the point is on it
+                let (cos_theta, sin_theta)  = ((-f64::consts::PI/6.0).cos(), (-f64::consts::PI/6.0).sin());
+                let (x, y)                  = (p.x()*cos_theta - p.y()*sin_theta, p.x()*sin_theta + p.y()*cos_theta);
+
+                assert!(((x/4.0).powi(2) + (y/2.0).powi(2) - 1.0).abs() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn reversed_arc_runs_backwards() {
+        let ellipse                 = Ellipse::new(Coord2(0.0, 0.0), 1.0, 1.0, 0.0);
+        let curves: Vec<Curve<_>>   = ellipse.arc(f64::consts::PI, 0.0).to_bezier_curves();
+
+        assert!(curves.len() == 2);
+        assert!(curves[0].start_point().distance_to(&Coord2(-1.0, 0.0)) < 0.0001);
+        assert!(curves[0].end_point().distance_to(&Coord2(0.0, 1.0)) < 0.0001);
+        assert!(curves[1].end_point().distance_to(&Coord2(1.0, 0.0)) < 0.0001);
+    }
+}
@@ -1,5 +1,7 @@
 mod circle;
+mod ellipse;
 
 pub use self::circle::*;
+pub use self::ellipse::*;
 
 // TODO: represent arcs in more than 2 dimensions
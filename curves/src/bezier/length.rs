@@ -0,0 +1,197 @@
+use super::curve::*;
+use super::super::coordinate::*;
+
+/// The maximum number of times a curve is subdivided when measuring its length (limits the work done near cusps)
+const MAX_LENGTH_DEPTH: usize = 24;
+
+/// The number of iterations used when searching a section for a particular distance
+const SECTION_SEARCH_ITERATIONS: usize = 32;
+
+///
+/// Returns the lower and upper bounds for the length of a curve
+///
+/// A curve is never shorter than the line between its start and end points and never longer than
+/// the polygon formed by its control points.
+///
+pub fn curve_length_bounds<Curve: BezierCurve>(curve: &Curve) -> (f64, f64) {
+    let start       = curve.start_point();
+    let end         = curve.end_point();
+    let (cp1, cp2)  = curve.control_points();
+
+    let chord_length    = start.distance_to(&end);
+    let polygon_length  = start.distance_to(&cp1) + cp1.distance_to(&cp2) + cp2.distance_to(&end);
+
+    (chord_length, polygon_length)
+}
+
+///
+/// Measures the length of a curve, to within max_error of the actual length
+///
+pub fn curve_length<Curve: BezierCurve>(curve: &Curve, max_error: f64) -> f64 {
+    ArcLengthParametrisation::new(curve, max_error).length()
+}
+
+///
+/// Finds the t value of the point a particular distance along a curve
+///
+/// Distances outside the curve are clamped to its start or end.
+///
+pub fn t_for_distance<Curve: BezierCurve>(curve: &Curve, distance: f64, max_error: f64) -> f64 {
+    ArcLengthParametrisation::new(curve, max_error).t_for_distance(distance)
+}
+
+///
+/// Generates a set of points evenly spaced along a curve, including its start and end points
+///
+pub fn resample_curve<Curve: BezierCurve>(curve: &Curve, num_points: usize, max_error: f64) -> Vec<Curve::Point> {
+    let parametrisation = ArcLengthParametrisation::new(curve, max_error);
+    let length          = parametrisation.length();
+
+    match num_points {
+        0 => vec![],
+        1 => vec![curve.start_point()],
+        _ => (0..num_points)
+            .map(|index| (index as f64) / ((num_points-1) as f64) * length)
+            .map(|distance| parametrisation.point_at_distance(distance))
+            .collect()
+    }
+}
+
+///
+/// A section of a curve that is close enough to a straight line to measure by its end points
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct LengthSection {
+    /// The t value where this section starts
+    t_start: f64,
+
+    /// The t value where this section ends
+    t_end: f64,
+
+    /// The distance along the curve where this section starts
+    start_distance: f64,
+
+    /// The length of this section
+    length: f64
+}
+
+///
+/// Maps distances along a curve to t values
+///
+/// Creating this divides the curve into sections that can each be measured to within a fraction of
+/// the requested error, so it's cheaper to create one of these than to call `t_for_distance` when
+/// finding many points on the same curve.
+///
+#[derive(Clone, Debug)]
+pub struct ArcLengthParametrisation<Curve: BezierCurve> {
+    /// The curve that this is a parametrisation of
+    curve: Curve,
+
+    /// The sections of the curve, in order
+    sections: Vec<LengthSection>
+}
+
+impl<Curve: BezierCurve> ArcLengthParametrisation<Curve> {
+    ///
+    /// Creates an arc length parametrisation of a curve with lengths accurate to within max_error
+    ///
+    pub fn new(curve: &Curve, max_error: f64) -> ArcLengthParametrisation<Curve> {
+        let mut sections = vec![];
+        Self::add_sections(curve, 0.0, 1.0, max_error.abs(), 0, &mut sections);
+
+        // Work out where each section starts
+        let mut distance = 0.0;
+        for section in sections.iter_mut() {
+            section.start_distance  = distance;
+            distance                += section.length;
+        }
+
+        ArcLengthParametrisation {
+            curve:      curve.clone(),
+            sections:   sections
+        }
+    }
+
+    ///
+    /// Subdivides a curve until each section is measured to within the error
+    ///
+    fn add_sections(section: &Curve, t_start: f64, t_end: f64, max_error: f64, depth: usize, sections: &mut Vec<LengthSection>) {
+        let (min_length, max_length) = curve_length_bounds(section);
+
+        // The midpoint of the bounds is never more than half their difference away from the actual length
+        if (max_length - min_length)/2.0 <= max_error || depth >= MAX_LENGTH_DEPTH {
+            sections.push(LengthSection {
+                t_start:        t_start,
+                t_end:          t_end,
+                start_distance: 0.0,
+                length:         (min_length + max_length)/2.0
+            });
+        } else {
+            // Each half gets half of the error
+            let (first, second) = section.subdivide(0.5);
+            let t_mid           = (t_start + t_end)/2.0;
+
+            Self::add_sections(&first, t_start, t_mid, max_error/2.0, depth+1, sections);
+            Self::add_sections(&second, t_mid, t_end, max_error/2.0, depth+1, sections);
+        }
+    }
+
+    ///
+    /// The curve that this parametrises
+    ///
+    pub fn curve(&self) -> &Curve {
+        &self.curve
+    }
+
+    ///
+    /// The length of the curve
+    ///
+    pub fn length(&self) -> f64 {
+        self.sections.last()
+            .map(|section| section.start_distance + section.length)
+            .unwrap_or(0.0)
+    }
+
+    ///
+    /// Finds the t value of the point a particular distance along the curve
+    ///
+    pub fn t_for_distance(&self, distance: f64) -> f64 {
+        if distance <= 0.0 {
+            return 0.0;
+        } else if distance >= self.length() {
+            return 1.0;
+        }
+
+        // Find the section containing this distance
+        let section_index   = self.sections.iter()
+            .position(|section| section.start_distance + section.length >= distance)
+            .unwrap_or(self.sections.len()-1);
+        let section         = &self.sections[section_index];
+
+        // Sections are close to straight lines, so the distance from the start of the section is a good approximation of the distance along it
+        let section_start   = self.curve.point_at_pos(section.t_start);
+        let target          = distance - section.start_distance;
+
+        let mut low         = section.t_start;
+        let mut high        = section.t_end;
+
+        for _ in 0..SECTION_SEARCH_ITERATIONS {
+            let mid = (low + high)/2.0;
+
+            if self.curve.point_at_pos(mid).distance_to(&section_start) < target {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        (low + high)/2.0
+    }
+
+    ///
+    /// Finds the point a particular distance along the curve
+    ///
+    pub fn point_at_distance(&self, distance: f64) -> Curve::Point {
+        self.curve.point_at_pos(self.t_for_distance(distance))
+    }
+}
@@ -10,6 +10,8 @@ mod fit;
 mod offset;
 mod search;
 mod intersection;
+mod length;
+mod nearest;
 
 pub mod path;
 
@@ -25,5 +27,7 @@ pub use self::fit::*;
 pub use self::offset::*;
 pub use self::search::*;
 pub use self::intersection::*;
+pub use self::length::*;
+pub use self::nearest::*;
 
 pub use super::geo::*;
@@ -0,0 +1,85 @@
+use super::curve::*;
+use super::super::coordinate::*;
+
+/// The number of samples taken along a curve to find the regions that might contain the nearest point
+const NEAREST_SAMPLES: usize = 32;
+
+/// The number of iterations used to refine each candidate for the nearest point
+const NEAREST_ITERATIONS: usize = 48;
+
+///
+/// Finds the t value of the point on a curve that is closest to a target point
+///
+/// This samples the curve to find candidate regions and then narrows each one down by comparing
+/// distances. Derivatives aren't used, so this still works near cusps, where the tangent of the
+/// curve disappears and methods based on Newton's method can fail to converge.
+///
+pub fn nearest_point_on_curve<Curve: BezierCurve>(curve: &Curve, point: &Curve::Point) -> f64 {
+    let distance_squared_at = |t: f64| {
+        let offset = curve.point_at_pos(t) - *point;
+        offset.dot(&offset)
+    };
+
+    // Sample the curve at regular intervals
+    let samples: Vec<_> = (0..=NEAREST_SAMPLES)
+        .map(|index| (index as f64) / (NEAREST_SAMPLES as f64))
+        .map(|t| (t, distance_squared_at(t)))
+        .collect();
+
+    // The end points are candidates in their own right (the nearest point is often an end point and needs no refinement)
+    let mut best = if samples[0].1 <= samples[NEAREST_SAMPLES].1 { samples[0] } else { samples[NEAREST_SAMPLES] };
+
+    // Any sample that's no further away than its neighbours is next to a local minimum
+    for index in 0..=NEAREST_SAMPLES {
+        let before  = if index > 0 { samples[index-1].1 } else { samples[index].1 };
+        let after   = if index < NEAREST_SAMPLES { samples[index+1].1 } else { samples[index].1 };
+
+        if samples[index].1 > before || samples[index].1 > after {
+            continue;
+        }
+
+        // The minimum lies between the neighbouring samples: narrow it down with a golden section search
+        let mut low     = if index > 0 { samples[index-1].0 } else { 0.0 };
+        let mut high    = if index < NEAREST_SAMPLES { samples[index+1].0 } else { 1.0 };
+
+        let ratio       = (5.0f64.sqrt() - 1.0) / 2.0;
+        let mut t1      = high - (high-low)*ratio;
+        let mut t2      = low + (high-low)*ratio;
+        let mut d1      = distance_squared_at(t1);
+        let mut d2      = distance_squared_at(t2);
+
+        for _ in 0..NEAREST_ITERATIONS {
+            if d1 <= d2 {
+                high    = t2;
+                t2      = t1;
+                d2      = d1;
+                t1      = high - (high-low)*ratio;
+                d1      = distance_squared_at(t1);
+            } else {
+                low     = t1;
+                t1      = t2;
+                d1      = d2;
+                t2      = low + (high-low)*ratio;
+                d2      = distance_squared_at(t2);
+            }
+        }
+
+        let t           = (low + high)/2.0;
+        let distance    = distance_squared_at(t);
+
+        if distance < best.1 {
+            best = (t, distance);
+        }
+    }
+
+    best.0
+}
+
+///
+/// Finds the distance from a point to the nearest point on a curve
+///
+pub fn distance_to_curve<Curve: BezierCurve>(curve: &Curve, point: &Curve::Point) -> f64 {
+    let t = nearest_point_on_curve(curve, point);
+
+    curve.point_at_pos(t).distance_to(point)
+}
@@ -2,6 +2,12 @@ use super::deform::*;
 use super::normal::*;
 use super::super::coordinate::*;
 
+/// The maximum number of times a section of curve is subdivided when its offset is inaccurate
+const MAX_OFFSET_DEPTH: usize = 6;
+
+/// Curves shorter than this are treated as points
+const MIN_CURVE_LENGTH: f64 = 0.00001;
+
 ///
 /// Computes a series of curves that approximate an offset curve from the specified origin curve
 /// 
@@ -20,25 +26,60 @@ where Curve::Point: Normalize {
     let split_points    = curve.find_extremities();
     let curves          = split_offsets(curve, initial_offset, final_offset, &split_points);
 
+    // Offsets are accurate to within 1% of the offset distance (or 0.01 units for small offsets)
+    let max_error           = (initial_offset.abs().max(final_offset.abs()) * 0.01).max(0.01);
+
     // Offset the curves
     let mut offset_curves   = vec![];
     let mut previous_offset = initial_offset;
 
     for (curve, next_offset) in curves {
         // Offset this curve
-        let (offset_curve, _error) = simple_offset(curve, previous_offset, next_offset);
-        offset_curves.push(offset_curve);
+        subdivided_offset(curve, previous_offset, next_offset, max_error, 0, &mut offset_curves);
 
         // This is the initial offset of the next curve
         previous_offset = next_offset;
     }
 
-    // TODO: check the offset curve against an error bound and subdivide further if it doesn't make it
-    // TODO: we sometimes generate NaN curves (though not very often)
     // This is the final result
     offset_curves
 }
 
+///
+/// Offsets a curve, subdividing it until the offset is within the specified error
+/// 
+fn subdivided_offset<Curve: NormalCurve>(curve: Curve, initial_offset: f64, final_offset: f64, max_error: f64, depth: usize, offset_curves: &mut Vec<Curve>)
+where Curve::Point: Normalize {
+    // Sections with no length have no direction to offset in (the neighbouring sections will meet over them)
+    if curve.estimate_length(1.0) < MIN_CURVE_LENGTH {
+        return;
+    }
+
+    let (offset_curve, error) = simple_offset(curve.clone(), initial_offset, final_offset);
+
+    if error > max_error && depth < MAX_OFFSET_DEPTH {
+        // Offset each half of the curve separately
+        let mid_offset      = offset_at_pos(&curve, 0.5, initial_offset, final_offset);
+        let (left, right)   = curve.subdivide(0.5);
+
+        subdivided_offset(left, initial_offset, mid_offset, max_error, depth+1, offset_curves);
+        subdivided_offset(right, mid_offset, final_offset, max_error, depth+1, offset_curves);
+    } else {
+        offset_curves.push(offset_curve);
+    }
+}
+
+///
+/// Works out the offset at a point along a curve (the offset changes linearly with the length of the curve)
+/// 
+#[inline]
+fn offset_at_pos<Curve: NormalCurve>(curve: &Curve, t: f64, initial_offset: f64, final_offset: f64) -> f64 {
+    let overall_length  = curve.estimate_length(1.0);
+    let proportion      = if overall_length < MIN_CURVE_LENGTH { t } else { curve.estimate_length(t)/overall_length };
+
+    (final_offset - initial_offset) * proportion + initial_offset
+}
+
 ///
 /// Splits a curve at a given set of ordered offsets, returning a list of curves and
 /// their final offsets
@@ -48,8 +89,6 @@ fn split_offsets<Curve: NormalCurve>(curve: &Curve, initial_offset: f64, final_o
     let mut remaining           = curve.clone();
     let mut remaining_t         = 0.0;
     
-    let overall_length          = curve.estimate_length(1.0);
-
     for point in split_points {
         // Don't subdivide at point 0 (it doesn't produce a curve) or point 1 (this is just the remaining curve we add at the end)
         if point <= &0.01 || point >= &0.99 { continue; }
@@ -61,8 +100,7 @@ fn split_offsets<Curve: NormalCurve>(curve: &Curve, initial_offset: f64, final_o
         let (left_curve, right_curve) = remaining.subdivide(t);
 
         // Work out the offset at this point
-        let left_len    = curve.estimate_length(*point);
-        let offset      = (final_offset-initial_offset)*(left_len/overall_length) + initial_offset;
+        let offset      = offset_at_pos(curve, *point, initial_offset, final_offset);
 
         // Add the left curve to the result
         curves_and_offsets.push((left_curve, offset));
@@ -84,7 +122,7 @@ fn split_offsets<Curve: NormalCurve>(curve: &Curve, initial_offset: f64, final_o
 #[inline]
 fn offset_error<Curve: NormalCurve>(original_curve: &Curve, offset_curve: &Curve, t: f64, initial_offset: f64, final_offset: f64) -> Curve::Point {
     // Work out how much we need to offset the mid-point
-    let midpoint_offset     = (final_offset - initial_offset) * (original_curve.estimate_length(t)/original_curve.estimate_length(1.0)) + initial_offset;
+    let midpoint_offset     = offset_at_pos(original_curve, t, initial_offset, final_offset);
     let midpoint_normal     = original_curve.normal_at_pos(t).to_unit_vector();
     let original_midpoint   = original_curve.point_at_pos(t);
     let new_midpoint        = offset_curve.point_at_pos(t);
@@ -106,9 +144,11 @@ where Curve::Point: Normalize {
     let end         = curve.end_point();
     let (cp1, cp2)  = curve.control_points();
 
-    // The start and end CPs define the curve tangents at the start and end
-    let normal_start    = Curve::Point::to_normal(&start, &(cp1-start));
-    let normal_end      = Curve::Point::to_normal(&end, &(end-cp2));
+    // The start and end CPs define the curve tangents at the start and end (if a control point is on top of the end point, the tangent is towards the next point along)
+    let tangent_start   = if start.distance_to(&cp1) >= MIN_CURVE_LENGTH { cp1-start } else if start.distance_to(&cp2) >= MIN_CURVE_LENGTH { cp2-start } else { end-start };
+    let tangent_end     = if end.distance_to(&cp2) >= MIN_CURVE_LENGTH { end-cp2 } else if end.distance_to(&cp1) >= MIN_CURVE_LENGTH { end-cp1 } else { end-start };
+    let normal_start    = Curve::Point::to_normal(&start, &tangent_start);
+    let normal_end      = Curve::Point::to_normal(&end, &tangent_end);
     let normal_start    = Curve::Point::from_components(&normal_start).to_unit_vector();
     let normal_end      = Curve::Point::from_components(&normal_end).to_unit_vector();
 
@@ -128,13 +168,18 @@ where Curve::Point: Normalize {
         // Work out th error at this point
         let move_offset = offset_error(&curve, &offset_curve, sample_t, initial_offset, final_offset);
 
-        // Adjust the curve by the offset
-        offset_curve = move_point(&offset_curve, sample_t, move_offset);
+        // Adjust the curve by the offset (the normal can't be calculated at a cusp, so the curve is left alone there)
+        if !move_offset.is_nan() {
+            offset_curve = move_point(&offset_curve, sample_t, move_offset);
+        }
     }
 
-    // Use the offset at the curve's midway point as the error
-    let error_offset    = offset_error(&curve, &offset_curve, 0.5, initial_offset, final_offset);
-    let error           = Curve::Point::origin().distance_to(&error_offset);
+    // Use the largest offset at a few points along the curve as the error
+    let error = [0.5, 0.25, 0.75, 0.125, 0.875].iter()
+        .map(|sample_t| offset_error(&curve, &offset_curve, *sample_t, initial_offset, final_offset))
+        .map(|error_offset| Curve::Point::origin().distance_to(&error_offset))
+        .filter(|error| !error.is_nan())
+        .fold(0.0, |max_error: f64, error| max_error.max(error));
 
     (offset_curve, error)
 }
@@ -0,0 +1,109 @@
+use super::path::*;
+use super::super::curve::*;
+use super::super::length::*;
+
+///
+/// A position found by walking along a path
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PathPosition<Point> {
+    /// The index of the curve section that this position is in
+    pub section: usize,
+
+    /// The t value of this position within its section
+    pub t: f64,
+
+    /// The distance along the path of this position
+    pub distance: f64,
+
+    /// The point at this position
+    pub point: Point
+}
+
+///
+/// Measures the length of a path, to within max_error of the actual length
+///
+pub fn path_length<P: BezierPath>(path: &P, max_error: f64) -> f64 {
+    let curves          = path.to_curves::<Curve<_>>();
+    let section_error   = max_error / (curves.len().max(1) as f64);
+
+    curves.iter()
+        .map(|curve| curve_length(curve, section_error))
+        .sum()
+}
+
+///
+/// Finds the positions at a set of distances along a path
+///
+/// The distances must be in ascending order. Distances beyond the end of the path are clamped to its end.
+///
+pub fn path_positions_at_distances<P: BezierPath, Distances: IntoIterator<Item=f64>>(path: &P, distances: Distances, max_error: f64) -> Vec<PathPosition<P::Point>> {
+    let curves          = path.to_curves::<Curve<_>>();
+    let section_error   = max_error / (curves.len().max(1) as f64);
+    let sections: Vec<_> = curves.iter()
+        .map(|curve| ArcLengthParametrisation::new(curve, section_error))
+        .collect();
+
+    if sections.is_empty() {
+        // A path with no curves is just its start point
+        return distances.into_iter()
+            .map(|distance| PathPosition { section: 0, t: 0.0, distance: distance, point: path.start_point() })
+            .collect();
+    }
+
+    let mut result          = vec![];
+    let mut section_index   = 0;
+    let mut start_distance  = 0.0;
+
+    for distance in distances {
+        // Move on to the section containing this distance
+        while distance > start_distance + sections[section_index].length() && section_index+1 < sections.len() {
+            start_distance  += sections[section_index].length();
+            section_index   += 1;
+        }
+
+        let section = &sections[section_index];
+        let t       = section.t_for_distance(distance - start_distance);
+
+        result.push(PathPosition {
+            section:    section_index,
+            t:          t,
+            distance:   distance,
+            point:      section.curve().point_at_pos(t)
+        });
+    }
+
+    result
+}
+
+///
+/// Walks along a path, returning positions spaced a fixed distance apart starting at the beginning of the path
+///
+pub fn walk_path<P: BezierPath>(path: &P, spacing: f64, max_error: f64) -> Vec<PathPosition<P::Point>> {
+    let length      = path_length(path, max_error);
+
+    // A path can't be walked without moving forward
+    let num_steps   = if spacing > 0.0 { (length / spacing).floor() as usize } else { 0 };
+
+    path_positions_at_distances(path, (0..=num_steps).map(|step| (step as f64) * spacing), max_error)
+}
+
+///
+/// Generates a set of points evenly spaced along a path, including its start and end points
+///
+pub fn resample_path<P: BezierPath>(path: &P, num_points: usize, max_error: f64) -> Vec<P::Point> {
+    let length = path_length(path, max_error);
+
+    match num_points {
+        0 => vec![],
+        1 => vec![path.start_point()],
+        _ => {
+            let distances = (0..num_points).map(|index| (index as f64) / ((num_points-1) as f64) * length);
+
+            path_positions_at_distances(path, distances, max_error)
+                .into_iter()
+                .map(|position| position.point)
+                .collect()
+        }
+    }
+}
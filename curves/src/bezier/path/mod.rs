@@ -3,9 +3,17 @@ mod to_curves;
 mod point;
 mod bounds;
 mod intersection;
+mod length;
+mod nearest;
+mod svg;
+mod stroke;
 
 pub use self::path::*;
 pub use self::to_curves::*;
 pub use self::point::*;
 pub use self::bounds::*;
-pub use self::intersection::*;
\ No newline at end of file
+pub use self::intersection::*;
+pub use self::length::*;
+pub use self::nearest::*;
+pub use self::svg::*;
+pub use self::stroke::*;
\ No newline at end of file
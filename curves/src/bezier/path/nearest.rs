@@ -0,0 +1,38 @@
+use super::path::*;
+use super::to_curves::*;
+use super::super::curve::*;
+use super::super::nearest::*;
+use super::super::super::coordinate::*;
+
+///
+/// Finds the point on a path that's closest to a target point
+///
+/// The return value is the index of the curve section containing the nearest point, the t value
+/// within that section and the point itself. A path with no curves is just its start point.
+///
+pub fn nearest_point_on_path<P: BezierPath>(path: &P, point: &P::Point) -> (usize, f64, P::Point) {
+    let mut nearest             = (0, 0.0, path.start_point());
+    let mut nearest_distance    = path.start_point().distance_to(point);
+
+    for (section, curve) in path_to_curves::<_, Curve<_>>(path).enumerate() {
+        let t               = nearest_point_on_curve(&curve, point);
+        let curve_point     = curve.point_at_pos(t);
+        let distance        = curve_point.distance_to(point);
+
+        if distance < nearest_distance {
+            nearest             = (section, t, curve_point);
+            nearest_distance    = distance;
+        }
+    }
+
+    nearest
+}
+
+///
+/// Finds the distance from a point to the nearest point on a path
+///
+pub fn distance_to_path<P: BezierPath>(path: &P, point: &P::Point) -> f64 {
+    let (_, _, nearest) = nearest_point_on_path(path, point);
+
+    nearest.distance_to(point)
+}
@@ -0,0 +1,575 @@
+use super::path::*;
+use super::super::curve::*;
+use super::super::length::*;
+use super::super::nearest::*;
+use super::super::tangent::*;
+use super::super::super::arc::*;
+use super::super::super::coordinate::*;
+
+use std::f64;
+
+/// The maximum number of times a curve is subdivided while offsetting it (limits the work done near cusps)
+const MAX_OFFSET_DEPTH: usize = 12;
+
+/// Curves whose control polygons are shorter than this are treated as points
+const MIN_LENGTH: f64 = 1e-9;
+
+///
+/// How the outer corners of a stroke are drawn
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StrokeJoin {
+    /// Extend the edges until they meet (falling back to a bevel beyond the miter limit)
+    Miter,
+
+    /// Draw an arc around the corner
+    Round,
+
+    /// Draw a straight line across the corner
+    Bevel
+}
+
+///
+/// How the ends of an open stroke are drawn
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StrokeCap {
+    /// Finish the stroke at its end point
+    Butt,
+
+    /// Draw a semicircle around the end point
+    Round,
+
+    /// Extend the stroke by half its width past the end point
+    Square
+}
+
+///
+/// Describes how a path should be stroked
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct StrokeOptions {
+    /// The width of the stroke
+    pub width: f64,
+
+    /// How corners are drawn
+    pub join: StrokeJoin,
+
+    /// How the ends of open paths are drawn
+    pub cap: StrokeCap,
+
+    /// The longest a miter join can be, as a multiple of the width, before it's drawn as a bevel
+    pub miter_limit: f64,
+
+    /// Alternating lengths of dashes and gaps (an empty pattern draws a solid line)
+    pub dash_pattern: Vec<f64>,
+
+    /// The distance into the dash pattern that the path starts at
+    pub dash_offset: f64,
+
+    /// The maximum distance the outline can be from the actual edge of the stroke
+    pub max_error: f64
+}
+
+impl StrokeOptions {
+    ///
+    /// Creates the options for a solid stroke with a particular width and mitered corners
+    ///
+    pub fn new(width: f64) -> StrokeOptions {
+        StrokeOptions {
+            width:          width,
+            join:           StrokeJoin::Miter,
+            cap:            StrokeCap::Butt,
+            miter_limit:    10.0,
+            dash_pattern:   vec![],
+            dash_offset:    0.0,
+            max_error:      0.01
+        }
+    }
+}
+
+///
+/// Builds up the points of an outline path
+///
+struct OutlineBuilder<Point> {
+    /// The start point of the outline
+    start: Point,
+
+    /// The curves in the outline so far
+    curves: Vec<(Point, Point, Point)>
+}
+
+impl<Point: Coordinate+Coordinate2D> OutlineBuilder<Point> {
+    fn new(start: Point) -> OutlineBuilder<Point> {
+        OutlineBuilder {
+            start:  start,
+            curves: vec![]
+        }
+    }
+
+    ///
+    /// The last point in the outline
+    ///
+    fn current(&self) -> Point {
+        self.curves.last().map(|&(_, _, end)| end).unwrap_or(self.start)
+    }
+
+    fn curve_to(&mut self, cp1: Point, cp2: Point, end: Point) {
+        self.curves.push((cp1, cp2, end));
+    }
+
+    fn line_to(&mut self, end: Point) {
+        let start = self.current();
+
+        if start.distance_to(&end) > MIN_LENGTH {
+            self.curves.push((start + (end-start)*(1.0/3.0), start + (end-start)*(2.0/3.0), end));
+        }
+    }
+
+    ///
+    /// Adds a circular arc around a center point, finishing at the specified end point
+    ///
+    fn arc_to(&mut self, center: Point, end: Point, sweep: f64) {
+        let start       = self.current();
+        let radius      = start.distance_to(&center);
+        let start_angle = (start.y()-center.y()).atan2(start.x()-center.x());
+
+        let circle                  = Ellipse::new(center, radius, radius, 0.0);
+        let arc: Vec<Curve<Point>>  = circle.arc(start_angle, start_angle+sweep).to_bezier_curves();
+        let num_curves              = arc.len();
+
+        for (index, curve) in arc.into_iter().enumerate() {
+            let (cp1, cp2)  = curve.control_points();
+            let curve_end   = if index == num_curves-1 { end } else { curve.end_point() };
+
+            self.curve_to(cp1, cp2, curve_end);
+        }
+    }
+
+    ///
+    /// Returns the same outline running in the opposite direction
+    ///
+    fn reversed(self) -> OutlineBuilder<Point> {
+        let mut result = OutlineBuilder::new(self.current());
+
+        for index in (0..self.curves.len()).rev() {
+            let (cp1, cp2, _)   = self.curves[index];
+            let previous        = if index > 0 { self.curves[index-1].2 } else { self.start };
+
+            result.curve_to(cp2, cp1, previous);
+        }
+
+        result
+    }
+
+    ///
+    /// Adds the curves from another outline to this one, with a line to its start if they're not already connected
+    ///
+    fn append(&mut self, other: OutlineBuilder<Point>) {
+        self.line_to(other.start);
+        self.curves.extend(other.curves);
+    }
+
+    ///
+    /// Converts to a path, closing it if it doesn't already end at its start point
+    ///
+    fn into_path<P: BezierPath<Point=Point>>(mut self) -> P {
+        let start = self.start;
+        self.line_to(start);
+
+        P::from_points(self.start, self.curves)
+    }
+}
+
+///
+/// Creates a 2D point
+///
+#[inline]
+fn point<Point: Coordinate>(x: f64, y: f64) -> Point {
+    Point::from_components(&[x, y])
+}
+
+///
+/// The normal to a unit tangent (pointing to the left of the direction of travel)
+///
+#[inline]
+fn normal<Point: Coordinate+Coordinate2D>(tangent: Point) -> Point {
+    point(-tangent.y(), tangent.x())
+}
+
+///
+/// The z component of the cross product of two 2D vectors
+///
+#[inline]
+fn cross<Point: Coordinate2D>(a: Point, b: Point) -> f64 {
+    a.x()*b.y() - a.y()*b.x()
+}
+
+///
+/// Picks the first of a set of directions that has a length, as a unit vector
+///
+fn first_direction<Point: Coordinate+Coordinate2D>(directions: &[Point]) -> Point {
+    directions.iter()
+        .find(|direction| direction.magnitude() > MIN_LENGTH)
+        .map(|direction| direction.to_unit_vector())
+        .unwrap_or_else(|| point(1.0, 0.0))
+}
+
+///
+/// The direction of a curve at its start point
+///
+/// Control points that are on top of the start point don't affect the direction, so this still works at cusps.
+///
+fn start_tangent<Point: Coordinate+Coordinate2D>(curve: &Curve<Point>) -> Point {
+    let (cp1, cp2) = curve.control_points();
+    let start = curve.start_point();
+
+    first_direction(&[cp1-start, cp2-start, curve.end_point()-start])
+}
+
+///
+/// The direction of a curve at its end point
+///
+fn end_tangent<Point: Coordinate+Coordinate2D>(curve: &Curve<Point>) -> Point {
+    let (cp1, cp2) = curve.control_points();
+    let end = curve.end_point();
+
+    first_direction(&[end-cp2, end-cp1, end-curve.start_point()])
+}
+
+///
+/// True if a curve is too short to have a direction
+///
+fn is_point<Point: Coordinate+Coordinate2D>(curve: &Curve<Point>) -> bool {
+    let (cp1, cp2) = curve.control_points();
+
+    curve.start_point().distance_to(&cp1) + cp1.distance_to(&cp2) + cp2.distance_to(&curve.end_point()) <= MIN_LENGTH
+}
+
+///
+/// Works out how much a control arm should be scaled by when offsetting a curve
+///
+/// Offsetting towards the center of curvature shrinks the curve and offsetting away from it grows it, so
+/// the arms scale with the change in the radius of curvature.
+///
+fn arm_scale<Point: Coordinate+Coordinate2D>(arm: Point, second_difference: Point, distance: f64) -> f64 {
+    let arm_length = arm.magnitude();
+
+    if arm_length <= MIN_LENGTH {
+        1.0
+    } else {
+        let curvature = (2.0/3.0) * cross(arm, second_difference) / (arm_length*arm_length*arm_length);
+        (1.0 - distance*curvature).max(0.0)
+    }
+}
+
+///
+/// Offsets a curve by a distance along its normal, subdividing it until the offset is within max_error
+///
+fn offset_curve<Point: Coordinate+Coordinate2D>(curve: &Curve<Point>, distance: f64, max_error: f64, depth: usize, offset_curves: &mut Vec<Curve<Point>>) {
+    let start       = curve.start_point();
+    let end         = curve.end_point();
+    let (cp1, cp2)  = curve.control_points();
+
+    let start_tangent   = start_tangent(curve);
+    let end_tangent     = end_tangent(curve);
+
+    // Move the end points along their normals and scale the control arms with the curvature
+    let new_start       = start + normal(start_tangent)*distance;
+    let new_end         = end + normal(end_tangent)*distance;
+    let new_cp1         = new_start + (cp1-start)*arm_scale(cp1-start, cp2 - cp1*2.0 + start, distance);
+    let new_cp2         = new_end + (cp2-end)*arm_scale(end-cp2, end - cp2*2.0 + cp1, distance);
+    let offset          = Curve::from_points(new_start, new_end, new_cp1, new_cp2);
+
+    if depth < MAX_OFFSET_DEPTH {
+        // Curves that turn sharply are always subdivided: the estimate above only works for gentle curves
+        let sharp_turn  = start_tangent.dot(&end_tangent) < 0.5;
+
+        // Measure how far the offset curve is from where it should be at a few sample points
+        let tangent     = Tangent::from(curve);
+        let error       = [0.25, 0.5, 0.75].iter()
+            .map(|t| (curve.point_at_pos(*t), tangent.tangent(*t)))
+            .filter(|(_, tangent)| tangent.magnitude() > MIN_LENGTH)
+            .map(|(point, tangent)| point + normal(tangent.to_unit_vector())*distance)
+            .map(|target| distance_to_curve(&offset, &target))
+            .fold(0.0, |a: f64, b: f64| if b.is_nan() { f64::MAX } else { a.max(b) });
+
+        if sharp_turn || error > max_error {
+            let (first, second) = curve.subdivide(0.5);
+
+            offset_curve(&first, distance, max_error, depth+1, offset_curves);
+            offset_curve(&second, distance, max_error, depth+1, offset_curves);
+            return;
+        }
+    }
+
+    offset_curves.push(offset);
+}
+
+///
+/// Adds a join between two sections of one side of a stroke
+///
+fn add_join<Point: Coordinate+Coordinate2D>(outline: &mut OutlineBuilder<Point>, corner: Point, incoming: Point, outgoing: Point, distance: f64, options: &StrokeOptions) {
+    let from    = outline.current();
+    let to      = corner + normal(outgoing)*distance;
+
+    // Sections that meet smoothly need no join
+    if from.distance_to(&to) <= options.max_error {
+        outline.line_to(to);
+        return;
+    }
+
+    // The outer side of a corner is the one where the sections move apart
+    let turn    = cross(incoming, outgoing);
+    let outer   = if turn.abs() <= MIN_LENGTH { distance > 0.0 } else { turn*distance < 0.0 };
+
+    if !outer {
+        // Passing through the corner point makes sure the inner side is covered by the fill
+        outline.line_to(corner);
+        outline.line_to(to);
+        return;
+    }
+
+    match options.join {
+        StrokeJoin::Bevel   => outline.line_to(to),
+
+        StrokeJoin::Miter   => {
+            let bisector    = normal(incoming) + normal(outgoing);
+            let length      = bisector.magnitude();
+            let ratio       = if length > MIN_LENGTH { 2.0/length } else { f64::MAX };
+
+            if ratio <= options.miter_limit {
+                outline.line_to(corner + bisector.to_unit_vector()*(distance*ratio));
+            }
+
+            outline.line_to(to);
+        },
+
+        StrokeJoin::Round   => {
+            let (from_dir, to_dir)  = (from-corner, to-corner);
+            let sweep               = cross(from_dir, to_dir).atan2(from_dir.dot(&to_dir));
+
+            outline.arc_to(corner, to, sweep);
+        }
+    }
+}
+
+///
+/// Adds a cap to the end of a stroke, going from the left side of the end point to the right side
+///
+fn add_cap<Point: Coordinate+Coordinate2D>(outline: &mut OutlineBuilder<Point>, end: Point, direction: Point, distance: f64, cap: StrokeCap) {
+    let to = end - normal(direction)*distance;
+
+    match cap {
+        StrokeCap::Butt     => outline.line_to(to),
+
+        StrokeCap::Square   => {
+            let extension = direction*distance;
+            let from      = outline.current();
+
+            outline.line_to(from + extension);
+            outline.line_to(to + extension);
+            outline.line_to(to);
+        },
+
+        StrokeCap::Round    => outline.arc_to(end, to, -f64::consts::PI)
+    }
+}
+
+///
+/// Generates one side of a stroke by offsetting each section and joining them together
+///
+fn offset_side<Point: Coordinate+Coordinate2D>(sections: &[Curve<Point>], distance: f64, closed: bool, options: &StrokeOptions) -> OutlineBuilder<Point> {
+    let first_start = sections[0].start_point() + normal(start_tangent(&sections[0]))*distance;
+    let mut outline = OutlineBuilder::new(first_start);
+
+    for (index, section) in sections.iter().enumerate() {
+        if index > 0 {
+            add_join(&mut outline, section.start_point(), end_tangent(&sections[index-1]), start_tangent(section), distance, options);
+        }
+
+        let mut offset_curves = vec![];
+        offset_curve(section, distance, options.max_error, 0, &mut offset_curves);
+
+        for offset in offset_curves {
+            let (cp1, cp2) = offset.control_points();
+
+            outline.line_to(offset.start_point());
+            outline.curve_to(cp1, cp2, offset.end_point());
+        }
+    }
+
+    if closed {
+        // Join the end back up to the start
+        let last = &sections[sections.len()-1];
+        add_join(&mut outline, sections[0].start_point(), end_tangent(last), start_tangent(&sections[0]), distance, options);
+    }
+
+    outline
+}
+
+///
+/// Generates the outline of a stroke that's just a point (which is only visible if it has caps)
+///
+fn stroke_point<P: BezierPath>(center: P::Point, options: &StrokeOptions) -> Vec<P>
+where P::Point: Coordinate2D {
+    let distance = options.width/2.0;
+
+    match options.cap {
+        StrokeCap::Butt     => vec![],
+        StrokeCap::Round    => vec![Circle::new(center, distance).to_path()],
+        StrokeCap::Square   => {
+            let mut outline = OutlineBuilder::new(center + point(-distance, -distance));
+            outline.line_to(center + point(distance, -distance));
+            outline.line_to(center + point(distance, distance));
+            outline.line_to(center + point(-distance, distance));
+
+            vec![outline.into_path()]
+        }
+    }
+}
+
+///
+/// Generates the outline of a stroke along a single path with no dashes
+///
+fn stroke_subpath<P: BezierPath>(path: &P, options: &StrokeOptions) -> Vec<P>
+where P::Point: Coordinate2D {
+    let sections: Vec<Curve<_>> = path.to_curves::<Curve<_>>()
+        .into_iter()
+        .filter(|curve| !is_point(curve))
+        .collect();
+
+    if sections.is_empty() {
+        return stroke_point(path.start_point(), options);
+    }
+
+    let distance    = options.width/2.0;
+    let first       = &sections[0];
+    let last        = &sections[sections.len()-1];
+    let closed      = first.start_point().distance_to(&last.end_point()) <= MIN_LENGTH;
+
+    let left        = offset_side(&sections, distance, closed, options);
+    let right       = offset_side(&sections, -distance, closed, options).reversed();
+
+    if closed {
+        // Closed paths have an inside and an outside edge, which run in opposite directions so the area between them is filled
+        vec![left.into_path(), right.into_path()]
+    } else {
+        // Open paths go up one side, around the end cap, back down the other side and around the start cap
+        let mut outline = left;
+
+        add_cap(&mut outline, last.end_point(), end_tangent(last), distance, options.cap);
+        outline.append(right);
+        add_cap(&mut outline, first.start_point(), start_tangent(first)*-1.0, distance, options.cap);
+
+        vec![outline.into_path()]
+    }
+}
+
+///
+/// Splits a path into dashes
+///
+/// The pattern is a list of alternating dash and gap lengths, starting `offset` units into the pattern. Patterns
+/// with an odd number of entries are repeated to make them even, and patterns that can't be followed (because
+/// they're empty, negative or zero length) leave the path as it is.
+///
+pub fn dash_path<P: BezierPath>(path: &P, pattern: &[f64], offset: f64, max_error: f64) -> Vec<P> {
+    let total: f64 = pattern.iter().sum();
+
+    if pattern.is_empty() || pattern.iter().any(|length| *length < 0.0) || total <= 0.0 {
+        return vec![path.clone()];
+    }
+
+    let pattern: Vec<f64>   = if pattern.len()%2 == 1 { pattern.iter().chain(pattern.iter()).cloned().collect() } else { pattern.to_vec() };
+    let total: f64          = pattern.iter().sum();
+
+    // Find where in the pattern the path starts
+    let mut skip            = ((offset % total) + total) % total;
+    let mut dash_index      = 0;
+    let mut dash_remaining  = pattern[0];
+
+    while skip > 0.0 {
+        if skip >= dash_remaining {
+            skip            -= dash_remaining;
+            dash_index      = (dash_index+1) % pattern.len();
+            dash_remaining  = pattern[dash_index];
+        } else {
+            dash_remaining  -= skip;
+            skip            = 0.0;
+        }
+    }
+
+    // Walk along the path, generating a new path for each dash
+    let mut dashes          = vec![];
+    let mut current_dash    = if dash_index%2 == 0 { Some((path.start_point(), vec![])) } else { None };
+
+    for curve in path.to_curves::<Curve<_>>() {
+        let parametrisation = ArcLengthParametrisation::new(&curve, max_error);
+        let length          = parametrisation.length();
+        let mut pos         = 0.0;
+
+        loop {
+            let dash_end    = if pos + dash_remaining < length { pos + dash_remaining } else { length };
+            let is_on       = dash_index%2 == 0;
+
+            if is_on {
+                // Add the part of the curve covered by the dash
+                let t1 = parametrisation.t_for_distance(pos);
+                let t2 = parametrisation.t_for_distance(dash_end);
+
+                if t2 > t1 {
+                    let (before, _)     = curve.subdivide(t2);
+                    let (_, section)    = before.subdivide(t1/t2);
+                    let (cp1, cp2)      = section.control_points();
+
+                    if let Some((_, ref mut curves)) = current_dash {
+                        curves.push((cp1, cp2, section.end_point()));
+                    }
+                }
+            }
+
+            if pos + dash_remaining >= length {
+                // The rest of this dash or gap is on the next curve
+                dash_remaining -= length - pos;
+                break;
+            }
+
+            // Move on to the next dash or gap
+            if is_on {
+                dashes.extend(current_dash.take());
+            } else {
+                current_dash = Some((parametrisation.point_at_distance(dash_end), vec![]));
+            }
+
+            pos             = dash_end;
+            dash_index      = (dash_index+1) % pattern.len();
+            dash_remaining  = pattern[dash_index];
+        }
+    }
+
+    dashes.extend(current_dash.take());
+
+    dashes.into_iter()
+        .map(|(start, curves)| P::from_points(start, curves))
+        .collect()
+}
+
+///
+/// Generates the outline of a stroke along a path
+///
+/// The result is a set of paths that cover the area of the stroke when filled using the non-zero winding rule.
+/// Open paths (and dashes) produce a single outline including the caps, and closed paths produce an outline
+/// for each side of the stroke.
+///
+pub fn stroke_path<P: BezierPath>(path: &P, options: &StrokeOptions) -> Vec<P>
+where P::Point: Coordinate2D {
+    let subpaths = if options.dash_pattern.is_empty() {
+        vec![path.clone()]
+    } else {
+        dash_path(path, &options.dash_pattern, options.dash_offset, options.max_error)
+    };
+
+    subpaths.iter()
+        .flat_map(|subpath| stroke_subpath(subpath, options))
+        .collect()
+}
@@ -0,0 +1,522 @@
+use super::path::*;
+use super::super::curve::*;
+use super::super::super::arc::*;
+use super::super::super::coordinate::*;
+
+use std::f64;
+use std::fmt::Write;
+
+///
+/// Errors that can occur while parsing SVG path data
+///
+/// The position is the index of the byte in the path data where the error was detected.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SvgPathError {
+    /// A character that isn't a command or part of a number was found
+    UnexpectedCharacter(char, usize),
+
+    /// A command needed a number (or an arc flag) that was missing
+    ExpectedNumber(usize),
+
+    /// The path data didn't start with a move command
+    MissingMoveTo(usize)
+}
+
+///
+/// Reads the parts of an SVG path data string
+///
+struct SvgPathReader<'a> {
+    /// The path data
+    data: &'a [u8],
+
+    /// The next byte to read
+    pos: usize
+}
+
+impl<'a> SvgPathReader<'a> {
+    ///
+    /// Moves past whitespace and optionally a single comma
+    ///
+    fn skip_separators(&mut self) {
+        let mut seen_comma = false;
+
+        while self.pos < self.data.len() {
+            match self.data[self.pos] {
+                b' ' | b'\t' | b'\r' | b'\n' | b'\x0c'  => { },
+                b',' if !seen_comma                     => { seen_comma = true; },
+                _                                       => { break; }
+            }
+
+            self.pos += 1;
+        }
+    }
+
+    ///
+    /// True if the next item in the data is a number
+    ///
+    fn at_number(&mut self) -> bool {
+        self.skip_separators();
+
+        self.data.get(self.pos)
+            .map(|&c| c.is_ascii_digit() || c == b'-' || c == b'+' || c == b'.')
+            .unwrap_or(false)
+    }
+
+    ///
+    /// Reads the next command character, if there is one
+    ///
+    fn command(&mut self) -> Result<Option<u8>, SvgPathError> {
+        self.skip_separators();
+
+        match self.data.get(self.pos).cloned() {
+            None                                => Ok(None),
+            Some(c) if c.is_ascii_alphabetic()  => { self.pos += 1; Ok(Some(c)) },
+            Some(c)                             => Err(SvgPathError::UnexpectedCharacter(c as char, self.pos))
+        }
+    }
+
+    ///
+    /// Moves past a '+' or '-' sign, if there is one
+    ///
+    fn sign(&mut self) {
+        if self.data.get(self.pos) == Some(&b'-') || self.data.get(self.pos) == Some(&b'+') {
+            self.pos += 1;
+        }
+    }
+
+    ///
+    /// Moves past a sequence of digits, returning false if there were none
+    ///
+    fn digits(&mut self) -> bool {
+        let start = self.pos;
+
+        while self.pos < self.data.len() && self.data[self.pos].is_ascii_digit() {
+            self.pos += 1;
+        }
+
+        self.pos > start
+    }
+
+    ///
+    /// Reads a number
+    ///
+    /// Numbers don't need separators if there's no ambiguity, so '1.5.5' is two numbers and '1-2' is two numbers
+    ///
+    fn number(&mut self) -> Result<f64, SvgPathError> {
+        self.skip_separators();
+
+        let start       = self.pos;
+
+        // Sign
+        self.sign();
+
+        // Integer and fractional parts (at least one must be present)
+        let mut has_digits = self.digits();
+        if self.data.get(self.pos) == Some(&b'.') {
+            self.pos    += 1;
+            has_digits  = self.digits() || has_digits;
+        }
+
+        if !has_digits {
+            self.pos = start;
+            return Err(SvgPathError::ExpectedNumber(start));
+        }
+
+        // Exponent (only if it's followed by digits, so an 'e' can't be mistaken for something else)
+        if self.data.get(self.pos) == Some(&b'e') || self.data.get(self.pos) == Some(&b'E') {
+            let exponent_start = self.pos;
+            self.pos += 1;
+
+            self.sign();
+            if !self.digits() { self.pos = exponent_start; }
+        }
+
+        let text = String::from_utf8_lossy(&self.data[start..self.pos]);
+        text.parse().map_err(|_| SvgPathError::ExpectedNumber(start))
+    }
+
+    ///
+    /// Reads an arc flag, which is a single '0' or '1' that may not be followed by a separator
+    ///
+    fn flag(&mut self) -> Result<bool, SvgPathError> {
+        self.skip_separators();
+
+        match self.data.get(self.pos).cloned() {
+            Some(b'0')  => { self.pos += 1; Ok(false) },
+            Some(b'1')  => { self.pos += 1; Ok(true) },
+            _           => Err(SvgPathError::ExpectedNumber(self.pos))
+        }
+    }
+
+    ///
+    /// Reads a coordinate pair
+    ///
+    fn pair(&mut self) -> Result<(f64, f64), SvgPathError> {
+        let x = self.number()?;
+        let y = self.number()?;
+
+        Ok((x, y))
+    }
+}
+
+/// A subpath read from SVG path data (the start point and the curves)
+type SvgSubpath<Point> = (Point, Vec<(Point, Point, Point)>);
+
+///
+/// Tracks the state of the paths while parsing path data
+///
+struct SvgPathBuilder<Point> {
+    /// The paths that have been completed
+    paths: Vec<SvgSubpath<Point>>,
+
+    /// The start point and curves of the subpath being built, if there is one
+    current_path: Option<SvgSubpath<Point>>,
+
+    /// The current point
+    current_point: Point,
+
+    /// The point the current subpath started at (where a close command returns to)
+    subpath_start: Point,
+
+    /// The second control point of the last cubic curve, if the last command was a cubic curve
+    last_cubic_cp: Option<Point>,
+
+    /// The control point of the last quadratic curve, if the last command was a quadratic curve
+    last_quad_cp: Option<Point>
+}
+
+impl<Point: Coordinate+Coordinate2D> SvgPathBuilder<Point> {
+    fn new() -> SvgPathBuilder<Point> {
+        SvgPathBuilder {
+            paths:          vec![],
+            current_path:   None,
+            current_point:  Point::origin(),
+            subpath_start:  Point::origin(),
+            last_cubic_cp:  None,
+            last_quad_cp:   None
+        }
+    }
+
+    ///
+    /// Creates a point, relative to the current point if necessary
+    ///
+    fn point(&self, (x, y): (f64, f64), relative: bool) -> Point {
+        if relative {
+            Point::from_components(&[self.current_point.x() + x, self.current_point.y() + y])
+        } else {
+            Point::from_components(&[x, y])
+        }
+    }
+
+    ///
+    /// Finishes the current subpath
+    ///
+    fn finish_subpath(&mut self) {
+        if let Some((start, curves)) = self.current_path.take() {
+            // Subpaths made of a move on its own don't draw anything
+            if !curves.is_empty() {
+                self.paths.push((start, curves));
+            }
+        }
+    }
+
+    ///
+    /// Starts a new subpath at the specified point
+    ///
+    fn move_to(&mut self, point: Point) {
+        self.finish_subpath();
+
+        self.current_path   = Some((point, vec![]));
+        self.current_point  = point;
+        self.subpath_start  = point;
+        self.last_cubic_cp  = None;
+        self.last_quad_cp   = None;
+    }
+
+    ///
+    /// Adds a cubic curve from the current point
+    ///
+    fn curve_to(&mut self, cp1: Point, cp2: Point, end: Point) {
+        // Drawing after a close command continues from the start of the closed subpath
+        if self.current_path.is_none() {
+            self.current_path = Some((self.current_point, vec![]));
+        }
+
+        if let Some((_, ref mut curves)) = self.current_path {
+            curves.push((cp1, cp2, end));
+        }
+
+        self.current_point  = end;
+        self.last_cubic_cp  = Some(cp2);
+        self.last_quad_cp   = None;
+    }
+
+    ///
+    /// Adds a straight line from the current point
+    ///
+    fn line_to(&mut self, end: Point) {
+        let start = self.current_point;
+
+        self.curve_to(start + (end-start)*(1.0/3.0), start + (end-start)*(2.0/3.0), end);
+        self.last_cubic_cp = None;
+    }
+
+    ///
+    /// Adds a quadratic curve from the current point
+    ///
+    fn quad_to(&mut self, cp: Point, end: Point) {
+        let start = self.current_point;
+
+        // Quadratic curves are cubic curves with control points 2/3rds of the way to the quadratic control point
+        self.curve_to(start + (cp-start)*(2.0/3.0), end + (cp-end)*(2.0/3.0), end);
+        self.last_cubic_cp  = None;
+        self.last_quad_cp   = Some(cp);
+    }
+
+    ///
+    /// Adds an elliptical arc from the current point, using the SVG endpoint parameterisation
+    ///
+    fn arc_to(&mut self, radius_x: f64, radius_y: f64, rotation_degrees: f64, large_arc: bool, sweep: bool, end: Point) {
+        let start = self.current_point;
+
+        // Arcs with no radius are straight lines, and arcs that don't go anywhere are omitted
+        if start == end {
+            return;
+        }
+
+        if radius_x == 0.0 || radius_y == 0.0 {
+            self.line_to(end);
+            return;
+        }
+
+        // Work out the center of the ellipse (following the SVG implementation notes)
+        let rotation                = rotation_degrees.to_radians();
+        let (cos_phi, sin_phi)      = (rotation.cos(), rotation.sin());
+        let (half_dx, half_dy)      = ((start.x()-end.x())/2.0, (start.y()-end.y())/2.0);
+        let (x1, y1)                = (cos_phi*half_dx + sin_phi*half_dy, -sin_phi*half_dx + cos_phi*half_dy);
+
+        // Radii that are too small to reach the end point are scaled up
+        let (mut rx, mut ry)        = (radius_x.abs(), radius_y.abs());
+        let lambda                  = (x1*x1)/(rx*rx) + (y1*y1)/(ry*ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let numerator               = rx*rx*ry*ry - rx*rx*y1*y1 - ry*ry*x1*x1;
+        let denominator             = rx*rx*y1*y1 + ry*ry*x1*x1;
+        let coefficient             = (numerator/denominator).max(0.0).sqrt();
+        let coefficient             = if large_arc == sweep { -coefficient } else { coefficient };
+        let (cx1, cy1)              = (coefficient*rx*y1/ry, -coefficient*ry*x1/rx);
+
+        let center                  = Point::from_components(&[
+            cos_phi*cx1 - sin_phi*cy1 + (start.x()+end.x())/2.0,
+            sin_phi*cx1 + cos_phi*cy1 + (start.y()+end.y())/2.0
+        ]);
+
+        // Work out the angles of the start and end points
+        let angle                   = |(ux, uy): (f64, f64), (vx, vy): (f64, f64)| (ux*vy - uy*vx).atan2(ux*vx + uy*vy);
+        let start_angle             = angle((1.0, 0.0), ((x1-cx1)/rx, (y1-cy1)/ry));
+        let mut sweep_angle         = angle(((x1-cx1)/rx, (y1-cy1)/ry), ((-x1-cx1)/rx, (-y1-cy1)/ry));
+
+        if !sweep && sweep_angle > 0.0 { sweep_angle -= 2.0*f64::consts::PI; }
+        if sweep && sweep_angle < 0.0 { sweep_angle += 2.0*f64::consts::PI; }
+
+        // Add the arc as a series of curves
+        let ellipse                 = Ellipse::new(center, rx, ry, rotation);
+        let curves: Vec<Curve<_>>   = ellipse.arc(start_angle, start_angle+sweep_angle).to_bezier_curves();
+        let num_curves              = curves.len();
+
+        for (index, curve) in curves.into_iter().enumerate() {
+            let (cp1, cp2) = curve.control_points();
+
+            // The last curve ends exactly on the end point, whatever the rounding errors
+            let curve_end = if index == num_curves-1 { end } else { curve.end_point() };
+            self.curve_to(cp1, cp2, curve_end);
+        }
+
+        self.last_cubic_cp = None;
+    }
+
+    ///
+    /// Closes the current subpath
+    ///
+    fn close(&mut self) {
+        let start = self.subpath_start;
+
+        if self.current_path.is_some() && self.current_point != start {
+            self.line_to(start);
+        }
+
+        self.finish_subpath();
+
+        self.current_point  = start;
+        self.last_cubic_cp  = None;
+        self.last_quad_cp   = None;
+    }
+
+    ///
+    /// Reflects a control point around the current point, for the smooth curve commands
+    ///
+    fn reflect(&self, control_point: Option<Point>) -> Point {
+        match control_point {
+            Some(control_point) => self.current_point*2.0 - control_point,
+            None                => self.current_point
+        }
+    }
+}
+
+///
+/// Parses the data from an SVG path (the 'd' attribute) into a set of bezier paths
+///
+/// Each subpath in the data becomes a separate path. Lines, quadratic curves and arcs are converted
+/// to cubic curves, and closed subpaths end with a line back to their start point if they don't
+/// already finish there.
+///
+pub fn paths_from_svg<P: BezierPath>(data: &str) -> Result<Vec<P>, SvgPathError>
+where P::Point: Coordinate2D {
+    let mut reader  = SvgPathReader { data: data.as_bytes(), pos: 0 };
+    let mut builder = SvgPathBuilder::<P::Point>::new();
+    let mut first   = true;
+
+    while let Some(command) = reader.command()? {
+        let relative = command.is_ascii_lowercase();
+
+        if first && command != b'M' && command != b'm' {
+            return Err(SvgPathError::MissingMoveTo(reader.pos-1));
+        }
+        first = false;
+
+        // Most commands can be repeated by supplying more parameters
+        let mut repeat = false;
+
+        loop {
+            if repeat && !reader.at_number() {
+                break;
+            }
+
+            match command {
+                b'M' | b'm' => {
+                    let point = builder.point(reader.pair()?, relative);
+
+                    if repeat {
+                        // Extra coordinates after a move are lines
+                        builder.line_to(point);
+                    } else {
+                        builder.move_to(point);
+                    }
+                },
+
+                b'L' | b'l' => {
+                    let point = builder.point(reader.pair()?, relative);
+                    builder.line_to(point);
+                },
+
+                b'H' | b'h' => {
+                    let x       = reader.number()?;
+                    let x       = if relative { builder.current_point.x() + x } else { x };
+                    let point   = P::Point::from_components(&[x, builder.current_point.y()]);
+                    builder.line_to(point);
+                },
+
+                b'V' | b'v' => {
+                    let y       = reader.number()?;
+                    let y       = if relative { builder.current_point.y() + y } else { y };
+                    let point   = P::Point::from_components(&[builder.current_point.x(), y]);
+                    builder.line_to(point);
+                },
+
+                b'C' | b'c' => {
+                    let cp1 = builder.point(reader.pair()?, relative);
+                    let cp2 = builder.point(reader.pair()?, relative);
+                    let end = builder.point(reader.pair()?, relative);
+                    builder.curve_to(cp1, cp2, end);
+                },
+
+                b'S' | b's' => {
+                    let cp1 = builder.reflect(builder.last_cubic_cp);
+                    let cp2 = builder.point(reader.pair()?, relative);
+                    let end = builder.point(reader.pair()?, relative);
+                    builder.curve_to(cp1, cp2, end);
+                },
+
+                b'Q' | b'q' => {
+                    let cp  = builder.point(reader.pair()?, relative);
+                    let end = builder.point(reader.pair()?, relative);
+                    builder.quad_to(cp, end);
+                },
+
+                b'T' | b't' => {
+                    let cp  = builder.reflect(builder.last_quad_cp);
+                    let end = builder.point(reader.pair()?, relative);
+                    builder.quad_to(cp, end);
+                },
+
+                b'A' | b'a' => {
+                    let radius_x    = reader.number()?;
+                    let radius_y    = reader.number()?;
+                    let rotation    = reader.number()?;
+                    let large_arc   = reader.flag()?;
+                    let sweep       = reader.flag()?;
+                    let end         = builder.point(reader.pair()?, relative);
+                    builder.arc_to(radius_x, radius_y, rotation, large_arc, sweep, end);
+                },
+
+                b'Z' | b'z' => {
+                    // Close takes no parameters, so it's never repeated
+                    builder.close();
+                    break;
+                },
+
+                other => {
+                    return Err(SvgPathError::UnexpectedCharacter(other as char, reader.pos-1));
+                }
+            }
+
+            repeat = true;
+        }
+    }
+
+    builder.finish_subpath();
+
+    Ok(builder.paths.into_iter()
+        .map(|(start, curves)| P::from_points(start, curves))
+        .collect())
+}
+
+///
+/// Writes out a path as SVG path data (suitable for the 'd' attribute of a path element)
+///
+/// Paths that finish at their start point are written as closed paths.
+///
+pub fn svg_path_string<P: BezierPath>(path: &P) -> String
+where P::Point: Coordinate2D {
+    let mut result  = String::new();
+    let start       = path.start_point();
+    let mut end     = start;
+
+    write!(result, "M{} {}", start.x(), start.y()).unwrap();
+
+    for (cp1, cp2, point) in path.points() {
+        write!(result, " C{} {} {} {} {} {}", cp1.x(), cp1.y(), cp2.x(), cp2.y(), point.x(), point.y()).unwrap();
+        end = point;
+    }
+
+    if end == start && path.points().next().is_some() {
+        result.push_str(" Z");
+    }
+
+    result
+}
+
+///
+/// Writes out a set of paths as the data for a single SVG path element
+///
+pub fn svg_paths_string<'a, P: 'a+BezierPath, PathIter: IntoIterator<Item=&'a P>>(paths: PathIter) -> String
+where P::Point: Coordinate2D {
+    paths.into_iter()
+        .map(|path| svg_path_string(path))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
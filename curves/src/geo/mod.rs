@@ -1,6 +1,8 @@
 mod geo;
 mod bounding_box;
+mod rtree;
 
 pub use self::geo::*;
 pub use self::bounding_box::*;
+pub use self::rtree::*;
 pub use super::coordinate::*;
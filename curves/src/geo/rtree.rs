@@ -0,0 +1,420 @@
+use super::geo::*;
+use super::bounding_box::*;
+use super::super::coordinate::*;
+
+use std::mem;
+use std::sync::*;
+
+/// The maximum number of entries in a node before it's split
+const MAX_ENTRIES: usize = 8;
+
+/// The minimum number of entries in a node (nodes with fewer entries are removed and their contents reinserted)
+const MIN_ENTRIES: usize = 3;
+
+/// An entry in an R-tree node: the bounds (as minimum and maximum points) and the item with those bounds
+type RTreeEntry<Point, T> = ((Point, Point), T);
+
+///
+/// A node in an R-tree
+///
+/// Child nodes are shared between copies of a tree, so cloning a tree is cheap and changing it only copies
+/// the nodes along the path to the item that was changed.
+///
+#[derive(Clone, Debug)]
+enum RTreeNode<Point, Value> {
+    /// A node containing values and their bounds
+    Leaf(Vec<RTreeEntry<Point, Value>>),
+
+    /// A node containing other nodes and their bounds
+    Branch(Vec<RTreeEntry<Point, Arc<RTreeNode<Point, Value>>>>)
+}
+
+///
+/// An R-tree, which indexes values by their bounding boxes so the values in a particular region can be found quickly
+///
+#[derive(Clone, Debug)]
+pub struct RTree<Point, Value> {
+    /// The root node of the tree
+    root: Arc<RTreeNode<Point, Value>>,
+
+    /// The number of values in the tree
+    len: usize
+}
+
+///
+/// The smallest bounds that contain both of two bounding boxes
+///
+#[inline]
+fn union<Point: Coordinate>(a: &(Point, Point), b: &(Point, Point)) -> (Point, Point) {
+    (Point::from_smallest_components(a.0, b.0), Point::from_biggest_components(a.1, b.1))
+}
+
+///
+/// The area (or volume, or length, depending on the number of dimensions) of a bounding box
+///
+#[inline]
+fn area<Point: Coordinate>(bounds: &(Point, Point)) -> f64 {
+    (0..Point::len())
+        .map(|component| bounds.1.get(component) - bounds.0.get(component))
+        .product()
+}
+
+///
+/// True if two bounding boxes overlap (or touch)
+///
+#[inline]
+fn overlaps<Point: Coordinate>(a: &(Point, Point), b: &(Point, Point)) -> bool {
+    (0..Point::len())
+        .all(|component| a.0.get(component) <= b.1.get(component) && b.0.get(component) <= a.1.get(component))
+}
+
+///
+/// True if the first bounding box completely contains the second
+///
+#[inline]
+fn contains<Point: Coordinate>(outer: &(Point, Point), inner: &(Point, Point)) -> bool {
+    (0..Point::len())
+        .all(|component| outer.0.get(component) <= inner.0.get(component) && inner.1.get(component) <= outer.1.get(component))
+}
+
+///
+/// The bounds of a set of entries
+///
+fn entries_bounds<Point: Coordinate, T>(entries: &[RTreeEntry<Point, T>]) -> Option<(Point, Point)> {
+    entries.iter()
+        .map(|(bounds, _)| *bounds)
+        .fold(None, |result, bounds| Some(result.map(|result| union(&result, &bounds)).unwrap_or(bounds)))
+}
+
+///
+/// Divides a set of entries into two groups with bounds that overlap as little as possible
+///
+fn split_entries<Point: Coordinate, T>(mut entries: Vec<RTreeEntry<Point, T>>) -> (Vec<RTreeEntry<Point, T>>, Vec<RTreeEntry<Point, T>>) {
+    // Start the groups with the pair of entries that would waste the most space if they were together
+    let mut seeds       = (0, 1);
+    let mut max_waste   = f64::MIN;
+
+    for first in 0..entries.len() {
+        for second in (first+1)..entries.len() {
+            let (a, b)  = (&entries[first].0, &entries[second].0);
+            let waste   = area(&union(a, b)) - area(a) - area(b);
+
+            if waste > max_waste {
+                max_waste   = waste;
+                seeds       = (first, second);
+            }
+        }
+    }
+
+    // Remove the seeds (the second one first, so the index of the first doesn't change)
+    let second_seed     = entries.remove(seeds.1);
+    let first_seed      = entries.remove(seeds.0);
+
+    let mut first_bounds    = first_seed.0;
+    let mut second_bounds   = second_seed.0;
+    let mut first_group     = vec![first_seed];
+    let mut second_group    = vec![second_seed];
+
+    // Add the remaining entries to whichever group needs to grow the least to fit them
+    let total = entries.len();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let remaining           = total - index;
+        let first_growth        = area(&union(&first_bounds, &entry.0)) - area(&first_bounds);
+        let second_growth       = area(&union(&second_bounds, &entry.0)) - area(&second_bounds);
+
+        let use_first = if first_group.len() + remaining <= MIN_ENTRIES {
+            true
+        } else if second_group.len() + remaining <= MIN_ENTRIES {
+            false
+        } else {
+            first_growth < second_growth || (first_growth == second_growth && first_group.len() <= second_group.len())
+        };
+
+        if use_first {
+            first_bounds = union(&first_bounds, &entry.0);
+            first_group.push(entry);
+        } else {
+            second_bounds = union(&second_bounds, &entry.0);
+            second_group.push(entry);
+        }
+    }
+
+    (first_group, second_group)
+}
+
+impl<Point: Coordinate, Value: Clone+PartialEq> RTreeNode<Point, Value> {
+    ///
+    /// The bounds of everything in this node
+    ///
+    fn bounds(&self) -> Option<(Point, Point)> {
+        match self {
+            RTreeNode::Leaf(entries)    => entries_bounds(entries),
+            RTreeNode::Branch(children) => entries_bounds(children)
+        }
+    }
+
+    ///
+    /// The number of entries in this node
+    ///
+    fn num_entries(&self) -> usize {
+        match self {
+            RTreeNode::Leaf(entries)    => entries.len(),
+            RTreeNode::Branch(children) => children.len()
+        }
+    }
+
+    ///
+    /// Adds a value to this node, returning a new sibling node if this node had to be split
+    ///
+    fn insert(&mut self, bounds: (Point, Point), value: Value) -> Option<RTreeNode<Point, Value>> {
+        match self {
+            RTreeNode::Leaf(entries) => {
+                entries.push((bounds, value));
+
+                if entries.len() > MAX_ENTRIES {
+                    let (first, second) = split_entries(mem::replace(entries, vec![]));
+                    *entries            = first;
+
+                    Some(RTreeNode::Leaf(second))
+                } else {
+                    None
+                }
+            },
+
+            RTreeNode::Branch(children) => {
+                // Add to the child that needs to grow the least to contain the new value
+                let growth      = |child_bounds: &(Point, Point)| (area(&union(child_bounds, &bounds)) - area(child_bounds), area(child_bounds));
+                let mut best    = 0;
+
+                for index in 1..children.len() {
+                    if growth(&children[index].0) < growth(&children[best].0) {
+                        best = index;
+                    }
+                }
+
+                let split = {
+                    let (ref mut child_bounds, ref mut child) = children[best];
+                    let split       = Arc::make_mut(child).insert(bounds, value);
+                    *child_bounds   = child.bounds().unwrap_or(bounds);
+
+                    split
+                };
+
+                if let Some(sibling) = split {
+                    let sibling_bounds = sibling.bounds().unwrap_or(bounds);
+                    children.push((sibling_bounds, Arc::new(sibling)));
+                }
+
+                if children.len() > MAX_ENTRIES {
+                    let (first, second) = split_entries(mem::replace(children, vec![]));
+                    *children           = first;
+
+                    Some(RTreeNode::Branch(second))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    ///
+    /// True if this node contains a value within the specified bounds
+    ///
+    fn contains_value(&self, bounds: &(Point, Point), value: &Value) -> bool {
+        match self {
+            RTreeNode::Leaf(entries)    => entries.iter().any(|(entry_bounds, entry)| entry == value && entry_bounds == bounds),
+            RTreeNode::Branch(children) => children.iter().any(|(child_bounds, child)| contains(child_bounds, bounds) && child.contains_value(bounds, value))
+        }
+    }
+
+    ///
+    /// Adds all of the values in this node to a list
+    ///
+    fn collect_values(&self, values: &mut Vec<RTreeEntry<Point, Value>>) {
+        match self {
+            RTreeNode::Leaf(entries)    => values.extend(entries.iter().cloned()),
+            RTreeNode::Branch(children) => children.iter().for_each(|(_, child)| child.collect_values(values))
+        }
+    }
+
+    ///
+    /// Removes a value from this node
+    ///
+    /// The values from any nodes that become too small are added to the orphans list so they can be reinserted
+    ///
+    fn remove(&mut self, bounds: &(Point, Point), value: &Value, orphans: &mut Vec<RTreeEntry<Point, Value>>) -> bool {
+        match self {
+            RTreeNode::Leaf(entries) => {
+                if let Some(index) = entries.iter().position(|(entry_bounds, entry)| entry == value && entry_bounds == bounds) {
+                    entries.remove(index);
+                    true
+                } else {
+                    false
+                }
+            },
+
+            RTreeNode::Branch(children) => {
+                // Only copy the child that actually contains the value
+                let child_index = children.iter()
+                    .position(|(child_bounds, child)| contains(child_bounds, bounds) && child.contains_value(bounds, value));
+
+                if let Some(child_index) = child_index {
+                    let underfull = {
+                        let (ref mut child_bounds, ref mut child) = children[child_index];
+                        let child = Arc::make_mut(child);
+
+                        child.remove(bounds, value, orphans);
+
+                        if let Some(new_bounds) = child.bounds() { *child_bounds = new_bounds; }
+                        child.num_entries() < MIN_ENTRIES
+                    };
+
+                    if underfull {
+                        let (_, child) = children.remove(child_index);
+                        child.collect_values(orphans);
+                    }
+
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    ///
+    /// Finds the values that overlap a particular region
+    ///
+    fn query<'a>(&'a self, region: &(Point, Point), result: &mut Vec<&'a Value>) {
+        match self {
+            RTreeNode::Leaf(entries) => {
+                result.extend(entries.iter()
+                    .filter(|(bounds, _)| overlaps(bounds, region))
+                    .map(|(_, value)| value));
+            },
+
+            RTreeNode::Branch(children) => {
+                children.iter()
+                    .filter(|(bounds, _)| overlaps(bounds, region))
+                    .for_each(|(_, child)| child.query(region, result));
+            }
+        }
+    }
+}
+
+impl<Point: Coordinate, Value: Clone+PartialEq> RTree<Point, Value> {
+    ///
+    /// Creates a new, empty, R-tree
+    ///
+    pub fn new() -> RTree<Point, Value> {
+        RTree {
+            root:   Arc::new(RTreeNode::Leaf(vec![])),
+            len:    0
+        }
+    }
+
+    ///
+    /// The number of values in this tree
+    ///
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    ///
+    /// True if this tree has no values in it
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///
+    /// Adds a value with a particular bounding box to this tree
+    ///
+    pub fn insert<Bounds: BoundingBox<Point=Point>>(&mut self, bounds: Bounds, value: Value) {
+        self.insert_bounds((bounds.min(), bounds.max()), value);
+        self.len += 1;
+    }
+
+    ///
+    /// Adds a value to the tree using its normalised bounds
+    ///
+    fn insert_bounds(&mut self, bounds: (Point, Point), value: Value) {
+        let sibling = Arc::make_mut(&mut self.root).insert(bounds, value);
+
+        // Splitting the root node makes the tree a level deeper
+        if let Some(sibling) = sibling {
+            let old_root        = Arc::clone(&self.root);
+            let old_bounds      = old_root.bounds().unwrap_or(bounds);
+            let sibling_bounds  = sibling.bounds().unwrap_or(bounds);
+
+            self.root = Arc::new(RTreeNode::Branch(vec![(old_bounds, old_root), (sibling_bounds, Arc::new(sibling))]));
+        }
+    }
+
+    ///
+    /// Removes a value from this tree (the bounds must be the same as when the value was inserted)
+    ///
+    /// Returns true if the value was found.
+    ///
+    pub fn remove<Bounds: BoundingBox<Point=Point>>(&mut self, bounds: Bounds, value: &Value) -> bool {
+        let bounds = (bounds.min(), bounds.max());
+
+        if !self.root.contains_value(&bounds, value) {
+            return false;
+        }
+
+        let mut orphans = vec![];
+        Arc::make_mut(&mut self.root).remove(&bounds, value, &mut orphans);
+        self.len -= 1;
+
+        // A root with only one child can be replaced by that child
+        loop {
+            let only_child = match &*self.root {
+                RTreeNode::Branch(children) if children.len() == 1 => Some(Arc::clone(&children[0].1)),
+                RTreeNode::Branch(children) if children.is_empty()  => Some(Arc::new(RTreeNode::Leaf(vec![]))),
+                _                                                   => None
+            };
+
+            if let Some(only_child) = only_child {
+                self.root = only_child;
+            } else {
+                break;
+            }
+        }
+
+        // Put back any values from nodes that were removed
+        for (orphan_bounds, orphan) in orphans {
+            self.insert_bounds(orphan_bounds, orphan);
+        }
+
+        true
+    }
+
+    ///
+    /// Finds all of the values whose bounds overlap a region
+    ///
+    pub fn overlapping<Bounds: BoundingBox<Point=Point>>(&self, region: &Bounds) -> Vec<&Value> {
+        let mut result = vec![];
+        self.root.query(&(region.min(), region.max()), &mut result);
+        result
+    }
+
+    ///
+    /// Finds all of the values whose bounds contain a point
+    ///
+    pub fn at_point(&self, point: &Point) -> Vec<&Value> {
+        let mut result = vec![];
+        self.root.query(&(*point, *point), &mut result);
+        result
+    }
+}
+
+impl<Point: Coordinate, Value: Clone+PartialEq> Default for RTree<Point, Value> {
+    fn default() -> RTree<Point, Value> {
+        RTree::new()
+    }
+}
+
+impl<Point: Coordinate, Value> Geo for RTree<Point, Value> {
+    type Point = Point;
+}
@@ -1,3 +1,4 @@
 extern crate flo_curves;
 mod bezier;
 mod line;
+mod geo;
@@ -0,0 +1,70 @@
+use flo_curves::*;
+use flo_curves::arc::*;
+use flo_curves::bezier;
+
+#[test]
+fn length_of_straight_line() {
+    let line = bezier::Curve::from_points(Coord2(0.0, 0.0), Coord2(10.0, 0.0), Coord2(3.0, 0.0), Coord2(7.0, 0.0));
+
+    assert!((bezier::curve_length(&line, 0.001) - 10.0).abs() < 0.001);
+}
+
+#[test]
+fn length_is_within_bounds() {
+    let curve               = bezier::Curve::from_points(Coord2(10.0, 20.0), Coord2(100.0, 20.0), Coord2(20.0, 200.0), Coord2(80.0, -150.0));
+    let (min, max)          = bezier::curve_length_bounds(&curve);
+    let length              = bezier::curve_length(&curve, 0.01);
+
+    assert!(min <= length && length <= max);
+}
+
+#[test]
+fn length_of_quarter_circle() {
+    let circle                          = Circle::new(Coord2(0.0, 0.0), 100.0);
+    let quarter: bezier::Curve<Coord2>  = circle.arc(0.0, std::f64::consts::PI/2.0).to_bezier_curve();
+
+    assert!((bezier::curve_length(&quarter, 0.001) - 50.0*std::f64::consts::PI).abs() < 0.1);
+}
+
+#[test]
+fn t_for_distance_on_uneven_line() {
+    // Control points are bunched up at the start, so t does not increase evenly with distance
+    let line = bezier::Curve::from_points(Coord2(0.0, 0.0), Coord2(10.0, 0.0), Coord2(0.5, 0.0), Coord2(1.0, 0.0));
+
+    for distance in 0..=10 {
+        let t = bezier::t_for_distance(&line, distance as f64, 0.001);
+        assert!((line.point_at_pos(t).x() - (distance as f64)).abs() < 0.01);
+    }
+}
+
+#[test]
+fn t_for_distance_is_clamped() {
+    let line = bezier::Curve::from_points(Coord2(0.0, 0.0), Coord2(10.0, 0.0), Coord2(3.0, 0.0), Coord2(7.0, 0.0));
+
+    assert!(bezier::t_for_distance(&line, -1.0, 0.001) == 0.0);
+    assert!(bezier::t_for_distance(&line, 11.0, 0.001) == 1.0);
+}
+
+#[test]
+fn resampled_points_are_evenly_spaced() {
+    let curve   = bezier::Curve::from_points(Coord2(10.0, 20.0), Coord2(100.0, 20.0), Coord2(20.0, 200.0), Coord2(80.0, -150.0));
+    let points  = bezier::resample_curve(&curve, 21, 0.001);
+
+    assert!(points.len() == 21);
+    assert!(points[0] == curve.start_point());
+    assert!(points[20].distance_to(&curve.end_point()) < 0.001);
+
+    // Measure the length of each section using the arc length parametrisation
+    let parametrisation = bezier::ArcLengthParametrisation::new(&curve, 0.001);
+    let spacing         = parametrisation.length() / 20.0;
+
+    for index in 0..=20 {
+        let expected = parametrisation.point_at_distance((index as f64) * spacing);
+        assert!(points[index].distance_to(&expected) < 0.001);
+    }
+
+    // Points should be about the same distance apart (closer where the curve bends)
+    for index in 1..=20 {
+        assert!(points[index-1].distance_to(&points[index]) <= spacing + 0.01);
+    }
+}
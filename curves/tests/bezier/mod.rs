@@ -11,6 +11,9 @@ mod bounds;
 mod deform;
 mod search;
 mod intersection;
+mod length;
+mod nearest;
+mod offset;
 
 pub fn approx_equal(a: f64, b: f64) -> bool {
     f64::floor(f64::abs(a-b)*10000.0) == 0.0
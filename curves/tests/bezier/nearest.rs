@@ -0,0 +1,58 @@
+use flo_curves::*;
+use flo_curves::arc::*;
+use flo_curves::bezier;
+
+#[test]
+fn nearest_point_on_straight_line() {
+    let line    = bezier::Curve::from_points(Coord2(0.0, 0.0), Coord2(10.0, 0.0), Coord2(3.0, 0.0), Coord2(7.0, 0.0));
+    let t       = bezier::nearest_point_on_curve(&line, &Coord2(5.0, 2.0));
+
+    assert!(line.point_at_pos(t).distance_to(&Coord2(5.0, 0.0)) < 0.0001);
+    assert!((bezier::distance_to_curve(&line, &Coord2(5.0, 2.0)) - 2.0).abs() < 0.0001);
+}
+
+#[test]
+fn nearest_point_beyond_end_is_end_point() {
+    let line    = bezier::Curve::from_points(Coord2(0.0, 0.0), Coord2(10.0, 0.0), Coord2(3.0, 0.0), Coord2(7.0, 0.0));
+
+    assert!(bezier::nearest_point_on_curve(&line, &Coord2(15.0, 3.0)) == 1.0);
+    assert!(bezier::nearest_point_on_curve(&line, &Coord2(-5.0, -3.0)) == 0.0);
+}
+
+#[test]
+fn distance_to_quarter_circle() {
+    let circle                          = Circle::new(Coord2(0.0, 0.0), 100.0);
+    let quarter: bezier::Curve<Coord2>  = circle.arc(0.0, std::f64::consts::PI/2.0).to_bezier_curve();
+
+    // Points along the diagonal should be about 50 units from a radius 100 circle
+    let distance = bezier::distance_to_curve(&quarter, &Coord2(50.0/2.0f64.sqrt(), 50.0/2.0f64.sqrt()));
+    assert!((distance - 50.0).abs() < 0.1);
+}
+
+#[test]
+fn nearest_point_near_cusp() {
+    // The control points cross over, which creates a cusp in the middle of this curve
+    let curve   = bezier::Curve::from_points(Coord2(0.0, 0.0), Coord2(10.0, 0.0), Coord2(10.0, 10.0), Coord2(0.0, 10.0));
+    let cusp    = curve.point_at_pos(0.5);
+
+    // Points close to the cusp should find the cusp itself
+    let target  = cusp + Coord2(0.0, 1.0);
+    let t       = bezier::nearest_point_on_curve(&curve, &target);
+
+    assert!(curve.point_at_pos(t).distance_to(&cusp) < 0.01);
+    assert!((bezier::distance_to_curve(&curve, &target) - 1.0).abs() < 0.01);
+}
+
+#[test]
+fn nearest_point_is_on_closer_of_two_loops() {
+    let curve   = bezier::Curve::from_points(Coord2(0.0, 0.0), Coord2(0.0, 10.0), Coord2(20.0, 0.0), Coord2(20.0, 10.0));
+    let target  = Coord2(16.0, 5.0);
+    let t       = bezier::nearest_point_on_curve(&curve, &target);
+
+    // Check against a brute-force search of the curve
+    let brute_force = (0..=10000)
+        .map(|index| curve.point_at_pos((index as f64)/10000.0).distance_to(&target))
+        .fold(f64::MAX, |a, b| a.min(b));
+
+    assert!((curve.point_at_pos(t).distance_to(&target) - brute_force).abs() < 0.001);
+}
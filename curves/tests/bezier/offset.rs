@@ -0,0 +1,44 @@
+use flo_curves::*;
+use flo_curves::bezier;
+
+#[test]
+fn offset_line_is_parallel() {
+    let line    = bezier::Curve::from_points(Coord2(0.0, 0.0), Coord2(10.0, 0.0), Coord2(3.0, 0.0), Coord2(7.0, 0.0));
+    let offset  = bezier::offset(&line, 2.0, 2.0);
+
+    for curve in offset {
+        for t in 0..=10 {
+            let point = curve.point_at_pos((t as f64)/10.0);
+            assert!((point.y().abs()-2.0).abs() < 0.01);
+        }
+    }
+}
+
+#[test]
+fn offset_with_control_point_on_start_point_is_not_nan() {
+    let curve   = bezier::Curve::from_points(Coord2(0.0, 0.0), Coord2(10.0, 0.0), Coord2(0.0, 0.0), Coord2(10.0, 5.0));
+    let offset  = bezier::offset(&curve, 1.0, 1.0);
+
+    assert!(offset.len() > 0);
+    for curve in offset.iter() {
+        let (cp1, cp2) = curve.control_points();
+        assert!(!curve.start_point().is_nan() && !cp1.is_nan() && !cp2.is_nan() && !curve.end_point().is_nan());
+    }
+
+    assert!((offset[0].start_point().distance_to(&Coord2(0.0, 0.0)) - 1.0).abs() < 0.01);
+    assert!((offset[offset.len()-1].end_point().distance_to(&Coord2(10.0, 0.0)) - 1.0).abs() < 0.01);
+}
+
+#[test]
+fn offset_arc_is_within_error_bound() {
+    // Quarter circle with a radius of 100 around the origin
+    let arc     = bezier::Curve::from_points(Coord2(100.0, 0.0), Coord2(0.0, 100.0), Coord2(100.0, 55.2285), Coord2(55.2285, 100.0));
+    let offset  = bezier::offset(&arc, 10.0, 10.0);
+
+    for curve in offset {
+        for t in 0..=10 {
+            let radius = curve.point_at_pos((t as f64)/10.0).distance_to(&Coord2(0.0, 0.0));
+            assert!(((radius-100.0).abs() - 10.0).abs() < 0.2);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use flo_curves::*;
+use flo_curves::arc::*;
+use flo_curves::bezier::path::*;
+
+fn square() -> SimpleBezierPath {
+    (Coord2(0.0, 0.0), vec![
+        (Coord2(3.0, 0.0), Coord2(7.0, 0.0), Coord2(10.0, 0.0)),
+        (Coord2(10.0, 3.0), Coord2(10.0, 7.0), Coord2(10.0, 10.0)),
+        (Coord2(7.0, 10.0), Coord2(3.0, 10.0), Coord2(0.0, 10.0)),
+        (Coord2(0.0, 7.0), Coord2(0.0, 3.0), Coord2(0.0, 0.0))
+    ])
+}
+
+#[test]
+fn length_of_square() {
+    assert!((path_length(&square(), 0.001) - 40.0).abs() < 0.001);
+}
+
+#[test]
+fn length_of_circle() {
+    let circle: SimpleBezierPath = Circle::new(Coord2(5.0, 5.0), 4.0).to_path();
+
+    assert!((path_length(&circle, 0.001) - 8.0*std::f64::consts::PI).abs() < 0.01);
+}
+
+#[test]
+fn walk_around_square() {
+    let positions = walk_path(&square(), 5.0, 0.001);
+
+    assert!(positions.len() == 9);
+    assert!(positions[0].point.distance_to(&Coord2(0.0, 0.0)) < 0.001);
+    assert!(positions[1].point.distance_to(&Coord2(5.0, 0.0)) < 0.001);
+    assert!(positions[3].point.distance_to(&Coord2(10.0, 5.0)) < 0.001);
+    assert!(positions[3].section == 1);
+    assert!(positions[6].point.distance_to(&Coord2(0.0, 10.0)) < 0.001);
+    assert!(positions[8].point.distance_to(&Coord2(0.0, 0.0)) < 0.001);
+    assert!(positions[8].section == 3);
+}
+
+#[test]
+fn walk_with_no_spacing_stays_at_start() {
+    let positions = walk_path(&square(), 0.0, 0.001);
+
+    assert!(positions.len() == 1);
+    assert!(positions[0].point == Coord2(0.0, 0.0));
+}
+
+#[test]
+fn resample_square() {
+    let points = resample_path(&square(), 5, 0.001);
+
+    assert!(points.len() == 5);
+    assert!(points[0].distance_to(&Coord2(0.0, 0.0)) < 0.001);
+    assert!(points[1].distance_to(&Coord2(10.0, 0.0)) < 0.001);
+    assert!(points[2].distance_to(&Coord2(10.0, 10.0)) < 0.001);
+    assert!(points[3].distance_to(&Coord2(0.0, 10.0)) < 0.001);
+    assert!(points[4].distance_to(&Coord2(0.0, 0.0)) < 0.001);
+}
@@ -2,3 +2,7 @@ mod to_curves;
 mod point;
 mod intersection;
 mod bounds;
+mod length;
+mod nearest;
+mod svg;
+mod stroke;
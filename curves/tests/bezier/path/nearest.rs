@@ -0,0 +1,31 @@
+use flo_curves::*;
+use flo_curves::bezier::path::*;
+
+fn square() -> SimpleBezierPath {
+    (Coord2(0.0, 0.0), vec![
+        (Coord2(3.0, 0.0), Coord2(7.0, 0.0), Coord2(10.0, 0.0)),
+        (Coord2(10.0, 3.0), Coord2(10.0, 7.0), Coord2(10.0, 10.0)),
+        (Coord2(7.0, 10.0), Coord2(3.0, 10.0), Coord2(0.0, 10.0)),
+        (Coord2(0.0, 7.0), Coord2(0.0, 3.0), Coord2(0.0, 0.0))
+    ])
+}
+
+#[test]
+fn nearest_point_on_square() {
+    let (section, _t, point) = nearest_point_on_path(&square(), &Coord2(12.0, 4.0));
+
+    assert!(section == 1);
+    assert!(point.distance_to(&Coord2(10.0, 4.0)) < 0.0001);
+}
+
+#[test]
+fn distance_from_inside_square() {
+    assert!((distance_to_path(&square(), &Coord2(5.0, 8.0)) - 2.0).abs() < 0.0001);
+}
+
+#[test]
+fn distance_to_empty_path_is_distance_to_start() {
+    let path: SimpleBezierPath = (Coord2(1.0, 1.0), vec![]);
+
+    assert!((distance_to_path(&path, &Coord2(4.0, 5.0)) - 5.0).abs() < 0.0001);
+}
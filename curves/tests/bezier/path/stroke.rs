@@ -0,0 +1,202 @@
+use flo_curves::*;
+use flo_curves::arc::*;
+use flo_curves::bezier::path::*;
+
+fn line(from: Coord2, to: Coord2) -> SimpleBezierPath {
+    (from, vec![(from + (to-from)*(1.0/3.0), from + (to-from)*(2.0/3.0), to)])
+}
+
+fn square() -> SimpleBezierPath {
+    (Coord2(0.0, 0.0), vec![
+        (Coord2(3.0, 0.0), Coord2(7.0, 0.0), Coord2(10.0, 0.0)),
+        (Coord2(10.0, 3.0), Coord2(10.0, 7.0), Coord2(10.0, 10.0)),
+        (Coord2(7.0, 10.0), Coord2(3.0, 10.0), Coord2(0.0, 10.0)),
+        (Coord2(0.0, 7.0), Coord2(0.0, 3.0), Coord2(0.0, 0.0))
+    ])
+}
+
+fn bounds(path: &SimpleBezierPath) -> (Coord2, Coord2) {
+    path.bounding_box()
+}
+
+/// Picks the edge of a closed stroke that's on the outside
+fn outer_edge(outline: &[SimpleBezierPath]) -> SimpleBezierPath {
+    let size = |path: &SimpleBezierPath| { let (min, max) = bounds(path); max.x()-min.x() };
+
+    if size(&outline[0]) > size(&outline[1]) { outline[0].clone() } else { outline[1].clone() }
+}
+
+fn options(width: f64, join: StrokeJoin, cap: StrokeCap) -> StrokeOptions {
+    let mut options = StrokeOptions::new(width);
+    options.join    = join;
+    options.cap     = cap;
+    options
+}
+
+#[test]
+fn stroke_line_with_butt_caps() {
+    let outline = stroke_path(&line(Coord2(0.0, 0.0), Coord2(10.0, 0.0)), &options(2.0, StrokeJoin::Miter, StrokeCap::Butt));
+
+    assert!(outline.len() == 1);
+
+    let (min, max) = bounds(&outline[0]);
+    assert!(min.distance_to(&Coord2(0.0, -1.0)) < 0.0001);
+    assert!(max.distance_to(&Coord2(10.0, 1.0)) < 0.0001);
+}
+
+#[test]
+fn square_caps_extend_line() {
+    let outline     = stroke_path(&line(Coord2(0.0, 0.0), Coord2(10.0, 0.0)), &options(2.0, StrokeJoin::Miter, StrokeCap::Square));
+    let (min, max)  = bounds(&outline[0]);
+
+    assert!(min.distance_to(&Coord2(-1.0, -1.0)) < 0.0001);
+    assert!(max.distance_to(&Coord2(11.0, 1.0)) < 0.0001);
+}
+
+#[test]
+fn round_caps_are_semicircles() {
+    let outline = stroke_path(&line(Coord2(0.0, 0.0), Coord2(10.0, 0.0)), &options(2.0, StrokeJoin::Miter, StrokeCap::Round));
+
+    for curve in outline[0].to_curves::<bezier::Curve<_>>() {
+        for t in 0..=10 {
+            let point = curve.point_at_pos((t as f64)/10.0);
+
+            if point.x() < 0.0 { assert!((point.distance_to(&Coord2(0.0, 0.0)) - 1.0).abs() < 0.01); }
+            if point.x() > 10.0 { assert!((point.distance_to(&Coord2(10.0, 0.0)) - 1.0).abs() < 0.01); }
+        }
+    }
+
+    let (min, max) = bounds(&outline[0]);
+    assert!(min.distance_to(&Coord2(-1.0, -1.0)) < 0.01);
+    assert!(max.distance_to(&Coord2(11.0, 1.0)) < 0.01);
+}
+
+#[test]
+fn closed_path_has_inside_and_outside_edges() {
+    let outline = stroke_path(&square(), &options(2.0, StrokeJoin::Miter, StrokeCap::Butt));
+
+    assert!(outline.len() == 2);
+
+    // The square runs anticlockwise, so the left-hand side of the stroke is on the inside
+    let (outer_min, outer_max) = bounds(&outline[1]);
+    let (inner_min, inner_max) = bounds(&outline[0]);
+
+    assert!(outer_min.distance_to(&Coord2(-1.0, -1.0)) < 0.0001);
+    assert!(outer_max.distance_to(&Coord2(11.0, 11.0)) < 0.0001);
+    assert!(inner_min.x() >= 0.0 && inner_min.y() >= 0.0 && inner_max.x() <= 10.0 && inner_max.y() <= 10.0);
+    assert!(distance_to_path(&outline[0], &Coord2(1.0, 5.0)) < 0.0001);
+    assert!(distance_to_path(&outline[0], &Coord2(5.0, 9.0)) < 0.0001);
+}
+
+#[test]
+fn bevel_joins_cut_corners() {
+    let outline = stroke_path(&square(), &options(2.0, StrokeJoin::Bevel, StrokeCap::Butt));
+
+    let outer   = outer_edge(&outline);
+
+    assert!(distance_to_path(&outer, &Coord2(-1.0, -1.0)) > 0.5);
+    assert!(distance_to_path(&outer, &Coord2(-0.5, -0.5)) < 0.0001);
+}
+
+#[test]
+fn miter_limit_bevels_sharp_corners() {
+    let mut stroke_options  = options(2.0, StrokeJoin::Miter, StrokeCap::Butt);
+    stroke_options.miter_limit = 1.2;
+
+    let outline = stroke_path(&square(), &stroke_options);
+
+    // A right angle has a miter ratio of about 1.41
+    assert!(distance_to_path(&outer_edge(&outline), &Coord2(-1.0, -1.0)) > 0.5);
+}
+
+#[test]
+fn round_joins_are_arcs() {
+    let outline = stroke_path(&square(), &options(2.0, StrokeJoin::Round, StrokeCap::Butt));
+    let corner  = Coord2(-1.0/2.0f64.sqrt(), -1.0/2.0f64.sqrt());
+
+    assert!(distance_to_path(&outer_edge(&outline), &corner) < 0.01);
+}
+
+#[test]
+fn offset_circle_is_within_error() {
+    let circle: SimpleBezierPath    = Circle::new(Coord2(0.0, 0.0), 10.0).to_path();
+    let outline                     = stroke_path(&circle, &options(4.0, StrokeJoin::Miter, StrokeCap::Butt));
+
+    assert!(outline.len() == 2);
+
+    for (edge, radius) in outline.iter().zip([12.0, 8.0].iter()) {
+        for curve in edge.to_curves::<bezier::Curve<_>>() {
+            for t in 0..=10 {
+                let point = curve.point_at_pos((t as f64)/10.0);
+                assert!((point.distance_to(&Coord2(0.0, 0.0)) - radius).abs() < 0.02);
+            }
+        }
+    }
+}
+
+#[test]
+fn stroke_curve_with_cusp() {
+    let path: SimpleBezierPath  = (Coord2(0.0, 0.0), vec![(Coord2(10.0, 10.0), Coord2(0.0, 10.0), Coord2(10.0, 0.0))]);
+    let outline                 = stroke_path(&path, &options(2.0, StrokeJoin::Round, StrokeCap::Round));
+
+    assert!(outline.len() == 1);
+    assert!(!outline[0].1.is_empty());
+    assert!(outline[0].1.iter().all(|&(cp1, cp2, end)| !cp1.is_nan() && !cp2.is_nan() && !end.is_nan()));
+
+    // The whole outline should be about a half-width from the curve
+    for curve in outline[0].to_curves::<bezier::Curve<_>>() {
+        for t in 0..=10 {
+            let point = curve.point_at_pos((t as f64)/10.0);
+            assert!(distance_to_path(&path, &point) < 1.0 + 0.05);
+        }
+    }
+}
+
+#[test]
+fn dash_line() {
+    let dashes = dash_path(&line(Coord2(0.0, 0.0), Coord2(10.0, 0.0)), &[2.0, 1.0], 0.0, 0.001);
+
+    assert!(dashes.len() == 4);
+    assert!(dashes[1].0.distance_to(&Coord2(3.0, 0.0)) < 0.001);
+    assert!((path_length(&dashes[1], 0.001) - 2.0).abs() < 0.001);
+    assert!((path_length(&dashes[3], 0.001) - 1.0).abs() < 0.001);
+}
+
+#[test]
+fn dash_offset_moves_pattern() {
+    let dashes = dash_path(&line(Coord2(0.0, 0.0), Coord2(10.0, 0.0)), &[2.0, 1.0], 1.0, 0.001);
+
+    assert!(dashes.len() == 4);
+    assert!((path_length(&dashes[0], 0.001) - 1.0).abs() < 0.001);
+    assert!(dashes[1].0.distance_to(&Coord2(2.0, 0.0)) < 0.001);
+}
+
+#[test]
+fn dashes_continue_around_corners() {
+    let dashes = dash_path(&square(), &[15.0, 5.0], 0.0, 0.001);
+
+    assert!(dashes.len() == 2);
+    assert!(dashes[0].1.len() == 2);
+    assert!((path_length(&dashes[0], 0.001) - 15.0).abs() < 0.001);
+}
+
+#[test]
+fn invalid_dash_pattern_leaves_path_alone() {
+    assert!(dash_path(&square(), &[], 0.0, 0.001) == vec![square()]);
+    assert!(dash_path(&square(), &[0.0, 0.0], 0.0, 0.001) == vec![square()]);
+    assert!(dash_path(&square(), &[1.0, -1.0], 0.0, 0.001) == vec![square()]);
+}
+
+#[test]
+fn zero_length_dashes_with_round_caps_are_dots() {
+    let mut stroke_options      = options(2.0, StrokeJoin::Round, StrokeCap::Round);
+    stroke_options.dash_pattern = vec![0.0, 5.0];
+
+    let outline = stroke_path(&line(Coord2(0.0, 0.0), Coord2(11.0, 0.0)), &stroke_options);
+
+    assert!(outline.len() == 3);
+
+    let (min, max) = bounds(&outline[2]);
+    assert!(min.distance_to(&Coord2(9.0, -1.0)) < 0.01);
+    assert!(max.distance_to(&Coord2(11.0, 1.0)) < 0.01);
+}
@@ -0,0 +1,172 @@
+use flo_curves::*;
+use flo_curves::bezier::path::*;
+
+//
+// Most of the path data here is taken from real-world SVG files (icon sets and the MDN path tutorial)
+//
+
+/// Material design 'home' icon
+const HOME_ICON: &str           = "M10 20v-6h4v6h5v-8h3L12 3 2 12h3v8z";
+
+/// Material design 'check_circle' icon
+const CHECK_CIRCLE_ICON: &str   = "M12 2C6.48 2 2 6.48 2 12s4.48 10 10 10 10-4.48 10-10S17.52 2 12 2zm-2 15l-5-5 1.41-1.41L10 14.17l7.59-7.59L19 8l-9 9z";
+
+/// Material design 'search' icon
+const SEARCH_ICON: &str         = "M15.5 14h-.79l-.28-.27C15.41 12.59 16 11.11 16 9.5 16 5.91 13.09 3 9.5 3S3 5.91 3 9.5 5.91 16 9.5 16c1.61 0 3.09-.59 4.23-1.57l.27.28v.79l5 4.99L20.49 19l-4.99-5zm-6 0C7.01 14 5 11.99 5 9.5S7.01 5 9.5 5 14 7.01 14 9.5 11.99 14 9.5 14z";
+
+/// Bootstrap 'circle' icon
+const CIRCLE_ICON: &str         = "M8 15A7 7 0 1 1 8 1a7 7 0 0 1 0 14zm0 1A8 8 0 1 0 8 0a8 8 0 0 0 0 16z";
+
+/// Arcs example from the MDN path tutorial
+const MDN_ARCS: &str            = "M 10 315 L 110 215 A 30 50 0 0 1 162.55 162.45 L 172.55 152.45 A 30 50 -45 0 1 215.1 109.9 L 315 10";
+
+/// Smooth quadratic example from the MDN path tutorial
+const MDN_QUADRATIC: &str       = "M 10 80 Q 52.5 10, 95 80 T 180 80";
+
+/// Smooth cubic example from the MDN path tutorial
+const MDN_CUBIC: &str           = "M 10 80 C 40 10, 65 10, 95 80 S 150 150, 180 80";
+
+fn parse(data: &str) -> Vec<SimpleBezierPath> {
+    paths_from_svg(data).unwrap()
+}
+
+fn end_point(path: &SimpleBezierPath) -> Coord2 {
+    path.1.last().map(|&(_, _, end)| end).unwrap_or(path.0)
+}
+
+#[test]
+fn parse_home_icon() {
+    let paths = parse(HOME_ICON);
+
+    assert!(paths.len() == 1);
+    assert!(paths[0].0 == Coord2(10.0, 20.0));
+    assert!(paths[0].1.len() == 11);
+    assert!(end_point(&paths[0]) == Coord2(10.0, 20.0));
+
+    let (min, max): (Coord2, Coord2) = paths[0].bounding_box();
+    assert!(min.distance_to(&Coord2(2.0, 3.0)) < 0.0001);
+    assert!(max.distance_to(&Coord2(22.0, 20.0)) < 0.0001);
+}
+
+#[test]
+fn relative_move_after_close_starts_from_subpath_start() {
+    let paths = parse(CHECK_CIRCLE_ICON);
+
+    assert!(paths.len() == 2);
+    assert!(paths[1].0 == Coord2(10.0, 17.0));
+    assert!(paths[1].1[0].2 == Coord2(5.0, 12.0));
+    assert!(end_point(&paths[1]).distance_to(&Coord2(10.0, 17.0)) < 0.0001);
+}
+
+#[test]
+fn smooth_curves_in_check_circle_are_circular() {
+    let paths = parse(CHECK_CIRCLE_ICON);
+
+    for curve in paths[0].to_curves::<bezier::Curve<_>>() {
+        for t in 0..=10 {
+            let point = curve.point_at_pos((t as f64)/10.0);
+            assert!((point.distance_to(&Coord2(12.0, 12.0)) - 10.0).abs() < 0.05);
+        }
+    }
+}
+
+#[test]
+fn parse_search_icon() {
+    let paths = parse(SEARCH_ICON);
+
+    assert!(paths.len() == 2);
+    assert!(paths[0].0 == Coord2(15.5, 14.0));
+    assert!(paths[1].0 == Coord2(9.5, 14.0));
+    assert!(end_point(&paths[1]).distance_to(&Coord2(9.5, 14.0)) < 0.0001);
+}
+
+#[test]
+fn arcs_in_circle_icon_are_circular() {
+    let paths = parse(CIRCLE_ICON);
+
+    assert!(paths.len() == 2);
+    assert!(paths[1].0 == Coord2(8.0, 16.0));
+
+    for (path, radius) in paths.iter().zip([7.0, 8.0].iter()) {
+        for curve in path.to_curves::<bezier::Curve<_>>() {
+            for t in 0..=10 {
+                let point = curve.point_at_pos((t as f64)/10.0);
+                assert!((point.distance_to(&Coord2(8.0, 8.0)) - radius).abs() < 0.01);
+            }
+        }
+    }
+}
+
+#[test]
+fn rotated_arcs_reach_their_end_points() {
+    let paths = parse(MDN_ARCS);
+
+    assert!(paths.len() == 1);
+    assert!(end_point(&paths[0]) == Coord2(315.0, 10.0));
+
+    // Every arc should join up with the line after it
+    let ends: Vec<_> = paths[0].1.iter().map(|&(_, _, end)| end).collect();
+    assert!(ends.contains(&Coord2(162.55, 162.45)));
+    assert!(ends.contains(&Coord2(215.1, 109.9)));
+}
+
+#[test]
+fn smooth_quadratic_reflects_control_point() {
+    let paths   = parse(MDN_QUADRATIC);
+    let curves  = paths[0].to_curves::<bezier::Curve<_>>();
+
+    assert!(curves.len() == 2);
+    assert!(curves[0].point_at_pos(0.5).distance_to(&Coord2(52.5, 45.0)) < 0.0001);
+    assert!(curves[1].point_at_pos(0.5).distance_to(&Coord2(137.5, 115.0)) < 0.0001);
+}
+
+#[test]
+fn smooth_cubic_reflects_control_point() {
+    let paths = parse(MDN_CUBIC);
+
+    assert!(paths[0].1.len() == 2);
+    assert!(paths[0].1[1].0 == Coord2(125.0, 150.0));
+}
+
+#[test]
+fn parse_compact_numbers_and_flags() {
+    let paths = parse("M.5.5l-1e1-2E+1M0 0a5 5 0 105 5");
+
+    assert!(paths.len() == 2);
+    assert!(paths[0].1[0].2 == Coord2(-9.5, -19.5));
+    assert!(paths[1].1.len() == 3);
+    assert!(end_point(&paths[1]).distance_to(&Coord2(5.0, 5.0)) < 0.0001);
+}
+
+#[test]
+fn extra_move_coordinates_are_lines() {
+    let paths = parse("M 0 0 10 10 20 0");
+
+    assert!(paths.len() == 1);
+    assert!(paths[0].1.len() == 2);
+    assert!(end_point(&paths[0]) == Coord2(20.0, 0.0));
+}
+
+#[test]
+fn report_errors() {
+    assert!(paths_from_svg::<SimpleBezierPath>("L 10 10") == Err(SvgPathError::MissingMoveTo(0)));
+    assert!(paths_from_svg::<SimpleBezierPath>("M 10") == Err(SvgPathError::ExpectedNumber(4)));
+    assert!(paths_from_svg::<SimpleBezierPath>("M 0 0 # 1") == Err(SvgPathError::UnexpectedCharacter('#', 6)));
+}
+
+#[test]
+fn write_path() {
+    let path: SimpleBezierPath = (Coord2(1.0, 2.0), vec![(Coord2(3.0, 4.0), Coord2(5.0, 6.0), Coord2(7.5, 8.0))]);
+
+    assert!(svg_path_string(&path) == "M1 2 C3 4 5 6 7.5 8");
+}
+
+#[test]
+fn written_paths_parse_to_the_same_points() {
+    for data in [HOME_ICON, CHECK_CIRCLE_ICON, SEARCH_ICON, CIRCLE_ICON, MDN_ARCS, MDN_QUADRATIC, MDN_CUBIC].iter() {
+        let paths   = parse(data);
+        let written = svg_paths_string(&paths);
+
+        assert!(parse(&written) == paths);
+    }
+}
@@ -0,0 +1,104 @@
+use flo_curves::*;
+
+fn bounds_for(index: usize) -> (Coord2, Coord2) {
+    // A grid of 1x1 squares, 50 to a row
+    let x = (index % 50) as f64 * 2.0;
+    let y = (index / 50) as f64 * 2.0;
+
+    (Coord2(x, y), Coord2(x+1.0, y+1.0))
+}
+
+fn grid_tree(count: usize) -> RTree<Coord2, usize> {
+    let mut tree = RTree::new();
+
+    for index in 0..count {
+        tree.insert(bounds_for(index), index);
+    }
+
+    tree
+}
+
+fn sorted(values: Vec<&usize>) -> Vec<usize> {
+    let mut values: Vec<_> = values.into_iter().cloned().collect();
+    values.sort();
+    values
+}
+
+#[test]
+fn empty_tree_finds_nothing() {
+    let tree: RTree<Coord2, usize> = RTree::new();
+
+    assert!(tree.is_empty());
+    assert!(tree.at_point(&Coord2(0.0, 0.0)).is_empty());
+}
+
+#[test]
+fn find_value_at_point() {
+    let tree = grid_tree(1000);
+
+    assert!(tree.len() == 1000);
+    assert!(sorted(tree.at_point(&Coord2(0.5, 0.5))) == vec![0]);
+    assert!(sorted(tree.at_point(&Coord2(42.5, 6.5))) == vec![171]);
+    assert!(tree.at_point(&Coord2(1.5, 1.5)).is_empty());
+}
+
+#[test]
+fn find_values_in_region() {
+    let tree    = grid_tree(1000);
+    let found   = sorted(tree.overlapping(&(Coord2(1.5, 1.5), Coord2(4.5, 2.5))));
+
+    assert!(found == vec![51, 52]);
+}
+
+#[test]
+fn region_query_matches_linear_search() {
+    let tree    = grid_tree(1000);
+    let region  = (Coord2(13.0, 7.5), Coord2(37.2, 22.0));
+
+    let expected: Vec<_> = (0..1000)
+        .filter(|index| {
+            let (min, max) = bounds_for(*index);
+            min.x() <= region.1.x() && region.0.x() <= max.x() && min.y() <= region.1.y() && region.0.y() <= max.y()
+        })
+        .collect();
+
+    assert!(sorted(tree.overlapping(&region)) == expected);
+}
+
+#[test]
+fn remove_values() {
+    let mut tree = grid_tree(1000);
+
+    // Remove every other value
+    for index in (0..1000).filter(|index| index%2 == 0) {
+        assert!(tree.remove(bounds_for(index), &index));
+    }
+
+    assert!(tree.len() == 500);
+    assert!(tree.at_point(&Coord2(0.5, 0.5)).is_empty());
+    assert!(sorted(tree.at_point(&Coord2(2.5, 0.5))) == vec![1]);
+
+    let all = sorted(tree.overlapping(&(Coord2(-1.0, -1.0), Coord2(1000.0, 1000.0))));
+    assert!(all == (0..1000).filter(|index| index%2 == 1).collect::<Vec<_>>());
+}
+
+#[test]
+fn removing_missing_value_does_nothing() {
+    let mut tree = grid_tree(100);
+
+    assert!(!tree.remove(bounds_for(5), &6));
+    assert!(!tree.remove(bounds_for(500), &500));
+    assert!(tree.len() == 100);
+}
+
+#[test]
+fn clones_are_independent() {
+    let mut tree    = grid_tree(200);
+    let original    = tree.clone();
+
+    tree.remove(bounds_for(0), &0);
+    tree.insert(bounds_for(0), 1000);
+
+    assert!(sorted(original.at_point(&Coord2(0.5, 0.5))) == vec![0]);
+    assert!(sorted(tree.at_point(&Coord2(0.5, 0.5))) == vec![1000]);
+}
@@ -1,13 +1,21 @@
 use super::super::model::*;
+use super::super::style::*;
 
 use ui::*;
 use canvas::*;
 use binding::*;
 use animation::*;
 
+use std::iter;
 use std::sync::*;
 use std::collections::HashMap;
 
+/// The distance between the small ticks on the rulers
+const RULER_TICK_SPACING: f32 = 10.0;
+
+/// The distance between the numbered ticks on the rulers
+const RULER_LABEL_SPACING: f32 = 100.0;
+
 ///
 /// Represents a layer in the current frame
 /// 
@@ -50,7 +58,16 @@ pub struct CanvasRenderer {
     overlay_layers: HashMap<u32, OverlayLayer>,
 
     /// The layer that we're currently 'annotating'
-    annotated_layer: Option<u64>
+    annotated_layer: Option<u64>,
+
+    /// True if rulers are drawn along the edges of the frame
+    show_rulers: bool,
+
+    /// The spacing of the grid drawn over the frame, if there is one
+    grid_spacing: Option<f32>,
+
+    /// How the frame is zoomed and rotated on the canvas
+    view: CanvasView
 }
 
 impl OverlayLayer {
@@ -74,7 +91,10 @@ impl CanvasRenderer {
         CanvasRenderer {
             frame_layers:       HashMap::new(),
             overlay_layers:     HashMap::new(),
-            annotated_layer:    None
+            annotated_layer:    None,
+            show_rulers:        false,
+            grid_spacing:       None,
+            view:               CanvasView::fit()
         }
     }
 
@@ -91,6 +111,7 @@ impl CanvasRenderer {
     fn free_layer(&self) -> u32 {
         // Create an iterator of all the used layer IDs
         let used_layers = self.frame_layers.values().map(|layer| layer.layer_id)
+            .chain(iter::once(self.guides_layer()))
             .chain(self.overlay_layers.values().flat_map(|overlay| overlay.layers.values().map(|layer_id| *layer_id)));
         
         // Find the highest
@@ -100,6 +121,13 @@ impl CanvasRenderer {
         max_layer.unwrap_or(0)+1
     }
 
+    ///
+    /// Returns the layer that the rulers and grid are drawn on (just above the frame layers)
+    /// 
+    fn guides_layer(&self) -> u32 {
+        (self.frame_layers.len() as u32) + 1
+    }
+
     ///
     /// Invalidates the layers assigned to overlay canvases
     /// 
@@ -211,13 +239,34 @@ impl CanvasRenderer {
         // Clearing the canvas also removes any 'annotations' that might have been performed
         self.annotated_layer = None;
 
+        // The view is part of the canvas transformation, so pointer positions are mapped back to frame coordinates
+        let view                    = self.view;
+        let (center_x, center_y)    = view.center_point((width, height));
+        let (half_width, half_height) = ((width/2.0) as f32, (height/2.0) as f32);
+
         canvas.draw(move |gc| {
             gc.clear_canvas();
-            gc.canvas_height((height*1.05) as f32);
-            gc.center_region(0.0,0.0, width as f32, height as f32);
+            gc.canvas_height(view.visible_height((width, height)));
+            gc.center_region(center_x-half_width, center_y-half_height, center_x+half_width, center_y+half_height);
+
+            if view.rotation != 0.0 {
+                gc.transform(view.rotation_transform((width, height)));
+            }
         });
     }
 
+    ///
+    /// Sets how the frame is zoomed and rotated, returning true if this is a change
+    /// 
+    /// The frame layers need to be redrawn for a new view to take effect.
+    /// 
+    pub fn set_view(&mut self, view: CanvasView) -> bool {
+        let changed = self.view != view;
+        self.view   = view;
+
+        changed
+    }
+
     ///
     /// Draws the canvas background to a context
     /// 
@@ -256,13 +305,118 @@ impl CanvasRenderer {
         canvas.draw(|gc| self.draw_background(gc, size));
 
         // Draw the active set of layers
+        let frame_layers = &self.frame_layers;
         canvas.draw(move |gc| {
             // Draw the layers
-            for layer in self.frame_layers.values() {
+            for layer in frame_layers.values() {
                 gc.layer(layer.layer_id);
                 layer.layer_frame.render_to(gc);
             }
         });
+
+        // Draw the rulers and the grid on top
+        self.draw_guides(canvas, size);
+    }
+
+    ///
+    /// Sets whether or not the rulers and the grid are drawn, returning true if this is a change
+    /// 
+    pub fn set_guides(&mut self, show_rulers: bool, grid_spacing: Option<f32>) -> bool {
+        let changed = self.show_rulers != show_rulers || self.grid_spacing != grid_spacing;
+
+        self.show_rulers    = show_rulers;
+        self.grid_spacing   = grid_spacing;
+
+        changed
+    }
+
+    ///
+    /// Redraws the layer containing the rulers and the grid
+    /// 
+    pub fn draw_guides(&mut self, canvas: &BindingCanvas, (width, height): (f64, f64)) {
+        // Drawing on a different layer will break any annotation
+        self.clear_annotation(canvas);
+
+        let (width, height) = (width as f32, height as f32);
+        let guides_layer    = self.guides_layer();
+        let show_rulers     = self.show_rulers;
+        let grid_spacing    = self.grid_spacing.filter(|spacing| *spacing > 0.0);
+
+        canvas.draw(move |gc| {
+            gc.layer(guides_layer);
+            gc.clear_layer();
+
+            // The grid covers the frame
+            if let Some(spacing) = grid_spacing {
+                gc.new_path();
+
+                let mut x = 0.0;
+                while x <= width {
+                    gc.move_to(x, 0.0);
+                    gc.line_to(x, height);
+                    x += spacing;
+                }
+
+                let mut y = 0.0;
+                while y <= height {
+                    gc.move_to(0.0, y);
+                    gc.line_to(width, y);
+                    y += spacing;
+                }
+
+                gc.line_width_pixels(1.0);
+                gc.stroke_color(GRID_LINE);
+                gc.stroke();
+            }
+
+            // The rulers run along the top and left of the frame, in the margin around it
+            if show_rulers {
+                let ruler_size = height * 0.02;
+
+                gc.new_path();
+                gc.rect(0.0, height, width, height+ruler_size);
+                gc.rect(-ruler_size, 0.0, 0.0, height);
+                gc.fill_color(RULER_BACKGROUND);
+                gc.fill();
+
+                gc.new_path();
+                let mut pos = 0.0;
+                while pos <= f32::max(width, height) {
+                    let is_labelled = (pos % RULER_LABEL_SPACING) == 0.0;
+                    let tick_length = if is_labelled { ruler_size } else { ruler_size * 0.3 };
+
+                    if pos <= width {
+                        gc.move_to(pos, height);
+                        gc.line_to(pos, height + tick_length);
+                    }
+
+                    if pos <= height {
+                        gc.move_to(0.0, pos);
+                        gc.line_to(-tick_length, pos);
+                    }
+
+                    pos += RULER_TICK_SPACING;
+                }
+
+                gc.line_width_pixels(1.0);
+                gc.stroke_color(RULER_TICK);
+                gc.stroke();
+
+                // Number the longer ticks
+                gc.fill_color(RULER_TICK);
+                gc.font_size(ruler_size * 0.4);
+
+                let mut pos = RULER_LABEL_SPACING;
+                while pos <= f32::max(width, height) {
+                    let label = format!("{}", pos as i32);
+
+                    if pos <= width { gc.text(&label, pos + ruler_size*0.1, height + ruler_size*0.45); }
+                    if pos <= height { gc.text(&label, -ruler_size*0.95, pos + ruler_size*0.1); }
+
+                    pos += RULER_LABEL_SPACING;
+                }
+            }
+        });
     }
 
     ///
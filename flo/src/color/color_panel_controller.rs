@@ -0,0 +1,572 @@
+use super::generate_images::*;
+
+use ui::*;
+use canvas::*;
+use binding::*;
+
+use std::sync::*;
+use std::time::{Duration, Instant};
+
+/// Width of the preview strips shown underneath the sliders, in pixels
+const STRIP_WIDTH: u32 = 128;
+
+/// Height of the preview strips shown underneath the sliders, in pixels
+const STRIP_HEIGHT: u32 = 4;
+
+/// Minimum time between updates to the preview strips while a slider is being dragged
+const DRAG_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+
+///
+/// A value that can be edited in the colour panel
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ColorChannel {
+    Red,
+    Green,
+    Blue,
+
+    HsvHue,
+    HsvSaturation,
+    HsvValue,
+
+    HsluvHue,
+    HsluvSaturation,
+    HsluvLightness,
+
+    Alpha
+}
+
+impl ColorChannel {
+    ///
+    /// The name of this channel (used for the viewmodel properties and actions)
+    ///
+    pub fn name(&self) -> &'static str {
+        use self::ColorChannel::*;
+
+        match self {
+            Red             => "Red",
+            Green           => "Green",
+            Blue            => "Blue",
+            HsvHue          => "HsvHue",
+            HsvSaturation   => "HsvSaturation",
+            HsvValue        => "HsvValue",
+            HsluvHue        => "HsluvHue",
+            HsluvSaturation => "HsluvSaturation",
+            HsluvLightness  => "HsluvLightness",
+            Alpha           => "Alpha"
+        }
+    }
+
+    ///
+    /// The label displayed next to the slider for this channel
+    ///
+    pub fn label(&self) -> &'static str {
+        use self::ColorChannel::*;
+
+        match self {
+            Red                             => "R",
+            Green                           => "G",
+            Blue                            => "B",
+            HsvHue | HsluvHue               => "H",
+            HsvSaturation | HsluvSaturation => "S",
+            HsvValue                        => "V",
+            HsluvLightness                  => "L",
+            Alpha                           => "Opacity"
+        }
+    }
+
+    ///
+    /// The range of values that this channel can take
+    ///
+    pub fn range(&self) -> (f32, f32) {
+        use self::ColorChannel::*;
+
+        match self {
+            Red | Green | Blue          => (0.0, 255.0),
+            HsvHue | HsluvHue           => (0.0, 360.0),
+            _                           => (0.0, 100.0)
+        }
+    }
+
+    ///
+    /// Retrieves the value of this channel for a colour
+    ///
+    pub fn get(&self, color: &Color) -> f32 {
+        use self::ColorChannel::*;
+
+        match self {
+            Red             => color.to_rgba_components().0 * 255.0,
+            Green           => color.to_rgba_components().1 * 255.0,
+            Blue            => color.to_rgba_components().2 * 255.0,
+            HsvHue          => color.to_hsv_components().0,
+            HsvSaturation   => color.to_hsv_components().1 * 100.0,
+            HsvValue        => color.to_hsv_components().2 * 100.0,
+            HsluvHue        => color.to_hsluv_components().0,
+            HsluvSaturation => color.to_hsluv_components().1,
+            HsluvLightness  => color.to_hsluv_components().2,
+            Alpha           => color.to_rgba_components().3 * 100.0
+        }
+    }
+
+    ///
+    /// Returns a colour with this channel changed to a new value
+    ///
+    /// RGB and HSV changes produce an RGBA colour and HSLuv changes produce a HSLuv colour, so the
+    /// other channels in the same colour space are never altered by converting between formats.
+    ///
+    pub fn set(&self, color: &Color, value: f32) -> Color {
+        use self::ColorChannel::*;
+
+        let (min, max)  = self.range();
+        let value       = f32::min(f32::max(value, min), max);
+
+        match self {
+            Red | Green | Blue => {
+                let (r, g, b, a) = color.to_rgba_components();
+
+                match self {
+                    Red     => Color::Rgba(value/255.0, g, b, a),
+                    Green   => Color::Rgba(r, value/255.0, b, a),
+                    _       => Color::Rgba(r, g, value/255.0, a)
+                }
+            },
+
+            HsvHue | HsvSaturation | HsvValue => {
+                let (h, s, v, a) = color.to_hsv_components();
+
+                match self {
+                    HsvHue          => Color::from_hsv(value, s, v, a),
+                    HsvSaturation   => Color::from_hsv(h, value/100.0, v, a),
+                    _               => Color::from_hsv(h, s, value/100.0, a)
+                }
+            },
+
+            HsluvHue | HsluvSaturation | HsluvLightness => {
+                let (h, s, l, a) = color.to_hsluv_components();
+
+                match self {
+                    HsluvHue        => Color::Hsluv(value, s, l, a),
+                    HsluvSaturation => Color::Hsluv(h, value, l, a),
+                    _               => Color::Hsluv(h, s, value, a)
+                }
+            },
+
+            Alpha => color.with_alpha(value/100.0)
+        }
+    }
+
+    ///
+    /// Generates an image showing how the colour changes as this channel moves through its range
+    ///
+    pub fn preview_strip(&self, color: &Color) -> Image {
+        let (min, max) = self.range();
+
+        image_for_strip_fn(|ratio| {
+            let (r, g, b, a) = self.set(color, min + (ratio as f32)*(max-min)).to_rgba_components();
+            ((r*255.0) as u8, (g*255.0) as u8, (b*255.0) as u8, (a*255.0) as u8)
+        }, STRIP_WIDTH, STRIP_HEIGHT)
+    }
+}
+
+/// The columns of sliders shown in the colour panel
+const COLUMNS: [(&str, [ColorChannel; 3]); 3] = [
+    ("RGB",     [ColorChannel::Red, ColorChannel::Green, ColorChannel::Blue]),
+    ("HSV",     [ColorChannel::HsvHue, ColorChannel::HsvSaturation, ColorChannel::HsvValue]),
+    ("HSLuv",   [ColorChannel::HsluvHue, ColorChannel::HsluvSaturation, ColorChannel::HsluvLightness])
+];
+
+///
+/// The preview image shown underneath the slider for a channel
+///
+struct PreviewStrip {
+    channel:        ColorChannel,
+
+    /// The colour the image was generated for, with this channel at its minimum (the image only changes when this does)
+    generated_for:  Color,
+
+    image:          Binding<Resource<Image>>
+}
+
+///
+/// The preview strips for all of the channels
+///
+struct PreviewStrips {
+    strips:         Vec<PreviewStrip>,
+
+    /// True while a slider is being dragged
+    dragging:       bool,
+
+    /// When the strips were last regenerated
+    last_update:    Instant
+}
+
+///
+/// Controller that allows a colour to be entered numerically, as RGB, HSV or HSLuv values or a hex string
+///
+pub struct ColorPanelController {
+    ui:         BindRef<Control>,
+    images:     Arc<ResourceManager<Image>>,
+    strips:     Mutex<PreviewStrips>,
+
+    viewmodel:  Arc<DynamicViewModel>,
+    color:      Binding<Color>
+}
+
+impl ColorPanelController {
+    ///
+    /// Creates a new colour panel controller
+    ///
+    pub fn new(color: &Binding<Color>) -> ColorPanelController {
+        let images      = Arc::new(ResourceManager::new());
+        let color       = color.clone();
+        let viewmodel   = DynamicViewModel::new();
+
+        // Every channel has a value and a text version of the value
+        let channels = Self::all_channels();
+
+        for &channel in channels.iter() {
+            let col = color.clone();
+            viewmodel.set_computed(channel.name(), move || {
+                PropertyValue::Float(channel.get(&col.get()) as f64)
+            });
+
+            let col = color.clone();
+            viewmodel.set_computed(&format!("{}Text", channel.name()), move || {
+                PropertyValue::String(format!("{}", channel.get(&col.get()).round()))
+            });
+        }
+
+        let col = color.clone();
+        viewmodel.set_computed("Hex", move || PropertyValue::String(col.get().to_hex_string()));
+
+        // Each channel has a preview strip that's regenerated in tick() when the colour changes
+        let strips = channels.iter()
+            .map(|channel| Self::preview_strip(*channel, &color.get(), &images))
+            .collect::<Vec<_>>();
+
+        // Set up the UI
+        let ui = Self::create_ui(strips.iter().map(|strip| (strip.channel, BindRef::from(strip.image.clone()))).collect());
+
+        ColorPanelController {
+            ui:         ui,
+            images:     images,
+            strips:     Mutex::new(PreviewStrips { strips: strips, dragging: false, last_update: Instant::now() }),
+            viewmodel:  Arc::new(viewmodel),
+            color:      color
+        }
+    }
+
+    ///
+    /// Returns every channel that can be edited in this panel
+    ///
+    fn all_channels() -> Vec<ColorChannel> {
+        COLUMNS.iter()
+            .flat_map(|(_, channels)| channels.iter().cloned())
+            .chain(vec![ColorChannel::Alpha])
+            .collect()
+    }
+
+    ///
+    /// The colour that determines the preview image for a channel (the channel's own value doesn't affect it)
+    ///
+    fn strip_key(channel: ColorChannel, color: &Color) -> Color {
+        let (min, _max) = channel.range();
+
+        channel.set(color, min)
+    }
+
+    ///
+    /// Creates the preview strip for a channel
+    ///
+    fn preview_strip(channel: ColorChannel, color: &Color, images: &ResourceManager<Image>) -> PreviewStrip {
+        PreviewStrip {
+            channel:        channel,
+            generated_for:  Self::strip_key(channel, color),
+            image:          bind(images.register(channel.preview_strip(color)))
+        }
+    }
+
+    ///
+    /// Regenerates the preview strips that have changed since they were last generated
+    ///
+    /// While a slider is being dragged, this happens at most once every `DRAG_UPDATE_INTERVAL`
+    ///
+    fn update_strips(&self) {
+        let mut strips = self.strips.lock().unwrap();
+
+        if strips.dragging && strips.last_update.elapsed() < DRAG_UPDATE_INTERVAL {
+            return;
+        }
+
+        let color       = self.color.get();
+        let mut updated = false;
+
+        for strip in strips.strips.iter_mut() {
+            let key = Self::strip_key(strip.channel, &color);
+
+            if key != strip.generated_for {
+                strip.generated_for = key;
+                strip.image.clone().set(self.images.register(strip.channel.preview_strip(&color)));
+                updated = true;
+            }
+        }
+
+        if updated {
+            strips.last_update = Instant::now();
+        }
+    }
+
+    ///
+    /// Creates the slider for a channel
+    ///
+    fn channel_slider(channel: ColorChannel, label_width: f32, strip: Resource<Image>) -> Control {
+        let (min, max) = channel.range();
+
+        Control::container()
+            .with(Bounds::next_vert(24.0))
+            .with(vec![
+                Control::label()
+                    .with(channel.label())
+                    .with(Font::Size(11.0))
+                    .with(Bounds::next_horiz(label_width)),
+                Control::container()
+                    .with(Bounds::stretch_horiz(1.0))
+                    .with(vec![
+                        Control::slider()
+                            .with(Bounds::next_vert(18.0))
+                            .with(State::Range((min.to_property(), max.to_property())))
+                            .with(State::Value(Property::Bind(channel.name().to_string())))
+                            .with((ActionTrigger::EditValue, format!("Edit{}", channel.name())))
+                            .with((ActionTrigger::SetValue, format!("Set{}", channel.name()))),
+                        Control::empty()
+                            .with(Bounds::next_vert(STRIP_HEIGHT as f32))
+                            .with(strip)
+                    ]),
+                Control::label()
+                    .with(ControlAttribute::Text(Property::Bind(format!("{}Text", channel.name()))))
+                    .with(Font::Size(11.0))
+                    .with(TextAlign::Right)
+                    .with(Bounds::next_horiz(28.0))
+            ])
+    }
+
+    ///
+    /// Creates the UI for this controller
+    ///
+    fn create_ui(strips: Vec<(ColorChannel, BindRef<Resource<Image>>)>) -> BindRef<Control> {
+        BindRef::from(computed(move || {
+            let strip_for = |channel: ColorChannel| strips.iter()
+                .find(|(strip_channel, _)| *strip_channel == channel)
+                .map(|(_, strip)| strip.get())
+                .unwrap();
+
+            // One column for each colour space
+            let columns = COLUMNS.iter()
+                .enumerate()
+                .map(|(index, (title, channels))| {
+                    let padding = if index > 0 { 8 } else { 0 };
+
+                    let mut column = vec![
+                        Control::label()
+                            .with(*title)
+                            .with(TextAlign::Center)
+                            .with(Font::Size(12.0))
+                            .with(Bounds::next_vert(16.0))
+                    ];
+                    column.extend(channels.iter().map(|channel| Self::channel_slider(*channel, 12.0, strip_for(*channel))));
+
+                    Control::container()
+                        .with(Bounds::stretch_horiz(1.0))
+                        .with(ControlAttribute::Padding((padding, 0), (0, 0)))
+                        .with(column)
+                })
+                .collect::<Vec<_>>();
+
+            // The hex value and the opacity go along the bottom
+            let bottom_row = vec![
+                Control::label()
+                    .with("Hex")
+                    .with(Font::Size(11.0))
+                    .with(Bounds::next_horiz(28.0)),
+                Control::text_box()
+                    .with(Bounds { x1: Position::After, y1: Position::Start, x2: Position::Offset(80.0), y2: Position::Offset(20.0) })
+                    .with(State::Value(Property::Bind("Hex".to_string())))
+                    .with((ActionTrigger::EditValue, "SetHex"))
+                    .with((ActionTrigger::SetValue, "SetHex")),
+                Control::empty()
+                    .with(Bounds::next_horiz(16.0)),
+                Control::container()
+                    .with(Bounds::stretch_horiz(1.0))
+                    .with(vec![Self::channel_slider(ColorChannel::Alpha, 48.0, strip_for(ColorChannel::Alpha))])
+            ];
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(vec![
+                    Control::container()
+                        .with(Bounds::next_vert(16.0 + 24.0*3.0))
+                        .with(columns),
+                    Control::empty()
+                        .with(Bounds::next_vert(8.0)),
+                    Control::container()
+                        .with(Bounds::next_vert(24.0))
+                        .with(bottom_row)
+                ])
+        }))
+    }
+}
+
+impl Controller for ColorPanelController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.viewmodel.clone())
+    }
+
+    fn get_subcontroller(&self, _id: &str) -> Option<Arc<dyn Controller>> { None }
+
+    fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        use ui::ActionParameter::*;
+        use ui::PropertyValue::*;
+
+        match (action_id, action_data) {
+            ("SetHex", &Value(String(ref hex))) => {
+                // Hex values are ignored until they're valid, and don't change the opacity
+                if let Some(new_color) = Color::from_hex(hex) {
+                    let alpha = self.color.get().to_rgba_components().3;
+                    self.color.clone().set(new_color.with_alpha(alpha));
+                }
+            },
+
+            (action_id, &Value(Float(new_value))) => {
+                // Sliders send 'Edit' actions while they're being dragged and a 'Set' action when they're released
+                let dragging    = action_id.starts_with("Edit");
+                let channel     = Self::all_channels().into_iter()
+                    .find(|channel| action_id == format!("Set{}", channel.name()) || action_id == format!("Edit{}", channel.name()));
+
+                if let Some(channel) = channel {
+                    self.strips.lock().unwrap().dragging = dragging;
+
+                    let new_color = channel.set(&self.color.get(), new_value as f32);
+                    self.color.clone().set(new_color);
+                }
+            },
+
+            _ => ()
+        }
+    }
+
+    fn get_image_resources(&self) -> Option<Arc<ResourceManager<Image>>> {
+        Some(Arc::clone(&self.images))
+    }
+
+    fn tick(&self) {
+        self.update_strips();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn is_close(a: &Color, b: &Color) -> bool {
+        let (r1, g1, b1, a1) = a.to_rgba_components();
+        let (r2, g2, b2, a2) = b.to_rgba_components();
+
+        (r1-r2).abs() < 0.001 && (g1-g2).abs() < 0.001 && (b1-b2).abs() < 0.001 && (a1-a2).abs() < 0.001
+    }
+
+    #[test]
+    fn setting_channels_to_their_current_value_leaves_color_unchanged() {
+        let color = Color::Rgba(0.5, 0.7, 0.2, 0.9);
+
+        for channel in ColorPanelController::all_channels() {
+            let new_color = channel.set(&color, channel.get(&color));
+            assert!(is_close(&new_color, &color));
+        }
+    }
+
+    #[test]
+    fn hsluv_channels_keep_hsluv_format() {
+        let color       = Color::Hsluv(24.0, 66.0, 60.0, 0.8);
+        let new_color   = ColorChannel::HsluvLightness.set(&color, 30.0);
+
+        assert!(new_color == Color::Hsluv(24.0, 66.0, 30.0, 0.8));
+        assert!(is_close(&new_color.to_format(ColorFormat::Rgba).to_format(ColorFormat::Hsluv), &new_color));
+    }
+
+    #[test]
+    fn rgb_channels_produce_rgba_colors() {
+        let color       = Color::Hsluv(24.0, 66.0, 60.0, 0.8);
+        let new_color   = ColorChannel::Red.set(&color, 255.0);
+        let (r, g, b, a) = new_color.to_rgba_components();
+
+        assert!(new_color == Color::Rgba(r, g, b, a));
+        assert!(r == 1.0);
+        assert!(a == 0.8);
+    }
+
+    #[test]
+    fn set_hex_keeps_opacity() {
+        let color       = bind(Color::Rgba(0.0, 0.0, 0.0, 0.5));
+        let controller  = ColorPanelController::new(&color);
+
+        controller.action("SetHex", &ActionParameter::Value(PropertyValue::String("#ff8000".to_string())));
+
+        assert!(color.get().to_hex_string() == "#ff8000");
+        assert!(color.get().to_rgba_components().3 == 0.5);
+    }
+
+    fn strip_id(controller: &ColorPanelController, channel: ColorChannel) -> u32 {
+        controller.strips.lock().unwrap().strips.iter()
+            .find(|strip| strip.channel == channel)
+            .map(|strip| strip.image.get().id())
+            .unwrap()
+    }
+
+    #[test]
+    fn tick_without_changes_keeps_strips() {
+        let color       = bind(Color::Rgba(0.5, 0.7, 0.2, 0.9));
+        let controller  = ColorPanelController::new(&color);
+        let red_strip   = strip_id(&controller, ColorChannel::Red);
+
+        controller.tick();
+
+        assert!(strip_id(&controller, ColorChannel::Red) == red_strip);
+    }
+
+    #[test]
+    fn changing_channel_only_regenerates_strips_that_depend_on_it() {
+        let color       = bind(Color::Rgba(0.5, 0.7, 0.2, 0.9));
+        let controller  = ColorPanelController::new(&color);
+        let red_strip   = strip_id(&controller, ColorChannel::Red);
+        let green_strip = strip_id(&controller, ColorChannel::Green);
+
+        controller.action("SetRed", &ActionParameter::Value(PropertyValue::Float(10.0)));
+        controller.tick();
+
+        assert!(strip_id(&controller, ColorChannel::Red) == red_strip);
+        assert!(strip_id(&controller, ColorChannel::Green) != green_strip);
+    }
+
+    #[test]
+    fn dragging_throttles_strip_updates() {
+        let color       = bind(Color::Rgba(0.5, 0.7, 0.2, 0.9));
+        let controller  = ColorPanelController::new(&color);
+        let green_strip = strip_id(&controller, ColorChannel::Green);
+
+        // Strips were generated just now, so they aren't updated while dragging
+        controller.action("EditRed", &ActionParameter::Value(PropertyValue::Float(10.0)));
+        controller.tick();
+
+        assert!(strip_id(&controller, ColorChannel::Green) == green_strip);
+
+        // Releasing the slider brings them up to date
+        controller.action("SetRed", &ActionParameter::Value(PropertyValue::Float(20.0)));
+        controller.tick();
+
+        assert!(strip_id(&controller, ColorChannel::Green) != green_strip);
+    }
+}
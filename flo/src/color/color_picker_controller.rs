@@ -1,4 +1,5 @@
 use super::hsluv_picker_controller::*;
+use super::color_panel_controller::*;
 
 use ui::*;
 use canvas::*;
@@ -6,13 +7,17 @@ use binding::*;
 
 use std::sync::*;
 
+/// Height of the HSLuv colour wheel at the top of the picker
+const HSLUV_PICKER_HEIGHT: f32 = 100.0;
+
 ///
 /// Controller that makes it possible to pick a colour
 /// 
 pub struct ColorPickerController {
     ui:     BindRef<Control>,
 
-    hsluv:  Arc<HsluvPickerController>
+    hsluv:  Arc<HsluvPickerController>,
+    panel:  Arc<ColorPanelController>
 }
 
 impl ColorPickerController {
@@ -22,10 +27,12 @@ impl ColorPickerController {
     pub fn new(color: &Binding<Color>) -> ColorPickerController {
         let ui      = Self::create_ui();
         let hsluv   = HsluvPickerController::new(color);
+        let panel   = ColorPanelController::new(color);
 
         ColorPickerController {
             ui:     ui,
-            hsluv:  Arc::new(hsluv)
+            hsluv:  Arc::new(hsluv),
+            panel:  Arc::new(panel)
         }
     }
 
@@ -35,8 +42,17 @@ impl ColorPickerController {
     fn create_ui() -> BindRef<Control> {
         BindRef::from(computed(move || {
             Control::container()
-                .with_controller("HSLUV")
                 .with(Bounds::fill_all())
+                .with(vec![
+                    Control::container()
+                        .with_controller("HSLUV")
+                        .with(Bounds::next_vert(HSLUV_PICKER_HEIGHT)),
+                    Control::empty()
+                        .with(Bounds::next_vert(8.0)),
+                    Control::container()
+                        .with_controller("Panel")
+                        .with(Bounds::fill_vert())
+                ])
         }))
     }
 }
@@ -49,6 +65,7 @@ impl Controller for ColorPickerController {
     fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
         match id {
             "HSLUV" => Some(self.hsluv.clone()),
+            "Panel" => Some(self.panel.clone()),
             _       => None
         }
     }
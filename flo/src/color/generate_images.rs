@@ -78,3 +78,34 @@ pub fn rgba_data_for_wheel_fn<PixelFn: Fn(f64) -> (u8, u8, u8, u8)>(pixel: Pixel
 pub fn image_for_wheel_fn<PixelFn: Fn(f64) -> (u8, u8, u8, u8)>(pixel: PixelFn, size: u32, inner_radius: u32, rotate_degrees: f64) -> Image {
     Image::png_from_rgba_data(&rgba_data_for_wheel_fn(pixel, size, inner_radius, rotate_degrees), size, size)
 }
+
+///
+/// Given a function that takes a value from 0-1 (representing the distance
+/// from the left-hand side of the strip) and returns a pixel, generates
+/// the RGBA data for a horizontal strip
+/// 
+pub fn rgba_data_for_strip_fn<PixelFn: Fn(f64) -> (u8, u8, u8, u8)>(pixel: PixelFn, width: u32, height: u32) -> Vec<u8> {
+    let max_x = f64::max((width as f64)-1.0, 1.0);
+
+    // Generate a single row
+    let row: Vec<_> = (0..width)
+        .flat_map(|x| {
+            let (r, g, b, a) = pixel((x as f64)/max_x);
+            vec![r, g, b, a]
+        })
+        .collect();
+
+    // Every row in the strip is the same
+    (0..height)
+        .flat_map(|_| row.iter().cloned())
+        .collect()
+}
+
+///
+/// Given a function that takes a value from 0-1 (representing the distance
+/// from the left-hand side of the strip) and returns a pixel, generates a
+/// horizontal strip image
+/// 
+pub fn image_for_strip_fn<PixelFn: Fn(f64) -> (u8, u8, u8, u8)>(pixel: PixelFn, width: u32, height: u32) -> Image {
+    Image::png_from_rgba_data(&rgba_data_for_strip_fn(pixel, width, height), width, height)
+}
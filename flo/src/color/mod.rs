@@ -2,6 +2,12 @@ pub mod generate_images;
 pub mod images;
 pub mod color_picker_controller;
 pub mod hsluv_picker_controller;
+pub mod color_panel_controller;
+pub mod swatch_strip_controller;
+pub mod palette_file_controller;
 
 pub use self::color_picker_controller::*;
 pub use self::hsluv_picker_controller::*;
+pub use self::color_panel_controller::*;
+pub use self::swatch_strip_controller::*;
+pub use self::palette_file_controller::*;
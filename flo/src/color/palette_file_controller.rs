@@ -0,0 +1,179 @@
+use ui::*;
+use binding::*;
+use animation::*;
+use animation::gpl::*;
+
+use std::io;
+use std::sync::*;
+
+/// The file name suggested when importing or exporting a palette
+const PALETTE_FILE_NAME: &str = "palette.gpl";
+
+///
+/// Controller that imports and exports the swatches in the palette as GIMP palette (`.gpl`) files
+///
+/// The files are read and written by the UI (so the palette is uploaded or downloaded rather than
+/// being read from a path on the machine running the controller)
+///
+pub struct PaletteFileController {
+    /// The swatches in the palette
+    palette:        BindRef<Vec<Swatch>>,
+
+    /// Function called for each swatch read from an imported palette
+    on_add_swatch:  Arc<dyn Fn(Swatch)+Send+Sync>,
+
+    /// Message describing the result of the last import or export
+    status:         Binding<String>,
+
+    view_model:     Arc<DynamicViewModel>,
+    ui:             BindRef<Control>
+}
+
+impl PaletteFileController {
+    ///
+    /// Creates a new palette file controller
+    ///
+    pub fn new(palette: &BindRef<Vec<Swatch>>, on_add_swatch: Arc<dyn Fn(Swatch)+Send+Sync>) -> PaletteFileController {
+        let status      = bind(String::new());
+        let view_model  = Arc::new(DynamicViewModel::new());
+
+        let vm_palette  = palette.clone();
+        let vm_status   = status.clone();
+        view_model.set_computed("PaletteData", move || PropertyValue::String(Self::export(&vm_palette.get())));
+        view_model.set_computed("PaletteStatus", move || PropertyValue::String(vm_status.get()));
+
+        PaletteFileController {
+            palette:        palette.clone(),
+            on_add_swatch:  on_add_swatch,
+            status:         status,
+            view_model:     view_model,
+            ui:             Self::create_ui()
+        }
+    }
+
+    ///
+    /// Creates a popup for importing and exporting palettes
+    ///
+    pub fn popup(palette: &BindRef<Vec<Swatch>>, is_open: &Binding<bool>, on_add_swatch: Arc<dyn Fn(Swatch)+Send+Sync>) -> PopupController<PaletteFileController> {
+        PopupController::new(Self::new(palette, on_add_swatch), is_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(240, 64))
+    }
+
+    ///
+    /// Creates the UI binding (buttons to import or export a palette file and a status line)
+    ///
+    fn create_ui() -> BindRef<Control> {
+        BindRef::from(bind(Control::container()
+            .with(Bounds::fill_all())
+            .with(ControlAttribute::Padding((4, 4), (4, 4)))
+            .with(vec![
+                Control::container()
+                    .with(Bounds::next_vert(28.0))
+                    .with(ControlAttribute::Padding((0, 2), (0, 2)))
+                    .with(vec![
+                        Control::button()
+                            .with(vec![Control::label().with("Import...").with(TextAlign::Center).with(Bounds::fill_all())])
+                            .with(Bounds::stretch_horiz(1.0))
+                            .with((ActionTrigger::LoadFile(PALETTE_FILE_NAME.to_string()), "ImportPalette")),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Control::button()
+                            .with(vec![Control::label().with("Export...").with(TextAlign::Center).with(Bounds::fill_all())])
+                            .with(Bounds::stretch_horiz(1.0))
+                            .with(State::Value(Property::Bind("PaletteData".to_string())))
+                            .with((ActionTrigger::SaveFile(PALETTE_FILE_NAME.to_string()), "ExportPalette"))
+                    ]),
+                Control::label()
+                    .with(Bounds::next_vert(24.0))
+                    .with(Font::Size(11.0))
+                    .with(ControlAttribute::Text(Property::Bind("PaletteStatus".to_string())))
+            ])))
+    }
+
+    ///
+    /// Reads the swatches from the contents of a GIMP palette file
+    ///
+    fn import(contents: &str) -> io::Result<Vec<Swatch>> {
+        read_gpl(contents.as_bytes())
+    }
+
+    ///
+    /// Generates the contents of a GIMP palette file for a set of swatches
+    ///
+    fn export(swatches: &[Swatch]) -> String {
+        let mut contents = vec![];
+        write_gpl("FlowBetween", swatches, &mut contents).unwrap();
+
+        String::from_utf8_lossy(&contents).to_string()
+    }
+}
+
+impl Controller for PaletteFileController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        match (action_id, action_parameter) {
+            ("ImportPalette", &ActionParameter::Value(PropertyValue::String(ref contents))) => {
+                // Importing adds to the palette (swatches with the same name as an existing swatch replace it)
+                let status = match Self::import(contents) {
+                    Ok(swatches) => {
+                        let num_swatches = swatches.len();
+                        swatches.into_iter().for_each(|swatch| (self.on_add_swatch)(swatch));
+
+                        format!("Imported {} colours", num_swatches)
+                    },
+                    Err(_) => "Could not import: not a GIMP palette file".to_string()
+                };
+
+                self.status.clone().set(status);
+            },
+
+            ("ExportPalette", _) => {
+                self.status.clone().set(format!("Exported {} colours", self.palette.get().len()));
+            },
+
+            _ => { }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use canvas::*;
+
+    #[test]
+    fn exported_palette_can_be_imported() {
+        let palette     = BindRef::from(bind(vec![Swatch::new("Red", Color::Rgba(1.0, 0.0, 0.0, 1.0)), Swatch::new("Blue", Color::Rgba(0.0, 0.0, 1.0, 1.0))]));
+        let imported    = Arc::new(Mutex::new(vec![]));
+
+        let on_add      = imported.clone();
+        let controller  = PaletteFileController::new(&palette, Arc::new(move |swatch| on_add.lock().unwrap().push(swatch)));
+
+        let exported    = controller.get_viewmodel().unwrap().get_property("PaletteData").get();
+        controller.action("ExportPalette", &ActionParameter::None);
+        assert!(controller.status.get() == "Exported 2 colours");
+
+        controller.action("ImportPalette", &ActionParameter::Value(exported));
+
+        assert!(controller.status.get() == "Imported 2 colours");
+        assert!(*imported.lock().unwrap() == palette.get());
+    }
+
+    #[test]
+    fn invalid_palette_is_not_imported() {
+        let palette     = BindRef::from(bind(vec![]));
+        let controller  = PaletteFileController::new(&palette, Arc::new(|_swatch| { }));
+
+        controller.action("ImportPalette", &ActionParameter::Value(PropertyValue::String("Not a palette".to_string())));
+
+        assert!(controller.status.get() == "Could not import: not a GIMP palette file");
+    }
+}
@@ -0,0 +1,220 @@
+use super::palette_file_controller::*;
+
+use ui::*;
+use canvas::*;
+use binding::*;
+use animation::*;
+
+use std::sync::*;
+
+/// Width of a swatch in the strip
+const SWATCH_WIDTH: f32 = 16.0;
+
+///
+/// Controller that shows the palette swatches and recently used colours as a strip of buttons
+///
+pub struct SwatchStripController {
+    /// The colour that's being edited
+    color:          Binding<Color>,
+
+    /// The swatches in the palette
+    palette:        BindRef<Vec<Swatch>>,
+
+    /// The colours that were used most recently
+    recent_colors:  BindRef<Vec<Color>>,
+
+    /// The name of the swatch the user last picked from the palette
+    selected:       Binding<Option<String>>,
+
+    /// Function called when the user adds the current colour to the palette
+    on_add_swatch:  Arc<dyn Fn(Swatch)+Send+Sync>,
+
+    /// Function called when the user removes a swatch from the palette
+    on_remove_swatch: Box<dyn Fn(String)+Send+Sync>,
+
+    /// Whether or not the popup for importing and exporting palettes is open
+    file_open:      Binding<bool>,
+
+    /// Popup for importing and exporting palettes
+    file_popup:     Arc<PopupController<PaletteFileController>>,
+
+    ui:             BindRef<Control>
+}
+
+impl SwatchStripController {
+    ///
+    /// Creates a new swatch strip controller that sets the specified colour when a swatch is clicked
+    ///
+    pub fn new<AddSwatchFn, RemoveSwatchFn>(color: &Binding<Color>, palette: &BindRef<Vec<Swatch>>, recent_colors: &BindRef<Vec<Color>>, on_add_swatch: AddSwatchFn, on_remove_swatch: RemoveSwatchFn) -> SwatchStripController
+    where AddSwatchFn: 'static+Fn(Swatch)+Send+Sync, RemoveSwatchFn: 'static+Fn(String)+Send+Sync {
+        let on_add_swatch: Arc<dyn Fn(Swatch)+Send+Sync> = Arc::new(on_add_swatch);
+
+        let selected    = bind(None);
+        let file_open   = bind(false);
+        let file_popup  = PaletteFileController::popup(palette, &file_open, on_add_swatch.clone());
+        let ui          = Self::create_ui(palette.clone(), recent_colors.clone(), selected.clone(), file_open.clone());
+
+        SwatchStripController {
+            color:              color.clone(),
+            palette:            palette.clone(),
+            recent_colors:      recent_colors.clone(),
+            selected:           selected,
+            on_add_swatch:      on_add_swatch,
+            on_remove_swatch:   Box::new(on_remove_swatch),
+            file_open:          file_open,
+            file_popup:         Arc::new(file_popup),
+            ui:                 ui
+        }
+    }
+
+    ///
+    /// Creates a button that displays a colour
+    ///
+    fn swatch_button(color: Color, action: String, selected: bool) -> Control {
+        Control::button()
+            .with(Appearance::Background(color.with_alpha(1.0)))
+            .with(State::Selected(Property::Bool(selected)))
+            .with(Bounds::next_horiz(SWATCH_WIDTH))
+            .with((ActionTrigger::Click, action))
+    }
+
+    ///
+    /// Creates a button with a text label
+    ///
+    fn text_button(label: &str, action: &str) -> Control {
+        Control::button()
+            .with(vec![Control::label().with(label).with(TextAlign::Center).with(Bounds::fill_all())])
+            .with(Bounds::next_horiz(SWATCH_WIDTH + 4.0))
+            .with((ActionTrigger::Click, action))
+    }
+
+    ///
+    /// Creates the UI binding (palette swatches, then the recent colours, then buttons to add or remove swatches and to import or export the palette)
+    ///
+    fn create_ui(palette: BindRef<Vec<Swatch>>, recent_colors: BindRef<Vec<Color>>, selected: Binding<Option<String>>, file_open: Binding<bool>) -> BindRef<Control> {
+        BindRef::from(computed(move || {
+            let mut controls    = vec![];
+            let selected        = selected.get();
+
+            for (index, swatch) in palette.get().into_iter().enumerate() {
+                let is_selected = Some(&swatch.name) == selected.as_ref();
+
+                controls.push(Self::swatch_button(swatch.color, format!("Swatch-{}", index), is_selected));
+                controls.push(Control::empty().with(Bounds::next_horiz(2.0)));
+            }
+
+            controls.push(Control::empty().with(Bounds::next_horiz(8.0)));
+
+            for (index, color) in recent_colors.get().into_iter().enumerate() {
+                controls.push(Self::swatch_button(color, format!("Recent-{}", index), false));
+                controls.push(Control::empty().with(Bounds::next_horiz(2.0)));
+            }
+
+            controls.push(Control::empty().with(Bounds::next_horiz(4.0)));
+            controls.push(Self::text_button("+", "AddSwatch"));
+            controls.push(Control::empty().with(Bounds::next_horiz(2.0)));
+            controls.push(Self::text_button("-", "RemoveSwatch"));
+            controls.push(Control::empty().with(Bounds::next_horiz(2.0)));
+            controls.push(Self::text_button("...", "ShowPaletteFile")
+                .with(State::Badged(Property::Bool(file_open.get())))
+                .with_controller("PaletteFile"));
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(ControlAttribute::Padding((0, 5), (0, 5)))
+                .with(controls)
+        }))
+    }
+
+    ///
+    /// Returns a name for a new swatch that isn't already used in the palette
+    ///
+    fn new_swatch_name(palette: &Vec<Swatch>) -> String {
+        (palette.len()+1..)
+            .map(|index| format!("Colour {}", index))
+            .find(|name| !palette.iter().any(|swatch| &swatch.name == name))
+            .unwrap()
+    }
+}
+
+impl Controller for SwatchStripController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        match id {
+            "PaletteFile"   => Some(self.file_popup.clone()),
+            _               => None
+        }
+    }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        if action_id == "AddSwatch" {
+            // Store the current colour in the palette
+            let name = Self::new_swatch_name(&self.palette.get());
+            (self.on_add_swatch)(Swatch::new(&name, self.color.get()));
+        } else if action_id == "RemoveSwatch" {
+            // Remove the swatch the user last picked
+            if let Some(name) = self.selected.get() {
+                self.selected.clone().set(None);
+                (self.on_remove_swatch)(name);
+            }
+        } else if action_id == "ShowPaletteFile" {
+            self.file_open.clone().set(true);
+        } else if action_id.starts_with("Swatch-") {
+            // Pick a colour from the palette
+            let swatch = action_id["Swatch-".len()..].parse::<usize>().ok()
+                .and_then(|index| self.palette.get().get(index).cloned());
+
+            if let Some(swatch) = swatch {
+                self.color.clone().set(swatch.color);
+                self.selected.clone().set(Some(swatch.name));
+            }
+        } else if action_id.starts_with("Recent-") {
+            // Pick one of the recently used colours
+            let color = action_id["Recent-".len()..].parse::<usize>().ok()
+                .and_then(|index| self.recent_colors.get().get(index).cloned());
+
+            if let Some(color) = color {
+                self.color.clone().set(color);
+                self.selected.clone().set(None);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new_swatch_names_are_unique() {
+        let palette = vec![Swatch::new("Colour 2", Color::Rgba(0.0, 0.0, 0.0, 1.0))];
+
+        assert!(SwatchStripController::new_swatch_name(&palette) == "Colour 3");
+        assert!(SwatchStripController::new_swatch_name(&vec![]) == "Colour 1");
+    }
+
+    #[test]
+    fn remove_swatch_removes_the_picked_swatch() {
+        let color       = bind(Color::Rgba(0.0, 0.0, 0.0, 1.0));
+        let palette     = BindRef::from(bind(vec![Swatch::new("Red", Color::Rgba(1.0, 0.0, 0.0, 1.0)), Swatch::new("Blue", Color::Rgba(0.0, 0.0, 1.0, 1.0))]));
+        let recent      = BindRef::from(bind(vec![]));
+        let removed     = Arc::new(Mutex::new(vec![]));
+
+        let on_remove   = removed.clone();
+        let strip       = SwatchStripController::new(&color, &palette, &recent, |_swatch| { }, move |name| on_remove.lock().unwrap().push(name));
+
+        // Nothing is removed until a swatch is picked
+        strip.action("RemoveSwatch", &ActionParameter::None);
+        assert!(removed.lock().unwrap().len() == 0);
+
+        strip.action("Swatch-1", &ActionParameter::None);
+        assert!(color.get() == Color::Rgba(0.0, 0.0, 1.0, 1.0));
+
+        strip.action("RemoveSwatch", &ActionParameter::None);
+        strip.action("RemoveSwatch", &ActionParameter::None);
+        assert!(*removed.lock().unwrap() == vec!["Blue".to_string()]);
+    }
+}
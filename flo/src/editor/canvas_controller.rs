@@ -168,6 +168,40 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
         });
     }
 
+    ///
+    /// Updates the rulers and the grid from the model, redrawing them if they've changed
+    /// 
+    fn update_guides(&self) {
+        let guides          = self.anim_model.guides();
+        let show_rulers     = guides.show_rulers.get();
+        let grid_spacing    = if guides.show_grid.get() { Some(guides.grid_spacing.get()) } else { None };
+
+        let canvas          = self.canvases.get_named_resource(MAIN_CANVAS).unwrap();
+        let size            = self.anim_model.size();
+
+        self.core.sync(move |core| {
+            if core.renderer.set_guides(show_rulers, grid_spacing) {
+                core.renderer.draw_guides(&*canvas, size);
+            }
+        });
+    }
+
+    ///
+    /// Updates the zoom and rotation of the canvas from the model, redrawing the frame if they've changed
+    /// 
+    fn update_view(&self) {
+        let view    = self.anim_model.view().get();
+        let canvas  = self.canvases.get_named_resource(MAIN_CANVAS).unwrap();
+        let size    = self.anim_model.size();
+
+        self.core.sync(move |core| {
+            if core.renderer.set_view(view) {
+                core.renderer.draw_frame_layers(&*canvas, size);
+                core.renderer.draw_overlays(&*canvas);
+            }
+        });
+    }
+
     ///
     /// Performs a series of painting actions on the canvas
     /// 
@@ -177,6 +211,7 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
         // Update the paint device in the tool model if we're starting a new paint action
         if actions.len() > 0 && actions[0].action == PaintAction::Start {
             self.anim_model.tools().current_pointer.clone().set((device, actions[0].pointer_id));
+            self.anim_model.tools().current_modifiers.clone().set(actions[0].modifiers);
         }
 
         // Fetch the canvas we're going to draw to
@@ -232,6 +267,12 @@ impl<Anim: Animation+EditableAnimation+'static> Controller for CanvasController<
             });
         }
 
+        // Redraw the rulers and the grid if their settings have changed
+        self.update_guides();
+
+        // Redraw the frame if it has been zoomed or rotated
+        self.update_view();
+
         // Check that the frame time hasn't changed and the frame has not been invalidated since it was last drawn
         let displayed_invalidation_count    = self.core.sync(|core| core.current_invalidation_count);
         let displayed_time                  = self.core.sync(|core| core.current_time);
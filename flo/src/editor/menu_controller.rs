@@ -10,6 +10,12 @@ use animation::*;
 use std::sync::*;
 use std::collections::HashMap;
 
+/// The name of the controller for the rulers, grid and snapping settings
+const GUIDES_CONTROLLER: &str = "Guides";
+
+/// The name of the controller for the zoom and rotation of the canvas
+const VIEW_CONTROLLER: &str = "View";
+
 ///
 /// The menu controller handles the menbu at the top of the UI
 ///
@@ -18,6 +24,8 @@ pub struct MenuController<Anim: Animation> {
     ui:                 BindRef<Control>,
     tool_controllers:   Mutex<HashMap<String, Arc<dyn Controller>>>,
 
+    guides_menu:        Arc<GuidesMenuController>,
+    view_menu:          Arc<ViewMenuController>,
     empty_menu:         Arc<EmptyMenuController>
 }
 
@@ -31,6 +39,8 @@ impl<Anim: 'static+Animation> MenuController<Anim> {
         let tool_controller = BindRef::from(computed(move || format!("Tool_{}", effective_tool.get().map(|tool| tool.tool_name()).unwrap_or(String::new()))));
         let ui              = Self::create_ui(&tool_controller);
         let empty_menu      = Arc::new(EmptyMenuController::new());
+        let guides_menu     = Arc::new(GuidesMenuController::new(anim_model.guides()));
+        let view_menu       = Arc::new(ViewMenuController::new(anim_model.view()));
 
         // Create the controller
         MenuController {
@@ -38,6 +48,8 @@ impl<Anim: 'static+Animation> MenuController<Anim> {
             ui:                 BindRef::from(ui),
             tool_controllers:   Mutex::new(HashMap::new()),
 
+            guides_menu:        guides_menu,
+            view_menu:          view_menu,
             empty_menu:         empty_menu
         }
     }
@@ -71,6 +83,16 @@ impl<Anim: 'static+Animation> MenuController<Anim> {
                         .with(Bounds::stretch_horiz(1.0))
                         .with(Font::Size(12.0))
                         .with_controller(&tool_controller),
+
+                    Control::empty()
+                        .with(Bounds::next_horiz(240.0))
+                        .with(Font::Size(12.0))
+                        .with_controller(VIEW_CONTROLLER),
+
+                    Control::empty()
+                        .with(Bounds::next_horiz(404.0))
+                        .with(Font::Size(12.0))
+                        .with_controller(GUIDES_CONTROLLER),
                 ])
                 .with(Appearance::Background(MENU_BACKGROUND))
         }))
@@ -97,6 +119,15 @@ impl<Anim: Animation+'static> Controller for MenuController<Anim>  {
     fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
         use std::collections::hash_map::Entry::*;
 
+        // The guides and view menus are always available
+        if id == GUIDES_CONTROLLER {
+            return Some(self.guides_menu.clone());
+        }
+
+        if id == VIEW_CONTROLLER {
+            return Some(self.view_menu.clone());
+        }
+
         // Try to fetch the existing controller for this ID
         let mut tool_controllers    = self.tool_controllers.lock().unwrap();
         let entry                   = tool_controllers.entry(id.to_string());
@@ -96,19 +96,23 @@ impl<Anim: 'static+Animation> ToolboxController<Anim> {
         let select  = images.register(svg_static(include_bytes!("../../svg/tools/select.svg")));
         let adjust  = images.register(svg_static(include_bytes!("../../svg/tools/adjust.svg")));
         let pan     = images.register(svg_static(include_bytes!("../../svg/tools/pan.svg")));
+        let zoom    = images.register(svg_static(include_bytes!("../../svg/tools/zoom.svg")));
 
         let pencil  = images.register(svg_static(include_bytes!("../../svg/tools/pencil.svg")));
         let ink     = images.register(svg_static(include_bytes!("../../svg/tools/ink.svg")));
         let eraser  = images.register(svg_static(include_bytes!("../../svg/tools/eraser.svg")));
+        let eyedropper = images.register(svg_static(include_bytes!("../../svg/tools/eyedropper.svg")));
 
         // Assign names to them
         images.assign_name(&select, "select");
         images.assign_name(&adjust, "adjust");
         images.assign_name(&pan, "pan");
+        images.assign_name(&zoom, "zoom");
 
         images.assign_name(&pencil, "pencil");
         images.assign_name(&ink, "ink");
         images.assign_name(&eraser, "eraser");
+        images.assign_name(&eyedropper, "eyedropper");
 
         images
     }
@@ -0,0 +1,162 @@
+use ui::*;
+use binding::*;
+use animation::*;
+
+use std::sync::*;
+
+/// Height of a preset in the preset list
+const PRESET_HEIGHT: f32 = 24.0;
+
+/// Height of the row used to save the current brush as a new preset
+const SAVE_ROW_HEIGHT: f32 = 28.0;
+
+///
+/// Controller that lets the user pick one of the brush presets stored in the animation, or save the current brush as a new preset
+///
+pub struct BrushPresetController {
+    /// The presets that are being displayed
+    presets:    BindRef<Vec<BrushPreset>>,
+
+    /// Function called when the user picks a preset
+    on_select:  Box<dyn Fn(&BrushPreset)+Send+Sync>,
+
+    /// Function called when the user saves the current brush as a preset with a particular name
+    on_save:    Box<dyn Fn(String)+Send+Sync>,
+
+    /// The name the user has typed for a new preset
+    new_name:   Binding<String>,
+
+    view_model: Arc<DynamicViewModel>,
+    ui:         BindRef<Control>
+}
+
+impl BrushPresetController {
+    ///
+    /// Creates a new brush preset controller, showing the presets with a particular drawing style
+    ///
+    pub fn new<SelectFn, SaveFn>(presets: &BindRef<Vec<BrushPreset>>, drawing_style: BrushDrawingStyle, on_select: SelectFn, on_save: SaveFn) -> BrushPresetController
+    where SelectFn: 'static+Fn(&BrushPreset)+Send+Sync, SaveFn: 'static+Fn(String)+Send+Sync {
+        let all_presets = presets.clone();
+        let presets     = BindRef::from(computed(move || {
+            all_presets.get().into_iter()
+                .filter(|preset| preset.drawing_style == drawing_style)
+                .collect::<Vec<_>>()
+        }));
+
+        let new_name    = bind(String::new());
+        let view_model  = Arc::new(DynamicViewModel::new());
+
+        let vm_new_name = new_name.clone();
+        view_model.set_computed("NewPresetName", move || PropertyValue::String(vm_new_name.get()));
+
+        let ui          = Self::create_ui(presets.clone());
+
+        BrushPresetController {
+            presets:    presets,
+            on_select:  Box::new(on_select),
+            on_save:    Box::new(on_save),
+            new_name:   new_name,
+            view_model: view_model,
+            ui:         ui
+        }
+    }
+
+    ///
+    /// Creates a popup for picking a preset, which closes when a preset is picked
+    ///
+    pub fn popup<SelectFn, SaveFn>(presets: &BindRef<Vec<BrushPreset>>, drawing_style: BrushDrawingStyle, is_open: &Binding<bool>, on_select: SelectFn, on_save: SaveFn) -> PopupController<BrushPresetController>
+    where SelectFn: 'static+Fn(&BrushPreset)+Send+Sync, SaveFn: 'static+Fn(String)+Send+Sync {
+        let close       = is_open.clone();
+        let controller  = Self::new(presets, drawing_style, move |preset| {
+            close.clone().set(false);
+            on_select(preset);
+        }, on_save);
+        let size        = controller.popup_size();
+
+        PopupController::new(controller, is_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(size)
+    }
+
+    ///
+    /// Creates the UI binding (a button for each preset, using the preset name as the action, followed by a row for saving a new preset)
+    ///
+    fn create_ui(presets: BindRef<Vec<BrushPreset>>) -> BindRef<Control> {
+        BindRef::from(computed(move || {
+            let mut controls: Vec<_> = presets.get().iter()
+                .map(|preset| {
+                    Control::button()
+                        .with(vec![Control::label().with(preset.name.clone()).with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(Bounds::next_vert(PRESET_HEIGHT))
+                        .with((ActionTrigger::Click, preset.name.clone()))
+                })
+                .collect();
+
+            controls.push(Control::container()
+                .with(Bounds::next_vert(SAVE_ROW_HEIGHT))
+                .with(ControlAttribute::Padding((0, 4), (0, 0)))
+                .with(vec![
+                    Control::text_box()
+                        .with(Bounds::stretch_horiz(1.0))
+                        .with(State::Value(Property::Bind("NewPresetName".to_string())))
+                        .with((ActionTrigger::EditValue, "SetNewPresetName"))
+                        .with((ActionTrigger::SetValue, "SetNewPresetName")),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::button()
+                        .with(vec![Control::label().with("Save").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(Bounds::next_horiz(40.0))
+                        .with((ActionTrigger::Click, "SaveNewPreset"))
+                ]));
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(ControlAttribute::Padding((4, 4), (4, 4)))
+                .with(controls)
+        }))
+    }
+
+    ///
+    /// The size of the popup needed to show the presets
+    ///
+    pub fn popup_size(&self) -> BindRef<(u32, u32)> {
+        let presets = self.presets.clone();
+
+        BindRef::from(computed(move || (160, (presets.get().len() as f32 * PRESET_HEIGHT + SAVE_ROW_HEIGHT) as u32 + 8)))
+    }
+}
+
+impl Controller for BrushPresetController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        match (action_id, action_parameter) {
+            ("SetNewPresetName", &ActionParameter::Value(PropertyValue::String(ref name))) => {
+                self.new_name.clone().set(name.clone());
+            },
+
+            ("SaveNewPreset", _) => {
+                // Presets need a name: saving with the name of an existing preset replaces it
+                let name = self.new_name.get().trim().to_string();
+
+                if name.len() > 0 {
+                    (self.on_save)(name);
+                    self.new_name.clone().set(String::new());
+                }
+            },
+
+            _ => {
+                let preset = self.presets.get().into_iter().find(|preset| preset.name == action_id);
+
+                if let Some(preset) = preset {
+                    (self.on_select)(&preset);
+                }
+            }
+        }
+    }
+}
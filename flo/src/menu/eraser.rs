@@ -1,5 +1,7 @@
 use super::ink::*;
 use super::controls;
+use super::brush_presets::*;
+use super::super::standard_tools::EraserMode;
 
 use ui::*;
 use canvas::*;
@@ -16,33 +18,49 @@ use std::sync::*;
 pub struct EraserMenuController {
     size:               Binding<f32>,
     opacity:            Binding<f32>,
+    mode:               Binding<EraserMode>,
 
     canvases:           Arc<ResourceManager<BindingCanvas>>,
     ui:                 BindRef<Control>,
     view_model:         Arc<DynamicViewModel>,
+
+    presets_open:       Binding<bool>,
+    preset_picker:      Arc<PopupController<BrushPresetController>>
 }
 
 impl EraserMenuController {
     ///
     /// Creates a new eraser menu controller
     /// 
-    pub fn new(size: &Binding<f32>, opacity: &Binding<f32>) -> EraserMenuController {
+    pub fn new<SelectPresetFn, SavePresetFn>(size: &Binding<f32>, opacity: &Binding<f32>, mode: &Binding<EraserMode>, brush_definition: &BindRef<BrushDefinition>, presets: &BindRef<Vec<BrushPreset>>, on_select_preset: SelectPresetFn, on_save_preset: SavePresetFn) -> EraserMenuController
+    where SelectPresetFn: 'static+Fn(&BrushPreset)+Send+Sync, SavePresetFn: 'static+Fn(String)+Send+Sync {
         // Set up the view model
         let view_model = Arc::new(DynamicViewModel::new());
 
         let vm_size     = size.clone();
         let vm_opacity  = opacity.clone();
+        let vm_vector   = mode.clone();
+        let vm_paint    = mode.clone();
 
         view_model.set_computed("Size", move || PropertyValue::Float(vm_size.get() as f64));
         view_model.set_computed("Opacity", move || PropertyValue::Float(vm_opacity.get() as f64));
+        view_model.set_computed("VectorMode", move || PropertyValue::Bool(vm_vector.get() == EraserMode::Vector));
+        view_model.set_computed("PaintMode", move || PropertyValue::Bool(vm_paint.get() == EraserMode::Paint));
 
         view_model.set_property("EditSize", PropertyValue::Bool(false));
         view_model.set_property("EditOpacity", PropertyValue::Bool(false));
 
+        // Create the preset picker popup (the eraser only shows the erasing presets)
+        let presets_open    = Binding::new(false);
+        let preset_picker   = BrushPresetController::popup(presets, BrushDrawingStyle::Erase, &presets_open, on_select_preset, on_save_preset);
+
+        let vm_presets_open = presets_open.clone();
+        view_model.set_computed("PresetsOpen", move || PropertyValue::Bool(vm_presets_open.get()));
+
         // Create the canvases
         let canvases = Arc::new(ResourceManager::new());
 
-        let brush_preview           = Self::eraser_preview(size, opacity);
+        let brush_preview           = Self::eraser_preview(size, opacity, brush_definition);
         let brush_preview           = canvases.register(brush_preview);
         canvases.assign_name(&brush_preview, "BrushPreview");
 
@@ -80,6 +98,27 @@ impl EraserMenuController {
                     Control::canvas()
                         .with(brush_preview)
                         .with(Bounds::next_horiz(64.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::button()
+                        .with(vec![Control::label().with("Presets").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(State::Badged(Property::Bind("PresetsOpen".to_string())))
+                        .with(Bounds::next_horiz(56.0))
+                        .with((ActionTrigger::Click, "ShowPresets"))
+                        .with_controller("PresetPopup"),
+
+                    controls::divider(),
+
+                    Control::button()
+                        .with(vec![Control::label().with("Cut").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(State::Selected(Property::Bind("VectorMode".to_string())))
+                        .with(Bounds::next_horiz(40.0))
+                        .with((ActionTrigger::Click, "SetVectorMode")),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::button()
+                        .with(vec![Control::label().with("Paint").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(State::Selected(Property::Bind("PaintMode".to_string())))
+                        .with(Bounds::next_horiz(40.0))
+                        .with((ActionTrigger::Click, "SetPaintMode")),
 
                     controls::divider(),
 
@@ -153,19 +192,24 @@ impl EraserMenuController {
         EraserMenuController {
             size:               size.clone(),
             opacity:            opacity.clone(),
+            mode:               mode.clone(),
 
             canvases:           canvases, 
             ui:                 ui,
             view_model:         view_model,
+
+            presets_open:       presets_open,
+            preset_picker:      Arc::new(preset_picker)
         }
     }
 
     ///
     /// Creates the erasaer preview canvas
     /// 
-    pub fn eraser_preview(size: &Binding<f32>, opacity: &Binding<f32>) -> BindingCanvas {
-        let size    = size.clone();
-        let opacity = opacity.clone();
+    pub fn eraser_preview(size: &Binding<f32>, opacity: &Binding<f32>, brush_definition: &BindRef<BrushDefinition>) -> BindingCanvas {
+        let size                = size.clone();
+        let opacity             = opacity.clone();
+        let brush_definition    = brush_definition.clone();
 
         let control_height  = 32.0 - 6.0;
         let control_width   = 64.0;
@@ -188,8 +232,8 @@ impl EraserMenuController {
             gc.rect(-control_width/2.0, -control_height/2.0, control_width/2.0, control_height/2.0);
             gc.fill();
 
-            // Create an ink brush
-            let brush = InkBrush::new(&InkDefinition::default(), BrushDrawingStyle::Erase);
+            // Create the brush that the eraser uses
+            let brush = create_brush_from_definition(&brush_definition.get(), BrushDrawingStyle::Erase);
 
             // Render a test brush stroke
             let mut points = vec![];
@@ -208,7 +252,8 @@ impl EraserMenuController {
             let brush_properties = BrushProperties {
                 size:       size.get(),
                 opacity:    opacity.get(),
-                color:      Color::Rgba(0.0, 0.0, 0.0, 1.0)
+                color:      Color::Rgba(0.0, 0.0, 0.0, 1.0),
+                gradient:   None
             };
 
             let points = brush.brush_points_for_raw_points(&points);
@@ -228,6 +273,13 @@ impl Controller for EraserMenuController {
         Some(self.view_model.clone())
     }
 
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        match id {
+            "PresetPopup"   => Some(self.preset_picker.clone()),
+            _               => None
+        }
+    }
+
     fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> { 
         Some(self.canvases.clone())
     }
@@ -258,6 +310,21 @@ impl Controller for EraserMenuController {
                 // User has dragged the 'opacity' property
                 self.opacity.clone().set(new_opacity as f32);
                 self.view_model.set_property("EditOpacity", PropertyValue::Bool(false));
+            },
+
+            ("ShowPresets", _) => {
+                // User has clicked the presets button
+                self.presets_open.clone().set(true)
+            },
+
+            ("SetVectorMode", _) => {
+                // Eraser cuts through brush strokes
+                self.mode.clone().set(EraserMode::Vector);
+            },
+
+            ("SetPaintMode", _) => {
+                // Eraser paints over the drawing
+                self.mode.clone().set(EraserMode::Paint);
             }
 
             _ => ()
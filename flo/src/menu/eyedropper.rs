@@ -0,0 +1,79 @@
+use super::controls;
+
+use ui::*;
+use binding::*;
+
+use std::sync::*;
+
+///
+/// The menu controller for the eyedropper tool
+///
+pub struct EyedropperMenuController {
+    pick_brush: Binding<bool>,
+
+    ui:         BindRef<Control>,
+    view_model: Arc<DynamicViewModel>
+}
+
+impl EyedropperMenuController {
+    ///
+    /// Creates a new eyedropper menu controller
+    ///
+    pub fn new(pick_brush: &Binding<bool>) -> EyedropperMenuController {
+        // Set up the view model
+        let view_model      = Arc::new(DynamicViewModel::new());
+
+        let vm_pick_brush   = pick_brush.clone();
+        view_model.set_computed("PickBrush", move || PropertyValue::Bool(vm_pick_brush.get()));
+
+        // Generate the UI
+        let ui = BindRef::from(bind(Control::container()
+                .with(Bounds::fill_all())
+                .with(ControlAttribute::Padding((0, 3), (0, 3)))
+                .with(vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Eyedropper:")
+                        .with(FontWeight::Light)
+                        .with(TextAlign::Right)
+                        .with(Font::Size(14.0))
+                        .with(Bounds::next_horiz(80.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(8.0)),
+
+                    Control::button()
+                        .with(vec![Control::label().with("Pick brush").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(State::Selected(Property::Bind("PickBrush".to_string())))
+                        .with(Bounds::next_horiz(72.0))
+                        .with((ActionTrigger::Click, "TogglePickBrush")),
+
+                    controls::divider()
+                ])));
+
+        EyedropperMenuController {
+            pick_brush: pick_brush.clone(),
+
+            ui:         ui,
+            view_model: view_model
+        }
+    }
+}
+
+impl Controller for EyedropperMenuController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        if action_id == "TogglePickBrush" {
+            // Toggle whether or not the brush definition is picked up along with the colour and size
+            let pick_brush = self.pick_brush.get();
+            self.pick_brush.clone().set(!pick_brush);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use super::controls;
+use super::super::model::*;
+
+use ui::*;
+use binding::*;
+
+use std::sync::*;
+
+/// The smallest spacing that the grid can be set to
+const MIN_GRID_SPACING: f32 = 5.0;
+
+/// The largest spacing that the grid can be set to
+const MAX_GRID_SPACING: f32 = 200.0;
+
+///
+/// The menu controller for the rulers, grid and snapping settings
+///
+pub struct GuidesMenuController {
+    guides:     GuidesModel,
+
+    ui:         BindRef<Control>,
+    view_model: Arc<DynamicViewModel>
+}
+
+impl GuidesMenuController {
+    ///
+    /// Creates a new guides menu controller
+    ///
+    pub fn new(guides: &GuidesModel) -> GuidesMenuController {
+        // Set up the view model
+        let view_model = Arc::new(DynamicViewModel::new());
+
+        let vm_rulers           = guides.show_rulers.clone();
+        let vm_grid             = guides.show_grid.clone();
+        let vm_spacing          = guides.grid_spacing.clone();
+        let vm_snap_grid        = guides.snap_to_grid.clone();
+        let vm_snap_elements    = guides.snap_to_elements.clone();
+        let vm_snap_center      = guides.snap_to_center.clone();
+
+        view_model.set_computed("ShowRulers", move || PropertyValue::Bool(vm_rulers.get()));
+        view_model.set_computed("ShowGrid", move || PropertyValue::Bool(vm_grid.get()));
+        view_model.set_computed("GridSpacing", move || PropertyValue::String(format!("{}", vm_spacing.get())));
+        view_model.set_computed("SnapToGrid", move || PropertyValue::Bool(vm_snap_grid.get()));
+        view_model.set_computed("SnapToElements", move || PropertyValue::Bool(vm_snap_elements.get()));
+        view_model.set_computed("SnapToCenter", move || PropertyValue::Bool(vm_snap_center.get()));
+
+        let ui = Self::ui();
+
+        GuidesMenuController {
+            guides:     guides.clone(),
+
+            ui:         ui,
+            view_model: view_model
+        }
+    }
+
+    ///
+    /// Creates a button that toggles one of the settings
+    ///
+    fn toggle_button(label: &str, width: f32, property: &str, action: &str) -> Control {
+        Control::button()
+            .with(vec![Control::label().with(label).with(TextAlign::Center).with(Bounds::fill_all())])
+            .with(State::Selected(Property::Bind(property.to_string())))
+            .with(Bounds::next_horiz(width))
+            .with((ActionTrigger::Click, action))
+    }
+
+    ///
+    /// Creates the UI for the guides menu controller
+    ///
+    fn ui() -> BindRef<Control> {
+        let ui = bind(Control::container()
+                    .with(Bounds::fill_all())
+                    .with(ControlAttribute::Padding((0, 3), (0, 3)))
+                    .with(vec![
+                        controls::divider(),
+
+                        Self::toggle_button("Rulers", 48.0, "ShowRulers", "ToggleRulers"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::toggle_button("Grid", 40.0, "ShowGrid", "ToggleGrid"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+
+                        Control::button()
+                            .with(vec![Control::label().with("-").with(TextAlign::Center).with(Bounds::fill_all())])
+                            .with(Bounds::next_horiz(20.0))
+                            .with((ActionTrigger::Click, "ShrinkGrid")),
+                        Control::label()
+                            .with(ControlAttribute::Text(Property::Bind("GridSpacing".to_string())))
+                            .with(TextAlign::Center)
+                            .with(Bounds::next_horiz(32.0)),
+                        Control::button()
+                            .with(vec![Control::label().with("+").with(TextAlign::Center).with(Bounds::fill_all())])
+                            .with(Bounds::next_horiz(20.0))
+                            .with((ActionTrigger::Click, "GrowGrid")),
+
+                        controls::divider(),
+
+                        Control::label()
+                            .with("Snap:")
+                            .with(FontWeight::Light)
+                            .with(TextAlign::Right)
+                            .with(Font::Size(14.0))
+                            .with(Bounds::next_horiz(40.0)),
+                        Control::empty()
+                            .with(Bounds::next_horiz(8.0)),
+
+                        Self::toggle_button("Grid", 40.0, "SnapToGrid", "ToggleSnapToGrid"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::toggle_button("Objects", 56.0, "SnapToElements", "ToggleSnapToElements"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::toggle_button("Centre", 48.0, "SnapToCenter", "ToggleSnapToCenter"),
+
+                        controls::divider()
+                    ])
+            );
+
+        BindRef::from(ui)
+    }
+
+    ///
+    /// Flips the value of a boolean setting
+    ///
+    fn toggle(setting: &Binding<bool>) {
+        let value = setting.get();
+        setting.clone().set(!value);
+    }
+}
+
+impl Controller for GuidesMenuController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        match action_id {
+            "ToggleRulers"          => Self::toggle(&self.guides.show_rulers),
+            "ToggleGrid"            => Self::toggle(&self.guides.show_grid),
+
+            "ShrinkGrid"            => {
+                let spacing = self.guides.grid_spacing.get();
+                self.guides.grid_spacing.clone().set(f32::max(spacing / 2.0, MIN_GRID_SPACING));
+            },
+
+            "GrowGrid"              => {
+                let spacing = self.guides.grid_spacing.get();
+                self.guides.grid_spacing.clone().set(f32::min(spacing * 2.0, MAX_GRID_SPACING));
+            },
+
+            "ToggleSnapToGrid"      => Self::toggle(&self.guides.snap_to_grid),
+            "ToggleSnapToElements"  => Self::toggle(&self.guides.snap_to_elements),
+            "ToggleSnapToCenter"    => Self::toggle(&self.guides.snap_to_center),
+
+            _ => ()
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use super::controls;
+use super::brush_presets::*;
 use super::super::color::*;
+use super::super::model::*;
+use super::super::standard_tools::InkModel;
 
 use ui::*;
 use canvas::*;
@@ -10,26 +13,83 @@ use animation::brushes::*;
 use std::f32;
 use std::sync::*;
 
+/// The velocity width to use when the user turns on speed-sensitive brush widths
+const DEFAULT_VELOCITY_WIDTH: f32 = 20.0;
+
+/// The size of the pressure curve editor canvas
+const CURVE_EDITOR_SIZE: f32 = 100.0;
+
+/// The width of the strip of palette swatches and recent colours
+const SWATCH_STRIP_WIDTH: f32 = 240.0;
+
+/// How close the user needs to click to an existing control point to move it rather than add a new one
+const CURVE_POINT_RADIUS: f32 = 0.08;
+
+/// The thinnest nib (as a proportion of its width) that can be picked in the nib settings
+const MIN_NIB_RATIO: f32 = 0.02;
+
 ///
 /// Controller used for the ink tool
 /// 
 pub struct InkMenuController {
     size:               Binding<f32>,
     opacity:            Binding<f32>,
+    pressure_curve:     Binding<PressureCurve>,
+    velocity_width:     Binding<Option<f32>>,
+    calligraphy:        Binding<bool>,
+    calligraphy_defn:   Binding<CalligraphyDefinition>,
+    gradient_type:      Binding<Option<BrushGradientType>>,
+
+    /// The pressure curve control point that is currently being dragged in the editor
+    editing_point:      Mutex<Option<(f32, f32)>>,
 
     canvases:           Arc<ResourceManager<BindingCanvas>>,
     ui:                 BindRef<Control>,
     view_model:         Arc<DynamicViewModel>,
 
     color_picker_open:  Binding<bool>,
-    color_picker:       Arc<PopupController<ColorPickerController>>
+    color_picker:       Arc<PopupController<ColorPickerController>>,
+
+    gradient_open:      Binding<bool>,
+    gradient_picker:    Arc<PopupController<ColorPickerController>>,
+
+    presets_open:       Binding<bool>,
+    preset_picker:      Arc<PopupController<BrushPresetController>>,
+
+    swatch_strip:       Arc<SwatchStripController>
 }
 
 impl InkMenuController {
     ///
     /// Creates a new ink menu controller
     /// 
-    pub fn new(size: &Binding<f32>, opacity: &Binding<f32>, colour: &Binding<Color>) -> InkMenuController {
+    pub fn new<Anim: Animation+'static>(ink_model: &InkModel, flo_model: &FloModel<Anim>) -> InkMenuController {
+        // The settings edited by this menu
+        let size                = &ink_model.size;
+        let opacity             = &ink_model.opacity;
+        let colour              = &ink_model.color;
+        let gradient_type       = &ink_model.gradient_type;
+        let gradient_color      = &ink_model.gradient_color;
+        let pressure_curve      = &ink_model.pressure_curve;
+        let velocity_width      = &ink_model.velocity_width;
+        let calligraphy         = &ink_model.calligraphy;
+        let calligraphy_defn    = &ink_model.calligraphy_definition;
+        let brush_definition    = &ink_model.brush_definition;
+
+        // The presets and the palette are stored in the animation
+        let presets             = &flo_model.brush_presets;
+        let palette             = &flo_model.palette;
+        let recent_colors       = &flo_model.recent_colors;
+
+        let preset_model        = ink_model.clone();
+        let save_model          = ink_model.clone();
+        let swatch_model        = ink_model.clone();
+        let remove_model        = ink_model.clone();
+        let on_select_preset    = move |preset: &BrushPreset| preset_model.apply_preset(preset);
+        let on_save_preset      = move |name| save_model.save_preset(name, BrushDrawingStyle::Draw);
+        let on_add_swatch       = move |swatch| swatch_model.add_swatch(swatch);
+        let on_remove_swatch    = move |name| remove_model.remove_swatch(name);
+
         // Set up the view model
         let view_model = Arc::new(DynamicViewModel::new());
 
@@ -41,21 +101,61 @@ impl InkMenuController {
 
         view_model.set_property("EditSize", PropertyValue::Bool(false));
         view_model.set_property("EditOpacity", PropertyValue::Bool(false));
+        view_model.set_property("EditPressureCurve", PropertyValue::Bool(false));
+
+        let vm_velocity_width = velocity_width.clone();
+        view_model.set_computed("VelocityWidth", move || PropertyValue::Bool(vm_velocity_width.get().is_some()));
+
+        let vm_calligraphy = calligraphy.clone();
+        view_model.set_computed("Calligraphy", move || PropertyValue::Bool(vm_calligraphy.get()));
+
+        // The nib angle is shown in degrees, and the ratio is the thickness of the nib relative to its width
+        let vm_calligraphy_defn = calligraphy_defn.clone();
+        view_model.set_computed("NibAngle", move || PropertyValue::Float(vm_calligraphy_defn.get().nib_angle.to_degrees() as f64));
+        let vm_calligraphy_defn = calligraphy_defn.clone();
+        view_model.set_computed("NibRatio", move || {
+            let defn = vm_calligraphy_defn.get();
+            PropertyValue::Float((defn.nib_thickness / defn.nib_width) as f64)
+        });
+        view_model.set_property("EditNib", PropertyValue::Bool(false));
 
         // Create the colour picker popup
         let color_picker_open   = Binding::new(false);
         let color_picker        = ColorPickerController::new(colour);
         let color_picker        = PopupController::new(color_picker, &color_picker_open)
             .with_direction(&PopupDirection::Below)
-            .with_size(&(500, 124));
+            .with_size(&(500, 264));
 
         let vm_color_picker_open = color_picker_open.clone();
         view_model.set_computed("ColorPickerOpen", move || PropertyValue::Bool(vm_color_picker_open.get()));
 
+        // Create the popup for the colour at the end of the gradient
+        let gradient_picker_open    = Binding::new(false);
+        let gradient_picker         = ColorPickerController::new(gradient_color);
+        let gradient_picker         = PopupController::new(gradient_picker, &gradient_picker_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(500, 264));
+
+        let vm_gradient_picker_open = gradient_picker_open.clone();
+        view_model.set_computed("GradientPickerOpen", move || PropertyValue::Bool(vm_gradient_picker_open.get()));
+
+        let vm_gradient_type = gradient_type.clone();
+        view_model.set_computed("GradientType", move || PropertyValue::String(Self::gradient_name(vm_gradient_type.get()).to_string()));
+
+        // Create the preset picker popup
+        let presets_open        = Binding::new(false);
+        let preset_picker       = BrushPresetController::popup(presets, BrushDrawingStyle::Draw, &presets_open, on_select_preset, on_save_preset);
+
+        let vm_presets_open = presets_open.clone();
+        view_model.set_computed("PresetsOpen", move || PropertyValue::Bool(vm_presets_open.get()));
+
+        // Create the strip of palette swatches and recent colours
+        let swatch_strip        = SwatchStripController::new(colour, palette, recent_colors, on_add_swatch, on_remove_swatch);
+
         // Create the canvases
         let canvases = Arc::new(ResourceManager::new());
 
-        let brush_preview           = Self::brush_preview(size, opacity, colour);
+        let brush_preview           = Self::brush_preview(size, opacity, colour, gradient_type, gradient_color, brush_definition, BrushDrawingStyle::Draw);
         let brush_preview           = canvases.register(brush_preview);
         canvases.assign_name(&brush_preview, "BrushPreview");
 
@@ -79,6 +179,22 @@ impl InkMenuController {
         let colour_preview          = canvases.register(colour_preview);
         canvases.assign_name(&colour_preview, "ColourPreview");
 
+        let gradient_preview        = Self::colour_preview(gradient_color);
+        let gradient_preview        = canvases.register(gradient_preview);
+        canvases.assign_name(&gradient_preview, "GradientPreview");
+
+        let curve_preview           = Self::pressure_curve_preview(pressure_curve, 32.0-6.0, false);
+        let curve_preview           = canvases.register(curve_preview);
+        canvases.assign_name(&curve_preview, "PressureCurvePreview");
+
+        let curve_editor            = Self::pressure_curve_preview(pressure_curve, CURVE_EDITOR_SIZE, true);
+        let curve_editor            = canvases.register(curve_editor);
+        canvases.assign_name(&curve_editor, "PressureCurveEditor");
+
+        let nib_preview             = Self::nib_preview(calligraphy_defn, 32.0-6.0);
+        let nib_preview             = canvases.register(nib_preview);
+        canvases.assign_name(&nib_preview, "NibPreview");
+
         // Generate the UI
         let ui = BindRef::from(bind(Control::container()
                 .with(Bounds::fill_all())
@@ -97,6 +213,55 @@ impl InkMenuController {
                     Control::canvas()
                         .with(brush_preview)
                         .with(Bounds::next_horiz(64.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::button()
+                        .with(vec![Control::label().with("Nib").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(State::Selected(Property::Bind("Calligraphy".to_string())))
+                        .with(Bounds::next_horiz(40.0))
+                        .with((ActionTrigger::Click, "ToggleCalligraphy")),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(nib_preview)
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("EditNib".to_string())))
+                        .with((ActionTrigger::Click, "ShowNibSettings"))
+                        .with(vec![
+                            Control::popup()
+                                .with(Popup::IsOpen(Property::Bind("EditNib".to_string())))
+                                .with(Popup::Direction(PopupDirection::Below))
+                                .with(Popup::Size(140, 104))
+                                .with(Popup::Offset(14))
+                                .with(ControlAttribute::ZIndex(1000))
+                                .with(ControlAttribute::Padding((8, 8), (8, 8)))
+                                .with((ActionTrigger::Dismiss, "HideNibSettings"))
+                                .with(vec![
+                                    Control::label()
+                                        .with("Angle:")
+                                        .with(Bounds::next_vert(20.0)),
+                                    Control::slider()
+                                        .with(State::Range((0.0.to_property(), 180.0.to_property())))
+                                        .with(State::Value(Property::Bind("NibAngle".to_string())))
+                                        .with(Bounds::next_vert(24.0))
+                                        .with((ActionTrigger::EditValue, "ChangeNibAngle".to_string()))
+                                        .with((ActionTrigger::SetValue, "ChangeNibAngle".to_string())),
+                                    Control::label()
+                                        .with("Thickness:")
+                                        .with(Bounds::next_vert(20.0)),
+                                    Control::slider()
+                                        .with(State::Range((MIN_NIB_RATIO.to_property(), 1.0.to_property())))
+                                        .with(State::Value(Property::Bind("NibRatio".to_string())))
+                                        .with(Bounds::next_vert(24.0))
+                                        .with((ActionTrigger::EditValue, "ChangeNibRatio".to_string()))
+                                        .with((ActionTrigger::SetValue, "ChangeNibRatio".to_string()))
+                                ])
+                        ]),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::button()
+                        .with(vec![Control::label().with("Presets").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(State::Badged(Property::Bind("PresetsOpen".to_string())))
+                        .with(Bounds::next_horiz(56.0))
+                        .with((ActionTrigger::Click, "ShowPresets"))
+                        .with_controller("PresetPopup"),
 
                     controls::divider(),
 
@@ -111,6 +276,21 @@ impl InkMenuController {
                         .with(State::Badged(Property::Bind("ColorPickerOpen".to_string())))
                         .with((ActionTrigger::Click, "ShowColorPopup"))
                         .with_controller("ColorPopup"),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::button()
+                        .with(vec![Control::label().with(ControlAttribute::Text(Property::Bind("GradientType".to_string()))).with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(Bounds::next_horiz(48.0))
+                        .with((ActionTrigger::Click, "NextGradientType")),
+                    Control::canvas()
+                        .with(gradient_preview)
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("GradientPickerOpen".to_string())))
+                        .with((ActionTrigger::Click, "ShowGradientColorPopup"))
+                        .with_controller("GradientColorPopup"),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::container()
+                        .with(Bounds::next_horiz(SWATCH_STRIP_WIDTH))
+                        .with_controller("SwatchStrip"),
 
                     controls::divider(),
 
@@ -175,6 +355,50 @@ impl InkMenuController {
                                 ])
                         ]),
 
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Pressure:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(60.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::canvas()
+                        .with(curve_preview)
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("EditPressureCurve".to_string())))
+                        .with((ActionTrigger::Click, "ShowPressureCurve"))
+                        .with(vec![
+                            Control::popup()
+                                .with(Popup::IsOpen(Property::Bind("EditPressureCurve".to_string())))
+                                .with(Popup::Direction(PopupDirection::Below))
+                                .with(Popup::Size(116, 144))
+                                .with(Popup::Offset(14))
+                                .with(ControlAttribute::ZIndex(1000))
+                                .with(ControlAttribute::Padding((8, 8), (8, 8)))
+                                .with((ActionTrigger::Dismiss, "HidePressureCurve"))
+                                .with(vec![
+                                    Control::canvas()
+                                        .with(Bounds::next_vert(CURVE_EDITOR_SIZE))
+                                        .with(curve_editor)
+                                        .with((
+                                            (ActionTrigger::Paint(PaintDevice::Pen),                        "EditPressureCurve"),
+                                            (ActionTrigger::Paint(PaintDevice::Touch),                      "EditPressureCurve"),
+                                            (ActionTrigger::Paint(PaintDevice::Mouse(MouseButton::Left)),   "EditPressureCurve")
+                                        )),
+                                    Control::empty().with(Bounds::next_vert(4.0)),
+                                    Control::button()
+                                        .with(vec![Control::label().with("Linear").with(TextAlign::Center).with(Bounds::fill_all())])
+                                        .with(Bounds::next_vert(22.0))
+                                        .with((ActionTrigger::Click, "LinearPressureCurve"))
+                                ])
+                        ]),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::button()
+                        .with(vec![Control::label().with("Speed").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(State::Selected(Property::Bind("VelocityWidth".to_string())))
+                        .with(Bounds::next_horiz(48.0))
+                        .with((ActionTrigger::Click, "ToggleVelocityWidth")),
+
                     Control::empty()
                         .with(Bounds::next_horiz(16.0)),
                     controls::divider()
@@ -184,13 +408,50 @@ impl InkMenuController {
         InkMenuController {
             size:               size.clone(),
             opacity:            opacity.clone(),
+            pressure_curve:     pressure_curve.clone(),
+            velocity_width:     velocity_width.clone(),
+            calligraphy:        calligraphy.clone(),
+            calligraphy_defn:   calligraphy_defn.clone(),
+            gradient_type:      gradient_type.clone(),
+
+            editing_point:      Mutex::new(None),
 
             canvases:           canvases, 
             ui:                 ui,
             view_model:         view_model,
 
             color_picker_open:  color_picker_open,
-            color_picker:       Arc::new(color_picker)
+            color_picker:       Arc::new(color_picker),
+
+            gradient_open:      gradient_picker_open,
+            gradient_picker:    Arc::new(gradient_picker),
+
+            presets_open:       presets_open,
+            preset_picker:      Arc::new(preset_picker),
+
+            swatch_strip:       Arc::new(swatch_strip)
+        }
+    }
+
+    ///
+    /// The label used for a gradient type
+    /// 
+    fn gradient_name(gradient_type: Option<BrushGradientType>) -> &'static str {
+        match gradient_type {
+            None                                => "Solid",
+            Some(BrushGradientType::Linear)     => "Linear",
+            Some(BrushGradientType::Radial)     => "Radial"
+        }
+    }
+
+    ///
+    /// The gradient type that follows another one (cycles between no gradient, a linear gradient and a radial gradient)
+    /// 
+    fn next_gradient_type(gradient_type: Option<BrushGradientType>) -> Option<BrushGradientType> {
+        match gradient_type {
+            None                                => Some(BrushGradientType::Linear),
+            Some(BrushGradientType::Linear)     => Some(BrushGradientType::Radial),
+            Some(BrushGradientType::Radial)     => None
         }
     }
 
@@ -255,13 +516,98 @@ impl InkMenuController {
         })
     }
 
+    ///
+    /// Creates a canvas showing the pressure curve (with its control points if it's being used as an editor)
+    /// 
+    pub fn pressure_curve_preview(pressure_curve: &Binding<PressureCurve>, control_height: f32, show_points: bool) -> BindingCanvas {
+        let pressure_curve  = pressure_curve.clone();
+
+        BindingCanvas::with_drawing(move |gc| {
+            let pressure_curve  = pressure_curve.get();
+            let size            = if show_points { control_height } else { control_height - 8.0 };
+            let border          = (control_height - size)/2.0;
+
+            // The curve is drawn in a square region from 0-size
+            gc.canvas_height(control_height);
+            gc.center_region(-border, -border, size+border, size+border);
+
+            gc.fill_color(Color::Rgba(0.2, 0.2, 0.2, 1.0));
+            gc.new_path();
+            gc.rect(0.0, 0.0, size, size);
+            gc.fill();
+
+            // Linear response for reference
+            gc.line_width_pixels(1.0);
+            gc.stroke_color(Color::Rgba(0.5, 0.5, 0.5, 1.0));
+            gc.new_path();
+            gc.move_to(0.0, 0.0);
+            gc.line_to(size, size);
+            gc.stroke();
+
+            // The curve itself
+            let control_points = pressure_curve.control_points();
+
+            gc.line_width_pixels(2.0);
+            gc.stroke_color(Color::Rgba(0.8, 0.8, 0.8, 1.0));
+            gc.new_path();
+            gc.move_to(control_points[0].0*size, control_points[0].1*size);
+            for &(input, output) in control_points.iter().skip(1) {
+                gc.line_to(input*size, output*size);
+            }
+            gc.stroke();
+
+            // The user-editable control points
+            if show_points {
+                gc.fill_color(Color::Rgba(0.6, 0.8, 1.0, 1.0));
+
+                for &(input, output) in pressure_curve.points.iter() {
+                    gc.new_path();
+                    gc.circle(input*size, output*size, 3.0);
+                    gc.fill();
+                }
+            }
+        })
+    }
+
+    ///
+    /// Creates a canvas showing the shape and angle of the calligraphy nib
+    /// 
+    pub fn nib_preview(calligraphy_defn: &Binding<CalligraphyDefinition>, control_height: f32) -> BindingCanvas {
+        let calligraphy_defn = calligraphy_defn.clone();
+
+        BindingCanvas::with_drawing(move |gc| {
+            let calligraphy_defn    = calligraphy_defn.get();
+            let width               = control_height - 8.0;
+            let thickness           = (width * calligraphy_defn.nib_thickness / calligraphy_defn.nib_width).max(1.0);
+
+            // Corners of the nib, turned to the nib angle
+            let (sin, cos)          = calligraphy_defn.nib_angle.sin_cos();
+            let along               = (cos*width/2.0, sin*width/2.0);
+            let across              = (-sin*thickness/2.0, cos*thickness/2.0);
+
+            gc.canvas_height(control_height);
+            gc.fill_color(Color::Rgba(0.8, 0.8, 0.8, 1.0));
+
+            gc.new_path();
+            gc.move_to(along.0+across.0, along.1+across.1);
+            gc.line_to(-along.0+across.0, -along.1+across.1);
+            gc.line_to(-along.0-across.0, -along.1-across.1);
+            gc.line_to(along.0-across.0, along.1-across.1);
+            gc.close_path();
+            gc.fill();
+        })
+    }
+
     ///
     /// Creates the brush preview canvas
     /// 
-    pub fn brush_preview(size: &Binding<f32>, opacity: &Binding<f32>, color: &Binding<Color>) -> BindingCanvas {
-        let size    = size.clone();
-        let opacity = opacity.clone();
-        let color   = color.clone();
+    pub fn brush_preview(size: &Binding<f32>, opacity: &Binding<f32>, color: &Binding<Color>, gradient_type: &Binding<Option<BrushGradientType>>, gradient_color: &Binding<Color>, brush_definition: &BindRef<BrushDefinition>, drawing_style: BrushDrawingStyle) -> BindingCanvas {
+        let size                = size.clone();
+        let opacity             = opacity.clone();
+        let color               = color.clone();
+        let gradient_type       = gradient_type.clone();
+        let gradient_color      = gradient_color.clone();
+        let brush_definition    = brush_definition.clone();
 
         let control_height  = 32.0 - 6.0;
         let control_width   = 64.0;
@@ -278,8 +624,8 @@ impl InkMenuController {
             gc.rect(-control_width/2.0, -control_height/2.0, control_width/2.0, control_height/2.0);
             gc.fill();
 
-            // Create an ink brush
-            let brush = InkBrush::new(&InkDefinition::default(), BrushDrawingStyle::Draw);
+            // Create the brush
+            let brush = create_brush_from_definition(&brush_definition.get(), drawing_style);
 
             // Render a test brush stroke
             let mut points = vec![];
@@ -298,7 +644,8 @@ impl InkMenuController {
             let brush_properties = BrushProperties {
                 size:       size.get(),
                 opacity:    opacity.get(),
-                color:      color.get()
+                color:      color.get(),
+                gradient:   gradient_type.get().map(|gradient_type| BrushGradient { gradient_type: gradient_type, end_color: gradient_color.get() })
             };
 
             let points = brush.brush_points_for_raw_points(&points);
@@ -307,6 +654,57 @@ impl InkMenuController {
             gc.draw_list(brush.render_brush(&brush_properties, &points));
         })
     }
+
+    ///
+    /// Updates the pressure curve after the user paints on the curve editor
+    /// 
+    fn edit_pressure_curve(&self, painting: &Painting) {
+        let mut editing_point   = self.editing_point.lock().unwrap();
+        let mut points          = self.pressure_curve.get().points;
+
+        // Convert the location to a point on the curve
+        let (x, y)              = painting.location;
+        let location            = ((x/CURVE_EDITOR_SIZE).max(0.0).min(1.0), (y/CURVE_EDITOR_SIZE).max(0.0).min(1.0));
+
+        match painting.action {
+            PaintAction::Start => {
+                // Pick up the nearest point if there's one close enough, otherwise add a new point
+                let distance    = |&(input, output): &(f32, f32)| ((input-location.0).powi(2) + (output-location.1).powi(2)).sqrt();
+                let nearest     = points.iter()
+                    .cloned()
+                    .filter(|point| distance(point) <= CURVE_POINT_RADIUS)
+                    .min_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap_or(::std::cmp::Ordering::Equal));
+
+                if let Some(nearest) = nearest {
+                    *editing_point = Some(nearest);
+                } else {
+                    points.push(location);
+                    *editing_point = Some(location);
+                }
+            },
+
+            PaintAction::Continue | PaintAction::Finish => {
+                // Move the point that's being edited to the new location
+                if let Some(old_location) = *editing_point {
+                    if let Some(index) = points.iter().position(|point| point == &old_location) {
+                        points[index] = location;
+                    }
+
+                    *editing_point = Some(location);
+                }
+
+                if painting.action == PaintAction::Finish {
+                    *editing_point = None;
+                }
+            },
+
+            PaintAction::Cancel => {
+                *editing_point = None;
+            }
+        }
+
+        self.pressure_curve.clone().set(PressureCurve::new(points));
+    }
 }
 
 impl Controller for InkMenuController {
@@ -321,6 +719,9 @@ impl Controller for InkMenuController {
     fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
         match id {
             "ColorPopup"        => Some(self.color_picker.clone()),
+            "GradientColorPopup" => Some(self.gradient_picker.clone()),
+            "PresetPopup"       => Some(self.preset_picker.clone()),
+            "SwatchStrip"       => Some(self.swatch_strip.clone()),
             _                   => None
         }
     }
@@ -360,6 +761,70 @@ impl Controller for InkMenuController {
             ("ShowColorPopup", _) => {
                 // User has clicked the colour icon
                 self.color_picker_open.clone().set(true)
+            },
+
+            ("ShowGradientColorPopup", _) => {
+                // User has clicked the gradient end colour icon
+                self.gradient_open.clone().set(true)
+            },
+
+            ("NextGradientType", _) => {
+                let next_type = Self::next_gradient_type(self.gradient_type.get());
+                self.gradient_type.clone().set(next_type);
+            },
+
+            ("ShowPresets", _) => {
+                // User has clicked the presets button
+                self.presets_open.clone().set(true)
+            },
+
+            ("ShowPressureCurve", _) => {
+                self.view_model.set_property("EditPressureCurve", PropertyValue::Bool(true));
+            },
+
+            ("HidePressureCurve", _) => {
+                self.view_model.set_property("EditPressureCurve", PropertyValue::Bool(false));
+            },
+
+            ("EditPressureCurve", &Paint(_, ref painting)) => {
+                // User is dragging control points in the pressure curve editor
+                painting.iter().for_each(|painting| self.edit_pressure_curve(painting));
+            },
+
+            ("LinearPressureCurve", _) => {
+                self.pressure_curve.clone().set(PressureCurve::linear());
+            },
+
+            ("ToggleCalligraphy", _) => {
+                // Switch between the round ink brush and the flat calligraphy nib
+                let calligraphy = self.calligraphy.get();
+                self.calligraphy.clone().set(!calligraphy);
+            },
+
+            ("ShowNibSettings", _) => {
+                self.view_model.set_property("EditNib", PropertyValue::Bool(true));
+            },
+
+            ("HideNibSettings", _) => {
+                self.view_model.set_property("EditNib", PropertyValue::Bool(false));
+            },
+
+            ("ChangeNibAngle", &Value(PropertyValue::Float(new_angle))) => {
+                let mut calligraphy_defn    = self.calligraphy_defn.get();
+                calligraphy_defn.nib_angle  = (new_angle as f32).to_radians();
+                self.calligraphy_defn.clone().set(calligraphy_defn);
+            },
+
+            ("ChangeNibRatio", &Value(PropertyValue::Float(new_ratio))) => {
+                let mut calligraphy_defn        = self.calligraphy_defn.get();
+                calligraphy_defn.nib_thickness  = calligraphy_defn.nib_width * (new_ratio as f32).max(MIN_NIB_RATIO).min(1.0);
+                self.calligraphy_defn.clone().set(calligraphy_defn);
+            },
+
+            ("ToggleVelocityWidth", _) => {
+                // Switch between pressure and speed-sensitive brush widths
+                let new_velocity_width = if self.velocity_width.get().is_some() { Option::None } else { Some(DEFAULT_VELOCITY_WIDTH) };
+                self.velocity_width.clone().set(new_velocity_width);
             }
 
             _ => ()
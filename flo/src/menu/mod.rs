@@ -4,9 +4,17 @@ mod ink;
 mod eraser;
 mod select;
 mod adjust;
+mod brush_presets;
+mod eyedropper;
+mod guides;
+mod view;
 
 pub use self::empty::*;
 pub use self::ink::*;
 pub use self::eraser::*;
 pub use self::select::*;
-pub use self::adjust::*;
\ No newline at end of file
+pub use self::adjust::*;
+pub use self::brush_presets::*;
+pub use self::eyedropper::*;
+pub use self::guides::*;
+pub use self::view::*;
\ No newline at end of file
@@ -1,30 +1,55 @@
 use super::controls;
+use super::super::tools::RequestQueue;
+use super::super::standard_tools::{SelectMode, Arrangement};
 
 use ui::*;
 use binding::*;
 
+use std::sync::*;
+
 ///
 /// The menu controller for the selection tool
-/// 
+///
 pub struct SelectMenuController {
-    ui: BindRef<Control>
+    mode:       Binding<SelectMode>,
+    animate:    Binding<bool>,
+    arrange:    RequestQueue<Arrangement>,
+
+    ui:         BindRef<Control>,
+    view_model: Arc<DynamicViewModel>
 }
 
 impl SelectMenuController {
     ///
     /// Creates a new select menu controller
-    /// 
-    pub fn new() -> SelectMenuController {
+    ///
+    pub fn new(mode: &Binding<SelectMode>, animate: &Binding<bool>, arrange: &RequestQueue<Arrangement>) -> SelectMenuController {
+        // Set up the view model
+        let view_model = Arc::new(DynamicViewModel::new());
+
+        let vm_rectangle    = mode.clone();
+        let vm_lasso        = mode.clone();
+        let vm_animate      = animate.clone();
+
+        view_model.set_computed("RectangleMode", move || PropertyValue::Bool(vm_rectangle.get() == SelectMode::Rectangle));
+        view_model.set_computed("LassoMode", move || PropertyValue::Bool(vm_lasso.get() == SelectMode::Lasso));
+        view_model.set_computed("Animate", move || PropertyValue::Bool(vm_animate.get()));
+
         let ui = Self::ui();
 
         SelectMenuController {
-            ui: ui
+            mode:       mode.clone(),
+            animate:    animate.clone(),
+            arrange:    arrange.clone(),
+
+            ui:         ui,
+            view_model: view_model
         }
     }
 
     ///
     /// Creates the UI for the select menu controller
-    /// 
+    ///
     fn ui() -> BindRef<Control> {
         let ui = bind(Control::container()
                     .with(Bounds::fill_all())
@@ -37,16 +62,117 @@ impl SelectMenuController {
                             .with(FontWeight::Light)
                             .with(TextAlign::Right)
                             .with(Font::Size(14.0))
-                            .with(Bounds::next_horiz(48.0))
+                            .with(Bounds::next_horiz(48.0)),
+                        Control::empty()
+                            .with(Bounds::next_horiz(8.0)),
+
+                        Control::button()
+                            .with(vec![Control::label().with("Rect").with(TextAlign::Center).with(Bounds::fill_all())])
+                            .with(State::Selected(Property::Bind("RectangleMode".to_string())))
+                            .with(Bounds::next_horiz(40.0))
+                            .with((ActionTrigger::Click, "SetRectangleMode")),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Control::button()
+                            .with(vec![Control::label().with("Lasso").with(TextAlign::Center).with(Bounds::fill_all())])
+                            .with(State::Selected(Property::Bind("LassoMode".to_string())))
+                            .with(Bounds::next_horiz(40.0))
+                            .with((ActionTrigger::Click, "SetLassoMode")),
+
+
+                        controls::divider(),
+
+                        Control::button()
+                            .with(vec![Control::label().with("Animate").with(TextAlign::Center).with(Bounds::fill_all())])
+                            .with(State::Selected(Property::Bind("Animate".to_string())))
+                            .with(Bounds::next_horiz(56.0))
+                            .with((ActionTrigger::Click, "ToggleAnimate")),
+
+                        controls::divider(),
+
+                        Control::label()
+                            .with("Align:")
+                            .with(FontWeight::Light)
+                            .with(TextAlign::Right)
+                            .with(Font::Size(14.0))
+                            .with(Bounds::next_horiz(40.0)),
+                        Control::empty()
+                            .with(Bounds::next_horiz(8.0)),
+
+                        Self::arrange_button("Left", 40.0, "AlignLeft"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::arrange_button("Centre", 48.0, "AlignCenter"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::arrange_button("Right", 40.0, "AlignRight"),
+                        Control::empty().with(Bounds::next_horiz(8.0)),
+                        Self::arrange_button("Top", 40.0, "AlignTop"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::arrange_button("Middle", 48.0, "AlignMiddle"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::arrange_button("Bottom", 48.0, "AlignBottom"),
+
+                        controls::divider(),
+
+                        Control::label()
+                            .with("Distribute:")
+                            .with(FontWeight::Light)
+                            .with(TextAlign::Right)
+                            .with(Font::Size(14.0))
+                            .with(Bounds::next_horiz(64.0)),
+                        Control::empty()
+                            .with(Bounds::next_horiz(8.0)),
+
+                        Self::arrange_button("Across", 48.0, "DistributeHorizontally"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::arrange_button("Down", 40.0, "DistributeVertically"),
+
+                        controls::divider()
                     ])
             );
 
         BindRef::from(ui)
     }
+
+    ///
+    /// Creates a button that aligns or distributes the selection
+    ///
+    fn arrange_button(label: &str, width: f32, action: &str) -> Control {
+        Control::button()
+            .with(vec![Control::label().with(label).with(TextAlign::Center).with(Bounds::fill_all())])
+            .with(Bounds::next_horiz(width))
+            .with((ActionTrigger::Click, action))
+    }
 }
 
 impl Controller for SelectMenuController {
     fn ui(&self) -> BindRef<Control> {
         self.ui.clone()
     }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        match action_id {
+            "SetRectangleMode"          => self.mode.clone().set(SelectMode::Rectangle),
+            "SetLassoMode"              => self.mode.clone().set(SelectMode::Lasso),
+
+            "AlignLeft"                 => self.arrange.send(Arrangement::AlignLeft),
+            "AlignCenter"               => self.arrange.send(Arrangement::AlignCenter),
+            "AlignRight"                => self.arrange.send(Arrangement::AlignRight),
+            "AlignTop"                  => self.arrange.send(Arrangement::AlignTop),
+            "AlignMiddle"               => self.arrange.send(Arrangement::AlignMiddle),
+            "AlignBottom"               => self.arrange.send(Arrangement::AlignBottom),
+            "DistributeHorizontally"    => self.arrange.send(Arrangement::DistributeHorizontally),
+            "DistributeVertically"      => self.arrange.send(Arrangement::DistributeVertically),
+
+            "ToggleAnimate"             => {
+                // Switch between changing the selected elements and attaching motions to them when they're transformed
+                let animate = self.animate.get();
+                self.animate.clone().set(!animate);
+            },
+
+            _ => ()
+        }
+    }
 }
@@ -0,0 +1,129 @@
+use super::controls;
+use super::super::model::*;
+
+use ui::*;
+use binding::*;
+
+use std::sync::*;
+
+/// How much the zoom in and zoom out commands change the zoom level by
+const ZOOM_STEP: f32 = 1.5;
+
+/// How many degrees the rotate commands turn the canvas by
+const ROTATE_STEP: f32 = 15.0;
+
+///
+/// The menu controller for the zoom and rotation of the canvas
+///
+pub struct ViewMenuController {
+    view:       Binding<CanvasView>,
+
+    ui:         BindRef<Control>,
+    view_model: Arc<DynamicViewModel>
+}
+
+impl ViewMenuController {
+    ///
+    /// Creates a new view menu controller
+    ///
+    pub fn new(view: &Binding<CanvasView>) -> ViewMenuController {
+        // Set up the view model
+        let view_model  = Arc::new(DynamicViewModel::new());
+
+        let vm_view     = view.clone();
+        view_model.set_computed("Zoom", move || PropertyValue::String(format!("{}%", (vm_view.get().zoom*100.0).round())));
+
+        let vm_view     = view.clone();
+        view_model.set_computed("Rotation", move || PropertyValue::String(format!("{}°", vm_view.get().rotation.round())));
+
+        let ui = Self::ui();
+
+        ViewMenuController {
+            view:       view.clone(),
+
+            ui:         ui,
+            view_model: view_model
+        }
+    }
+
+    ///
+    /// Creates a button that performs a command
+    ///
+    fn command_button(label: &str, width: f32, action: &str) -> Control {
+        Control::button()
+            .with(vec![Control::label().with(label).with(TextAlign::Center).with(Bounds::fill_all())])
+            .with(Bounds::next_horiz(width))
+            .with((ActionTrigger::Click, action))
+    }
+
+    ///
+    /// Creates a label showing one of the properties of the view
+    ///
+    fn value_label(property: &str, width: f32) -> Control {
+        Control::label()
+            .with(ControlAttribute::Text(Property::Bind(property.to_string())))
+            .with(TextAlign::Center)
+            .with(Bounds::next_horiz(width))
+    }
+
+    ///
+    /// Creates the UI for the view menu controller
+    ///
+    fn ui() -> BindRef<Control> {
+        let ui = bind(Control::container()
+                    .with(Bounds::fill_all())
+                    .with(ControlAttribute::Padding((0, 3), (0, 3)))
+                    .with(vec![
+                        controls::divider(),
+
+                        Self::command_button("-", 20.0, "ZoomOut"),
+                        Self::value_label("Zoom", 48.0),
+                        Self::command_button("+", 20.0, "ZoomIn"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::command_button("100%", 40.0, "ResetZoom"),
+                        Control::empty().with(Bounds::next_horiz(4.0)),
+                        Self::command_button("Fit", 32.0, "FitToWindow"),
+
+                        controls::divider(),
+
+                        Self::command_button("↺", 20.0, "RotateAnticlockwise"),
+                        Self::value_label("Rotation", 40.0),
+                        Self::command_button("↻", 20.0, "RotateClockwise")
+                    ])
+            );
+
+        BindRef::from(ui)
+    }
+
+    ///
+    /// Updates the view
+    ///
+    fn update_view<UpdateFn: FnOnce(CanvasView) -> CanvasView>(&self, update: UpdateFn) {
+        let view = self.view.get();
+        self.view.clone().set(update(view));
+    }
+}
+
+impl Controller for ViewMenuController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        match action_id {
+            "ZoomIn"                => self.update_view(|view| view.with_zoom(view.zoom * ZOOM_STEP)),
+            "ZoomOut"               => self.update_view(|view| view.with_zoom(view.zoom / ZOOM_STEP)),
+            "ResetZoom"             => self.update_view(|view| view.with_zoom(1.0)),
+            "FitToWindow"           => self.update_view(|_view| CanvasView::fit()),
+
+            "RotateAnticlockwise"   => self.update_view(|view| view.rotated_by(ROTATE_STEP)),
+            "RotateClockwise"       => self.update_view(|view| view.rotated_by(-ROTATE_STEP)),
+
+            _ => ()
+        }
+    }
+}
@@ -1,11 +1,15 @@
 use super::*;
 
+use canvas::*;
 use animation::*;
 use futures::*;
 
 use std::ops::{Deref, Range};
 use std::time::Duration;
 
+/// The number of colours that are kept in the recent colours list
+const MAX_RECENT_COLORS: usize = 8;
+
 impl<Anim: Animation> Animation for FloModel<Anim> {
     ///
     /// Retrieves the frame size of this animation
@@ -49,6 +53,20 @@ impl<Anim: Animation> Animation for FloModel<Anim> {
         self.animation.get_num_edits()
     }
 
+    ///
+    /// Retrieves the brush presets stored in this animation
+    /// 
+    fn brush_presets(&self) -> Vec<BrushPreset> {
+        self.animation.brush_presets()
+    }
+
+    ///
+    /// Retrieves the swatches in the colour palette for this animation
+    /// 
+    fn palette(&self) -> Vec<Swatch> {
+        self.animation.palette()
+    }
+
     ///
     /// Reads from the edit log for this animation
     /// 
@@ -155,6 +173,9 @@ impl<Anim: Animation+EditableAnimation> EditableAnimation for FloModel<Anim> {
         // Borrow the bits of the viewmodel we can change
         let frame_edit_counter  = self.frame_edit_counter.clone();
         let mut size_binding    = self.size_binding.clone();
+        let mut presets_binding = self.brush_presets_binding.clone();
+        let mut palette_binding = self.palette_binding.clone();
+        let mut recent_colors   = self.recent_colors_binding.clone();
 
         // Pipe the edits so they modify the model as a side-effect
         let model_edit          = FloModelSink::new(animation_edit, move |edits: &Vec<AnimationEdit>| {
@@ -174,10 +195,59 @@ impl<Anim: Animation+EditableAnimation> EditableAnimation for FloModel<Anim> {
                     AddNewLayer(_)              |
                     RemoveLayer(_)              |
                     Element(_, _, _)            |
-                    Motion(_, _)                |
-                    Layer(_, Paint(_, _))       => {
+                    Motion(_, _)                => {
+                        advance_edit_counter = true;
+                    },
+
+                    Layer(_, Paint(_, paint))   => {
+                        // Colours used for painting go to the front of the recent colours list
+                        if let PaintEdit::BrushProperties(_, properties) = paint {
+                            let mut recent = recent_colors.get();
+                            recent.retain(|color| color != &properties.color);
+                            recent.insert(0, properties.color);
+                            recent.truncate(MAX_RECENT_COLORS);
+
+                            recent_colors.set(recent);
+                        }
+
                         advance_edit_counter = true;
-                    }
+                    },
+
+                    SetBrushPreset(preset)      => {
+                        let mut presets = presets_binding.get();
+
+                        if let Some(existing) = presets.iter_mut().find(|existing| existing.name == preset.name) {
+                            *existing = preset.clone();
+                        } else {
+                            presets.push(preset.clone());
+                        }
+
+                        presets_binding.set(presets);
+                    },
+
+                    RemoveBrushPreset(name)     => {
+                        let mut presets = presets_binding.get();
+                        presets.retain(|preset| &preset.name != name);
+                        presets_binding.set(presets);
+                    },
+
+                    SetSwatch(swatch)           => {
+                        let mut palette = palette_binding.get();
+
+                        if let Some(existing) = palette.iter_mut().find(|existing| existing.name == swatch.name) {
+                            *existing = swatch.clone();
+                        } else {
+                            palette.push(swatch.clone());
+                        }
+
+                        palette_binding.set(palette);
+                    },
+
+                    RemoveSwatch(name)          => {
+                        let mut palette = palette_binding.get();
+                        palette.retain(|swatch| &swatch.name != name);
+                        palette_binding.set(palette);
+                    },
 
                     Layer(_, AddKeyFrame(_))    |
                     Layer(_, RemoveKeyFrame(_)) => {
@@ -220,4 +290,58 @@ mod test {
         assert!(model.size()        == (800.0, 600.0));
         assert!(model.size.get()    == (800.0, 600.0));
     }
+
+    #[test]
+    fn preset_commands_update_preset_binding() {
+        let model   = FloModel::new(InMemoryAnimation::new());
+        let preset  = BrushPreset::new("Nib", BrushDefinition::Calligraphy(CalligraphyDefinition::default()), BrushDrawingStyle::Draw, BrushProperties::new());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![AnimationEdit::SetBrushPreset(preset.clone()), AnimationEdit::RemoveBrushPreset("Eraser".to_string())]).unwrap();
+        }
+
+        assert!(model.brush_presets.get() == model.brush_presets());
+        assert!(model.brush_presets.get().last() == Some(&preset));
+    }
+
+    #[test]
+    fn swatch_commands_update_palette_binding() {
+        let model = FloModel::new(InMemoryAnimation::new());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::SetSwatch(Swatch::new("Sky", Color::Rgba(0.5, 0.75, 1.0, 1.0))),
+                AnimationEdit::SetSwatch(Swatch::new("Grass", Color::Rgba(0.25, 0.75, 0.25, 1.0))),
+                AnimationEdit::RemoveSwatch("Sky".to_string())
+            ]).unwrap();
+        }
+
+        assert!(model.palette.get() == model.palette());
+        assert!(model.palette.get() == vec![Swatch::new("Grass", Color::Rgba(0.25, 0.75, 0.25, 1.0))]);
+    }
+
+    #[test]
+    fn painting_updates_recent_colors() {
+        let model   = FloModel::new(InMemoryAnimation::new());
+        let paint   = |color| {
+            let mut properties  = BrushProperties::new();
+            properties.color    = color;
+
+            AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, properties)))
+        };
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(0),
+                paint(Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+                paint(Color::Rgba(0.0, 1.0, 0.0, 1.0)),
+                paint(Color::Rgba(1.0, 0.0, 0.0, 1.0))
+            ]).unwrap();
+        }
+
+        assert!(model.recent_colors.get() == vec![Color::Rgba(1.0, 0.0, 0.0, 1.0), Color::Rgba(0.0, 1.0, 0.0, 1.0)]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,153 @@
+use canvas::*;
+use animation::*;
+
+/// The amount of the canvas height taken up by the frame when the view is fitted to the window (the remainder is the margin around the frame)
+const FIT_MARGIN: f64 = 1.05;
+
+/// The smallest zoom level that the canvas can be shown at
+pub const MIN_ZOOM: f32 = 0.125;
+
+/// The largest zoom level that the canvas can be shown at
+pub const MAX_ZOOM: f32 = 32.0;
+
+///
+/// Describes how the frame is positioned on the canvas: how far it's zoomed in, how it's rotated and which part is in the centre
+///
+/// The view is applied to the canvas transformation, so positions passed in to the tools are always in frame coordinates.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CanvasView {
+    /// How far the canvas is magnified (at 1.0, the frame fits the height of the canvas)
+    pub zoom: f32,
+
+    /// The rotation of the canvas around its centre, in degrees anticlockwise
+    pub rotation: f32,
+
+    /// The point in the frame that is shown in the centre of the canvas, or None to show the centre of the frame
+    pub center: Option<(f32, f32)>
+}
+
+impl CanvasView {
+    ///
+    /// Returns the view that shows the whole frame, unrotated
+    ///
+    pub fn fit() -> CanvasView {
+        CanvasView {
+            zoom:       1.0,
+            rotation:   0.0,
+            center:     None
+        }
+    }
+
+    ///
+    /// Returns a copy of this view at a different zoom level
+    ///
+    pub fn with_zoom(&self, zoom: f32) -> CanvasView {
+        CanvasView {
+            zoom:       f32::min(f32::max(zoom, MIN_ZOOM), MAX_ZOOM),
+            rotation:   self.rotation,
+            center:     self.center
+        }
+    }
+
+    ///
+    /// Returns a copy of this view rotated by a number of degrees
+    ///
+    pub fn rotated_by(&self, degrees: f32) -> CanvasView {
+        CanvasView {
+            zoom:       self.zoom,
+            rotation:   (self.rotation + degrees).rem_euclid(360.0),
+            center:     self.center
+        }
+    }
+
+    ///
+    /// Returns a copy of this view with a different point of the frame at the centre of the canvas
+    ///
+    pub fn with_center(&self, center: (f32, f32)) -> CanvasView {
+        CanvasView {
+            zoom:       self.zoom,
+            rotation:   self.rotation,
+            center:     Some(center)
+        }
+    }
+
+    ///
+    /// Returns the point in the frame that is at the centre of the canvas
+    ///
+    pub fn center_point(&self, (width, height): (f64, f64)) -> (f32, f32) {
+        self.center.unwrap_or(((width/2.0) as f32, (height/2.0) as f32))
+    }
+
+    ///
+    /// The height of the region of the frame that is visible in the canvas
+    ///
+    pub fn visible_height(&self, (_width, height): (f64, f64)) -> f32 {
+        ((height * FIT_MARGIN) as f32) / self.zoom
+    }
+
+    ///
+    /// Returns the view that fits a rectangle of the frame into the canvas
+    ///
+    /// We don't know the shape of the window, so the rectangle is fitted as if the canvas were the same shape as the frame.
+    ///
+    pub fn zoom_to_rect(&self, rect: Rect, (width, height): (f64, f64)) -> CanvasView {
+        let rect            = rect.normalize();
+        let (rect_width, rect_height) = (rect.x2-rect.x1, rect.y2-rect.y1);
+
+        if rect_width <= 0.0 && rect_height <= 0.0 {
+            return *self;
+        }
+
+        let required_height = f32::max(rect_height, rect_width * ((height/width) as f32));
+        let center          = ((rect.x1+rect.x2)/2.0, (rect.y1+rect.y2)/2.0);
+
+        self.with_zoom((height as f32) / required_height).with_center(center)
+    }
+
+    ///
+    /// The transformation that rotates the frame around the centre of the canvas
+    ///
+    pub fn rotation_transform(&self, size: (f64, f64)) -> Transform2D {
+        let (center_x, center_y) = self.center_point(size);
+
+        Transform2D::translate(center_x, center_y) * Transform2D::rotate_degrees(self.rotation) * Transform2D::translate(-center_x, -center_y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn zoom_is_clamped() {
+        assert!(CanvasView::fit().with_zoom(1000.0).zoom == MAX_ZOOM);
+        assert!(CanvasView::fit().with_zoom(0.0).zoom == MIN_ZOOM);
+    }
+
+    #[test]
+    fn rotation_wraps_around() {
+        assert!(CanvasView::fit().rotated_by(-90.0).rotation == 270.0);
+        assert!(CanvasView::fit().rotated_by(270.0).rotated_by(180.0).rotation == 90.0);
+    }
+
+    #[test]
+    fn zoom_to_rect_centers_on_rect() {
+        let view = CanvasView::fit().zoom_to_rect(Rect::with_points(100.0, 200.0, 300.0, 250.0), (1000.0, 500.0));
+
+        // The rectangle is wider than it is tall compared to the frame, so its width determines the zoom
+        assert!(view.center == Some((200.0, 225.0)));
+        assert!(view.zoom == 5.0);
+    }
+
+    #[test]
+    fn rotation_keeps_center_fixed() {
+        let view            = CanvasView::fit().with_center((100.0, 50.0)).rotated_by(90.0);
+        let transform       = view.rotation_transform((1000.0, 500.0));
+        let (x, y)          = transform.transform_point(100.0, 50.0);
+        let (rot_x, rot_y)  = transform.transform_point(110.0, 50.0);
+
+        assert!((x-100.0).abs() < 0.001 && (y-50.0).abs() < 0.001);
+        assert!((rot_x-100.0).abs() < 0.001 && (rot_y-60.0).abs() < 0.001);
+    }
+}
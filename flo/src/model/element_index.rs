@@ -0,0 +1,181 @@
+use animation::*;
+use curves::*;
+
+use std::sync::*;
+use std::collections::HashMap;
+
+///
+/// Spatial index of the elements in a frame, used to quickly find the elements near a point or in an area
+///
+/// The index is updated incrementally from the bounding boxes of the elements, so only elements that have
+/// changed need to be re-indexed when the frame is edited. Copies of the index share most of their data.
+///
+#[derive(Clone, Default)]
+pub struct ElementIndex {
+    /// The R-tree containing the (normalised) bounds of each element
+    tree: RTree<PathPoint, ElementId>,
+
+    /// The bounding boxes that are currently in the tree
+    bounds: Arc<HashMap<ElementId, Rect>>,
+
+    /// The position of each element in the frame (elements later in the frame are drawn on top)
+    order: Arc<HashMap<ElementId, usize>>
+}
+
+impl ElementIndex {
+    ///
+    /// Creates a new, empty, element index
+    ///
+    pub fn new() -> ElementIndex {
+        ElementIndex::default()
+    }
+
+    ///
+    /// The number of elements in this index
+    ///
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    ///
+    /// True if there are no elements in this index
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    ///
+    /// Updates this index with a new set of elements (in the order they're drawn) and their bounding boxes
+    ///
+    /// Only the elements whose bounds have changed are updated in the tree.
+    ///
+    pub fn update<Elements: IntoIterator<Item=ElementId>>(&mut self, elements: Elements, bounding_boxes: Arc<HashMap<ElementId, Rect>>) {
+        // Remove any element that has moved or that is no longer in the frame
+        for (element_id, old_bounds) in self.bounds.iter() {
+            if bounding_boxes.get(element_id) != Some(old_bounds) {
+                self.tree.remove(old_bounds.normalize(), element_id);
+            }
+        }
+
+        // Add any element that's new or has moved
+        for (element_id, new_bounds) in bounding_boxes.iter() {
+            if self.bounds.get(element_id) != Some(new_bounds) {
+                self.tree.insert(new_bounds.normalize(), *element_id);
+            }
+        }
+
+        self.bounds = bounding_boxes;
+        self.order  = Arc::new(elements.into_iter()
+            .enumerate()
+            .map(|(index, element_id)| (element_id, index))
+            .collect());
+    }
+
+    ///
+    /// The position of an element in the frame, if it's in this index
+    ///
+    pub fn order(&self, element_id: ElementId) -> Option<usize> {
+        self.order.get(&element_id).cloned()
+    }
+
+    ///
+    /// Sorts a set of element IDs so the topmost element is first
+    ///
+    fn topmost_first<'a, Elements: IntoIterator<Item=&'a ElementId>>(&self, elements: Elements) -> Vec<ElementId> {
+        let mut elements = elements.into_iter()
+            .filter_map(|element_id| self.order.get(element_id).map(|order| (*order, *element_id)))
+            .collect::<Vec<_>>();
+
+        elements.sort_by(|(order_a, _), (order_b, _)| order_b.cmp(order_a));
+        elements.into_iter().map(|(_, element_id)| element_id).collect()
+    }
+
+    ///
+    /// Returns the elements whose bounding boxes are within `tolerance` of a point, topmost first
+    ///
+    pub fn elements_at_point(&self, point: (f32, f32), tolerance: f32) -> Vec<ElementId> {
+        let (x, y)  = point;
+        let region  = Rect::with_points(x-tolerance, y-tolerance, x+tolerance, y+tolerance);
+
+        self.topmost_first(self.tree.overlapping(&region))
+    }
+
+    ///
+    /// Returns the elements whose bounding boxes overlap a rectangle, topmost first
+    ///
+    pub fn elements_in_rect(&self, rect: &Rect) -> Vec<ElementId> {
+        let rect = rect.normalize();
+
+        self.topmost_first(self.tree.overlapping(&rect)
+            .into_iter()
+            .filter(|element_id| self.bounds.get(element_id).map(|bounds| bounds.overlaps(&rect)).unwrap_or(false)))
+    }
+
+    ///
+    /// Returns the elements whose bounding boxes overlap the bounds of a polygon, topmost first
+    ///
+    /// This finds the candidates for a lasso selection: the elements returned may still be outside the polygon itself.
+    ///
+    pub fn elements_in_polygon_bounds(&self, points: &[(f32, f32)]) -> Vec<ElementId> {
+        if points.is_empty() {
+            return vec![];
+        }
+
+        let bounds = points.iter()
+            .fold(Rect::with_points(points[0].0, points[0].1, points[0].0, points[0].1), |bounds, &(x, y)| {
+                Rect::with_points(f32::min(bounds.x1, x), f32::min(bounds.y1, y), f32::max(bounds.x2, x), f32::max(bounds.y2, y))
+            });
+
+        self.topmost_first(self.tree.overlapping(&bounds))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn index_for(rects: Vec<(i64, Rect)>) -> ElementIndex {
+        let mut index = ElementIndex::new();
+        index.update(rects.iter().map(|(id, _)| ElementId::Assigned(*id)).collect::<Vec<_>>(),
+            Arc::new(rects.into_iter().map(|(id, rect)| (ElementId::Assigned(id), rect)).collect()));
+        index
+    }
+
+    #[test]
+    fn finds_elements_at_point_topmost_first() {
+        let index = index_for(vec![
+            (1, Rect::with_points(0.0, 0.0, 100.0, 100.0)),
+            (2, Rect::with_points(50.0, 50.0, 150.0, 150.0)),
+            (3, Rect::with_points(200.0, 200.0, 300.0, 300.0))
+        ]);
+
+        assert!(index.elements_at_point((75.0, 75.0), 0.0) == vec![ElementId::Assigned(2), ElementId::Assigned(1)]);
+        assert!(index.elements_at_point((25.0, 25.0), 0.0) == vec![ElementId::Assigned(1)]);
+        assert!(index.elements_at_point((175.0, 175.0), 0.0) == vec![]);
+        assert!(index.elements_at_point((155.0, 155.0), 10.0) == vec![ElementId::Assigned(2)]);
+    }
+
+    #[test]
+    fn finds_elements_in_rect() {
+        let index = index_for((0..100).map(|id| (id, Rect::with_points((id*10) as f32, 0.0, (id*10+5) as f32, 5.0))).collect());
+
+        assert!(index.len() == 100);
+        assert!(index.elements_in_rect(&Rect::with_points(22.0, 1.0, 0.0, 2.0)) == vec![ElementId::Assigned(2), ElementId::Assigned(1), ElementId::Assigned(0)]);
+    }
+
+    #[test]
+    fn update_moves_and_removes_elements() {
+        let mut index = index_for(vec![
+            (1, Rect::with_points(0.0, 0.0, 10.0, 10.0)),
+            (2, Rect::with_points(20.0, 0.0, 30.0, 10.0))
+        ]);
+
+        let mut bounds = HashMap::new();
+        bounds.insert(ElementId::Assigned(2), Rect::with_points(0.0, 0.0, 10.0, 10.0));
+        index.update(vec![ElementId::Assigned(2)], Arc::new(bounds));
+
+        assert!(index.len() == 1);
+        assert!(index.elements_at_point((5.0, 5.0), 0.0) == vec![ElementId::Assigned(2)]);
+        assert!(index.elements_at_point((25.0, 5.0), 0.0) == vec![]);
+    }
+}
@@ -1,6 +1,9 @@
+use super::element_index::*;
+
 use binding::*;
 use animation::*;
-use curves::bezier::path::path_contains_point;
+use curves::bezier::{BezierCurve, Curve, curve_intersects_line};
+use curves::bezier::path::{path_contains_point, distance_to_path, path_to_curves};
 
 use std::sync::*;
 use std::collections::HashMap;
@@ -12,7 +15,7 @@ use std::time::Duration;
 /// 
 #[derive(Clone, Copy, PartialEq)]
 pub enum ElementMatch {
-    /// The point is inside the path for the specified element
+    /// The point is inside the path for the specified element (or close enough to its edge to count as inside)
     InsidePath(ElementId),
 
     /// The point is not inside the element path but is inside the element's bounding box
@@ -55,7 +58,10 @@ pub struct FrameModel {
     pub elements: BindRef<Arc<Vec<(Vector, Arc<VectorProperties>)>>>,
 
     /// The bounding boxes of all of the elements
-    pub bounding_boxes: BindRef<Arc<HashMap<ElementId, Rect>>>
+    pub bounding_boxes: BindRef<Arc<HashMap<ElementId, Rect>>>,
+
+    /// Spatial index of the elements, for finding the elements at a point or in an area
+    pub spatial_index: BindRef<Arc<ElementIndex>>
 }
 
 impl FrameModel {
@@ -137,13 +143,15 @@ impl FrameModel {
         let frame           = Self::current_frame(selected_layer, layers.clone());
         let elements        = Self::element_properties(frame.clone());
         let bounding_boxes  = Self::bounding_boxes(elements.clone());
+        let spatial_index   = Self::spatial_index(elements.clone(), bounding_boxes.clone());
 
         // Result is a new FrameModel containing these layers
         FrameModel {
             layers:         BindRef::new(&layers),
             frame:          frame,
             elements:       elements,
-            bounding_boxes: bounding_boxes
+            bounding_boxes: bounding_boxes,
+            spatial_index:  spatial_index
         }
     }
 
@@ -213,42 +221,53 @@ impl FrameModel {
         }))
     }
 
+    ///
+    /// Returns a binding that keeps a spatial index of the elements up to date
+    /// 
+    /// The index is updated from the previous one, so only the elements that have changed are re-indexed.
+    /// 
+    fn spatial_index<Elements: 'static+Bound<Arc<Vec<(Vector, Arc<VectorProperties>)>>>, BoundingBoxes: 'static+Bound<Arc<HashMap<ElementId, Rect>>>>(elements: Elements, bounding_boxes: BoundingBoxes) -> BindRef<Arc<ElementIndex>> {
+        let last_index = Mutex::new(ElementIndex::new());
+
+        BindRef::new(&computed(move || {
+            let elements        = elements.get();
+            let bounding_boxes  = bounding_boxes.get();
+
+            let mut index       = last_index.lock().unwrap();
+            index.update(elements.iter().map(|(vector, _)| vector.id()), bounding_boxes);
+
+            Arc::new(index.clone())
+        }))
+    }
+
     ///
     /// Returns the elements at the specified point
     /// 
-    pub fn elements_at_point(&self, point: (f32, f32)) -> impl Iterator<Item=ElementMatch> {
-        // Fetch the elements and their bounding boxes
+    /// Points within `tolerance` of the edge of an element's path are treated as being inside it, which
+    /// makes it possible to pick thin strokes.
+    /// 
+    pub fn elements_at_point(&self, point: (f32, f32), tolerance: f32) -> impl Iterator<Item=ElementMatch> {
+        // Fetch the elements and the spatial index
         let elements        = self.elements.get();
-        let more_elements   = Arc::clone(&elements);
-        let bounding_boxes  = self.bounding_boxes.get();
+        let spatial_index   = self.spatial_index.get();
 
         let (x, y)          = point;
         let path_point      = PathPoint::new(x, y);
 
-        // This would be considerably more elegant if rust understood that it could keep the Arc<Vec<_>> around to make the lifetime
-        // of elements.iter() work out. We use array indexes and multiple references to the elements array instead here, so the elements
-        // object can be owned by those functions.
-
-        // Iterate through the elements in reverse
-        let indexes = (0..elements.len()).into_iter().rev();
-
-        // Filter to the elements where the point is inside the bounding box
-        let inside_bounds = indexes.filter(move |element_index| {
-            bounding_boxes.get(&elements[*element_index].0.id())
-                .map(|bounds| bounds.contains(x, y))
-                .unwrap_or(false)
-        });
+        // Use the spatial index to find the elements whose bounding box is close to the point (topmost first)
+        let inside_bounds   = spatial_index.elements_at_point(point, tolerance*2.0)
+            .into_iter()
+            .filter_map(move |element_id| spatial_index.order(element_id));
 
         // Generate a result based on whether or not the match is inside the path for the element
         let matches = inside_bounds
             .map(move |element_index| {
-                // Get the vector properties from the more_elements array (elements is used above so we need two references)
-                let &(ref vector, ref properties)   = &more_elements[element_index];
+                let &(ref vector, ref properties)   = &elements[element_index];
                 let element_id                      = vector.id();
 
-                // Convert the element to paths and check if the point is inside
+                // Convert the element to paths and check if the point is inside or close to the edge
                 let paths                           = vector.to_path(properties);
-                let inside_path                     = paths.map(|paths| paths.into_iter().any(|path| path_contains_point(&path, &path_point))).unwrap_or(false);
+                let inside_path                     = paths.map(|paths| paths.into_iter().any(|path| path_contains_point(&path, &path_point) || distance_to_path(&path, &path_point) <= tolerance as f64)).unwrap_or(false);
 
                 // Any match inside the bounds is a match, but we often treat a point inside the path as a stronger match
                 if inside_path {
@@ -260,4 +279,109 @@ impl FrameModel {
 
         matches
     }
+    ///
+    /// Creates a closed path from the points of a lasso
+    /// 
+    fn lasso_path(lasso: &[(f32, f32)]) -> Path {
+        let points = lasso.iter().map(|&(x, y)| PathPoint::new(x, y));
+
+        Path::from_elements(points.enumerate()
+            .map(|(index, point)| if index == 0 { PathElement::Move(point) } else { PathElement::Line(point) })
+            .chain(Some(PathElement::Close)))
+    }
+
+    ///
+    /// True if any part of a path is inside a lasso
+    /// 
+    fn path_in_lasso(path: &Path, lasso_path: &Path, lasso: &[(f32, f32)]) -> bool {
+        // The edges of the lasso, including the one that closes it
+        let lasso_edges: Vec<_> = lasso.iter()
+            .zip(lasso.iter().skip(1).chain(lasso.first()))
+            .map(|(&(x1, y1), &(x2, y2))| (PathPoint::new(x1, y1), PathPoint::new(x2, y2)))
+            .collect();
+
+        // Part of the path is in the lasso if it starts inside it or if it crosses one of its edges
+        let crosses_lasso = path_to_curves::<_, Curve<_>>(path)
+            .any(|curve| path_contains_point(lasso_path, &curve.start_point())
+                || lasso_edges.iter().any(|edge| curve_intersects_line(&curve, edge).len() > 0));
+
+        // The lasso may also be drawn entirely inside a large element
+        crosses_lasso || lasso.first().map(|&(x, y)| path_contains_point(path, &PathPoint::new(x, y))).unwrap_or(false)
+    }
+
+    ///
+    /// Returns the elements that are entirely or partly inside a lasso, topmost first
+    /// 
+    /// The lasso is a polygon, which is closed by joining its last point to its first.
+    /// 
+    pub fn elements_in_lasso(&self, lasso: &[(f32, f32)]) -> Vec<ElementId> {
+        if lasso.len() < 3 { return vec![]; }
+
+        let elements        = self.elements.get();
+        let spatial_index   = self.spatial_index.get();
+        let lasso_path      = Self::lasso_path(lasso);
+
+        // The bounding boxes give the candidates, and the paths decide which are actually in the lasso
+        spatial_index.elements_in_polygon_bounds(lasso)
+            .into_iter()
+            .filter(|element_id| {
+                let paths = spatial_index.order(*element_id)
+                    .and_then(|index| elements.get(index))
+                    .and_then(|(vector, properties)| vector.to_path(properties))
+                    .unwrap_or_default();
+
+                paths.iter().any(|path| Self::path_in_lasso(path, &lasso_path, lasso))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use animation::inmemory::*;
+    use futures::executor;
+
+    use std::sync::*;
+    use std::time::Duration;
+
+    fn model_with_stroke(points: Vec<(f32, f32)>) -> FloModel<InMemoryAnimation> {
+        let model   = FloModel::new(InMemoryAnimation::new());
+        let when    = Duration::from_millis(0);
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(0),
+                AnimationEdit::Layer(0, LayerEdit::AddKeyFrame(when)),
+                AnimationEdit::Layer(0, LayerEdit::Paint(when, PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(0, LayerEdit::Paint(when, PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(0, LayerEdit::Paint(when, PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(points.into_iter().map(RawPoint::from).collect()))))
+            ]).unwrap();
+        }
+
+        model
+    }
+
+    #[test]
+    fn lasso_around_element_selects_it() {
+        let model = model_with_stroke(vec![(100.0, 100.0), (150.0, 110.0), (200.0, 100.0)]);
+
+        assert!(model.frame().elements_in_lasso(&[(50.0, 50.0), (250.0, 50.0), (250.0, 150.0), (50.0, 150.0)]).len() == 1);
+    }
+
+    #[test]
+    fn lasso_away_from_element_does_not_select_it() {
+        let model = model_with_stroke(vec![(100.0, 100.0), (150.0, 110.0), (200.0, 100.0)]);
+
+        assert!(model.frame().elements_in_lasso(&[(300.0, 50.0), (400.0, 50.0), (400.0, 150.0), (300.0, 150.0)]).len() == 0);
+    }
+
+    #[test]
+    fn narrow_lasso_across_element_selects_it() {
+        // The lasso only covers a thin slice through the middle of a long stroke
+        let model = model_with_stroke(vec![(0.0, 100.0), (500.0, 100.0), (1000.0, 100.0)]);
+
+        assert!(model.frame().elements_in_lasso(&[(331.0, 0.0), (332.0, 0.0), (332.0, 200.0), (331.0, 200.0)]).len() == 1);
+    }
 }
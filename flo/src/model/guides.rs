@@ -0,0 +1,44 @@
+use binding::*;
+
+/// The grid spacing used when the editor starts
+const DEFAULT_GRID_SPACING: f32 = 20.0;
+
+///
+/// Model representing the measurement aids on the canvas (rulers and the grid) and what tools snap to
+///
+#[derive(Clone)]
+pub struct GuidesModel {
+    /// True if rulers are drawn along the edges of the canvas
+    pub show_rulers: Binding<bool>,
+
+    /// True if the grid is drawn over the canvas
+    pub show_grid: Binding<bool>,
+
+    /// The distance between the lines of the grid
+    pub grid_spacing: Binding<f32>,
+
+    /// True if points snap to the grid
+    pub snap_to_grid: Binding<bool>,
+
+    /// True if points snap to the bounding boxes and control points of other elements
+    pub snap_to_elements: Binding<bool>,
+
+    /// True if points snap to the centre of the canvas
+    pub snap_to_center: Binding<bool>
+}
+
+impl GuidesModel {
+    ///
+    /// Creates a new guides model
+    ///
+    pub fn new() -> GuidesModel {
+        GuidesModel {
+            show_rulers:        bind(false),
+            show_grid:          bind(false),
+            grid_spacing:       bind(DEFAULT_GRID_SPACING),
+            snap_to_grid:       bind(false),
+            snap_to_elements:   bind(false),
+            snap_to_center:     bind(false)
+        }
+    }
+}
@@ -3,7 +3,10 @@ mod tools;
 mod layer;
 mod keyframe;
 mod frame;
+mod element_index;
 mod selection;
+mod guides;
+mod canvas_view;
 mod animation;
 
 pub use self::timeline::*;
@@ -11,10 +14,14 @@ pub use self::tools::*;
 pub use self::layer::*;
 pub use self::keyframe::*;
 pub use self::frame::*;
+pub use self::element_index::*;
 pub use self::selection::*;
+pub use self::guides::*;
+pub use self::canvas_view::*;
 pub use self::animation::*;
 
 use binding::*;
+use canvas::*;
 use animation::*;
 
 use std::sync::*;
@@ -38,12 +45,36 @@ pub struct FloModel<Anim: Animation> {
     /// The selection model
     selection: SelectionModel,
 
+    /// The rulers, grid and snapping settings
+    guides: GuidesModel,
+
+    /// How the frame is zoomed and rotated on the canvas
+    view: Binding<CanvasView>,
+
     /// The size of the animation
     pub size: BindRef<(f64, f64)>,
 
     /// The underlying size binding
     size_binding: Binding<(f64, f64)>,
 
+    /// The brush presets stored in the animation
+    pub brush_presets: BindRef<Vec<BrushPreset>>,
+
+    /// The underlying brush presets binding
+    brush_presets_binding: Binding<Vec<BrushPreset>>,
+
+    /// The colour palette stored in the animation
+    pub palette: BindRef<Vec<Swatch>>,
+
+    /// The underlying palette binding
+    palette_binding: Binding<Vec<Swatch>>,
+
+    /// The colours most recently used for painting (most recent first)
+    pub recent_colors: BindRef<Vec<Color>>,
+
+    /// The underlying recent colours binding
+    recent_colors_binding: Binding<Vec<Color>>,
+
     /// Counter used to set an edit ID for the frame (essentially indicates when the frame has been redrawn)
     frame_edit_counter: Binding<u64>
 }
@@ -59,8 +90,13 @@ impl<Anim: Animation+'static> FloModel<Anim> {
         let frame_edit_counter  = bind(0);
         let frame               = FrameModel::new(Arc::clone(&animation), BindRef::new(&timeline.current_time), BindRef::new(&frame_edit_counter), BindRef::new(&timeline.selected_layer));
         let selection           = SelectionModel::new();
+        let guides              = GuidesModel::new();
+        let view                = bind(CanvasView::fit());
 
         let size_binding        = bind(animation.size());
+        let presets_binding     = bind(animation.brush_presets());
+        let palette_binding     = bind(animation.palette());
+        let recent_binding      = bind(vec![]);
 
         FloModel {
             animation:          animation,
@@ -69,9 +105,20 @@ impl<Anim: Animation+'static> FloModel<Anim> {
             frame_edit_counter: frame_edit_counter,
             frame:              frame,
             selection:          selection,
+            guides:             guides,
+            view:               view,
 
             size:               BindRef::from(size_binding.clone()),
-            size_binding:       size_binding
+            size_binding:       size_binding,
+
+            brush_presets:          BindRef::from(presets_binding.clone()),
+            brush_presets_binding:  presets_binding,
+
+            palette:                BindRef::from(palette_binding.clone()),
+            palette_binding:        palette_binding,
+
+            recent_colors:          BindRef::from(recent_binding.clone()),
+            recent_colors_binding:  recent_binding
         }
     }
 
@@ -103,6 +150,20 @@ impl<Anim: Animation+'static> FloModel<Anim> {
         &self.selection
     }
 
+    ///
+    /// Retrieves the model for the rulers, grid and snapping for this animation
+    /// 
+    pub fn guides(&self) -> &GuidesModel {
+        &self.guides
+    }
+
+    ///
+    /// Retrieves the binding for how the frame is zoomed and rotated on the canvas
+    /// 
+    pub fn view(&self) -> &Binding<CanvasView> {
+        &self.view
+    }
+
     ///
     /// Retrieves the frame update binding for this animation
     /// 
@@ -121,9 +182,20 @@ impl<Anim: Animation> Clone for FloModel<Anim> {
             frame_edit_counter: self.frame_edit_counter.clone(),
             frame:              self.frame.clone(),
             selection:          self.selection.clone(),
+            guides:             self.guides.clone(),
+            view:               self.view.clone(),
 
             size:               self.size.clone(),
-            size_binding:       self.size_binding.clone()
+            size_binding:       self.size_binding.clone(),
+
+            brush_presets:          self.brush_presets.clone(),
+            brush_presets_binding:  self.brush_presets_binding.clone(),
+
+            palette:                self.palette.clone(),
+            palette_binding:        self.palette_binding.clone(),
+
+            recent_colors:          self.recent_colors.clone(),
+            recent_colors_binding:  self.recent_colors_binding.clone()
         }
     }
 }
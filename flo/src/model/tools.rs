@@ -19,6 +19,9 @@ pub struct ToolModel<Anim: Animation> {
     /// The ID of the pointer that's currently in use (device and pointer ID)
     pub current_pointer: Binding<(PaintDevice, i32)>,
 
+    /// The modifier keys that were held down when the current pointer started painting
+    pub current_modifiers: Binding<PaintModifiers>,
+
     /// The tool that is in effect at the current moment (might change if the user chooses a different pointer)
     pub effective_tool: BindRef<Option<Arc<FloTool<Anim>>>>,
 
@@ -44,8 +47,9 @@ impl<Anim: Animation+'static> ToolModel<Anim> {
         let selected_tool               = bind(None);
         let tool_sets                   = bind(default_tool_sets);
         let current_pointer             = bind((PaintDevice::Mouse(MouseButton::Left), 0));
+        let current_modifiers           = bind(PaintModifiers::none());
         let tool_models                 = Arc::new(Mutex::new(HashMap::new()));
-        let effective_tool              = Self::effective_tool(selected_tool.clone(), current_pointer.clone(), tool_sets.clone());
+        let effective_tool              = Self::effective_tool(selected_tool.clone(), current_pointer.clone(), current_modifiers.clone(), tool_sets.clone());
 
         // Finish up the object
         ToolModel {
@@ -53,6 +57,7 @@ impl<Anim: Animation+'static> ToolModel<Anim> {
             selected_tool:              selected_tool,
             tool_sets:                  tool_sets,
             current_pointer:            current_pointer,
+            current_modifiers:          current_modifiers,
             tool_models:                tool_models
         }
     }
@@ -70,35 +75,32 @@ impl<Anim: Animation+'static> ToolModel<Anim> {
     ///
     /// Returns a binding for the 'effective tool'
     /// 
-    fn effective_tool(selected_tool: Binding<Option<Arc<FloTool<Anim>>>>, current_pointer: Binding<(PaintDevice, i32)>, tool_sets: Binding<Vec<Arc<dyn ToolSet<Anim>>>>) -> BindRef<Option<Arc<FloTool<Anim>>>> {
+    fn effective_tool(selected_tool: Binding<Option<Arc<FloTool<Anim>>>>, current_pointer: Binding<(PaintDevice, i32)>, current_modifiers: Binding<PaintModifiers>, tool_sets: Binding<Vec<Arc<dyn ToolSet<Anim>>>>) -> BindRef<Option<Arc<FloTool<Anim>>>> {
         let effective_tool = computed(move || {
-            let (device, _pointer_id) = current_pointer.get();
+            let (device, _pointer_id)   = current_pointer.get();
+            let selected_tool           = selected_tool.get();
+
+            // Holding down alt while painting with the ink tool picks up brushes with the eyedropper instead
+            let is_ink = selected_tool.as_ref().map(|tool| tool.tool_name() == "Ink").unwrap_or(false);
+            if is_ink && current_modifiers.get().alt {
+                if let Some(eyedropper) = Self::find_tool(&tool_sets.get(), "Eyedropper") {
+                    return Some(eyedropper);
+                }
+            }
 
             match device {
-                // For the mouse, we always use the selected tool
-                PaintDevice::Mouse(_) => selected_tool.get(),
-                PaintDevice::Other => selected_tool.get(),
-
                 // The eraser defaults to the erase tool if it's available
                 // TODO: or whatever was selected using the eraser
                 PaintDevice::Eraser => {
-                    let mut erase_tool = selected_tool.get();
-
-                    let tool_sets = tool_sets.get();
-                    for set in tool_sets {
-                        let tools = set.tools();
-                        for tool in tools {
-                            if &tool.tool_name() == "Eraser" {
-                                erase_tool = Some(tool);
-                            }
-                        }
-                    }
-
-                    erase_tool
+                    Self::find_tool(&tool_sets.get(), "Eraser").or(selected_tool)
                 },
 
+                // For the mouse, we always use the selected tool
+                PaintDevice::Mouse(_) => selected_tool,
+                PaintDevice::Other => selected_tool,
+
                 // Other pointers default to the selected tool but will use whatever tool they were last used to select if there is one
-                _ => selected_tool.get()
+                _ => selected_tool
             }
         });
 
@@ -109,19 +111,43 @@ impl<Anim: Animation+'static> ToolModel<Anim> {
     /// Finds the tool with the specified name and marks it as active
     /// 
     pub fn choose_tool_with_name(&self, name: &str) {
+        let tool_with_name = self.tool_with_name(name);
+
+        // Set as the selected tool
+        self.selected_tool.clone().set(tool_with_name)
+    }
+
+    ///
+    /// Finds the tool with the specified name
+    /// 
+    pub fn tool_with_name(&self, name: &str) -> Option<Arc<FloTool<Anim>>> {
+        Self::find_tool(&self.tool_sets.get(), name)
+    }
+
+    ///
+    /// Searches a list of toolsets for a tool with the specified name (returning the last match)
+    /// 
+    fn find_tool(tool_sets: &[Arc<dyn ToolSet<Anim>>], name: &str) -> Option<Arc<FloTool<Anim>>> {
         let mut tool_with_name = None;
 
         // Search all of the toolsets for a tool matching the specified name
-        for set in self.tool_sets.get() {
+        for set in tool_sets {
             for tool in set.tools() {
-                if &tool.tool_name() == name {
+                if tool.tool_name() == name {
                     tool_with_name = Some(tool);
                 }
             }
         }
 
-        // Set as the selected tool
-        self.selected_tool.clone().set(tool_with_name)
+        tool_with_name
+    }
+
+    ///
+    /// Retrieves the model for the tool with the specified name, if it exists and has a model of the specified type
+    /// 
+    pub fn model_for_tool_with_name<Model: 'static+Send+Sync>(&self, name: &str, model: Arc<FloModel<Anim>>) -> Option<Arc<Model>> {
+        self.tool_with_name(name)
+            .and_then(move |tool| self.model_for_tool(&*tool, model).get_ref())
     }
 }
 
@@ -131,8 +157,59 @@ impl<Anim: Animation> Clone for ToolModel<Anim> {
             selected_tool:              Binding::clone(&self.selected_tool),
             tool_sets:                  Binding::clone(&self.tool_sets),
             current_pointer:            Binding::clone(&self.current_pointer),
+            current_modifiers:          Binding::clone(&self.current_modifiers),
             effective_tool:             BindRef::clone(&self.effective_tool),
             tool_models:                Arc::clone(&self.tool_models)
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use animation::inmemory::*;
+
+    fn effective_tool_name(tools: &ToolModel<InMemoryAnimation>) -> Option<String> {
+        tools.effective_tool.get().map(|tool| tool.tool_name())
+    }
+
+    #[test]
+    fn alt_uses_eyedropper_with_ink() {
+        let tools = ToolModel::<InMemoryAnimation>::new();
+
+        tools.choose_tool_with_name("Ink");
+        tools.current_modifiers.clone().set(PaintModifiers { alt: true, ..PaintModifiers::none() });
+
+        assert!(effective_tool_name(&tools) == Some("Eyedropper".to_string()));
+    }
+
+    #[test]
+    fn alt_does_not_change_other_tools() {
+        let tools = ToolModel::<InMemoryAnimation>::new();
+
+        tools.choose_tool_with_name("Select");
+        tools.current_modifiers.clone().set(PaintModifiers { alt: true, ..PaintModifiers::none() });
+
+        assert!(effective_tool_name(&tools) == Some("Select".to_string()));
+    }
+
+    #[test]
+    fn right_button_uses_selected_tool() {
+        let tools = ToolModel::<InMemoryAnimation>::new();
+
+        tools.choose_tool_with_name("Select");
+        tools.current_pointer.clone().set((PaintDevice::Mouse(MouseButton::Right), 0));
+
+        assert!(effective_tool_name(&tools) == Some("Select".to_string()));
+    }
+
+    #[test]
+    fn left_button_uses_selected_tool() {
+        let tools = ToolModel::<InMemoryAnimation>::new();
+
+        tools.choose_tool_with_name("Ink");
+        tools.current_pointer.clone().set((PaintDevice::Mouse(MouseButton::Left), 0));
+
+        assert!(effective_tool_name(&tools) == Some("Ink".to_string()));
+    }
+}
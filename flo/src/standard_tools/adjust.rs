@@ -16,6 +16,12 @@ use std::f32;
 use std::sync::*;
 use std::collections::HashSet;
 
+/// How close the user needs to click to a control point to start dragging it
+const CONTROL_POINT_RADIUS: f32 = 8.0;
+
+/// How far a click can be from the edge of an element and still select it
+const PICK_TOLERANCE: f32 = 4.0;
+
 ///
 /// The current action being performed by the adjust tool
 /// 
@@ -46,7 +52,10 @@ pub struct AdjustData {
     selected_elements: Arc<HashSet<ElementId>>,
 
     // The element, index and location of all of the control points
-    control_points: Arc<Vec<(ElementId, usize, (f32, f32))>>
+    control_points: Arc<Vec<(ElementId, usize, (f32, f32))>>,
+
+    // Where dragged control points snap to
+    snapping: Arc<Snapping>
 }
 
 impl AdjustData {
@@ -370,32 +379,63 @@ impl Adjust {
             .flatten()
     }
     
+    ///
+    /// Returns the element whose path is at (or close to) a particular location, if there is one
+    /// 
+    fn element_at_point<Anim: 'static+Animation>(flo_model: &FloModel<Anim>, location: (f32, f32)) -> Option<ElementId> {
+        flo_model.frame().elements_at_point(location, PICK_TOLERANCE)
+            .filter_map(|element_match| match element_match {
+                ElementMatch::InsidePath(element_id)    => Some(element_id),
+                ElementMatch::OnlyInBounds(_)           => None
+            })
+            .nth(0)
+    }
+
     ///
     /// Generates the tool actions for a painting action
     /// 
-    fn paint(&self, painting: Painting, data: &AdjustData) -> Vec<ToolAction<AdjustData>> {
+    fn paint<Anim: 'static+Animation>(&self, flo_model: &FloModel<Anim>, painting: Painting, data: &AdjustData) -> Vec<ToolAction<AdjustData>> {
         let state           = data.state.get();
         let paint_action    = painting.action;
 
         match (state, paint_action) {
             // A start paint action might change the selection or start dragging a control point
             (_, PaintAction::Start) => {
-                if let Some((cp_index, distance)) = data.nearest_control_point_index(painting.location) {
-                    if distance < 8.0 {
-                        // Start dragging this control point
-                        let &(element_id, index, _pos) = &data.control_points[cp_index];
-                        
-                        data.state.clone().set(AdjustAction::DragControlPoint(element_id, index, painting.location, painting.location));
+                let control_point = data.nearest_control_point_index(painting.location)
+                    .and_then(|(cp_index, distance)| if distance < CONTROL_POINT_RADIUS { Some(cp_index) } else { None });
+
+                if let Some(cp_index) = control_point {
+                    // Start dragging this control point
+                    let &(element_id, index, _pos) = &data.control_points[cp_index];
+                    
+                    data.state.clone().set(AdjustAction::DragControlPoint(element_id, index, painting.location, painting.location));
+
+                    vec![]
+                } else {
+                    // Clicking on (or just beside) an element that's not already selected selects it so its control points can be adjusted
+                    match Self::element_at_point(flo_model, painting.location) {
+                        Some(element_id) if !data.selected_elements.contains(&element_id) => {
+                            data.state.clone().set(AdjustAction::Select);
+                            vec![ToolAction::ClearSelection, ToolAction::Select(element_id)]
+                        },
+
+                        _ => vec![]
                     }
                 }
-
-                // No tool actions to perform
-                vec![]
             },
 
             (AdjustAction::DragControlPoint(element_id, index, from, _to), PaintAction::Continue) => {
+                // Snap the control point being dragged to anything nearby (other than its own element)
+                let control_point   = data.control_points.iter()
+                    .filter(|&&(cp_element_id, cp_index, _)| cp_element_id == element_id && cp_index == index)
+                    .map(|&(_, _, position)| position)
+                    .nth(0)
+                    .unwrap_or(from);
+                let ignore          = vec![element_id].into_iter().collect();
+                let offset          = data.snapping.snap_offset(&[control_point], (painting.location.0-from.0, painting.location.1-from.1), &ignore);
+
                 // Continue the control point drag by updating the 'to' location
-                data.state.clone().set(AdjustAction::DragControlPoint(element_id, index, from, painting.location));
+                data.state.clone().set(AdjustAction::DragControlPoint(element_id, index, from, (from.0+offset.0, from.1+offset.1)));
 
                 // No tool actions to perform
                 vec![]
@@ -442,6 +482,7 @@ impl<Anim: 'static+Animation> Tool<Anim> for Adjust {
         // Also track the selected elements
         let selected_elements   = flo_model.selection().selected_element.clone();
         let control_points      = Self::control_points(&*flo_model);
+        let snapping            = Snapping::for_model(&*flo_model);
 
         // Draw control points when the frame changes
         let draw_control_points = Self::draw_control_point_overlay(flo_model.clone(), BindRef::new(&adjust_state));
@@ -450,13 +491,14 @@ impl<Anim: 'static+Animation> Tool<Anim> for Adjust {
         let draw_drag_result = Self::draw_edit_overlay(flo_model, BindRef::new(&adjust_state));
 
         // Build the model from the current frame and selected elements
-        let update_adjust_data = follow(computed(move || (current_frame.get(), selected_elements.get(), control_points.get())))
-            .map(move |(frame, selected_elements, control_points)| {
+        let update_adjust_data = follow(computed(move || (current_frame.get(), selected_elements.get(), control_points.get(), snapping.get())))
+            .map(move |(frame, selected_elements, control_points, snapping)| {
                 ToolAction::Data(AdjustData {
                     frame:              frame,
                     state:              adjust_state.clone(),
                     selected_elements:  Arc::new(selected_elements.into_iter().collect()),
-                    control_points:     control_points
+                    control_points:     control_points,
+                    snapping:           snapping
                 })
             });
         
@@ -464,7 +506,7 @@ impl<Anim: 'static+Animation> Tool<Anim> for Adjust {
         Box::new(update_adjust_data.select(draw_control_points).select(draw_drag_result))
     }
 
-    fn actions_for_input<'a>(&'a self, _flo_model: Arc<FloModel<Anim>>, data: Option<Arc<AdjustData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<AdjustData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<AdjustData>>> {
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<AdjustData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<AdjustData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<AdjustData>>> {
         let mut data = data;
         let mut actions = vec![];
 
@@ -478,7 +520,7 @@ impl<Anim: 'static+Animation> Tool<Anim> for Adjust {
 
                 ToolInput::Paint(painting) => {
                     if let Some(data) = data.as_ref() {
-                        actions.extend(self.paint(painting, &**data));
+                        actions.extend(self.paint(&*flo_model, painting, &**data));
                     }
                 }
 
@@ -490,3 +532,68 @@ impl<Anim: 'static+Animation> Tool<Anim> for Adjust {
         Box::new(actions.into_iter())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use animation::inmemory::*;
+
+    fn data_for_curve() -> AdjustData {
+        // A single curve section with its end points 300 pixels apart
+        let element_id = ElementId::Assigned(1);
+
+        AdjustData {
+            frame:              None,
+            state:              bind(AdjustAction::NoAction),
+            selected_elements:  Arc::new(vec![element_id].into_iter().collect()),
+            control_points:     Arc::new(vec![(element_id, 0, (0.0, 0.0)), (element_id, 1, (100.0, 0.0)), (element_id, 2, (200.0, 0.0)), (element_id, 3, (300.0, 0.0))]),
+            snapping:           Arc::new(Snapping::none())
+        }
+    }
+
+    fn paint_at(action: PaintAction, location: (f32, f32)) -> Painting {
+        Painting {
+            action:     action,
+            pointer_id: 0,
+            location:   location,
+            pressure:   1.0,
+            tilt_x:     0.0,
+            tilt_y:     0.0,
+            modifiers:  PaintModifiers::none()
+        }
+    }
+
+    #[test]
+    fn nearest_control_point_ignores_curve() {
+        let data = data_for_curve();
+
+        // Close to the curve but nowhere near either end of it
+        let (index, distance) = data.nearest_control_point_index((290.0, 2.0)).unwrap();
+
+        assert!(index == 3);
+        assert!((distance - (10.0f32*10.0 + 2.0*2.0).sqrt()).abs() < 0.01);
+    }
+
+    #[test]
+    fn clicking_on_curve_away_from_control_points_does_not_drag() {
+        let flo_model   = FloModel::new(InMemoryAnimation::new());
+        let data        = data_for_curve();
+        let adjust      = Adjust::new();
+
+        adjust.paint(&flo_model, paint_at(PaintAction::Start, (150.0, 2.0)), &data);
+
+        assert!(data.state.get() == AdjustAction::NoAction);
+    }
+
+    #[test]
+    fn clicking_near_control_point_drags_it() {
+        let flo_model   = FloModel::new(InMemoryAnimation::new());
+        let data        = data_for_curve();
+        let adjust      = Adjust::new();
+
+        adjust.paint(&flo_model, paint_at(PaintAction::Start, (203.0, 2.0)), &data);
+
+        assert!(data.state.get() == AdjustAction::DragControlPoint(ElementId::Assigned(1), 2, (203.0, 2.0), (203.0, 2.0)));
+    }
+}
@@ -0,0 +1,134 @@
+use animation::*;
+
+use std::cmp::Ordering;
+use std::time::Duration;
+
+///
+/// Ways that a set of elements can be lined up with each other
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Arrangement {
+    /// Moves the left edges of the elements to the left edge of the selection
+    AlignLeft,
+
+    /// Moves the centres of the elements to the horizontal centre of the selection
+    AlignCenter,
+
+    /// Moves the right edges of the elements to the right edge of the selection
+    AlignRight,
+
+    /// Moves the top edges of the elements to the top edge of the selection
+    AlignTop,
+
+    /// Moves the centres of the elements to the vertical centre of the selection
+    AlignMiddle,
+
+    /// Moves the bottom edges of the elements to the bottom edge of the selection
+    AlignBottom,
+
+    /// Spaces the centres of the elements evenly between the leftmost and rightmost elements
+    DistributeHorizontally,
+
+    /// Spaces the centres of the elements evenly between the topmost and bottommost elements
+    DistributeVertically
+}
+
+impl Arrangement {
+    ///
+    /// Works out how far each element needs to move to be arranged, given their bounding boxes
+    ///
+    /// Elements that don't need to move are left out of the result.
+    ///
+    pub fn offsets(&self, bounds: &[(ElementId, Rect)]) -> Vec<(ElementId, (f32, f32))> {
+        use self::Arrangement::*;
+
+        let bounds          = bounds.iter().map(|&(element_id, bounds)| (element_id, bounds.normalize())).collect::<Vec<_>>();
+        let selection       = bounds.iter().fold(Rect::empty(), |selection, &(_, bounds)| selection.union(bounds));
+        let center          = |bounds: &Rect| ((bounds.x1+bounds.x2)/2.0, (bounds.y1+bounds.y2)/2.0);
+
+        let offsets: Vec<(ElementId, (f32, f32))> = match self {
+            AlignLeft               => bounds.iter().map(|&(element_id, bounds)| (element_id, (selection.x1-bounds.x1, 0.0))).collect(),
+            AlignCenter             => bounds.iter().map(|&(element_id, bounds)| (element_id, (center(&selection).0-center(&bounds).0, 0.0))).collect(),
+            AlignRight              => bounds.iter().map(|&(element_id, bounds)| (element_id, (selection.x2-bounds.x2, 0.0))).collect(),
+            AlignTop                => bounds.iter().map(|&(element_id, bounds)| (element_id, (0.0, selection.y2-bounds.y2))).collect(),
+            AlignMiddle             => bounds.iter().map(|&(element_id, bounds)| (element_id, (0.0, center(&selection).1-center(&bounds).1))).collect(),
+            AlignBottom             => bounds.iter().map(|&(element_id, bounds)| (element_id, (0.0, selection.y1-bounds.y1))).collect(),
+
+            DistributeHorizontally  => Self::distribute(bounds.iter().map(|&(element_id, bounds)| (element_id, center(&bounds).0)).collect())
+                .into_iter()
+                .map(|(element_id, offset)| (element_id, (offset, 0.0)))
+                .collect(),
+            DistributeVertically    => Self::distribute(bounds.iter().map(|&(element_id, bounds)| (element_id, center(&bounds).1)).collect())
+                .into_iter()
+                .map(|(element_id, offset)| (element_id, (0.0, offset)))
+                .collect()
+        };
+
+        offsets.into_iter()
+            .filter(|&(_, (x, y))| x != 0.0 || y != 0.0)
+            .collect()
+    }
+
+    ///
+    /// Given the positions of a set of elements along an axis, returns how far each one needs to move to be evenly spaced
+    ///
+    fn distribute(mut positions: Vec<(ElementId, f32)>) -> Vec<(ElementId, f32)> {
+        if positions.len() < 3 {
+            return vec![];
+        }
+
+        positions.sort_by(|&(_, a), &(_, b)| a.partial_cmp(&b).unwrap_or(Ordering::Equal));
+
+        let first   = positions[0].1;
+        let last    = positions[positions.len()-1].1;
+        let spacing = (last-first) / ((positions.len()-1) as f32);
+
+        positions.into_iter()
+            .enumerate()
+            .map(|(index, (element_id, position))| (element_id, first + spacing*(index as f32) - position))
+            .collect()
+    }
+
+    ///
+    /// Creates the edits that arrange a set of elements in a frame at a particular time
+    ///
+    pub fn to_animation_edits<Anim: Animation>(&self, animation: &Anim, when: Duration, bounds: &[(ElementId, Rect)]) -> Vec<AnimationEdit> {
+        self.offsets(bounds)
+            .into_iter()
+            .flat_map(|(element_id, (x, y))| MotionEditAction::MoveElements(vec![element_id], when, (0.0, 0.0), (x, y)).to_animation_edits(animation))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bounds() -> Vec<(ElementId, Rect)> {
+        vec![
+            (ElementId::Assigned(1), Rect::with_points(0.0, 0.0, 10.0, 10.0)),
+            (ElementId::Assigned(2), Rect::with_points(20.0, 30.0, 40.0, 50.0)),
+            (ElementId::Assigned(3), Rect::with_points(100.0, 5.0, 110.0, 25.0))
+        ]
+    }
+
+    #[test]
+    fn align_left() {
+        assert!(Arrangement::AlignLeft.offsets(&bounds()) == vec![(ElementId::Assigned(2), (-20.0, 0.0)), (ElementId::Assigned(3), (-100.0, 0.0))]);
+    }
+
+    #[test]
+    fn align_middle() {
+        assert!(Arrangement::AlignMiddle.offsets(&bounds()) == vec![(ElementId::Assigned(1), (0.0, 20.0)), (ElementId::Assigned(2), (0.0, -15.0)), (ElementId::Assigned(3), (0.0, 10.0))]);
+    }
+
+    #[test]
+    fn distribute_horizontally() {
+        assert!(Arrangement::DistributeHorizontally.offsets(&bounds()) == vec![(ElementId::Assigned(2), (25.0, 0.0))]);
+    }
+
+    #[test]
+    fn distribute_needs_three_elements() {
+        assert!(Arrangement::DistributeVertically.offsets(&bounds()[0..2]) == vec![]);
+    }
+}
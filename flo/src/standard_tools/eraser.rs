@@ -9,16 +9,48 @@ use animation::*;
 
 use futures::*;
 use std::sync::*;
+use std::time::Duration;
 
 ///
-/// TODO: really, we should make the eraser subtract from existing paths rather
-/// than drawing over the top (this means when moving things around, any erasings
-/// stick around: also when something is entire erased it should be removed from
-/// the drawing).
-/// 
-/// We need to add path arithmetic at least before this is possible to do,
-/// however.
-/// 
+/// The ways that the eraser can remove things from the drawing
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EraserMode {
+    /// Cuts the parts of brush strokes under the eraser out of the drawing (deleting strokes that are entirely erased)
+    Vector,
+
+    /// Paints over the existing drawing using an erasing brush
+    Paint
+}
+
+///
+/// Data for the eraser tool
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct EraserData {
+    /// The ink settings used for the eraser (and its preview)
+    pub ink: InkData,
+
+    /// How the eraser removes things from the drawing
+    pub mode: EraserMode,
+
+    /// The time of the frame that is being erased
+    pub when: Duration,
+
+    /// The path followed by the eraser stroke that's in progress
+    pub erase_path: Arc<Vec<(f32, f32)>>
+}
+
+///
+/// The eraser UI model
+///
+pub struct EraserModel {
+    /// The ink settings for the eraser
+    pub ink: InkModel,
+
+    /// How the eraser removes things from the drawing
+    pub mode: Binding<EraserMode>
+}
 
 ///
 /// The Eraser tool (Erasers control points of existing objects)
@@ -36,62 +68,179 @@ impl Eraser {
             ink: Ink::new()
         }
     }
+
+    ///
+    /// Returns the edits that erase the path in a set of eraser data
+    ///
+    fn erase_edits<Anim: Animation>(animation: &Anim, data: &EraserData) -> Vec<AnimationEdit> {
+        let radius = data.ink.brush_properties.size / 2.0;
+
+        EraseEditAction::ErasePath(data.ink.selected_layer, data.when, Arc::clone(&data.erase_path), radius)
+            .to_animation_edits(animation)
+    }
 }
 
 impl<Anim: Animation+'static> Tool<Anim> for Eraser {
-    type ToolData   = InkData;
-    type Model      = InkModel;
+    type ToolData   = EraserData;
+    type Model      = EraserModel;
 
     fn tool_name(&self) -> String { "Eraser".to_string() }
 
     fn image_name(&self) -> String { "eraser".to_string() }
 
-    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> InkModel {
-        let mut model = InkModel::new();
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> EraserModel {
+        let mut ink = InkModel::new();
 
-        model.size.set(10.0);
+        ink.size.set(10.0);
 
-        model
+        EraserModel {
+            ink:    ink,
+            mode:   bind(EraserMode::Vector)
+        }
     }
 
-    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &InkModel) -> Option<Arc<dyn Controller>> {
-        Some(Arc::new(EraserMenuController::new(&tool_model.size, &tool_model.opacity)))
+    fn create_menu_controller(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &EraserModel) -> Option<Arc<dyn Controller>> {
+        let preset_model = tool_model.ink.clone();
+        let save_model   = tool_model.ink.clone();
+
+        Some(Arc::new(EraserMenuController::new(&tool_model.ink.size, &tool_model.ink.opacity, &tool_model.mode, &tool_model.ink.brush_definition,
+            &flo_model.brush_presets, move |preset| preset_model.apply_preset(preset), move |name| save_model.save_preset(name, BrushDrawingStyle::Erase))))
     }
 
-    fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &InkModel) -> Box<dyn Stream<Item=ToolAction<InkData>, Error=()>+Send> {
+    fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &EraserModel) -> Box<dyn Stream<Item=ToolAction<EraserData>, Error=()>+Send> {
         // Fetch the brush properties
-        let brush_properties    = tool_model.brush_properties.clone();
+        let brush_properties    = tool_model.ink.brush_properties.clone();
+        let brush_definition    = tool_model.ink.brush_definition.clone();
+        let mode                = tool_model.mode.clone();
         let selected_layer      = flo_model.timeline().selected_layer.clone();
+        let current_time        = flo_model.timeline().current_time.clone();
 
         // Create a computed binding that generates the data for the brush
-        let ink_data            = computed(move || {
-            InkData {
-                brush:              BrushDefinition::Ink(InkDefinition::default()),
-                brush_properties:   brush_properties.get(),
-                selected_layer:     selected_layer.get().unwrap_or(0)
+        let eraser_data         = computed(move || {
+            EraserData {
+                ink:        InkData {
+                    brush:              brush_definition.get(),
+                    brush_properties:   brush_properties.get(),
+                    selected_layer:     selected_layer.get().unwrap_or(0)
+                },
+                mode:       mode.get(),
+                when:       current_time.get(),
+                erase_path: Arc::new(vec![])
             }
         });
 
         // Turn the computed values into a stream and update the brush whenever the values change
-        Box::new(follow(ink_data).map(|ink_data| ToolAction::Data(ink_data)))
+        let data_actions    = follow(eraser_data).map(|eraser_data| ToolAction::Data(eraser_data));
+        let edit_actions    = tool_model.ink.edit_actions(&*flo_model);
+
+        Box::new(data_actions.select(edit_actions))
     }
 
-    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<InkData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<InkData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<InkData>>> {
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<EraserData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<EraserData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<EraserData>>> {
         use self::ToolAction::*;
         use self::BrushPreviewAction::*;
 
         let ink: &dyn Tool<Anim, ToolData=InkData, Model=InkModel> = &self.ink;
 
-        // As for the ink tool, except that we use the eraser drawing style
-        let actions = ink.actions_for_input(flo_model, data, input)
-            .map(|action| {
+        let mut data        = data;
+        let mut actions     = vec![];
+        let mut data_change = false;
+
+        for input in input {
+            // Track the path followed by the eraser in the tool data
+            let ink_input = match input {
+                ToolInput::Data(new_data) => {
+                    // Data that's just our own feedback needs no further processing
+                    if data.as_ref() == Some(&new_data) { continue; }
+
+                    // Changes from the model keep the path of any eraser stroke that's in progress
+                    let mut new_data = (*new_data).clone();
+                    if let Some(ref data) = data {
+                        new_data.erase_path = data.erase_path.clone();
+                    }
+
+                    let ink_data    = Arc::new(new_data.ink.clone());
+                    data            = Some(Arc::new(new_data));
+
+                    ToolInput::Data(ink_data)
+                },
+
+                ToolInput::Paint(painting) => {
+                    if let Some(ref mut data) = data {
+                        // Continuing a stroke adds to the existing path, other actions start a new one
+                        let new_path = match painting.action {
+                            PaintAction::Start      => Some(vec![]),
+                            PaintAction::Continue   => None,
+                            PaintAction::Finish     => if data.mode == EraserMode::Vector { None } else { Some(vec![]) },
+                            PaintAction::Cancel     => Some(vec![])
+                        };
+
+                        if let Some(new_path) = new_path {
+                            Arc::make_mut(data).erase_path = Arc::new(new_path);
+                            data_change = true;
+                        }
+
+                        // The path is shared with the data that was fed back, so it's copied the first time it's extended in a set of inputs
+                        match painting.action {
+                            PaintAction::Start | PaintAction::Continue  => {
+                                Arc::make_mut(&mut Arc::make_mut(data).erase_path).push(painting.location);
+                                data_change = true;
+                            },
+                            _                                           => { }
+                        }
+                    }
+
+                    ToolInput::Paint(painting)
+                },
+
+                ToolInput::Select               => ToolInput::Select,
+                ToolInput::Deselect             => ToolInput::Deselect,
+                ToolInput::PaintDevice(device)  => ToolInput::PaintDevice(device)
+            };
+
+            // Use the ink tool to generate the preview for the eraser
+            let ink_data    = data.as_ref().map(|data| Arc::new(data.ink.clone()));
+            let ink_actions = ink.actions_for_input(flo_model.clone(), ink_data, Box::new(vec![ink_input].into_iter()))
+                .collect::<Vec<_>>();
+
+            for action in ink_actions {
                 match action {
-                    BrushPreview(BrushDefinition(brush, BrushDrawingStyle::Draw)) => BrushPreview(BrushDefinition(brush, BrushDrawingStyle::Erase)),
-                    
-                    other => other
+                    // The preview uses the erasing drawing style
+                    BrushPreview(BrushDefinition(brush, BrushDrawingStyle::Draw)) => actions.push(BrushPreview(BrushDefinition(brush, BrushDrawingStyle::Erase))),
+
+                    BrushPreview(Commit) => {
+                        let vector_data = data.as_mut().filter(|data| data.mode == EraserMode::Vector);
+
+                        if let Some(data) = vector_data {
+                            // In vector mode, the preview is replaced by edits that cut the erased sections out of the brush strokes
+                            actions.push(BrushPreview(Clear));
+                            actions.extend(Self::erase_edits(&*flo_model, data).into_iter().map(|edit| Edit(edit)));
+
+                            Arc::make_mut(data).erase_path = Arc::new(vec![]);
+                            data_change = true;
+                        } else {
+                            actions.push(BrushPreview(Commit));
+                        }
+                    },
+
+                    BrushPreview(preview)   => actions.push(BrushPreview(preview)),
+                    Data(_)                 => { },
+                    InvalidateFrame         => actions.push(InvalidateFrame),
+                    Edit(edit)              => actions.push(Edit(edit)),
+                    Overlay(overlay)        => actions.push(Overlay(overlay)),
+                    ClearSelection          => actions.push(ClearSelection),
+                    Select(element)         => actions.push(Select(element))
                 }
-            });
+            }
+        }
+
+        // Feed back the eraser path so it's available for the next set of inputs
+        if data_change {
+            if let Some(data) = data {
+                actions.push(Data((*data).clone()));
+            }
+        }
 
-        Box::new(actions)
+        Box::new(actions.into_iter())
     }
 }
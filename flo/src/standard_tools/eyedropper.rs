@@ -0,0 +1,275 @@
+use super::ink::*;
+use super::super::menu::*;
+use super::super::tools::*;
+use super::super::model::*;
+
+use ui::*;
+use binding::*;
+use animation::*;
+
+use futures::*;
+use std::sync::*;
+
+/// How far the pointer can be from the edge of a brush stroke and still pick it up
+const PICK_TOLERANCE: f32 = 4.0;
+
+///
+/// The eyedropper UI model
+///
+pub struct EyedropperModel {
+    /// True if the eyedropper picks up the brush definition (eg, ink or calligraphy) as well as the brush properties
+    pub pick_brush: Binding<bool>
+}
+
+///
+/// The Eyedropper tool (copies the brush settings from an existing brush stroke to the ink tool)
+///
+pub struct Eyedropper { }
+
+impl Eyedropper {
+    ///
+    /// Creates a new instance of the Eyedropper tool
+    ///
+    pub fn new() -> Eyedropper {
+        Eyedropper {}
+    }
+
+    ///
+    /// Finds the properties of the topmost element under a point
+    ///
+    fn properties_at_point(frame: &FrameModel, point: (f32, f32)) -> Option<Arc<VectorProperties>> {
+        // Only elements whose path is under the pointer count (being inside the bounding box isn't enough)
+        let element_id = frame.elements_at_point(point, PICK_TOLERANCE)
+            .filter_map(|element_match| match element_match {
+                ElementMatch::InsidePath(element_id)    => Some(element_id),
+                ElementMatch::OnlyInBounds(_)           => None
+            })
+            .nth(0)?;
+
+        frame.elements.get()
+            .iter()
+            .find(|(vector, _)| vector.id() == element_id)
+            .map(|(_, properties)| Arc::clone(properties))
+    }
+
+    ///
+    /// Copies the brush settings from some element properties to an ink model
+    ///
+    fn pick(ink_model: &InkModel, properties: &VectorProperties, pick_brush: bool) {
+        if pick_brush {
+            let (definition, _drawing_style) = properties.brush.to_definition();
+            ink_model.apply_brush(Some(&definition), &properties.brush_properties);
+        } else {
+            ink_model.apply_brush(None, &properties.brush_properties);
+        }
+    }
+}
+
+impl<Anim: Animation+'static> Tool<Anim> for Eyedropper {
+    type ToolData   = bool;
+    type Model      = EyedropperModel;
+
+    fn tool_name(&self) -> String { "Eyedropper".to_string() }
+
+    fn image_name(&self) -> String { "eyedropper".to_string() }
+
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> EyedropperModel {
+        EyedropperModel {
+            pick_brush: bind(false)
+        }
+    }
+
+    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &EyedropperModel) -> Option<Arc<dyn Controller>> {
+        Some(Arc::new(EyedropperMenuController::new(&tool_model.pick_brush)))
+    }
+
+    ///
+    /// The data for this tool is whether or not it picks up the brush definition
+    ///
+    fn actions_for_model(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &EyedropperModel) -> Box<dyn Stream<Item=ToolAction<bool>, Error=()>+Send> {
+        Box::new(follow(tool_model.pick_brush.clone()).map(ToolAction::Data))
+    }
+
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<bool>>, input: Box<dyn 'a+Iterator<Item=ToolInput<bool>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<bool>>> {
+        let mut pick_brush  = data.map(|data| *data).unwrap_or(false);
+        let ink_model       = flo_model.tools().model_for_tool_with_name::<InkModel>("Ink", Arc::clone(&flo_model));
+
+        for input in input {
+            match input {
+                ToolInput::Data(new_data)   => pick_brush = *new_data,
+
+                // Pick up the brush under the pointer whenever it moves, so the user can drag around to find the stroke they want
+                ToolInput::Paint(painting) if painting.action != PaintAction::Cancel => {
+                    let properties = Self::properties_at_point(flo_model.frame(), painting.location);
+
+                    if let (Some(ink_model), Some(properties)) = (ink_model.as_ref(), properties) {
+                        Self::pick(ink_model, &properties, pick_brush);
+                    }
+                },
+
+                _ => ()
+            }
+        }
+
+        // The eyedropper only updates the ink model, so it never generates any actions of its own
+        Box::new(vec![].into_iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use animation::brushes::*;
+    use animation::inmemory::*;
+    use canvas::*;
+    use futures::executor;
+
+    use std::time::Duration;
+
+    fn calligraphy_properties() -> VectorProperties {
+        VectorProperties {
+            brush:              Arc::new(CalligraphyBrush::new(&CalligraphyDefinition::default(), BrushDrawingStyle::Draw)),
+            brush_properties:   BrushProperties {
+                size:       12.0,
+                opacity:    0.5,
+                color:      Color::Rgba(1.0, 0.0, 0.0, 1.0),
+                gradient:   None
+            }
+        }
+    }
+
+    #[test]
+    fn pick_copies_brush_properties() {
+        let ink_model = InkModel::new();
+
+        Eyedropper::pick(&ink_model, &calligraphy_properties(), false);
+
+        assert!(ink_model.brush_properties.get() == calligraphy_properties().brush_properties);
+        assert!(!ink_model.calligraphy.get());
+    }
+
+    #[test]
+    fn pick_can_copy_brush_definition() {
+        let ink_model = InkModel::new();
+
+        Eyedropper::pick(&ink_model, &calligraphy_properties(), true);
+
+        assert!(ink_model.calligraphy.get());
+    }
+
+    #[test]
+    fn pick_keeps_whole_ink_definition() {
+        let ink_model   = InkModel::new();
+        let ink_defn    = InkDefinition { min_width: 0.5, max_width: 3.0, scale_up_distance: 42.0, ..InkDefinition::default() };
+        let properties  = VectorProperties {
+            brush:              Arc::new(InkBrush::new(&ink_defn, BrushDrawingStyle::Draw)),
+            brush_properties:   calligraphy_properties().brush_properties
+        };
+
+        Eyedropper::pick(&ink_model, &properties, true);
+
+        assert!(!ink_model.calligraphy.get());
+        assert!(ink_model.brush_definition.get() == BrushDefinition::Ink(ink_defn));
+    }
+
+    #[test]
+    fn pick_copies_gradient() {
+        let ink_model       = InkModel::new();
+        let mut properties  = calligraphy_properties();
+        properties.brush_properties.gradient = Some(BrushGradient { gradient_type: BrushGradientType::Radial, end_color: Color::Rgba(0.0, 0.0, 1.0, 1.0) });
+
+        Eyedropper::pick(&ink_model, &properties, false);
+
+        assert!(ink_model.gradient_type.get() == Some(BrushGradientType::Radial));
+        assert!(ink_model.brush_properties.get() == properties.brush_properties);
+    }
+
+    #[test]
+    fn pick_keeps_whole_calligraphy_definition() {
+        let ink_model           = InkModel::new();
+        let calligraphy_defn    = CalligraphyDefinition { nib_width: 9.0, nib_angle: 0.3, ..CalligraphyDefinition::default() };
+        let properties          = VectorProperties {
+            brush:              Arc::new(CalligraphyBrush::new(&calligraphy_defn, BrushDrawingStyle::Draw)),
+            brush_properties:   calligraphy_properties().brush_properties
+        };
+
+        Eyedropper::pick(&ink_model, &properties, true);
+
+        assert!(ink_model.brush_definition.get() == BrushDefinition::Calligraphy(calligraphy_defn));
+    }
+
+    fn model_with_stroke(definition: BrushDefinition, properties: BrushProperties) -> Arc<FloModel<InMemoryAnimation>> {
+        let model   = Arc::new(FloModel::new(InMemoryAnimation::new()));
+        let when    = Duration::from_millis(0);
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(0),
+                AnimationEdit::Layer(0, LayerEdit::AddKeyFrame(when)),
+                AnimationEdit::Layer(0, LayerEdit::Paint(when, PaintEdit::SelectBrush(ElementId::Unassigned, definition, BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(0, LayerEdit::Paint(when, PaintEdit::BrushProperties(ElementId::Unassigned, properties))),
+                AnimationEdit::Layer(0, LayerEdit::Paint(when, PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![(100.0, 100.0), (150.0, 100.0), (200.0, 100.0)].into_iter().map(RawPoint::from).collect()))))
+            ]).unwrap();
+        }
+
+        model
+    }
+
+    fn paint_at(action: PaintAction, location: (f32, f32)) -> ToolInput<bool> {
+        ToolInput::Paint(Painting {
+            action:     action,
+            pointer_id: 0,
+            location:   location,
+            pressure:   1.0,
+            tilt_x:     0.0,
+            tilt_y:     0.0,
+            modifiers:  PaintModifiers::none()
+        })
+    }
+
+    fn ink_model(flo_model: &Arc<FloModel<InMemoryAnimation>>) -> Arc<InkModel> {
+        flo_model.tools().model_for_tool_with_name::<InkModel>("Ink", Arc::clone(flo_model)).unwrap()
+    }
+
+    #[test]
+    fn painting_over_stroke_updates_ink_tool() {
+        let ink_defn    = InkDefinition { min_width: 0.5, max_width: 3.0, scale_up_distance: 42.0, ..InkDefinition::default() };
+        let properties  = calligraphy_properties().brush_properties;
+        let flo_model   = model_with_stroke(BrushDefinition::Ink(ink_defn.clone()), properties);
+        let eyedropper  = Eyedropper::new();
+
+        let actions     = eyedropper.actions_for_input(Arc::clone(&flo_model), Some(Arc::new(true)), Box::new(vec![paint_at(PaintAction::Start, (150.0, 100.0))].into_iter()));
+        assert!(actions.count() == 0);
+
+        let ink_model   = ink_model(&flo_model);
+        assert!(ink_model.brush_properties.get() == properties);
+        assert!(ink_model.brush_definition.get() == BrushDefinition::Ink(ink_defn));
+    }
+
+    #[test]
+    fn painting_away_from_stroke_leaves_ink_tool_alone() {
+        let flo_model   = model_with_stroke(BrushDefinition::Ink(InkDefinition::default()), calligraphy_properties().brush_properties);
+        let eyedropper  = Eyedropper::new();
+        let ink_model   = ink_model(&flo_model);
+        let original    = ink_model.brush_properties.get();
+
+        let actions     = eyedropper.actions_for_input(Arc::clone(&flo_model), Some(Arc::new(true)), Box::new(vec![paint_at(PaintAction::Start, (150.0, 300.0))].into_iter()));
+        assert!(actions.count() == 0);
+
+        assert!(ink_model.brush_properties.get() == original);
+    }
+
+    #[test]
+    fn cancelled_paint_does_not_pick() {
+        let properties  = calligraphy_properties().brush_properties;
+        let flo_model   = model_with_stroke(BrushDefinition::Ink(InkDefinition::default()), properties);
+        let eyedropper  = Eyedropper::new();
+
+        let actions     = eyedropper.actions_for_input(Arc::clone(&flo_model), Some(Arc::new(false)), Box::new(vec![paint_at(PaintAction::Cancel, (150.0, 100.0))].into_iter()));
+        assert!(actions.count() == 0);
+
+        assert!(ink_model(&flo_model).brush_properties.get() != properties);
+    }
+}
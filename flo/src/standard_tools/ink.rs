@@ -8,6 +8,7 @@ use binding::*;
 use animation::*;
 
 use futures::*;
+use futures::stream;
 use std::sync::*;
 
 ///
@@ -23,6 +24,7 @@ pub struct InkData {
 ///
 /// The ink UI model
 /// 
+#[derive(Clone)]
 pub struct InkModel {
     /// The size of the brush (pixels)
     pub size: Binding<f32>,
@@ -33,8 +35,47 @@ pub struct InkModel {
     /// The colour of the brush (in general alpha should be left at 1.0 here)
     pub color: Binding<Color>,
 
+    /// If set, the shape of the gradient that brush strokes are filled with
+    pub gradient_type: Binding<Option<BrushGradientType>>,
+
+    /// The colour at the end of the gradient (which starts at the brush colour)
+    pub gradient_color: Binding<Color>,
+
+    /// If set, brush strokes are filled with a gradient from the colour to the gradient's end colour
+    pub gradient: BindRef<Option<BrushGradient>>,
+
+    /// The ink brush that the pressure curve and velocity width are applied to (keeps the other settings of presets and picked brushes)
+    pub ink_brush: Binding<InkDefinition>,
+
+    /// The curve mapping the pressure of the input device to the pressure used by the brush
+    pub pressure_curve: Binding<PressureCurve>,
+
+    /// If set, the distance between points that produces the minimum brush width (the brush width is set by the speed of the stroke rather than the pressure)
+    pub velocity_width: Binding<Option<f32>>,
+
+    /// True if the ink tool should draw with a flat calligraphy nib instead of a round ink brush
+    pub calligraphy: Binding<bool>,
+
+    /// The shape of the nib used when drawing with the calligraphy brush
+    pub calligraphy_definition: Binding<CalligraphyDefinition>,
+
     /// The brush properties for the current brush view model
-    pub brush_properties: BindRef<BrushProperties>
+    pub brush_properties: BindRef<BrushProperties>,
+
+    /// The ink definition for the current brush view model
+    pub ink_definition: BindRef<InkDefinition>,
+
+    /// The brush definition for the current brush view model (ink or calligraphy)
+    pub brush_definition: BindRef<BrushDefinition>,
+
+    /// Presets the user has picked that have not been turned into edits yet
+    pub chosen_preset: RequestQueue<BrushPreset>,
+
+    /// Changes the user has made to the palette that have not been turned into edits yet
+    pub palette_edits: RequestQueue<AnimationEdit>,
+
+    /// Presets the user has saved that have not been turned into edits yet
+    pub new_preset: RequestQueue<BrushPreset>
 }
 
 ///
@@ -59,26 +100,190 @@ impl InkModel {
         let size                = bind(5.0);
         let opacity             = bind(1.0);
         let color               = bind(Color::Rgba(0.0, 0.0, 0.0, 1.0));
+        let gradient_type       = bind(None);
+        let gradient_color      = bind(Color::Rgba(1.0, 1.0, 1.0, 1.0));
+        let ink_brush           = bind(InkDefinition::default());
+        let pressure_curve      = bind(PressureCurve::linear());
+        let velocity_width      = bind(None);
+        let calligraphy         = bind(false);
+        let calligraphy_defn    = bind(CalligraphyDefinition::default());
 
-        let brush_properties    = Self::brush_properties(size.clone(), opacity.clone(), color.clone());
+        let gradient            = Self::gradient(gradient_type.clone(), gradient_color.clone());
+        let brush_properties    = Self::brush_properties(size.clone(), opacity.clone(), color.clone(), gradient.clone());
+        let ink_definition      = Self::ink_definition(ink_brush.clone(), pressure_curve.clone(), velocity_width.clone());
+        let brush_definition    = Self::brush_definition(ink_definition.clone(), calligraphy.clone(), calligraphy_defn.clone());
 
         InkModel {
-            size:               size,
-            opacity:            opacity,
-            color:              color,
-            brush_properties:   brush_properties
+            size:                   size,
+            opacity:                opacity,
+            color:                  color,
+            gradient_type:          gradient_type,
+            gradient_color:         gradient_color,
+            gradient:               gradient,
+            ink_brush:              ink_brush,
+            pressure_curve:         pressure_curve,
+            velocity_width:         velocity_width,
+            calligraphy:            calligraphy,
+            calligraphy_definition: calligraphy_defn,
+            brush_properties:       brush_properties,
+            ink_definition:         ink_definition,
+            brush_definition:       brush_definition,
+            chosen_preset:          RequestQueue::new(),
+            palette_edits:          RequestQueue::new(),
+            new_preset:             RequestQueue::new()
         }
     }
 
+    ///
+    /// Updates the settings in this model to match a brush preset
+    /// 
+    pub fn apply_preset(&self, preset: &BrushPreset) {
+        self.apply_brush(Some(&preset.definition), &preset.properties);
+
+        self.chosen_preset.send(preset.clone());
+    }
+
+    ///
+    /// Updates the settings in this model to match some brush properties and, optionally, a brush definition
+    /// 
+    pub fn apply_brush(&self, definition: Option<&BrushDefinition>, properties: &BrushProperties) {
+        self.size.clone().set(properties.size);
+        self.opacity.clone().set(properties.opacity);
+        self.color.clone().set(properties.color);
+        self.gradient_type.clone().set(properties.gradient.map(|gradient| gradient.gradient_type));
+
+        if let Some(gradient) = properties.gradient {
+            self.gradient_color.clone().set(gradient.end_color);
+        }
+
+        match definition {
+            Some(BrushDefinition::Ink(ink_defn)) => {
+                self.ink_brush.clone().set(ink_defn.clone());
+                self.pressure_curve.clone().set(ink_defn.pressure_curve.clone());
+                self.velocity_width.clone().set(ink_defn.velocity_width);
+                self.calligraphy.clone().set(false);
+            },
+
+            Some(BrushDefinition::Calligraphy(calligraphy_defn)) => {
+                self.calligraphy_definition.clone().set(calligraphy_defn.clone());
+                self.calligraphy.clone().set(true);
+            },
+
+            Some(BrushDefinition::Simple) | None => { }
+        }
+    }
+
+    ///
+    /// Adds a swatch to the palette of the animation
+    /// 
+    pub fn add_swatch(&self, swatch: Swatch) {
+        self.palette_edits.send(AnimationEdit::SetSwatch(swatch));
+    }
+
+    ///
+    /// Removes a swatch from the palette of the animation
+    /// 
+    pub fn remove_swatch(&self, name: String) {
+        self.palette_edits.send(AnimationEdit::RemoveSwatch(name));
+    }
+
+    ///
+    /// Saves the current brush as a preset in the animation
+    /// 
+    pub fn save_preset(&self, name: String, drawing_style: BrushDrawingStyle) {
+        let preset = BrushPreset {
+            name:           name,
+            definition:     self.brush_definition.get(),
+            drawing_style:  drawing_style,
+            properties:     self.brush_properties.get()
+        };
+
+        self.new_preset.send(preset);
+    }
+
+    ///
+    /// Returns a stream of the edits generated by this model (selecting and saving presets and changing the palette)
+    /// 
+    pub fn edit_actions<Anim: Animation+'static, ToolData: Send+'static>(&self, flo_model: &FloModel<Anim>) -> Box<dyn Stream<Item=ToolAction<ToolData>, Error=()>+Send> {
+        let selected_layer      = flo_model.timeline().selected_layer.clone();
+        let current_time        = flo_model.timeline().current_time.clone();
+
+        let preset_actions = self.chosen_preset.requests()
+            .map(move |preset| {
+                let mut actions = vec![];
+
+                if let Some(layer_id) = selected_layer.get() {
+                    let when = current_time.get();
+
+                    actions.push(ToolAction::Edit(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::SelectBrush(ElementId::Unassigned, preset.definition, preset.drawing_style)))));
+                    actions.push(ToolAction::Edit(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushProperties(ElementId::Unassigned, preset.properties)))));
+                }
+
+                stream::iter_ok(actions)
+            })
+            .flatten();
+
+        let swatch_actions = self.palette_edits.requests()
+            .map(|edit| ToolAction::Edit(edit));
+
+        let save_actions = self.new_preset.requests()
+            .map(|preset| ToolAction::Edit(AnimationEdit::SetBrushPreset(preset)));
+
+        Box::new(preset_actions.select(swatch_actions).select(save_actions))
+    }
+
+    ///
+    /// Creates the ink definition from the model bindings
+    /// 
+    fn ink_definition(ink_brush: Binding<InkDefinition>, pressure_curve: Binding<PressureCurve>, velocity_width: Binding<Option<f32>>) -> BindRef<InkDefinition> {
+        let ink_definition = computed(move || {
+            InkDefinition {
+                pressure_curve: pressure_curve.get(),
+                velocity_width: velocity_width.get(),
+                ..ink_brush.get()
+            }
+        });
+
+        BindRef::from(ink_definition)
+    }
+
+    ///
+    /// Creates the brush definition from the model bindings
+    /// 
+    fn brush_definition(ink_definition: BindRef<InkDefinition>, calligraphy: Binding<bool>, calligraphy_defn: Binding<CalligraphyDefinition>) -> BindRef<BrushDefinition> {
+        let brush_definition = computed(move || {
+            if calligraphy.get() {
+                BrushDefinition::Calligraphy(calligraphy_defn.get())
+            } else {
+                BrushDefinition::Ink(ink_definition.get())
+            }
+        });
+
+        BindRef::from(brush_definition)
+    }
+
+    ///
+    /// Creates the gradient from the model bindings
+    /// 
+    fn gradient(gradient_type: Binding<Option<BrushGradientType>>, gradient_color: Binding<Color>) -> BindRef<Option<BrushGradient>> {
+        BindRef::from(computed(move || {
+            gradient_type.get().map(|gradient_type| BrushGradient {
+                gradient_type:  gradient_type,
+                end_color:      gradient_color.get()
+            })
+        }))
+    }
+
     ///
     /// Creates brush properties from the model bindings
     /// 
-    fn brush_properties(size: Binding<f32>, opacity: Binding<f32>, color: Binding<Color>) -> BindRef<BrushProperties> {
+    fn brush_properties(size: Binding<f32>, opacity: Binding<f32>, color: Binding<Color>, gradient: BindRef<Option<BrushGradient>>) -> BindRef<BrushProperties> {
         let brush_properties = computed(move || {
             BrushProperties {
                 size:       size.get(),
                 opacity:    opacity.get(),
-                color:      color.get()
+                color:      color.get(),
+                gradient:   gradient.get()
             }
         });
 
@@ -110,8 +315,8 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
     ///
     /// Creates the menu controller for this tool (or None if this tool has no menu controller)
     /// 
-    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &InkModel) -> Option<Arc<dyn Controller>> {
-        Some(Arc::new(InkMenuController::new(&tool_model.size, &tool_model.opacity, &tool_model.color)))
+    fn create_menu_controller(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &InkModel) -> Option<Arc<dyn Controller>> {
+        Some(Arc::new(InkMenuController::new(tool_model, &*flo_model)))
     }
 
     ///
@@ -120,19 +325,23 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
     fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &InkModel) -> Box<dyn Stream<Item=ToolAction<InkData>, Error=()>+Send> {
         // Fetch the brush properties
         let brush_properties    = tool_model.brush_properties.clone();
+        let brush_definition    = tool_model.brush_definition.clone();
         let selected_layer      = flo_model.timeline().selected_layer.clone();
 
         // Create a computed binding that generates the data for the brush
         let ink_data            = computed(move || {
             InkData {
-                brush:              BrushDefinition::Ink(InkDefinition::default()),
+                brush:              brush_definition.get(),
                 brush_properties:   brush_properties.get(),
                 selected_layer:     selected_layer.get().unwrap_or(0)
             }
         });
 
         // Turn the computed values into a stream and update the brush whenever the values change
-        Box::new(follow(ink_data).map(|ink_data| ToolAction::Data(ink_data)))
+        let data_actions    = follow(ink_data).map(|ink_data| ToolAction::Data(ink_data));
+        let edit_actions    = tool_model.edit_actions(&*flo_model);
+
+        Box::new(data_actions.select(edit_actions))
     }
 
     ///
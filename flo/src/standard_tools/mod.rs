@@ -1,15 +1,21 @@
 mod select;
+mod arrange;
 mod adjust;
 mod pan;
+mod zoom;
 mod pencil;
 mod ink;
 mod eraser;
+mod eyedropper;
 mod tool_sets;
 
 pub use self::select::*;
+pub use self::arrange::*;
 pub use self::adjust::*;
 pub use self::pan::*;
+pub use self::zoom::*;
 pub use self::pencil::*;
 pub use self::ink::*;
 pub use self::eraser::*;
+pub use self::eyedropper::*;
 pub use self::tool_sets::*;
@@ -1,12 +1,14 @@
 use super::super::tools::*;
 use super::super::model::*;
 
+use ui::*;
+use binding::*;
 use animation::*;
 
 use std::sync::*;
 
 ///
-/// The Pan tool (Pans control points of existing objects)
+/// The Pan tool (drags the frame around the canvas)
 /// 
 pub struct Pan { }
 
@@ -19,8 +21,8 @@ impl Pan {
     }
 }
 
-impl<Anim: Animation> Tool<Anim> for Pan {
-    type ToolData   = ();
+impl<Anim: Animation+'static> Tool<Anim> for Pan {
+    type ToolData   = Option<(f32, f32)>;
     type Model      = ();
 
     fn tool_name(&self) -> String { "Pan".to_string() }
@@ -29,7 +31,46 @@ impl<Anim: Animation> Tool<Anim> for Pan {
 
     fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> () { }
 
-    fn actions_for_input<'a>(&'a self, _flo_model: Arc<FloModel<Anim>>, _data: Option<Arc<()>>, _input: Box<dyn 'a+Iterator<Item=ToolInput<()>>>) -> Box<dyn Iterator<Item=ToolAction<()>>> {
-        Box::new(vec![].into_iter())
+    ///
+    /// The data for this tool is the point in the frame that was grabbed at the start of the drag
+    /// 
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<Option<(f32, f32)>>>, input: Box<dyn 'a+Iterator<Item=ToolInput<Option<(f32, f32)>>>>) -> Box<dyn Iterator<Item=ToolAction<Option<(f32, f32)>>>> {
+        let mut grabbed = data.and_then(|data| *data);
+        let mut actions = vec![];
+
+        for input in input {
+            match input {
+                ToolInput::Data(new_data)   => grabbed = *new_data,
+
+                ToolInput::Paint(painting)  => {
+                    match (painting.action, grabbed) {
+                        (PaintAction::Start, _)                 => {
+                            grabbed = Some(painting.location);
+                            actions.push(ToolAction::Data(grabbed));
+                        },
+
+                        (PaintAction::Continue, Some(grabbed))  => {
+                            // Move the view so the point that was grabbed is back under the pointer
+                            let view            = flo_model.view().get();
+                            let (x, y)          = view.center_point(flo_model.size.get());
+                            let (dx, dy)        = (painting.location.0-grabbed.0, painting.location.1-grabbed.1);
+
+                            flo_model.view().clone().set(view.with_center((x-dx, y-dy)));
+                        },
+
+                        (PaintAction::Finish, _) | (PaintAction::Cancel, _) => {
+                            grabbed = None;
+                            actions.push(ToolAction::Data(None));
+                        },
+
+                        _ => ()
+                    }
+                },
+
+                _ => ()
+            }
+        }
+
+        Box::new(actions.into_iter())
     }
 }
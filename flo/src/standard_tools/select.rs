@@ -2,6 +2,7 @@ use super::super::menu::*;
 use super::super::tools::*;
 use super::super::model::*;
 use super::super::style::*;
+use super::arrange::*;
 
 use ui::*;
 use canvas::*;
@@ -13,10 +14,111 @@ use std::sync::*;
 use std::time::Duration;
 use std::collections::{HashSet};
 
+/// How far a click can be from the edge of an element and still select it
+const PICK_TOLERANCE: f32 = 4.0;
+
+/// The size of the handles used to transform a selection (in canvas units at a zoom level of 1)
+const HANDLE_SIZE: f32 = 6.0;
+
+/// How far the rotation handle is from the top of the selection (in canvas units at a zoom level of 1)
+const ROTATE_HANDLE_DISTANCE: f32 = 24.0;
+
+/// The angle that rotations snap to when they're constrained (by holding down shift)
+const ROTATE_SNAP_DEGREES: f32 = 15.0;
+
+///
+/// The shape that the select tool uses when the user drags out an area to select
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SelectMode {
+    /// Selects the elements that overlap a rectangle
+    Rectangle,
+
+    /// Selects the elements that are entirely or partly inside a freeform region
+    Lasso
+}
+
+///
+/// How a new selection is combined with the existing one (chosen by the modifier keys held down when the selection starts)
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SelectionOperation {
+    /// The new selection replaces the existing one
+    Replace,
+
+    /// The new selection is added to the existing one
+    Add,
+
+    /// The new selection is removed from the existing one
+    Subtract
+}
+
+///
+/// The model for the select tool
+///
+pub struct SelectModel {
+    /// The shape used when dragging out an area to select
+    pub mode: Binding<SelectMode>,
+
+    /// True if transformations are stored as motions rather than by changing the points of the selected elements
+    pub animate: Binding<bool>,
+
+    /// The point that the selection rotates around (None to use the center of the selection, reset whenever the selection changes)
+    pub pivot: Binding<Option<(f32, f32)>>,
+
+    /// Requests to align or distribute the selected elements that have not been carried out yet
+    pub arrange: RequestQueue<Arrangement>
+}
+
+///
+/// The handles that can be dragged to transform a selection
+///
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum TransformHandle {
+    /// Scales the selection from one of its edges or corners
+    ///
+    /// The values are the side being dragged along the x and y axes: -1 for the minimum, 1 for the maximum or 0 for an axis that's not scaled
+    Scale(i8, i8),
+
+    /// Rotates the selection around the pivot
+    Rotate,
+
+    /// Moves the pivot that the selection rotates around
+    Pivot
+}
+
+///
+/// A transformation made by dragging one of the transform handles
+///
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum HandleTransform {
+    /// The selection is left as it is
+    Identity,
+
+    /// The selection is scaled around a point (the second value is the scale factors along the x and y axes)
+    Scale((f32, f32), (f32, f32)),
+
+    /// The selection is rotated around a point by an angle in radians
+    Rotate((f32, f32), f32)
+}
+
+impl HandleTransform {
+    ///
+    /// Returns the matrix that performs this transformation
+    ///
+    fn transform(&self) -> Transform2D {
+        match *self {
+            HandleTransform::Identity                   => Transform2D::identity(),
+            HandleTransform::Scale(origin, scale)       => Transform2D::translate(origin.0, origin.1) * Transform2D::scale(scale.0, scale.1) * Transform2D::translate(-origin.0, -origin.1),
+            HandleTransform::Rotate(origin, angle)      => Transform2D::translate(origin.0, origin.1) * Transform2D::rotate(angle) * Transform2D::translate(-origin.0, -origin.1)
+        }
+    }
+}
+
 ///
 /// The actions that the tool can take
 /// 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 enum SelectAction {
     /// No action (or the current action has been cancelled)
     NoAction,
@@ -30,8 +132,14 @@ enum SelectAction {
     /// The user is picking some items using a selection box
     RubberBand,
 
+    /// The user is picking some items by drawing a lasso around them
+    Lasso,
+
     /// The user has dragged their selection (either by selecting and moving away from the current location or by clicking on an item that's already selected)
-    Drag
+    Drag,
+
+    /// The user is dragging one of the handles around the selection
+    Transform(TransformHandle)
 }
 
 ///
@@ -45,6 +153,9 @@ pub struct SelectData {
     // The bounding boxes of the elements in the current frame
     bounding_boxes: Arc<Vec<(ElementId, Arc<VectorProperties>, Rect)>>,
 
+    // Spatial index of the elements in the current frame
+    spatial_index: Arc<ElementIndex>,
+
     // The current set of selected elements
     selected_elements: Arc<HashSet<ElementId>>,
 
@@ -54,6 +165,30 @@ pub struct SelectData {
     /// The current select action
     action: SelectAction,
 
+    /// The shape used when dragging out an area to select
+    mode: SelectMode,
+
+    /// How new selections are combined with the existing selection
+    operation: SelectionOperation,
+
+    /// The points in the lasso that's being drawn
+    lasso: Arc<Vec<(f32, f32)>>,
+
+    /// True if transformations are stored as motions
+    animate: bool,
+
+    /// The point that the selection rotates around (None to use the center of the selection)
+    pivot: Option<(f32, f32)>,
+
+    /// Where the pivot is kept once it has been moved (the data is replaced whenever the model changes)
+    model_pivot: Binding<Option<(f32, f32)>>,
+
+    /// Where dragged points snap to
+    snapping: Arc<Snapping>,
+
+    /// How far the canvas is zoomed in (the handles are scaled so they stay the same size on screen)
+    zoom: f32,
+
     /// The position where the current action started
     initial_position: RawPoint,
 
@@ -74,9 +209,18 @@ impl SelectData {
         SelectData {
             frame:                  self.frame.clone(),
             bounding_boxes:         self.bounding_boxes.clone(),
+            spatial_index:          self.spatial_index.clone(),
             selected_elements:      self.selected_elements.clone(),
             selected_elements_draw: self.selected_elements_draw.clone(),
             action:                 new_action,
+            mode:                   self.mode,
+            operation:              self.operation,
+            lasso:                  self.lasso.clone(),
+            animate:                self.animate,
+            pivot:                  self.pivot,
+            model_pivot:            self.model_pivot.clone(),
+            snapping:               self.snapping.clone(),
+            zoom:                   self.zoom,
             initial_position:       self.initial_position.clone(),
             drag_position:          self.drag_position.clone()
         }
@@ -89,9 +233,18 @@ impl SelectData {
         SelectData {
             frame:                  self.frame.clone(),
             bounding_boxes:         self.bounding_boxes.clone(),
+            spatial_index:          self.spatial_index.clone(),
             selected_elements:      self.selected_elements.clone(),
             selected_elements_draw: self.selected_elements_draw.clone(),
             action:                 self.action,
+            mode:                   self.mode,
+            operation:              self.operation,
+            lasso:                  Arc::new(vec![new_initial_position.position]),
+            animate:                self.animate,
+            pivot:                  self.pivot,
+            model_pivot:            self.model_pivot.clone(),
+            snapping:               self.snapping.clone(),
+            zoom:                   self.zoom,
             initial_position:       new_initial_position,
             drag_position:          None
         }
@@ -104,13 +257,43 @@ impl SelectData {
         SelectData {
             frame:                  self.frame.clone(),
             bounding_boxes:         self.bounding_boxes.clone(),
+            spatial_index:          self.spatial_index.clone(),
             selected_elements:      self.selected_elements.clone(),
             selected_elements_draw: self.selected_elements_draw.clone(),
             action:                 self.action,
+            mode:                   self.mode,
+            operation:              self.operation,
+            lasso:                  self.lasso.clone(),
+            animate:                self.animate,
+            pivot:                  self.pivot,
+            model_pivot:            self.model_pivot.clone(),
+            snapping:               self.snapping.clone(),
+            zoom:                   self.zoom,
             initial_position:       self.initial_position.clone(),
             drag_position:          Some(new_drag_position)
         }
     }
+
+    ///
+    /// Creates a copy of this object with a new point added to the lasso
+    /// 
+    fn with_lasso_point(&self, new_point: RawPoint) -> SelectData {
+        let mut lasso       = (*self.lasso).clone();
+        lasso.push(new_point.position);
+
+        let mut new_data    = self.with_drag_position(new_point);
+        new_data.lasso      = Arc::new(lasso);
+        new_data
+    }
+
+    ///
+    /// Creates a copy of this object with a new selection operation
+    ///
+    fn with_operation(&self, new_operation: SelectionOperation) -> SelectData {
+        let mut new_data    = self.clone();
+        new_data.operation  = new_operation;
+        new_data
+    }
 }
 
 impl Select {
@@ -179,6 +362,29 @@ impl Select {
             .collect()
     }
 
+    ///
+    /// Returns the drawing instructions for extending a lasso from one point to the next
+    /// 
+    fn draw_lasso_segment(from: (f32, f32), to: (f32, f32)) -> Vec<Draw> {
+        // Only the new part of the lasso is drawn, so drawing the whole lasso takes time proportional to its length
+        vec![
+            Draw::Layer(1),
+
+            Draw::NewPath,
+            Draw::Move(from.0, from.1),
+            Draw::Line(to.0, to.1),
+
+            // Use the same style as the rubber band
+            Draw::LineWidthPixels(2.0),
+            Draw::StrokeColor(RUBBERBAND_OUTLINE),
+            Draw::Stroke,
+
+            Draw::LineWidthPixels(0.5),
+            Draw::StrokeColor(RUBBERBAND_LINE),
+            Draw::Stroke
+        ]
+    }
+
     ///
     /// Returns how the specified selected elements should be rendered (as a selection)
     /// 
@@ -232,17 +438,17 @@ impl Select {
     }
 
     ///
-    /// Draws a set of dragged elements
+    /// Draws a set of dragged (or otherwise transformed) elements
     /// 
-    fn draw_drag(data: &SelectData, selected_elements: Vec<(ElementId, Arc<VectorProperties>, Rect)>, initial_point: (f32, f32), drag_point: (f32, f32)) -> Vec<Draw> {
+    fn draw_drag(data: &SelectData, selected_elements: Vec<(ElementId, Arc<VectorProperties>, Rect)>, transform: Transform2D) -> Vec<Draw> {
         let mut drawing = vec![];
 
         drawing.layer(1);
         drawing.clear_layer();
 
-        // Draw everything translated by the drag distance
+        // Draw everything transformed by the drag
         drawing.push_state();
-        drawing.transform(Transform2D::translate(drag_point.0-initial_point.0, drag_point.1-initial_point.1));
+        drawing.transform(transform);
 
         // Draw the 'shadows' of the elements
         if data.selected_elements_draw.len() > 0 {
@@ -258,6 +464,241 @@ impl Select {
         drawing
     }
 
+    ///
+    /// Returns the selected elements along with their properties and bounding boxes
+    /// 
+    fn selected_element_bounds(data: &SelectData) -> Vec<(ElementId, Arc<VectorProperties>, Rect)> {
+        data.bounding_boxes.iter()
+            .filter(|&&(ref id, _, _)| data.selected_elements.contains(id))
+            .cloned()
+            .collect()
+    }
+
+    ///
+    /// Returns the bounds of the selection, if anything is selected
+    /// 
+    fn selection_bounds(data: &SelectData) -> Option<Rect> {
+        let bounds = Self::selected_element_bounds(data)
+            .into_iter()
+            .fold(Rect::empty(), |bounds, (_, _, element_bounds)| bounds.union(element_bounds));
+
+        if bounds.is_zero_size() { None } else { Some(bounds.normalize()) }
+    }
+
+    ///
+    /// Returns the point that a selection with the specified bounds rotates around
+    /// 
+    fn pivot(data: &SelectData, bounds: &Rect) -> (f32, f32) {
+        data.pivot.unwrap_or_else(|| ((bounds.x1+bounds.x2)/2.0, (bounds.y1+bounds.y2)/2.0))
+    }
+
+    ///
+    /// Returns the transform handles for a selection and where they are
+    /// 
+    fn transform_handles(bounds: &Rect, pivot: (f32, f32), zoom: f32) -> Vec<(TransformHandle, (f32, f32))> {
+        let position = |side, min, max| match side { -1 => min, 1 => max, _ => (min+max)/2.0 };
+
+        // Handles earlier in the list take priority: the pivot comes last so it can't hide the other handles when the selection is small
+        let mut handles = vec![
+            (TransformHandle::Rotate, (position(0, bounds.x1, bounds.x2), bounds.y2 + ROTATE_HANDLE_DISTANCE/zoom))
+        ];
+
+        for &(side_x, side_y) in [(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)].iter() {
+            handles.push((TransformHandle::Scale(side_x, side_y), (position(side_x, bounds.x1, bounds.x2), position(side_y, bounds.y1, bounds.y2))));
+        }
+
+        handles.push((TransformHandle::Pivot, pivot));
+
+        handles
+    }
+
+    ///
+    /// Returns the transform handle at a particular point, if there is one
+    /// 
+    fn handle_at_point(data: &SelectData, point: (f32, f32)) -> Option<TransformHandle> {
+        Self::selection_bounds(data)
+            .and_then(|bounds| {
+                let handle_size = HANDLE_SIZE/data.zoom;
+
+                Self::transform_handles(&bounds, Self::pivot(data, &bounds), data.zoom)
+                    .into_iter()
+                    .filter(|&(_, (x, y))| (point.0-x).abs() <= handle_size && (point.1-y).abs() <= handle_size)
+                    .map(|(handle, _)| handle)
+                    .nth(0)
+            })
+    }
+
+    ///
+    /// Returns the transform handle that a drag starting at a particular point will move
+    ///
+    /// The pivot starts in the middle of the selection, so dragging a selected element takes priority over moving it
+    ///
+    fn handle_to_drag(&self, data: &SelectData, frame: &FrameModel, point: (f32, f32)) -> Option<TransformHandle> {
+        match Self::handle_at_point(data, point) {
+            Some(TransformHandle::Pivot) => {
+                let element = Self::element_at_point(frame, |element_id| self.is_selected(data, element_id), point);
+
+                if element.map(|element| self.is_selected(data, element)).unwrap_or(false) {
+                    None
+                } else {
+                    Some(TransformHandle::Pivot)
+                }
+            },
+
+            handle => handle
+        }
+    }
+
+    ///
+    /// Returns the drawing instructions for the transform handles around a selection
+    /// 
+    fn draw_handles(bounds: Option<Rect>, pivot: Option<(f32, f32)>, zoom: f32) -> Vec<Draw> {
+        let mut drawing = vec![];
+
+        drawing.layer(2);
+        drawing.clear_layer();
+
+        if let Some(bounds) = bounds {
+            let pivot       = pivot.unwrap_or_else(|| ((bounds.x1+bounds.x2)/2.0, (bounds.y1+bounds.y2)/2.0));
+            let handle_size = HANDLE_SIZE/zoom;
+            let half_size   = handle_size/2.0;
+
+            // The rotation handle sits on a line from the top of the selection
+            let top_center  = ((bounds.x1+bounds.x2)/2.0, bounds.y2);
+            drawing.new_path();
+            drawing.move_to(top_center.0, top_center.1);
+            drawing.line_to(top_center.0, top_center.1 + ROTATE_HANDLE_DISTANCE/zoom);
+
+            drawing.line_width_pixels(0.5);
+            drawing.stroke_color(SELECTION_BBOX);
+            drawing.stroke();
+
+            for (handle, (x, y)) in Self::transform_handles(&bounds, pivot, zoom) {
+                drawing.new_path();
+
+                match handle {
+                    TransformHandle::Scale(_, _)    => drawing.rect(x-half_size, y-half_size, x+half_size, y+half_size),
+                    TransformHandle::Rotate         => drawing.circle(x, y, half_size),
+                    TransformHandle::Pivot          => {
+                        drawing.circle(x, y, half_size);
+                        drawing.move_to(x-handle_size, y);
+                        drawing.line_to(x+handle_size, y);
+                        drawing.move_to(x, y-handle_size);
+                        drawing.line_to(x, y+handle_size);
+                    }
+                }
+
+                drawing.fill_color(SELECTION_FILL);
+                drawing.fill();
+
+                drawing.line_width_pixels(2.0);
+                drawing.stroke_color(SELECTION_OUTLINE);
+                drawing.stroke();
+
+                drawing.line_width_pixels(0.5);
+                drawing.stroke_color(SELECTION_BBOX);
+                drawing.stroke();
+            }
+        }
+
+        drawing
+    }
+
+    ///
+    /// Snaps the position a selection or one of its handles has been dragged to
+    /// 
+    fn snap_drag_position(data: &SelectData, action: SelectAction, to: (f32, f32)) -> (f32, f32) {
+        let bounds  = match Self::selection_bounds(data) {
+            Some(bounds)    => bounds,
+            None            => return to
+        };
+        let from    = data.initial_position.position;
+        let center  = ((bounds.x1+bounds.x2)/2.0, (bounds.y1+bounds.y2)/2.0);
+
+        // Work out the points that are being moved by the drag
+        let points = match action {
+            SelectAction::Drag                                      => vec![(bounds.x1, bounds.y1), (bounds.x2, bounds.y2), center],
+            SelectAction::Transform(TransformHandle::Scale(_, _))   => {
+                Self::transform_handles(&bounds, Self::pivot(data, &bounds), data.zoom)
+                    .into_iter()
+                    .filter(|&(handle, _)| SelectAction::Transform(handle) == action)
+                    .map(|(_, position)| position)
+                    .collect()
+            },
+            _                                                       => vec![]
+        };
+
+        if points.is_empty() {
+            return to;
+        }
+
+        // The selection shouldn't snap to itself
+        let offset = data.snapping.snap_offset(&points, (to.0-from.0, to.1-from.1), &data.selected_elements);
+
+        (from.0+offset.0, from.1+offset.1)
+    }
+
+    ///
+    /// Works out the transformation made by dragging a transform handle from one point to another
+    ///
+    /// Constrained transformations keep the proportions of the selection when scaling, and rotate in fixed steps.
+    /// 
+    fn transform_for_handle(data: &SelectData, handle: TransformHandle, from: (f32, f32), to: (f32, f32), constrain: bool) -> HandleTransform {
+        let bounds = match Self::selection_bounds(data) {
+            Some(bounds)    => bounds,
+            None            => return HandleTransform::Identity
+        };
+
+        match handle {
+            TransformHandle::Pivot => HandleTransform::Identity,
+
+            TransformHandle::Rotate => {
+                // Rotate around the pivot by the angle the drag has moved through
+                let pivot           = Self::pivot(data, &bounds);
+                let start_angle     = (from.1-pivot.1).atan2(from.0-pivot.0);
+                let end_angle       = (to.1-pivot.1).atan2(to.0-pivot.0);
+                let mut angle       = end_angle - start_angle;
+
+                if constrain {
+                    let snap    = ROTATE_SNAP_DEGREES.to_radians();
+                    angle       = (angle/snap).round() * snap;
+                }
+
+                HandleTransform::Rotate(pivot, angle)
+            },
+
+            TransformHandle::Scale(side_x, side_y) => {
+                // Scale relative to the opposite edge or corner (dragging past it flips the selection)
+                let anchor_position = |side, min, max| match side { -1 => max, 1 => min, _ => (min+max)/2.0 };
+                let handle_position = |side, min, max| match side { -1 => min, 1 => max, _ => (min+max)/2.0 };
+                let scale_factor    = |side, min: f32, max: f32, offset: f32| {
+                    let anchor  = anchor_position(side, min, max);
+                    let handle  = handle_position(side, min, max);
+
+                    if side == 0 || handle == anchor { 1.0 } else { (handle + offset - anchor) / (handle - anchor) }
+                };
+
+                let anchor          = (anchor_position(side_x, bounds.x1, bounds.x2), anchor_position(side_y, bounds.y1, bounds.y2));
+                let mut scale_x     = scale_factor(side_x, bounds.x1, bounds.x2, to.0-from.0);
+                let mut scale_y     = scale_factor(side_y, bounds.y1, bounds.y2, to.1-from.1);
+
+                if constrain {
+                    // Scale both axes by the same amount (using the axis that's been dragged the furthest for corner handles)
+                    let uniform = match (side_x, side_y) {
+                        (0, _)  => scale_y.abs(),
+                        (_, 0)  => scale_x.abs(),
+                        _       => scale_x.abs().max(scale_y.abs())
+                    };
+
+                    scale_x = uniform * if scale_x < 0.0 { -1.0 } else { 1.0 };
+                    scale_y = uniform * if scale_y < 0.0 { -1.0 } else { 1.0 };
+                }
+
+                HandleTransform::Scale(anchor, (scale_x, scale_y))
+            }
+        }
+    }
+
     ///
     /// Returns the drawing actions to highlight the specified element
     /// 
@@ -305,7 +746,7 @@ impl Select {
     /// 
     fn element_at_point<IsSelected: Fn(ElementId) -> bool>(model: &FrameModel, element_is_selected: IsSelected, point: (f32, f32)) -> Option<ElementId> {
         // Find all of the elements at this point
-        let elements = model.elements_at_point(point);
+        let elements = model.elements_at_point(point, PICK_TOLERANCE);
 
         // Choose an element inside the path if possible, otherwise pick an already selected elements or an element in bounds
         let mut fallback_selection = None;
@@ -339,10 +780,53 @@ impl Select {
         let target = Rect::with_points(point1.0, point1.1, point2.0, point2.1).normalize();
 
         // Result is the IDs attached to the bounding boxes that overlap this rectangle
-        data.bounding_boxes.iter()
-            .filter(|&&(ref _id, ref _props, ref bounding_box)| bounding_box.overlaps(&target))
-            .map(|&(ref id, ref _props, ref _bounding_box)| *id)
-            .collect()
+        data.spatial_index.elements_in_rect(&target)
+    }
+
+    ///
+    /// Returns how a new selection is combined with the existing one for the modifier keys that are held down
+    /// 
+    /// Shift adds to the selection, and alt or control subtract from it
+    /// 
+    fn operation_for_modifiers(modifiers: &PaintModifiers) -> SelectionOperation {
+        if modifiers.alt || modifiers.control {
+            SelectionOperation::Subtract
+        } else if modifiers.shift {
+            SelectionOperation::Add
+        } else {
+            SelectionOperation::Replace
+        }
+    }
+
+    ///
+    /// Returns the actions that combine a newly picked set of elements with the existing selection
+    /// 
+    fn selection_actions(&self, data: &SelectData, elements: Vec<ElementId>) -> Vec<ToolAction<SelectData>> {
+        match data.operation {
+            SelectionOperation::Replace => {
+                Some(ToolAction::ClearSelection).into_iter()
+                    .chain(elements.into_iter().map(ToolAction::Select))
+                    .collect()
+            },
+
+            SelectionOperation::Add => {
+                elements.into_iter()
+                    .filter(|item| !self.is_selected(data, *item))
+                    .map(ToolAction::Select)
+                    .collect()
+            },
+
+            SelectionOperation::Subtract => {
+                // There's no action to deselect a single element, so clear the selection and select whatever remains
+                let removed: HashSet<_> = elements.into_iter().collect();
+
+                Some(ToolAction::ClearSelection).into_iter()
+                    .chain(data.selected_elements.iter()
+                        .filter(|item| !removed.contains(item))
+                        .map(|item| ToolAction::Select(*item)))
+                    .collect()
+            }
+        }
     }
 
     ///
@@ -353,7 +837,30 @@ impl Select {
         let mut data        = data;
         let current_action  = data.action;
 
+        // The modifier keys held down at the start of an action decide how it changes the selection
+        let mut start_handle = None;
+        if paint.action == PaintAction::Start {
+            data            = Arc::new(data.with_operation(Self::operation_for_modifiers(&paint.modifiers)));
+            start_handle    = self.handle_to_drag(&data, &*animation.frame(), paint.location);
+        }
+
         match (current_action, paint.action) {
+            (_, PaintAction::Start) if start_handle.is_some() => {
+                // Start dragging one of the transform handles
+                let handle          = start_handle.unwrap();
+                let mut new_data    = data.with_action(SelectAction::Transform(handle))
+                    .with_initial_position(RawPoint::from(paint.location));
+
+                // Pre-render the elements so we can draw the transformation faster
+                if handle != TransformHandle::Pivot {
+                    let selected                    = Self::selected_element_bounds(&data);
+                    new_data.selected_elements_draw = Arc::new(Self::rendering_for_elements(&new_data, selected));
+                }
+
+                actions.push(ToolAction::Data(new_data.clone()));
+                data = Arc::new(new_data);
+            },
+
             (_, PaintAction::Start) => {
                 // Find the element at this point
                 // TODO: preferentially check if the point is within the bounds of an already selected element
@@ -376,8 +883,12 @@ impl Select {
                     data = Arc::new(new_data);
 
                 } else {
-                    // Clicking outside the current selection starts rubber-banding
-                    let new_data = data.with_action(SelectAction::RubberBand)
+                    // Clicking outside the current selection starts rubber-banding (or drawing a lasso)
+                    let area_action = match data.mode {
+                        SelectMode::Rectangle   => SelectAction::RubberBand,
+                        SelectMode::Lasso       => SelectAction::Lasso
+                    };
+                    let new_data = data.with_action(area_action)
                         .with_initial_position(RawPoint::from(paint.location));
 
                     actions.push(ToolAction::Data(new_data.clone()));
@@ -390,21 +901,23 @@ impl Select {
             (SelectAction::Select, PaintAction::Continue) => {
                 // TODO: only start rubber-banding once the mouse has moved a certain distance
 
-                // Dragging after making a new selection moves us to rubber-band mode
-                let mut new_data = data.with_action(SelectAction::RubberBand);
+                // Dragging after making a new selection moves us to rubber-band (or lasso) mode
+                let area_action = match data.mode {
+                    SelectMode::Rectangle   => SelectAction::RubberBand,
+                    SelectMode::Lasso       => SelectAction::Lasso
+                };
+                let new_data = data.with_action(area_action);
                 actions.push(ToolAction::Data(new_data.clone()));
                 data = Arc::new(new_data);
             },
 
             (SelectAction::Select, PaintAction::Finish) => {
                 // Select whatever was at the initial position
-                actions.push(ToolAction::ClearSelection);
-                if let Some(selected) = Self::element_at_point(&*animation.frame(), |element_id| self.is_selected(&data, element_id), data.initial_position.position) {
-                    actions.push(ToolAction::Select(selected));
-                }
+                let selected = Self::element_at_point(&*animation.frame(), |element_id| self.is_selected(&data, element_id), data.initial_position.position);
+                actions.extend(self.selection_actions(&data, selected.into_iter().collect()));
 
                 // Reset the action
-                let mut new_data = data.with_action(SelectAction::NoAction);
+                let new_data = data.with_action(SelectAction::NoAction);
                 actions.push(ToolAction::Data(new_data.clone()));
                 data = Arc::new(new_data);
             },
@@ -427,10 +940,10 @@ impl Select {
                 data = Arc::new(new_data);
 
                 // Select any items in this area
-                actions.push(ToolAction::ClearSelection);
-                data.drag_position.as_ref().map(|drag_position| {
-                    actions.extend(self.elements_in_area(&data, data.initial_position.position, drag_position.position).into_iter().map(|item| ToolAction::Select(item)));
-                });
+                let in_area = data.drag_position.as_ref()
+                    .map(|drag_position| self.elements_in_area(&data, data.initial_position.position, drag_position.position))
+                    .unwrap_or_default();
+                actions.extend(self.selection_actions(&data, in_area));
 
                 // Clear layer 1 (it's used to draw the rubber band)
                 actions.push(ToolAction::Overlay(OverlayAction::Draw(vec![
@@ -439,18 +952,56 @@ impl Select {
                 ])));
             },
 
+            // -- Lasso selection behaviour
+
+            (SelectAction::Lasso, PaintAction::Continue) => {
+                // Extend the lasso to the new point
+                let last_point  = data.drag_position.as_ref().unwrap_or(&data.initial_position).position;
+                let new_data    = data.with_lasso_point(RawPoint::from(paint.location));
+
+                actions.push(ToolAction::Data(new_data.clone()));
+                data = Arc::new(new_data);
+
+                actions.push(ToolAction::Overlay(OverlayAction::Draw(Self::draw_lasso_segment(last_point, paint.location))));
+            },
+
+            (SelectAction::Lasso, PaintAction::Finish) => {
+                // Reset the data state to 'no action'
+                let new_data = data.with_action(SelectAction::NoAction);
+                actions.push(ToolAction::Data(new_data.clone()));
+                data = Arc::new(new_data);
+
+                // Select any items inside the lasso
+                let in_lasso = animation.frame().elements_in_lasso(&data.lasso);
+                actions.extend(self.selection_actions(&data, in_lasso));
+
+                // Clear layer 1 (it's used to draw the lasso)
+                actions.push(ToolAction::Overlay(OverlayAction::Draw(vec![
+                    Draw::Layer(1),
+                    Draw::ClearLayer
+                ])));
+            },
+
             // -- Dragging behaviour
 
+            (SelectAction::Reselect, PaintAction::Finish) => {
+                // Clicking an element that's already selected removes it from the selection when subtracting
+                let new_data = data.with_action(SelectAction::NoAction);
+                actions.push(ToolAction::Data(new_data.clone()));
+                data = Arc::new(new_data);
+
+                if data.operation == SelectionOperation::Subtract {
+                    let selected = Self::element_at_point(&*animation.frame(), |element_id| self.is_selected(&data, element_id), data.initial_position.position);
+                    actions.extend(self.selection_actions(&data, selected.into_iter().collect()));
+                }
+            },
+
             (SelectAction::Reselect, PaintAction::Continue) => {
                 // This begins a dragging operation
                 let mut new_data = data.with_action(SelectAction::Drag);
 
                 // Pre-render the elements so we can draw the drag faster
-                let selected_elements   = Arc::clone(&data.selected_elements);
-                let selected            = data.bounding_boxes.iter()
-                    .filter(|&&(ref id, _, _)| selected_elements.contains(id))
-                    .map(|item| item.clone())
-                    .collect();
+                let selected            = Self::selected_element_bounds(&data);
                 new_data.selected_elements_draw = Arc::new(Self::rendering_for_elements(&new_data, selected));
 
                 // Update the tool data
@@ -465,13 +1016,10 @@ impl Select {
                 data = Arc::new(new_data);
 
                 // Draw the current drag state
-                let selected_elements   = Arc::clone(&data.selected_elements);
-                let selected            = data.bounding_boxes.iter()
-                    .filter(|&&(ref id, _, _)| selected_elements.contains(id))
-                    .map(|item| item.clone())
-                    .collect();
+                let selected            = Self::selected_element_bounds(&data);
+                let (from, to)          = (data.initial_position.position, Self::snap_drag_position(&data, SelectAction::Drag, paint.location));
 
-                let draw_drag = Self::draw_drag(&*data, selected, data.initial_position.position, paint.location);
+                let draw_drag = Self::draw_drag(&*data, selected, Transform2D::translate(to.0-from.0, to.1-from.1));
                 actions.push(ToolAction::Overlay(OverlayAction::Draw(draw_drag)));
             },
 
@@ -484,7 +1032,8 @@ impl Select {
                 // Create a motion for this element
                 let selected_element_ids    = data.selected_elements.iter().cloned().collect();
                 let edit_time               = data.frame.as_ref().map(|frame| frame.time_index()).unwrap_or(Duration::from_millis(0));
-                let move_elements           = MotionEditAction::MoveElements(selected_element_ids, edit_time, data.initial_position.position, paint.location);
+                let drag_to                 = Self::snap_drag_position(&data, SelectAction::Drag, paint.location);
+                let move_elements           = MotionEditAction::MoveElements(selected_element_ids, edit_time, data.initial_position.position, drag_to);
 
                 actions.extend(move_elements.to_animation_edits(&*animation).into_iter().map(|elem| ToolAction::Edit(elem)));
 
@@ -498,6 +1047,77 @@ impl Select {
                 ])));
             },
 
+            // -- Transform handle behaviour
+
+            (SelectAction::Transform(TransformHandle::Pivot), PaintAction::Continue) |
+            (SelectAction::Transform(TransformHandle::Pivot), PaintAction::Finish) => {
+                // Move the pivot to the new location (it can snap to anything, including the selection itself)
+                let new_action      = if paint.action == PaintAction::Finish { SelectAction::NoAction } else { data.action };
+                let mut new_data    = data.with_action(new_action).with_drag_position(RawPoint::from(paint.location));
+                new_data.pivot      = Some(data.snapping.snap_point(paint.location, &HashSet::new()));
+
+                // Keep the pivot in the model once the drag is finished (updating the model replaces the data, so it's left alone while dragging)
+                if paint.action == PaintAction::Finish {
+                    data.model_pivot.clone().set(new_data.pivot);
+                }
+
+                actions.push(ToolAction::Data(new_data.clone()));
+                data = Arc::new(new_data);
+
+                actions.push(ToolAction::Overlay(OverlayAction::Draw(Self::draw_handles(Self::selection_bounds(&data), data.pivot, data.zoom))));
+            },
+
+            (SelectAction::Transform(handle), PaintAction::Continue) => {
+                // Update the drag position
+                let new_data = data.with_drag_position(RawPoint::from(paint.location));
+                actions.push(ToolAction::Data(new_data.clone()));
+                data = Arc::new(new_data);
+
+                // Draw the selection with the transformation applied
+                let selected    = Self::selected_element_bounds(&data);
+                let drag_to     = Self::snap_drag_position(&data, data.action, paint.location);
+                let transform   = Self::transform_for_handle(&data, handle, data.initial_position.position, drag_to, paint.modifiers.shift);
+
+                let draw_drag = Self::draw_drag(&*data, selected, transform.transform());
+                actions.push(ToolAction::Overlay(OverlayAction::Draw(draw_drag)));
+            },
+
+            (SelectAction::Transform(handle), PaintAction::Finish) => {
+                // Reset the data state to 'no action'
+                let new_data = data.with_action(SelectAction::NoAction);
+                actions.push(ToolAction::Data(new_data.clone()));
+                data = Arc::new(new_data);
+
+                let drag_to                 = Self::snap_drag_position(&data, SelectAction::Transform(handle), paint.location);
+                let transform               = Self::transform_for_handle(&data, handle, data.initial_position.position, drag_to, paint.modifiers.shift);
+                let selected_element_ids    = data.selected_elements.iter().cloned().collect();
+                let edit_time               = data.frame.as_ref().map(|frame| frame.time_index()).unwrap_or(Duration::from_millis(0));
+
+                if data.animate {
+                    // Attach a motion to the selected elements that transforms them at the current time
+                    let motion = match transform {
+                        HandleTransform::Identity               => None,
+                        HandleTransform::Scale(origin, scale)   => Some(MotionEditAction::ScaleElements(selected_element_ids, edit_time, origin, scale)),
+                        HandleTransform::Rotate(origin, angle)  => Some(MotionEditAction::RotateElements(selected_element_ids, edit_time, origin, angle))
+                    };
+
+                    actions.extend(motion.into_iter().flat_map(|motion| motion.to_animation_edits(&*animation)).map(ToolAction::Edit));
+                } else if let Some(layer_id) = animation.timeline().selected_layer.get() {
+                    // Rewrite the selected elements with the transformation applied
+                    let transform_elements = TransformEditAction::TransformElements(layer_id, edit_time, selected_element_ids, transform.transform());
+                    actions.extend(transform_elements.to_animation_edits(&*animation).into_iter().map(ToolAction::Edit));
+                }
+
+                // Cause the frame to be redrawn
+                actions.push(ToolAction::InvalidateFrame);
+
+                // Clear the preview of the transformation
+                actions.push(ToolAction::Overlay(OverlayAction::Draw(vec![
+                    Draw::Layer(1),
+                    Draw::ClearLayer
+                ])));
+            },
+
             // -- Generic behaviour
 
             (_, PaintAction::Finish) => {
@@ -530,7 +1150,7 @@ impl Select {
 
 impl<Anim: 'static+Animation> Tool<Anim> for Select {
     type ToolData   = SelectData;
-    type Model      = ();
+    type Model      = SelectModel;
 
     fn tool_name(&self) -> String { "Select".to_string() }
 
@@ -539,29 +1159,38 @@ impl<Anim: 'static+Animation> Tool<Anim> for Select {
     ///
     /// Creates the model for the Select tool
     /// 
-    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> () { }
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> SelectModel {
+        SelectModel {
+            mode:       bind(SelectMode::Rectangle),
+            animate:    bind(false),
+            pivot:      bind(None),
+            arrange:    RequestQueue::new()
+        }
+    }
 
     ///
     /// Creates the menu bar controller for the select tool
     /// 
-    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, _tool_model: &()) -> Option<Arc<dyn Controller>> {
-        Some(Arc::new(SelectMenuController::new()))
+    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &SelectModel) -> Option<Arc<dyn Controller>> {
+        Some(Arc::new(SelectMenuController::new(&tool_model.mode, &tool_model.animate, &tool_model.arrange)))
     }
 
     ///
     /// Returns a stream containing the actions for the view and tool model for the select tool
     /// 
-    fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, _tool_model: &()) -> Box<dyn Stream<Item=ToolAction<SelectData>, Error=()>+Send> {
+    fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &SelectModel) -> Box<dyn Stream<Item=ToolAction<SelectData>, Error=()>+Send> {
         // The set of currently selected elements
         let selected_elements = flo_model.selection().selected_element.clone();
         let selected_elements = computed(move || -> HashSet<_> { selected_elements.get().into_iter().collect() });
 
         // Create a binding that works out the frame for the currently selected layer
-        let current_frame = flo_model.frame().frame.clone();
+        let current_frame   = flo_model.frame().frame.clone();
+        let pivot           = tool_model.pivot.clone();
+        let view            = flo_model.view().clone();
 
         // Follow it, and draw an overlay showing the bounding boxes of everything that's selected
-        let draw_selection_overlay = follow(computed(move || (current_frame.get(), selected_elements.get())))
-            .map(|(current_frame, selected_elements)| {
+        let draw_selection_overlay = follow(computed(move || (current_frame.get(), selected_elements.get(), pivot.get(), view.get().zoom)))
+            .map(|(current_frame, selected_elements, pivot, zoom)| {
                 if let Some(current_frame) = current_frame {
                     // Get the elements in the current frame
                     let elements        = current_frame.vector_elements().unwrap_or_else(|| Box::new(vec![].into_iter()));
@@ -600,9 +1229,11 @@ impl<Anim: 'static+Animation> Tool<Anim> for Select {
                         selection.stroke();
                     }
                     
-                    // Create the overlay drawing
+                    // Create the overlay drawing (with the transform handles on top)
+                    let handles = Self::draw_handles(if bounds.is_zero_size() { None } else { Some(bounds.normalize()) }, pivot, zoom);
                     let overlay = Self::selection_drawing_settings().into_iter()
-                        .chain(selection);
+                        .chain(selection)
+                        .chain(handles);
 
                     ToolAction::Overlay(OverlayAction::Draw(overlay.collect()))
                 } else {
@@ -631,21 +1262,65 @@ impl<Anim: 'static+Animation> Tool<Anim> for Select {
         // (this also resets any in-progress action)
         let current_frame       = flo_model.frame().frame.clone();
         let selected_elements   = flo_model.selection().selected_element.clone();
-        let data_for_model  = follow(computed(move || (current_frame.get(), selected_elements.get(), combined_bounding_boxes.get())))
-            .map(|(current_frame, selected_elements, combined_bounding_boxes)| {
+        let spatial_index       = flo_model.frame().spatial_index.clone();
+        let mode                = tool_model.mode.clone();
+        let animate             = tool_model.animate.clone();
+        let pivot               = tool_model.pivot.clone();
+        let model_pivot         = tool_model.pivot.clone();
+        let snapping            = Snapping::for_model(&*flo_model);
+        let view                = flo_model.view().clone();
+        let data_for_model  = follow(computed(move || (current_frame.get(), selected_elements.get(), combined_bounding_boxes.get(), spatial_index.get(), mode.get(), animate.get(), pivot.get(), snapping.get(), view.get().zoom)))
+            .map(move |(current_frame, selected_elements, combined_bounding_boxes, spatial_index, mode, animate, pivot, snapping, zoom)| {
                 ToolAction::Data(SelectData {
                     frame:                  current_frame,
                     bounding_boxes:         combined_bounding_boxes,
+                    spatial_index:          spatial_index,
                     selected_elements:      Arc::new(selected_elements.into_iter().collect()),
                     selected_elements_draw: Arc::new(vec![]),
                     action:                 SelectAction::NoAction,
+                    mode:                   mode,
+                    operation:              SelectionOperation::Replace,
+                    lasso:                  Arc::new(vec![]),
+                    animate:                animate,
+                    pivot:                  pivot,
+                    model_pivot:            model_pivot.clone(),
+                    snapping:               snapping,
+                    zoom:                   zoom,
                     initial_position:       RawPoint::from((0.0, 0.0)),
                     drag_position:          None
                 })
             });
         
+        // The pivot goes back to the center of the selection whenever the selection changes
+        let mut pivot           = tool_model.pivot.clone();
+        let reset_pivot         = follow(flo_model.selection().selected_element.clone())
+            .filter_map(move |_| -> Option<ToolAction<SelectData>> {
+                pivot.set(None);
+                None
+            });
+
+        // Whenever an arrangement is requested, move the selected elements to carry it out
+        let arrange_elements    = tool_model.arrange.requests()
+            .map(move |arrangement| {
+                // Fetch the bounding boxes of the selected elements
+                let selected_elements   = flo_model.selection().selected_element.get();
+                let bounding_boxes      = flo_model.frame().bounding_boxes.get();
+                let selected_bounds     = selected_elements.into_iter()
+                    .filter_map(|element_id| bounding_boxes.get(&element_id).map(|bounds| (element_id, *bounds)))
+                    .collect::<Vec<_>>();
+
+                // Move the elements, then redraw the frame
+                let edit_time           = flo_model.frame().frame.get().map(|frame| frame.time_index()).unwrap_or(Duration::from_millis(0));
+                let edits               = arrangement.to_animation_edits(&*flo_model, edit_time, &selected_bounds);
+
+                stream::iter_ok(edits.into_iter()
+                    .map(ToolAction::Edit)
+                    .chain(vec![ToolAction::InvalidateFrame]))
+            })
+            .flatten();
+
         // Generate the final stream
-        let select_stream = data_for_model.select(draw_selection_overlay);
+        let select_stream = data_for_model.select(draw_selection_overlay).select(reset_pivot).select(arrange_elements);
         Box::new(select_stream)
     }
 
@@ -657,7 +1332,8 @@ impl<Anim: 'static+Animation> Tool<Anim> for Select {
             // We build up a vector of actions to perform as we go
             let mut actions = vec![];
 
-            // Filter the input so that there is only a single paint continue event
+            // Filter the input so that there is only a single paint continue event (a lasso needs every point, so it's left alone)
+            let keep_all_continues          = data.action == SelectAction::Lasso;
             let input: Vec<_>               = input.collect();
             let mut seen_continue           = false;
             let mut reversed_filtered_input = vec![];
@@ -666,7 +1342,7 @@ impl<Anim: 'static+Animation> Tool<Anim> for Select {
             for input in input.into_iter().rev() {
                 match input {
                     ToolInput::Paint(painting) => {
-                        if painting.action == PaintAction::Continue {
+                        if painting.action == PaintAction::Continue && !keep_all_continues {
                             if !seen_continue {
                                 // Only push the first continue
                                 reversed_filtered_input.push(ToolInput::Paint(painting));
@@ -722,3 +1398,184 @@ impl<Anim: 'static+Animation> Tool<Anim> for Select {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::f32;
+
+    fn data_with_selection(selected: Vec<i64>, operation: SelectionOperation) -> SelectData {
+        SelectData {
+            frame:                  None,
+            bounding_boxes:         Arc::new(vec![]),
+            spatial_index:          Arc::new(ElementIndex::new()),
+            selected_elements:      Arc::new(selected.into_iter().map(ElementId::Assigned).collect()),
+            selected_elements_draw: Arc::new(vec![]),
+            action:                 SelectAction::NoAction,
+            mode:                   SelectMode::Rectangle,
+            operation:              operation,
+            lasso:                  Arc::new(vec![]),
+            animate:                false,
+            pivot:                  None,
+            model_pivot:            bind(None),
+            snapping:               Arc::new(Snapping::none()),
+            zoom:                   1.0,
+            initial_position:       RawPoint::from((0.0, 0.0)),
+            drag_position:          None
+        }
+    }
+
+    fn data_with_bounds(bounds: Rect) -> SelectData {
+        let mut data        = data_with_selection(vec![1], SelectionOperation::Replace);
+        data.bounding_boxes = Arc::new(vec![(ElementId::Assigned(1), Arc::new(VectorProperties::default()), bounds)]);
+        data
+    }
+
+    fn is_close(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0-b.0).abs() < 0.0001 && (a.1-b.1).abs() < 0.0001
+    }
+
+    ///
+    /// Describes selection actions as the element selected (or None for clearing the selection)
+    ///
+    fn selection_changes(actions: Vec<ToolAction<SelectData>>) -> Vec<Option<ElementId>> {
+        actions.into_iter()
+            .map(|action| match action {
+                ToolAction::ClearSelection      => None,
+                ToolAction::Select(element_id)  => Some(element_id),
+                _                               => panic!("Unexpected action")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn modifiers_choose_selection_operation() {
+        let shift   = PaintModifiers { shift: true, ..PaintModifiers::none() };
+        let alt     = PaintModifiers { alt: true, ..PaintModifiers::none() };
+
+        assert!(Select::operation_for_modifiers(&PaintModifiers::none()) == SelectionOperation::Replace);
+        assert!(Select::operation_for_modifiers(&shift) == SelectionOperation::Add);
+        assert!(Select::operation_for_modifiers(&alt) == SelectionOperation::Subtract);
+    }
+
+    #[test]
+    fn replace_selection() {
+        let data    = data_with_selection(vec![1, 2], SelectionOperation::Replace);
+        let actions = Select::new().selection_actions(&data, vec![ElementId::Assigned(3)]);
+
+        assert!(selection_changes(actions) == vec![None, Some(ElementId::Assigned(3))]);
+    }
+
+    #[test]
+    fn add_to_selection() {
+        let data    = data_with_selection(vec![1, 2], SelectionOperation::Add);
+        let actions = Select::new().selection_actions(&data, vec![ElementId::Assigned(2), ElementId::Assigned(3)]);
+
+        assert!(selection_changes(actions) == vec![Some(ElementId::Assigned(3))]);
+    }
+
+    #[test]
+    fn subtract_from_selection() {
+        let data    = data_with_selection(vec![1, 2], SelectionOperation::Subtract);
+        let actions = Select::new().selection_actions(&data, vec![ElementId::Assigned(2), ElementId::Assigned(3)]);
+
+        assert!(selection_changes(actions) == vec![None, Some(ElementId::Assigned(1))]);
+    }
+
+    #[test]
+    fn lasso_points_are_added_to_new_data() {
+        let data        = data_with_selection(vec![], SelectionOperation::Replace).with_initial_position(RawPoint::from((1.0, 2.0)));
+        let extended    = data.with_lasso_point(RawPoint::from((3.0, 4.0))).with_lasso_point(RawPoint::from((5.0, 6.0)));
+
+        assert!(*extended.lasso == vec![(1.0, 2.0), (3.0, 4.0), (5.0, 6.0)]);
+        assert!(*data.lasso == vec![(1.0, 2.0)]);
+    }
+
+    #[test]
+    fn find_handles_around_selection() {
+        let data = data_with_bounds(Rect::with_points(0.0, 0.0, 100.0, 50.0));
+
+        assert!(Select::handle_at_point(&data, (101.0, 49.0)) == Some(TransformHandle::Scale(1, 1)));
+        assert!(Select::handle_at_point(&data, (0.0, 25.0)) == Some(TransformHandle::Scale(-1, 0)));
+        assert!(Select::handle_at_point(&data, (50.0, 50.0 + ROTATE_HANDLE_DISTANCE)) == Some(TransformHandle::Rotate));
+        assert!(Select::handle_at_point(&data, (50.0, 25.0)) == Some(TransformHandle::Pivot));
+        assert!(Select::handle_at_point(&data, (30.0, 30.0)).is_none());
+    }
+
+    #[test]
+    fn pivot_does_not_hide_scale_handles() {
+        let data = data_with_bounds(Rect::with_points(0.0, 0.0, 4.0, 4.0));
+
+        assert!(Select::handle_at_point(&data, (2.0, 2.0)) != Some(TransformHandle::Pivot));
+    }
+
+    #[test]
+    fn scale_from_corner() {
+        let data = data_with_bounds(Rect::with_points(0.0, 0.0, 100.0, 50.0));
+
+        match Select::transform_for_handle(&data, TransformHandle::Scale(1, 1), (100.0, 50.0), (200.0, 60.0), false) {
+            HandleTransform::Scale(origin, scale)   => { assert!(is_close(origin, (0.0, 0.0))); assert!(is_close(scale, (2.0, 1.2))); },
+            _                                       => assert!(false)
+        }
+    }
+
+    #[test]
+    fn constrained_scale_keeps_proportions() {
+        let data = data_with_bounds(Rect::with_points(0.0, 0.0, 100.0, 50.0));
+
+        match Select::transform_for_handle(&data, TransformHandle::Scale(1, 1), (100.0, 50.0), (200.0, 60.0), true) {
+            HandleTransform::Scale(origin, scale)   => { assert!(is_close(origin, (0.0, 0.0))); assert!(is_close(scale, (2.0, 2.0))); },
+            _                                       => assert!(false)
+        }
+    }
+
+    #[test]
+    fn dragging_past_opposite_edge_flips() {
+        let data = data_with_bounds(Rect::with_points(0.0, 0.0, 100.0, 50.0));
+
+        match Select::transform_for_handle(&data, TransformHandle::Scale(1, 0), (100.0, 25.0), (-100.0, 25.0), false) {
+            HandleTransform::Scale(origin, scale)   => { assert!(is_close(origin, (0.0, 25.0))); assert!(is_close(scale, (-1.0, 1.0))); },
+            _                                       => assert!(false)
+        }
+    }
+
+    #[test]
+    fn rotate_around_pivot() {
+        let mut data    = data_with_bounds(Rect::with_points(0.0, 0.0, 100.0, 50.0));
+        data.pivot      = Some((10.0, 10.0));
+
+        match Select::transform_for_handle(&data, TransformHandle::Rotate, (20.0, 10.0), (10.0, 30.0), false) {
+            HandleTransform::Rotate(origin, angle)  => { assert!(is_close(origin, (10.0, 10.0))); assert!((angle - f32::consts::PI/2.0).abs() < 0.0001); },
+            _                                       => assert!(false)
+        }
+    }
+
+    #[test]
+    fn constrained_rotation_snaps_to_steps() {
+        let data        = data_with_bounds(Rect::with_points(0.0, 0.0, 100.0, 50.0));
+        let to_angle    = 20.0f32.to_radians();
+
+        match Select::transform_for_handle(&data, TransformHandle::Rotate, (60.0, 25.0), (50.0 + to_angle.cos()*10.0, 25.0 + to_angle.sin()*10.0), true) {
+            HandleTransform::Rotate(origin, angle)  => { assert!(is_close(origin, (50.0, 25.0))); assert!((angle - ROTATE_SNAP_DEGREES.to_radians()).abs() < 0.0001); },
+            _                                       => assert!(false)
+        }
+    }
+
+    #[test]
+    fn moving_pivot_does_not_transform() {
+        let data = data_with_bounds(Rect::with_points(0.0, 0.0, 100.0, 50.0));
+
+        assert!(Select::transform_for_handle(&data, TransformHandle::Pivot, (50.0, 25.0), (80.0, 25.0), false) == HandleTransform::Identity);
+    }
+
+    #[test]
+    fn handles_stay_the_same_size_when_zoomed() {
+        let mut data    = data_with_bounds(Rect::with_points(0.0, 0.0, 100.0, 50.0));
+        data.zoom       = 2.0;
+
+        assert!(Select::handle_at_point(&data, (50.0, 50.0 + ROTATE_HANDLE_DISTANCE/2.0)) == Some(TransformHandle::Rotate));
+        assert!(Select::handle_at_point(&data, (104.0, 50.0)).is_none());
+        assert!(Select::handle_at_point(&data, (102.0, 50.0)) == Some(TransformHandle::Scale(1, 1)));
+    }
+}
@@ -11,16 +11,18 @@ use std::sync::*;
 pub struct SelectionTools<Anim: 'static+Animation> {
     select: Arc<FloTool<Anim>>,
     adjust: Arc<FloTool<Anim>>,
-    pan:    Arc<FloTool<Anim>>
+    pan:    Arc<FloTool<Anim>>,
+    zoom:   Arc<FloTool<Anim>>
 }
 
 ///
 /// The paint toolset
 /// 
 pub struct PaintTools<Anim: 'static+Animation> {
-    pencil: Arc<FloTool<Anim>>,
-    ink:    Arc<FloTool<Anim>>,
-    eraser: Arc<FloTool<Anim>>
+    pencil:     Arc<FloTool<Anim>>,
+    ink:        Arc<FloTool<Anim>>,
+    eraser:     Arc<FloTool<Anim>>,
+    eyedropper: Arc<FloTool<Anim>>
 }
 
 impl<Anim: Animation> SelectionTools<Anim> {
@@ -28,7 +30,8 @@ impl<Anim: Animation> SelectionTools<Anim> {
         SelectionTools {
             select: Select::new().to_flo_tool(),
             adjust: Adjust::new().to_flo_tool(),
-            pan:    Pan::new().to_flo_tool()
+            pan:    Pan::new().to_flo_tool(),
+            zoom:   Zoom::new().to_flo_tool()
         }
     }
 }
@@ -36,9 +39,10 @@ impl<Anim: Animation> SelectionTools<Anim> {
 impl<Anim: Animation> PaintTools<Anim> {
     pub fn new() -> PaintTools<Anim> {
         PaintTools {
-            pencil: Pencil::new().to_flo_tool(),
-            ink:    Ink::new().to_flo_tool(),
-            eraser: Eraser::new().to_flo_tool()
+            pencil:     Pencil::new().to_flo_tool(),
+            ink:        Ink::new().to_flo_tool(),
+            eraser:     Eraser::new().to_flo_tool(),
+            eyedropper: Eyedropper::new().to_flo_tool()
         }
     }
 }
@@ -50,7 +54,8 @@ impl<Anim: Animation> ToolSet<Anim> for SelectionTools<Anim> {
         vec![
             Arc::clone(&self.select),
             Arc::clone(&self.adjust),
-            Arc::clone(&self.pan)
+            Arc::clone(&self.pan),
+            Arc::clone(&self.zoom)
         ]
     }
 }
@@ -62,7 +67,8 @@ impl<Anim: Animation> ToolSet<Anim> for PaintTools<Anim> {
         vec![
             Arc::clone(&self.pencil),
             Arc::clone(&self.ink),
-            Arc::clone(&self.eraser)
+            Arc::clone(&self.eraser),
+            Arc::clone(&self.eyedropper)
         ]
     }
 }
@@ -0,0 +1,152 @@
+use super::super::tools::*;
+use super::super::model::*;
+use super::super::style::*;
+
+use ui::*;
+use canvas::*;
+use binding::*;
+use animation::*;
+
+use std::sync::*;
+
+/// How much a click with the zoom tool magnifies the canvas
+const CLICK_ZOOM_FACTOR: f32 = 2.0;
+
+/// The smallest drag (as a proportion of the visible height of the canvas) that zooms to a region rather than being treated as a click
+const MIN_DRAG_SIZE: f32 = 0.01;
+
+///
+/// The Zoom tool (clicking zooms in on a point, dragging zooms in on a region)
+///
+pub struct Zoom { }
+
+impl Zoom {
+    ///
+    /// Creates a new instance of the Zoom tool
+    ///
+    pub fn new() -> Zoom {
+        Zoom {}
+    }
+
+    ///
+    /// Returns the drawing instructions for the region that will be zoomed in on
+    ///
+    fn draw_zoom_region(initial_point: (f32, f32), final_point: (f32, f32)) -> Vec<Draw> {
+        let bounds                  = Rect::with_points(initial_point.0, initial_point.1, final_point.0, final_point.1);
+        let draw_bounds: Vec<Draw>  = bounds.normalize().into();
+
+        let mut drawing = vec![Draw::Layer(0), Draw::ClearLayer, Draw::NewPath];
+        drawing.extend(draw_bounds);
+        drawing.extend(vec![
+            Draw::FillColor(RUBBERBAND_FILL),
+            Draw::Fill,
+            Draw::LineWidthPixels(0.5),
+            Draw::StrokeColor(RUBBERBAND_LINE),
+            Draw::Stroke
+        ]);
+
+        drawing
+    }
+
+    ///
+    /// Returns the view that results from a click or drag from one point to another
+    ///
+    fn zoomed_view(view: CanvasView, initial_point: (f32, f32), final_point: (f32, f32), size: (f64, f64)) -> CanvasView {
+        let min_drag = view.visible_height(size) * MIN_DRAG_SIZE;
+        let region   = Rect::with_points(initial_point.0, initial_point.1, final_point.0, final_point.1).normalize();
+
+        if region.x2-region.x1 < min_drag && region.y2-region.y1 < min_drag {
+            // Clicks zoom in around the point that was clicked
+            view.with_zoom(view.zoom * CLICK_ZOOM_FACTOR).with_center(final_point)
+        } else {
+            // Drags fit the region into the canvas
+            view.zoom_to_rect(region, size)
+        }
+    }
+}
+
+impl<Anim: Animation+'static> Tool<Anim> for Zoom {
+    type ToolData   = Option<(f32, f32)>;
+    type Model      = ();
+
+    fn tool_name(&self) -> String { "Zoom".to_string() }
+
+    fn image_name(&self) -> String { "zoom".to_string() }
+
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> () { }
+
+    ///
+    /// The data for this tool is the point where the current drag started
+    ///
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<Option<(f32, f32)>>>, input: Box<dyn 'a+Iterator<Item=ToolInput<Option<(f32, f32)>>>>) -> Box<dyn Iterator<Item=ToolAction<Option<(f32, f32)>>>> {
+        let mut drag_start  = data.and_then(|data| *data);
+        let mut actions     = vec![];
+
+        for input in input {
+            match input {
+                ToolInput::Data(new_data)       => drag_start = *new_data,
+
+                ToolInput::Select | ToolInput::Deselect => {
+                    drag_start = None;
+                    actions.push(ToolAction::Data(None));
+                    actions.push(ToolAction::Overlay(OverlayAction::Clear));
+                },
+
+                ToolInput::Paint(painting)      => {
+                    match (painting.action, drag_start) {
+                        (PaintAction::Start, _)                 => {
+                            drag_start = Some(painting.location);
+                            actions.push(ToolAction::Data(drag_start));
+                        },
+
+                        (PaintAction::Continue, Some(start))    => {
+                            actions.push(ToolAction::Overlay(OverlayAction::Draw(Self::draw_zoom_region(start, painting.location))));
+                        },
+
+                        (PaintAction::Finish, Some(start))      => {
+                            let view        = flo_model.view().get();
+                            let new_view    = Self::zoomed_view(view, start, painting.location, flo_model.size.get());
+                            flo_model.view().clone().set(new_view);
+
+                            drag_start = None;
+                            actions.push(ToolAction::Data(None));
+                            actions.push(ToolAction::Overlay(OverlayAction::Clear));
+                        },
+
+                        (PaintAction::Cancel, _)                => {
+                            drag_start = None;
+                            actions.push(ToolAction::Data(None));
+                            actions.push(ToolAction::Overlay(OverlayAction::Clear));
+                        },
+
+                        _ => ()
+                    }
+                },
+
+                ToolInput::PaintDevice(_)       => ()
+            }
+        }
+
+        Box::new(actions.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn click_zooms_in_on_point() {
+        let view = Zoom::zoomed_view(CanvasView::fit(), (100.0, 200.0), (100.5, 200.0), (1000.0, 500.0));
+
+        assert!(view.zoom == 2.0);
+        assert!(view.center == Some((100.5, 200.0)));
+    }
+
+    #[test]
+    fn drag_zooms_to_region() {
+        let view = Zoom::zoomed_view(CanvasView::fit(), (300.0, 250.0), (100.0, 200.0), (1000.0, 500.0));
+
+        assert!(view == CanvasView::fit().zoom_to_rect(Rect::with_points(100.0, 200.0, 300.0, 250.0), (1000.0, 500.0)));
+    }
+}
@@ -29,3 +29,7 @@ pub const SELECTION_FILL:       Color = Color::Rgba(0.6, 0.8, 0.9, 0.3);
 pub const CP_BEZIER:            Color = Color::Rgba(0.9, 0.7, 0.5, 0.85);
 pub const CP_BEZIER_CP:         Color = Color::Rgba(0.6, 0.8, 0.9, 0.85);
 pub const CP_LINES:             Color = Color::Rgba(0.6, 0.6, 0.6, 0.7);
+
+pub const GRID_LINE:            Color = Color::Rgba(0.2, 0.6, 0.9, 0.2);
+pub const RULER_BACKGROUND:     Color = Color::Rgba(0.3, 0.3, 0.3, 1.0);
+pub const RULER_TICK:           Color = Color::Rgba(0.75, 0.8, 0.8, 1.0);
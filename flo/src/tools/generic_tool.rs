@@ -108,9 +108,9 @@ impl GenericToolData {
 
 impl GenericToolModel {
     ///
-    /// Retrieves a reference to the tool model
+    /// Retrieves a reference to the tool model (or None if the model is not of the specified type)
     ///
-    fn get_ref<Model: 'static+Send>(&self) -> Option<Arc<Model>> {
+    pub fn get_ref<Model: 'static+Send>(&self) -> Option<Arc<Model>> {
         self.0.lock().unwrap().downcast_ref().cloned()
     }
 }
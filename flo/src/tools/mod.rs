@@ -7,6 +7,8 @@ mod tool_set;
 mod generic_tool;
 mod tool_runner;
 mod convert;
+mod snapping;
+mod request_queue;
 
 pub use self::tool_action::*;
 pub use self::brush_preview_action::*;
@@ -17,3 +19,5 @@ pub use self::tool_set::*;
 pub use self::generic_tool::*;
 pub use self::tool_runner::*;
 pub use self::convert::*;
+pub use self::snapping::*;
+pub use self::request_queue::*;
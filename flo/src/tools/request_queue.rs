@@ -0,0 +1,93 @@
+use futures::*;
+use futures::stream;
+use futures::sync::mpsc;
+
+use std::sync::*;
+
+///
+/// Queue of requests (such as edits picked from a menu) that a tool turns into actions
+///
+/// Requests are commands rather than state, so they're sent through a channel instead of being stored
+/// in a binding: each request is received exactly once, no matter how quickly they arrive.
+///
+pub struct RequestQueue<Request> {
+    /// Where requests are sent to
+    sender: mpsc::UnboundedSender<Request>,
+
+    /// Where requests are received from (shared so a new stream can be created whenever the tool is activated)
+    receiver: Arc<Mutex<mpsc::UnboundedReceiver<Request>>>
+}
+
+impl<Request: Send+'static> RequestQueue<Request> {
+    ///
+    /// Creates a new, empty request queue
+    ///
+    pub fn new() -> RequestQueue<Request> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        RequestQueue {
+            sender:     sender,
+            receiver:   Arc::new(Mutex::new(receiver))
+        }
+    }
+
+    ///
+    /// Adds a request to the queue
+    ///
+    pub fn send(&self, request: Request) {
+        // The receiver lives as long as this queue, so sending can't fail
+        self.sender.unbounded_send(request).ok();
+    }
+
+    ///
+    /// Returns a stream of the requests sent to this queue
+    ///
+    /// Only the most recently created stream should be polled: requests are removed from the queue as they are read.
+    ///
+    pub fn requests(&self) -> Box<dyn Stream<Item=Request, Error=()>+Send> {
+        let receiver = Arc::clone(&self.receiver);
+
+        Box::new(stream::poll_fn(move || receiver.lock().unwrap().poll()))
+    }
+}
+
+impl<Request> Clone for RequestQueue<Request> {
+    fn clone(&self) -> RequestQueue<Request> {
+        RequestQueue {
+            sender:     self.sender.clone(),
+            receiver:   Arc::clone(&self.receiver)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn requests_are_received_once_in_order() {
+        let queue       = RequestQueue::new();
+        let mut stream  = executor::spawn(queue.requests());
+
+        queue.send(1);
+        queue.send(2);
+        queue.clone().send(3);
+
+        assert!(stream.wait_stream() == Some(Ok(1)));
+        assert!(stream.wait_stream() == Some(Ok(2)));
+        assert!(stream.wait_stream() == Some(Ok(3)));
+    }
+
+    #[test]
+    fn new_stream_receives_unread_requests() {
+        let queue       = RequestQueue::new();
+        let mut first   = executor::spawn(queue.requests());
+
+        queue.send(1);
+        queue.send(2);
+        assert!(first.wait_stream() == Some(Ok(1)));
+
+        let mut second  = executor::spawn(queue.requests());
+        assert!(second.wait_stream() == Some(Ok(2)));
+    }
+}
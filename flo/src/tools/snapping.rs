@@ -0,0 +1,315 @@
+use super::super::model::*;
+
+use binding::*;
+use animation::*;
+
+use std::f32;
+use std::sync::*;
+use std::collections::{HashSet, HashMap};
+
+/// How close a point has to be to something (in canvas units at a zoom level of 1) before it snaps to it
+const SNAP_DISTANCE: f32 = 6.0;
+
+///
+/// The elements in a frame that points can snap to
+///
+#[derive(Clone)]
+struct ElementTargets {
+    /// The elements in the frame, in the order they're drawn
+    elements: Arc<Vec<(Vector, Arc<VectorProperties>)>>,
+
+    /// The bounding box of each element
+    bounding_boxes: Arc<HashMap<ElementId, Rect>>,
+
+    /// Spatial index used to find the elements near a point
+    index: Arc<ElementIndex>
+}
+
+impl ElementTargets {
+    ///
+    /// Returns the points in an element that can be snapped to: the corners and centre of its bounding box and its control points
+    ///
+    fn targets_for_element(&self, element_id: ElementId) -> Vec<(f32, f32)> {
+        let mut targets = vec![];
+
+        if let Some(bounds) = self.bounding_boxes.get(&element_id) {
+            let bounds = bounds.normalize();
+            let center = ((bounds.x1+bounds.x2)/2.0, (bounds.y1+bounds.y2)/2.0);
+
+            targets.extend(vec![(bounds.x1, bounds.y1), (bounds.x2, bounds.y1), (bounds.x1, bounds.y2), (bounds.x2, bounds.y2), center]);
+        }
+
+        if let Some((vector, _)) = self.index.order(element_id).and_then(|index| self.elements.get(index)) {
+            targets.extend(vector.control_points().into_iter().map(|control_point| control_point.position()));
+        }
+
+        targets
+    }
+}
+
+impl PartialEq for ElementTargets {
+    fn eq(&self, other: &ElementTargets) -> bool {
+        // The frame data is shared, so it's enough to check that the targets refer to the same frame
+        Arc::ptr_eq(&self.elements, &other.elements) && Arc::ptr_eq(&self.bounding_boxes, &other.bounding_boxes) && Arc::ptr_eq(&self.index, &other.index)
+    }
+}
+
+///
+/// Works out where points being dragged by a tool should snap to
+///
+/// Tools fetch the snapping for the current frame from the model with `Snapping::for_model()`.
+///
+#[derive(Clone, PartialEq)]
+pub struct Snapping {
+    /// The spacing of the grid, if points snap to it
+    grid_spacing: Option<f32>,
+
+    /// The points that can be snapped to, along with the element they're from (or `Unassigned` for points that aren't part of an element)
+    targets: Vec<(ElementId, (f32, f32))>,
+
+    /// The elements that can be snapped to (only the ones near the points being moved are considered)
+    element_targets: Option<ElementTargets>,
+
+    /// How far points can be from something before they snap to it
+    distance: f32
+}
+
+impl Snapping {
+    ///
+    /// Creates a snapping object where nothing snaps
+    ///
+    pub fn none() -> Snapping {
+        Snapping {
+            grid_spacing:       None,
+            targets:            vec![],
+            element_targets:    None,
+            distance:           SNAP_DISTANCE
+        }
+    }
+
+    ///
+    /// Returns a version of this object that snaps to a grid
+    ///
+    pub fn with_grid(self, spacing: f32) -> Snapping {
+        Snapping {
+            grid_spacing:       if spacing > 0.0 { Some(spacing) } else { None },
+            targets:            self.targets,
+            element_targets:    self.element_targets,
+            distance:           self.distance
+        }
+    }
+
+    ///
+    /// Returns a version of this object that snaps to some extra points
+    ///
+    pub fn with_targets<Targets: IntoIterator<Item=(ElementId, (f32, f32))>>(self, targets: Targets) -> Snapping {
+        let mut new_targets = self.targets;
+        new_targets.extend(targets);
+
+        Snapping {
+            grid_spacing:       self.grid_spacing,
+            targets:            new_targets,
+            element_targets:    self.element_targets,
+            distance:           self.distance
+        }
+    }
+
+    ///
+    /// Returns a version of this object that snaps to the elements in a frame
+    ///
+    /// The spatial index is used to find the elements near the points being moved, so frames with many elements can be snapped to quickly.
+    ///
+    pub fn with_elements(self, elements: Arc<Vec<(Vector, Arc<VectorProperties>)>>, bounding_boxes: Arc<HashMap<ElementId, Rect>>, index: Arc<ElementIndex>) -> Snapping {
+        Snapping {
+            grid_spacing:       self.grid_spacing,
+            targets:            self.targets,
+            element_targets:    Some(ElementTargets { elements: elements, bounding_boxes: bounding_boxes, index: index }),
+            distance:           self.distance
+        }
+    }
+
+    ///
+    /// Returns a version of this object for a canvas shown at a particular zoom level (so the snapping distance stays the same size on screen)
+    ///
+    pub fn at_zoom(self, zoom: f32) -> Snapping {
+        Snapping {
+            grid_spacing:       self.grid_spacing,
+            targets:            self.targets,
+            element_targets:    self.element_targets,
+            distance:           SNAP_DISTANCE / zoom
+        }
+    }
+
+    ///
+    /// Creates a binding for the snapping that applies to the current frame of a model
+    ///
+    pub fn for_model<Anim: 'static+Animation>(flo_model: &FloModel<Anim>) -> BindRef<Arc<Snapping>> {
+        let guides          = flo_model.guides().clone();
+        let size            = flo_model.size.clone();
+        let view            = flo_model.view().clone();
+        let elements        = flo_model.frame().elements.clone();
+        let bounding_boxes  = flo_model.frame().bounding_boxes.clone();
+        let spatial_index   = flo_model.frame().spatial_index.clone();
+
+        BindRef::from(computed(move || {
+            let mut snapping = Snapping::none().at_zoom(view.get().zoom);
+
+            if guides.snap_to_grid.get() {
+                snapping = snapping.with_grid(guides.grid_spacing.get());
+            }
+
+            if guides.snap_to_center.get() {
+                let (width, height) = size.get();
+                snapping = snapping.with_targets(vec![(ElementId::Unassigned, ((width/2.0) as f32, (height/2.0) as f32))]);
+            }
+
+            if guides.snap_to_elements.get() {
+                // Elements can be snapped to by the corners and centres of their bounding boxes, and by their control points
+                snapping = snapping.with_elements(elements.get(), bounding_boxes.get(), spatial_index.get());
+            }
+
+            Arc::new(snapping)
+        }))
+    }
+
+    ///
+    /// True if there is nothing to snap to
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.grid_spacing.is_none() && self.targets.is_empty() && self.element_targets.is_none()
+    }
+
+    ///
+    /// Returns the points that a set of positions can snap to
+    ///
+    /// Elements are only considered if their bounding boxes are within the snapping distance of one of the positions.
+    ///
+    fn targets_near(&self, positions: &[(f32, f32)], ignore: &HashSet<ElementId>) -> Vec<(f32, f32)> {
+        let mut targets: Vec<_> = self.targets.iter()
+            .filter(|&&(element_id, _)| !ignore.contains(&element_id))
+            .map(|&(_, point)| point)
+            .collect();
+
+        if let Some(ref element_targets) = self.element_targets {
+            let nearby_elements: HashSet<_> = positions.iter()
+                .flat_map(|&position| element_targets.index.elements_at_point(position, self.distance))
+                .filter(|element_id| !ignore.contains(element_id))
+                .collect();
+
+            for element_id in nearby_elements {
+                targets.extend(element_targets.targets_for_element(element_id));
+            }
+        }
+
+        targets
+    }
+
+    ///
+    /// Finds the smallest adjustment along one axis that will snap any of a set of positions to something
+    ///
+    fn snap_axis<Positions: Clone+Iterator<Item=f32>, Targets: Clone+Iterator<Item=f32>>(&self, positions: Positions, targets: Targets) -> Option<f32> {
+        let mut nearest: Option<f32> = None;
+        let mut consider = |adjustment: f32| {
+            if adjustment.abs() <= self.distance && nearest.map(|nearest| adjustment.abs() < nearest.abs()).unwrap_or(true) {
+                nearest = Some(adjustment);
+            }
+        };
+
+        for position in positions {
+            if let Some(spacing) = self.grid_spacing {
+                consider((position/spacing).round()*spacing - position);
+            }
+
+            for target in targets.clone() {
+                consider(target - position);
+            }
+        }
+
+        nearest
+    }
+
+    ///
+    /// Moves a point to whatever is nearby that it can snap to
+    ///
+    /// Points that are part of any of the elements in `ignore` are not snapped to (this is usually the elements being moved).
+    /// Each axis is snapped separately.
+    ///
+    pub fn snap_point(&self, point: (f32, f32), ignore: &HashSet<ElementId>) -> (f32, f32) {
+        self.snap_offset(&[(0.0, 0.0)], point, ignore)
+    }
+
+    ///
+    /// Adjusts an offset that's going to be applied to a set of points (say, the corners of a selection) so one of them snaps to something nearby
+    ///
+    pub fn snap_offset(&self, points: &[(f32, f32)], offset: (f32, f32), ignore: &HashSet<ElementId>) -> (f32, f32) {
+        if self.is_empty() {
+            return offset;
+        }
+
+        let moved: Vec<_>   = points.iter().map(|&(x, y)| (x + offset.0, y + offset.1)).collect();
+        let targets         = self.targets_near(&moved, ignore);
+
+        let adjust_x        = self.snap_axis(moved.iter().map(|&(x, _)| x), targets.iter().map(|&(x, _)| x)).unwrap_or(0.0);
+        let adjust_y        = self.snap_axis(moved.iter().map(|&(_, y)| y), targets.iter().map(|&(_, y)| y)).unwrap_or(0.0);
+
+        (offset.0 + adjust_x, offset.1 + adjust_y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nothing_snaps_by_default() {
+        assert!(Snapping::none().snap_point((12.0, 17.0), &HashSet::new()) == (12.0, 17.0));
+    }
+
+    #[test]
+    fn snap_to_grid() {
+        let snapping = Snapping::none().with_grid(10.0);
+
+        assert!(snapping.snap_point((12.0, 17.0), &HashSet::new()) == (10.0, 20.0));
+        assert!(snapping.snap_point((-12.0, 3.0), &HashSet::new()) == (-10.0, 0.0));
+        assert!(Snapping::none().with_grid(20.0).snap_point((-12.0, 10.0), &HashSet::new()) == (-12.0, 10.0));
+    }
+
+    #[test]
+    fn snap_to_targets_ignores_elements() {
+        let snapping = Snapping::none().with_targets(vec![(ElementId::Assigned(1), (50.0, 50.0)), (ElementId::Assigned(2), (100.0, 100.0))]);
+        let ignore   = vec![ElementId::Assigned(2)].into_iter().collect();
+
+        assert!(snapping.snap_point((52.0, 47.0), &ignore) == (50.0, 50.0));
+        assert!(snapping.snap_point((98.0, 102.0), &ignore) == (98.0, 102.0));
+    }
+
+    #[test]
+    fn snap_offset_uses_nearest_point() {
+        let snapping = Snapping::none().with_targets(vec![(ElementId::Unassigned, (100.0, 100.0))]);
+
+        // Moving a 20x20 box from the origin: its right edge is nearest to the target
+        let offset = snapping.snap_offset(&[(0.0, 0.0), (20.0, 20.0)], (78.0, 40.0), &HashSet::new());
+        assert!(offset == (80.0, 40.0));
+    }
+
+    #[test]
+    fn snap_distance_shrinks_when_zoomed_in() {
+        assert!(Snapping::none().with_grid(10.0).snap_point((14.0, 12.0), &HashSet::new()) == (10.0, 10.0));
+        assert!(Snapping::none().with_grid(10.0).at_zoom(2.0).snap_point((14.0, 12.0), &HashSet::new()) == (14.0, 10.0));
+    }
+
+    #[test]
+    fn snap_to_nearby_elements() {
+        let element_id      = ElementId::Assigned(1);
+        let bounding_boxes  = Arc::new(vec![(element_id, Rect::with_points(100.0, 100.0, 200.0, 150.0))].into_iter().collect::<HashMap<_, _>>());
+        let mut index       = ElementIndex::new();
+        index.update(vec![element_id], bounding_boxes.clone());
+
+        let snapping        = Snapping::none().with_elements(Arc::new(vec![]), bounding_boxes, Arc::new(index));
+
+        // Points near the element snap to its bounds, but points far away from it don't (even if they line up with it)
+        assert!(snapping.snap_point((103.0, 148.0), &HashSet::new()) == (100.0, 150.0));
+        assert!(snapping.snap_point((103.0, 400.0), &HashSet::new()) == (103.0, 400.0));
+        assert!(snapping.snap_point((103.0, 148.0), &vec![element_id].into_iter().collect()) == (103.0, 148.0));
+    }
+}
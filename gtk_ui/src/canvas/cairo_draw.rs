@@ -16,13 +16,84 @@ enum ColorTarget {
     Fill
 }
 
+///
+/// The shape of a gradient
+///
+#[derive(Copy, Clone, PartialEq)]
+enum GradientShape {
+    Linear((f64, f64), (f64, f64)),
+    Radial((f64, f64), f64, (f64, f64), f64)
+}
+
+///
+/// A gradient defined by the drawing instructions (turned into a Cairo pattern when it's used)
+///
+#[derive(Clone)]
+struct GradientDefinition {
+    shape:      GradientShape,
+    stops:      Vec<(f64, Color)>,
+    transform:  Matrix
+}
+
+impl GradientDefinition {
+    ///
+    /// Creates a new gradient definition with no stops
+    ///
+    fn new(shape: GradientShape) -> GradientDefinition {
+        GradientDefinition {
+            shape:      shape,
+            stops:      vec![],
+            transform:  Matrix::identity()
+        }
+    }
+
+    ///
+    /// Sets this gradient as the source for a Cairo context
+    ///
+    fn set_source(&self, ctxt: &Context) {
+        // Cairo pattern matrices map from user space to pattern space, which is the inverse of the gradient transform
+        let mut pattern_matrix = self.transform;
+        pattern_matrix.invert();
+
+        match self.shape {
+            GradientShape::Linear((x1, y1), (x2, y2)) => {
+                let gradient = LinearGradient::new(x1, y1, x2, y2);
+                self.add_stops(&gradient);
+                gradient.set_matrix(pattern_matrix);
+                ctxt.set_source(&gradient);
+            },
+
+            GradientShape::Radial((x1, y1), r1, (x2, y2), r2) => {
+                let gradient = RadialGradient::new(x1, y1, r1, x2, y2, r2);
+                self.add_stops(&gradient);
+                gradient.set_matrix(pattern_matrix);
+                ctxt.set_source(&gradient);
+            }
+        }
+    }
+
+    ///
+    /// Adds the colour stops for this definition to a Cairo gradient
+    ///
+    fn add_stops<G: cairo::Gradient>(&self, gradient: &G) {
+        for &(pos, color) in self.stops.iter() {
+            let (r, g, b, a) = color.to_rgba_components();
+            gradient.add_color_stop_rgba(pos, r as f64, g as f64, b as f64, a as f64);
+        }
+    }
+}
+
 ///
 /// A saved state in a Cario drawing surface
 /// 
 struct SavedState {
-    dash_pattern:   Vec<f64>,
-    stroke_color:   Color,
-    fill_color:     Color
+    dash_pattern:       Vec<f64>,
+    stroke_color:       Color,
+    fill_color:         Color,
+    stroke_gradient:    Option<GradientDefinition>,
+    fill_gradient:      Option<GradientDefinition>,
+    font:               (FontFamily, flo::CanvasFontWeight, f32),
+    text_align:         TextAlignment
 }
 
 impl SavedState {
@@ -31,9 +102,13 @@ impl SavedState {
     /// 
     pub fn from_current(drawing: &CairoDraw) -> SavedState {
         SavedState {
-            dash_pattern:   drawing.dash_pattern.clone(),
-            stroke_color:   drawing.stroke_color.clone(),
-            fill_color:     drawing.fill_color.clone()
+            dash_pattern:       drawing.dash_pattern.clone(),
+            stroke_color:       drawing.stroke_color.clone(),
+            fill_color:         drawing.fill_color.clone(),
+            stroke_gradient:    drawing.stroke_gradient.clone(),
+            fill_gradient:      drawing.fill_gradient.clone(),
+            font:               drawing.font,
+            text_align:         drawing.text_align
         }
     }
 
@@ -44,6 +119,10 @@ impl SavedState {
         drawing.dash_pattern    = self.dash_pattern;
         drawing.stroke_color    = self.stroke_color;
         drawing.fill_color      = self.fill_color;
+        drawing.stroke_gradient = self.stroke_gradient;
+        drawing.fill_gradient   = self.fill_gradient;
+        drawing.font            = self.font;
+        drawing.text_align      = self.text_align;
         drawing.set_color       = ColorTarget::None;
     }
 }
@@ -58,6 +137,10 @@ pub struct CairoState {
     line_cap:       cairo::LineCap,
     fill_color:     Color,
     stroke_color:   Color,
+    fill_gradient:  Option<GradientDefinition>,
+    stroke_gradient: Option<GradientDefinition>,
+    font:           (FontFamily, flo::CanvasFontWeight, f32),
+    text_align:     TextAlignment,
     dash_pattern:   Vec<f64>
 }
 
@@ -83,9 +166,27 @@ pub struct CairoDraw {
     /// The current fill colour
     fill_color: Color,
 
+    /// The gradient that's currently being defined
+    gradient: Option<GradientDefinition>,
+
+    /// The gradient to use for strokes (instead of the stroke colour)
+    stroke_gradient: Option<GradientDefinition>,
+
+    /// The gradient to use for fills (instead of the fill colour)
+    fill_gradient: Option<GradientDefinition>,
+
     /// The colour that's currently set
     set_color: ColorTarget,
 
+    /// The font family, weight and size to use for text
+    font: (FontFamily, flo::CanvasFontWeight, f32),
+
+    /// How text is aligned relative to where it's drawn
+    text_align: TextAlignment,
+
+    /// The text run that will be drawn by the next text instruction
+    text: String,
+
     /// The viewport for this canvas
     viewport: CanvasViewport,
 
@@ -116,7 +217,13 @@ impl CairoDraw {
             dash_pattern:   vec![],
             stroke_color:   Color::Rgba(0.0, 0.0, 0.0, 1.0),
             fill_color:     Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            gradient:       None,
+            stroke_gradient: None,
+            fill_gradient:  None,
             set_color:      ColorTarget::None,
+            font:           (FontFamily::SansSerif, flo::CanvasFontWeight::Normal, 12.0),
+            text_align:     TextAlignment::Left,
+            text:           String::new(),
             initial_matrix: Matrix::from(&viewport),
             viewport:       viewport
         }
@@ -151,23 +258,92 @@ impl CairoDraw {
     fn set_color(&mut self, target: ColorTarget) {
         // Only change the colour if it's not already set
         if self.set_color != target {
-            // Get the RGBA components for this target
-            let (r, g, b, a) = {
-                match target {
-                    ColorTarget::None   => (0.0, 0.0, 0.0, 1.0),
-                    ColorTarget::Fill   => self.fill_color.to_rgba_components(),
-                    ColorTarget::Stroke => self.stroke_color.to_rgba_components()
-                }
+            // Gradients replace the colour for their target
+            let gradient = match target {
+                ColorTarget::None   => None,
+                ColorTarget::Fill   => self.fill_gradient.as_ref(),
+                ColorTarget::Stroke => self.stroke_gradient.as_ref()
             };
 
-            // Update the colour with Cairo
-            self.ctxt.set_source_rgba(r as f64, g as f64, b as f64, a as f64);
+            if let Some(gradient) = gradient {
+                // Use the gradient as the source
+                gradient.set_source(&self.ctxt);
+            } else {
+                // Get the RGBA components for this target
+                let (r, g, b, a) = {
+                    match target {
+                        ColorTarget::None   => (0.0, 0.0, 0.0, 1.0),
+                        ColorTarget::Fill   => self.fill_color.to_rgba_components(),
+                        ColorTarget::Stroke => self.stroke_color.to_rgba_components()
+                    }
+                };
+
+                // Update the colour with Cairo
+                self.ctxt.set_source_rgba(r as f64, g as f64, b as f64, a as f64);
+            }
 
             // Remember that this is the currently set colour
             self.set_color = target;
         }
     }
 
+    ///
+    /// Selects a font in the Cairo context
+    ///
+    fn select_font(&self, family: FontFamily, weight: flo::CanvasFontWeight, size: f32) {
+        let family = match family {
+            FontFamily::SansSerif   => "sans-serif",
+            FontFamily::Serif       => "serif",
+            FontFamily::Monospace   => "monospace"
+        };
+
+        let weight = match weight {
+            flo::CanvasFontWeight::Normal => cairo::FontWeight::Normal,
+            flo::CanvasFontWeight::Bold   => cairo::FontWeight::Bold
+        };
+
+        self.ctxt.select_font_face(family, FontSlant::Normal, weight);
+        self.ctxt.set_font_size(size as f64);
+    }
+
+    ///
+    /// Draws the current text run with its baseline starting at the specified position
+    ///
+    fn draw_text(&mut self, x: f32, y: f32, target: ColorTarget) {
+        let (family, weight, size) = self.font;
+
+        // Set the colour before saving the state so it's still set afterwards
+        self.set_color(target);
+
+        // Text is drawn as a path, which shouldn't replace the path the user has defined
+        let path = self.ctxt.copy_path();
+        self.ctxt.save();
+        self.ctxt.new_path();
+
+        self.select_font(family, weight, size);
+        let width   = self.ctxt.text_extents(&self.text).x_advance;
+        let offset  = match self.text_align {
+            TextAlignment::Left     => 0.0,
+            TextAlignment::Center   => -width/2.0,
+            TextAlignment::Right    => -width
+        };
+
+        // Canvas coordinates run upwards, so flip the text so it's the right way up
+        self.ctxt.translate(x as f64, y as f64);
+        self.ctxt.scale(1.0, -1.0);
+        self.ctxt.move_to(offset, 0.0);
+        self.ctxt.text_path(&self.text);
+
+        match target {
+            ColorTarget::Stroke => self.ctxt.stroke(),
+            _                   => self.ctxt.fill()
+        }
+
+        self.ctxt.restore();
+        self.ctxt.new_path();
+        self.ctxt.append_path(&path);
+    }
+
     ///
     /// Converts a blend mode into an operator
     /// 
@@ -197,6 +373,13 @@ impl CairoDraw {
         Matrix::new(a.0 as f64, b.0 as f64, a.1 as f64, b.1 as f64, a.2 as f64, b.2 as f64)
     }
 
+    ///
+    /// Converts a Cairo matrix to a Flo Transform2D
+    /// 
+    pub fn from_matrix(matrix: &Matrix) -> Transform2D {
+        Transform2D((matrix.xx as f32, matrix.xy as f32, matrix.x0 as f32), (matrix.yx as f32, matrix.yy as f32, matrix.y0 as f32), (0.0, 0.0, 1.0))
+    }
+
     ///
     /// Sets the line width in pixels
     /// 
@@ -218,57 +401,18 @@ impl CairoDraw {
     /// Computes the transformation to apply for a particular canvas height
     /// 
     fn height_matrix(height: f32) -> Matrix {
-        let height      = height as f64;
-        let mut ratio_x = 2.0/height;
-        let ratio_y     = ratio_x;
-
-        if height < 0.0 {
-            ratio_x = -ratio_x;
-        }
-
-        let mut result  = Matrix::identity();
-        result.scale(ratio_x, ratio_y);
-
-        result
+        Self::get_transform(Transform2D::canvas_height(height))
     }
 
     ///
     /// Computes a matrix to make a particular region centered in the viewport
     /// 
     fn center_matrix(current_matrix: &Matrix, viewport: &CanvasViewport, minx: f32, miny: f32, maxx: f32, maxy: f32) -> Matrix {
-        let minx            = minx as f64;
-        let miny            = miny as f64;
-        let maxx            = maxx as f64;
-        let maxy            = maxy as f64;
-
-        let pixel_width     = viewport.width as f64;
-        let pixel_height    = viewport.height as f64;
-
-        // Get the current scaling of this canvas
-        let mut xscale = (current_matrix.xx*current_matrix.xx + current_matrix.yx*current_matrix.yx).sqrt();
-        let mut yscale = (current_matrix.xy*current_matrix.xy + current_matrix.yy*current_matrix.yy).sqrt();
-        if xscale == 0.0 { xscale = 1.0; }
-        if yscale == 0.0 { yscale = 1.0; }
-
-        // Current X, Y coordinates (centered)
-        let cur_x = (current_matrix.x0-(pixel_width/2.0))/xscale;
-        let cur_y = (current_matrix.y0-(pixel_height/2.0))/yscale;
-        
-        // New center coordinates
-        let center_x = (minx+maxx)/2.0;
-        let center_y = (miny+maxy)/2.0;
-
-        // Compute the offsets and transform the canvas
-        let x_offset = cur_x - center_x;
-        let y_offset = cur_y - center_y;
+        // The region is centered on the pixel where the view origin is
+        let view_origin = viewport.transform().transform_point(0.0, 0.0);
+        let current     = Self::from_matrix(current_matrix);
 
-        let x_offset = x_offset + (viewport.viewport_x as f64)/xscale;
-        let y_offset = y_offset + (viewport.viewport_y as f64)/xscale;
-
-        // Generate the result matrix
-        let mut result = Matrix::identity();
-        result.translate(x_offset, y_offset);
-        result
+        Self::get_transform(current.center_region(view_origin, (minx, miny), (maxx, maxy)))
     }
 
     ///
@@ -278,6 +422,20 @@ impl CairoDraw {
         self.ctxt.get_matrix()
     }
 
+    ///
+    /// Paints a surface the same size as the viewport onto this context (using the current clipping and blend mode)
+    ///
+    pub fn paint_surface(&self, surface: &cairo::ImageSurface) {
+        let pattern = cairo::SurfacePattern::create(surface);
+        pattern.set_filter(cairo::Filter::Nearest);
+
+        self.ctxt.save();
+        self.ctxt.identity_matrix();
+        self.ctxt.set_source(&pattern);
+        self.ctxt.paint();
+        self.ctxt.restore();
+    }
+
     ///
     /// Copies the drawing state from another CairoDraw object
     /// 
@@ -288,7 +446,11 @@ impl CairoDraw {
         let line_cap        = self.ctxt.get_line_cap();
         let fill_color      = self.fill_color;
         let stroke_color    = self.stroke_color;
+        let fill_gradient   = self.fill_gradient.clone();
+        let stroke_gradient = self.stroke_gradient.clone();
         let dash_pattern    = self.dash_pattern.clone();
+        let font            = self.font;
+        let text_align      = self.text_align;
 
         CairoState {
             transform,
@@ -297,6 +459,10 @@ impl CairoDraw {
             line_cap,
             fill_color,
             stroke_color,
+            fill_gradient,
+            stroke_gradient,
+            font,
+            text_align,
             dash_pattern
         }
     }
@@ -311,6 +477,10 @@ impl CairoDraw {
         self.ctxt.set_line_cap(state.line_cap);
         self.fill_color     = state.fill_color;
         self.stroke_color   = state.stroke_color;
+        self.fill_gradient  = state.fill_gradient.clone();
+        self.stroke_gradient = state.stroke_gradient.clone();
+        self.font           = state.font;
+        self.text_align     = state.text_align;
         self.dash_pattern   = state.dash_pattern.clone();
         self.set_color      = ColorTarget::None;
     }
@@ -336,8 +506,21 @@ impl CairoDraw {
             NewDashPattern                              => { self.dash_pattern = vec![]; self.ctxt.set_dash(&[], 0.0); },
             DashLength(length)                          => { self.dash_pattern.push(length as f64); self.ctxt.set_dash(&self.dash_pattern, self.ctxt.get_dash_offset()); },
             DashOffset(offset)                          => { self.ctxt.set_dash(&self.dash_pattern, offset as f64); },
-            FillColor(color)                            => { self.set_color = ColorTarget::None; self.fill_color = color; },
-            StrokeColor(color)                          => { self.set_color = ColorTarget::None; self.stroke_color = color; },
+            FillColor(color)                            => { self.set_color = ColorTarget::None; self.fill_color = color; self.fill_gradient = None; },
+            StrokeColor(color)                          => { self.set_color = ColorTarget::None; self.stroke_color = color; self.stroke_gradient = None; },
+            NewLinearGradient((x1, y1), (x2, y2))       => { self.gradient = Some(GradientDefinition::new(GradientShape::Linear((x1 as f64, y1 as f64), (x2 as f64, y2 as f64)))); },
+            NewRadialGradient((x1, y1), r1, (x2, y2), r2) => { self.gradient = Some(GradientDefinition::new(GradientShape::Radial((x1 as f64, y1 as f64), r1 as f64, (x2 as f64, y2 as f64), r2 as f64))); },
+            GradientStop(pos, color)                    => { if let Some(ref mut gradient) = self.gradient { gradient.stops.push((pos as f64, color)); } },
+            GradientTransform(transform)                => { if let Some(ref mut gradient) = self.gradient { gradient.transform = Self::get_transform(transform); } },
+            FillGradient                                => { self.set_color = ColorTarget::None; self.fill_gradient = self.gradient.clone(); },
+            StrokeGradient                              => { self.set_color = ColorTarget::None; self.stroke_gradient = self.gradient.clone(); },
+            Font(family, weight)                        => { self.font.0 = family; self.font.1 = weight; },
+            FontSize(size)                              => { self.font.2 = size; },
+            TextAlign(alignment)                        => { self.text_align = alignment; },
+            NewText                                     => { self.text = String::new(); },
+            TextChar(c)                                 => { self.text.push(c); },
+            FillText(x, y)                              => { self.draw_text(x, y, ColorTarget::Fill); },
+            StrokeText(x, y)                            => { self.draw_text(x, y, ColorTarget::Stroke); },
             BlendMode(blend)                            => { self.ctxt.set_operator(Self::get_operator(blend)); },
             IdentityTransform                           => { self.ctxt.set_matrix(self.initial_matrix); },
             MultiplyTransform(transform)                => { self.ctxt.transform(Self::get_transform(transform)); },
@@ -350,6 +533,10 @@ impl CairoDraw {
             PopState                                    => { self.ctxt.restore(); self.saved_states.pop().map(|state| state.restore(self)); },
             Layer(_layer_id)                            => { /* Layers require external support */ },
             LayerBlend(_layer_id, _mode)                => { /* Layers require external support */ },
+            StartSprite(_sprite_id)                     => { /* Sprites require external support */ },
+            EndSprite                                   => { /* Sprites require external support */ },
+            DrawSprite(_sprite_id, _transform)          => { /* Sprites require external support */ },
+            FreeSprite(_sprite_id)                      => { /* Sprites require external support */ },
 
             CanvasHeight(height)                        => {
                 let transform   = self.initial_matrix.clone();
@@ -377,7 +564,13 @@ impl CairoDraw {
                 // Reset state
                 self.fill_color     = Color::Rgba(0.0, 0.0, 0.0, 1.0);
                 self.stroke_color   = Color::Rgba(0.0, 0.0, 0.0, 1.0);
+                self.gradient       = None;
+                self.fill_gradient  = None;
+                self.stroke_gradient = None;
                 self.set_color      = ColorTarget::None;
+                self.font           = (FontFamily::SansSerif, flo::CanvasFontWeight::Normal, 12.0);
+                self.text_align     = TextAlignment::Left;
+                self.text           = String::new();
                 self.dash_pattern   = vec![];
 
                 self.ctxt.set_dash(&[], 0.0);
@@ -389,16 +582,27 @@ impl CairoDraw {
     }
 }
 
-impl<'a> From<&'a CanvasViewport> for Matrix {
-    fn from(viewport: &'a CanvasViewport) -> Matrix {
-        let scale           = (viewport.height as f64)/2.0;
+impl FontMetrics for CairoDraw {
+    fn measure_text(&self, family: FontFamily, weight: flo::CanvasFontWeight, size: f32, text: &str) -> TextMetrics {
+        self.ctxt.save();
+        self.select_font(family, weight, size);
+
+        let text_extents = self.ctxt.text_extents(text);
+        let font_extents = self.ctxt.font_extents();
 
-        let mut matrix = Matrix::identity();
-        matrix.translate(-viewport.viewport_x as f64, -viewport.viewport_y as f64);
-        matrix.translate((viewport.width as f64)/2.0, (viewport.height as f64)/2.0);
-        matrix.scale(scale, -scale);
+        self.ctxt.restore();
 
-        matrix
+        TextMetrics {
+            width:      text_extents.x_advance as f32,
+            ascent:     font_extents.ascent as f32,
+            descent:    font_extents.descent as f32
+        }
+    }
+}
+
+impl<'a> From<&'a CanvasViewport> for Matrix {
+    fn from(viewport: &'a CanvasViewport) -> Matrix {
+        CairoDraw::get_transform(viewport.transform())
     }
 }
 
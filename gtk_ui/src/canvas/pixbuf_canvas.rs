@@ -19,6 +19,14 @@ struct Layer {
     context: CairoDraw
 }
 
+struct RenderedSprite {
+    /// The transformation that the sprite was rendered with
+    transform: Transform2D,
+
+    /// The surface containing the rendered sprite
+    surface: cairo::ImageSurface
+}
+
 ///
 /// The pixbuf canvas performs drawing operations using GDK pixel buffers for layers using Cairo
 /// 
@@ -36,7 +44,13 @@ pub struct PixBufCanvas {
     current_layer: u32,
 
     /// The state to restore during the next drawing operation
-    saved_state: Option<CairoState>
+    saved_state: Option<CairoState>,
+
+    /// The sprites defined for this canvas
+    sprites: SpriteCache,
+
+    /// The most recent rendering of each sprite that has been drawn
+    rendered_sprites: HashMap<u32, RenderedSprite>
 }
 
 impl PixBufCanvas {
@@ -49,7 +63,9 @@ impl PixBufCanvas {
             pixel_scale:    pixel_scale,
             viewport:       viewport,
             current_layer:  0,
-            saved_state:    None
+            saved_state:    None,
+            sprites:        SpriteCache::new(),
+            rendered_sprites: HashMap::new()
         }
     }
 
@@ -58,6 +74,7 @@ impl PixBufCanvas {
     /// 
     pub fn set_pixel_scale(&mut self, new_scale: f64) {
         self.pixel_scale = new_scale;
+        self.rendered_sprites.clear();
     }
 
     ///
@@ -129,7 +146,22 @@ impl PixBufCanvas {
     /// Performs a drawing action on this canvas
     /// 
     pub fn draw(&mut self, action: Draw) {
+        // Changing a sprite definition invalidates any renderings (which might include the sprite as a sub-sprite)
         match action {
+            Draw::StartSprite(_) | Draw::FreeSprite(_) | Draw::ClearCanvas => self.rendered_sprites.clear(),
+            _ => { }
+        }
+
+        // Sprite definitions are recorded rather than drawn
+        if self.sprites.record(&action) {
+            return;
+        }
+
+        match action {
+            Draw::DrawSprite(sprite_id, transform) => {
+                self.draw_sprite(sprite_id, transform);
+            },
+
             Draw::ClearCanvas => {
                 // Clearing the canvas clears all the layers and resets us to layer 0
                 self.layers.clear();
@@ -187,6 +219,52 @@ impl PixBufCanvas {
         }
     }
 
+    ///
+    /// Draws a sprite on the current layer, rendering it to a surface if it hasn't been drawn with the same transformation before
+    ///
+    fn draw_sprite(&mut self, sprite_id: u32, transform: Transform2D) {
+        // Fetch the current layer
+        let current_layer   = self.current_layer;
+        let viewport        = &self.viewport;
+        let pixel_scale     = self.pixel_scale;
+        let layer           = self.layers.entry(current_layer).or_insert_with(|| Self::create_layer(viewport, pixel_scale));
+
+        // Restore the saved state if there is one
+        if let Some(state) = self.saved_state.take() {
+            layer.context.set_state(&state);
+        }
+
+        // The rendering can be re-used if the sprite ends up in the same place on the canvas
+        let sprite_transform    = CairoDraw::from_matrix(&layer.context.get_matrix()) * transform;
+        let is_rendered         = self.rendered_sprites.get(&sprite_id)
+            .map(|rendered| rendered.transform == sprite_transform)
+            .unwrap_or(false);
+
+        if !is_rendered {
+            // Render the sprite on a new surface, starting from the layer's state
+            let surface         = cairo::ImageSurface::create(cairo::Format::ARgb32, viewport.viewport_width, viewport.viewport_height).unwrap();
+            let context         = cairo::Context::new(&surface);
+            context.set_antialias(cairo::Antialias::Fast);
+
+            let mut sprite_draw = CairoDraw::new(context, *viewport, pixel_scale);
+            sprite_draw.set_state(&layer.context.get_state());
+
+            for sprite_action in self.sprites.render_sprite(sprite_id, transform) {
+                sprite_draw.draw(sprite_action);
+            }
+
+            self.rendered_sprites.insert(sprite_id, RenderedSprite {
+                transform:  sprite_transform,
+                surface:    surface
+            });
+        }
+
+        // Paint the rendered sprite onto the layer
+        if let Some(rendered) = self.rendered_sprites.get(&sprite_id) {
+            layer.context.paint_surface(&rendered.surface);
+        }
+    }
+
     ///
     /// Retrieves the transformation matrix for this canvas
     /// 
@@ -230,6 +308,7 @@ impl PixBufCanvas {
     /// 
     pub fn set_viewport(&mut self, new_viewport: CanvasViewport) {
         self.layers.clear();
+        self.rendered_sprites.clear();
         self.viewport = new_viewport;
     }
 
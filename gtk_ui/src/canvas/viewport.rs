@@ -1,3 +1,5 @@
+use flo_canvas::*;
+
 ///
 /// Describes a region being drawn by a canvas
 ///
@@ -33,4 +35,16 @@ impl CanvasViewport {
             viewport_height:    1
         }
     }
+
+    ///
+    /// The transformation from view coordinates (where the canvas runs from -1 to 1 vertically, with the y axis
+    /// pointing upwards) to pixels in this viewport
+    ///
+    pub fn transform(&self) -> Transform2D {
+        let scale = (self.height as f32)/2.0;
+
+        Transform2D::translate(-self.viewport_x as f32, -self.viewport_y as f32)
+            * Transform2D::translate((self.width as f32)/2.0, (self.height as f32)/2.0)
+            * Transform2D::scale(scale, -scale)
+    }
 }
\ No newline at end of file
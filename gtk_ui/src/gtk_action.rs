@@ -45,6 +45,7 @@ pub enum GtkWidgetType {
     ToggleButton,
     Label,
     Scale,
+    TextBox,
     ScrollArea,
     Popover,
 
@@ -164,6 +165,9 @@ pub enum WidgetState {
     /// Sets the value of this widget
     SetValueFloat(f64),
 
+    /// Sets the value of this widget to some text
+    SetValueText(String),
+
     /// Sets the minimum value for this widget
     SetRangeMin(f64),
 
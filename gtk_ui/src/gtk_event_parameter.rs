@@ -21,6 +21,9 @@ pub enum GtkEventParameter {
     /// Event indicates the value set for a scale
     ScaleValue(f64),
 
+    /// Event indicates the text entered in a text box
+    Text(String),
+
     /// Painting started
     PaintStart(GtkPainting),
 
@@ -54,7 +57,8 @@ pub struct GtkPainting {
     pub position:   (f64, f64),
     pub pressure:   f64,
     pub xtilt:      f64,
-    pub ytilt:      f64
+    pub ytilt:      f64,
+    pub modifiers:  PaintModifiers
 }
 
 impl From<GtkEventParameter> for ActionParameter {
@@ -62,6 +66,7 @@ impl From<GtkEventParameter> for ActionParameter {
         match event {
             GtkEventParameter::None                                         => ActionParameter::None,
             GtkEventParameter::ScaleValue(value)                            => ActionParameter::Value(PropertyValue::Float(value)),
+            GtkEventParameter::Text(text)                                   => ActionParameter::Value(PropertyValue::String(text)),
             GtkEventParameter::PaintStart(paint)                            => ActionParameter::Paint(paint.get_device(), vec![ paint.to_painting(PaintAction::Start) ]),
             GtkEventParameter::PaintContinue(paint)                         => ActionParameter::Paint(paint.get_device(), vec![ paint.to_painting(PaintAction::Continue) ]),
             GtkEventParameter::PaintFinish(paint)                           => ActionParameter::Paint(paint.get_device(), vec![ paint.to_painting(PaintAction::Finish) ]),
@@ -124,7 +129,8 @@ impl GtkPainting {
             position: button.get_position(),
             pressure: 1.0,
             xtilt: 0.0,
-            ytilt: 0.0
+            ytilt: 0.0,
+            modifiers: Self::modifiers(button.get_state())
         };
 
         // Update from the axes available from this device
@@ -146,7 +152,8 @@ impl GtkPainting {
             position: motion.get_position(),
             pressure: 1.0,
             xtilt: 0.0,
-            ytilt: 0.0
+            ytilt: 0.0,
+            modifiers: Self::modifiers(motion.get_state())
         };
 
         // Update from the axes available from this device
@@ -159,6 +166,17 @@ impl GtkPainting {
         painting
     }
 
+    ///
+    /// Converts the modifier state from a GDK event
+    /// 
+    fn modifiers(state: gdk::ModifierType) -> PaintModifiers {
+        PaintModifiers {
+            shift:      state.contains(gdk::ModifierType::SHIFT_MASK),
+            control:    state.contains(gdk::ModifierType::CONTROL_MASK),
+            alt:        state.contains(gdk::ModifierType::MOD1_MASK)
+        }
+    }
+
     ///
     /// Turns this into an indicator of the device that performed the painting action
     /// 
@@ -179,7 +197,8 @@ impl GtkPainting {
             location:   (x as f32, y as f32),
             pressure:   self.pressure as f32,
             tilt_x:     self.xtilt as f32,
-            tilt_y:     self.ytilt as f32
+            tilt_y:     self.ytilt as f32,
+            modifiers:  self.modifiers
         }
     }
 }
@@ -45,7 +45,7 @@ impl GtkPaintDevice {
 ///
 /// Types of widget event that can be registered
 /// 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum GtkWidgetEventType {
     /// User pressed and released the mouse over a particular widget (or any of its children, if they do not generate their own event for this situation)
     Click,
@@ -66,7 +66,13 @@ pub enum GtkWidgetEventType {
     VirtualScroll(f32, f32),
 
     /// User has interacted outside of this widget
-    Dismiss
+    Dismiss,
+
+    /// User clicked the widget and picked a file to load (the string is a file name with the extension to look for)
+    LoadFile(String),
+
+    /// User clicked the widget and picked where to save its value (the string is the suggested file name)
+    SaveFile(String)
 }
 
 impl From<PaintDevice> for GtkPaintDevice {
@@ -100,7 +100,8 @@ impl ToGtkActions for Control {
             Label               => New(GtkWidgetType::Label),
             Canvas              => New(canvas_type_for_control(self)),
             Slider              => New(GtkWidgetType::Scale),
-            Rotor               => New(GtkWidgetType::Rotor)
+            Rotor               => New(GtkWidgetType::Rotor),
+            TextBox             => New(GtkWidgetType::TextBox)
         };
         
         // The widget class allows the style sheet to specifically target Flo widgets
@@ -114,7 +115,8 @@ impl ToGtkActions for Control {
             Label               => "flo-label",
             Canvas              => "flo-canvas",
             Slider              => "flo-slider",
-            Rotor               => "flo-rotor"
+            Rotor               => "flo-rotor",
+            TextBox             => "flo-textbox"
         };
 
         // Build into the 'create control' action
@@ -235,7 +237,10 @@ impl ToGtkActions for State {
         match self {
             &Selected(ref selected)     => vec![ PropertyAction::from_property(selected.clone(), |value| vec![ WidgetState::SetSelected(value.to_bool().unwrap_or(false)).into() ]) ],
             &Badged(ref badged)         => vec![ PropertyAction::from_property(badged.clone(), |value| vec![ WidgetState::SetBadged(value.to_bool().unwrap_or(false)).into() ]) ],
-            &Value(ref value)           => vec![ PropertyAction::from_property(value.clone(), |value| vec![ WidgetState::SetValueFloat(value.to_f64().unwrap_or(0.0)).into() ]) ],
+            &Value(ref value)           => vec![ PropertyAction::from_property(value.clone(), |value| match value {
+                PropertyValue::String(text) => vec![ WidgetState::SetValueText(text).into() ],
+                value                       => vec![ WidgetState::SetValueFloat(value.to_f64().unwrap_or(0.0)).into() ]
+            }) ],
             &Range((ref min, ref max))  => vec![ 
                 PropertyAction::from_property(min.clone(), |min| vec![ WidgetState::SetRangeMin(min.to_f64().unwrap_or(0.0)).into() ]),
                 PropertyAction::from_property(max.clone(), |max| vec![ WidgetState::SetRangeMax(max.to_f64().unwrap_or(0.0)).into() ]) 
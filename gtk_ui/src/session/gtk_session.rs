@@ -582,7 +582,9 @@ impl GtkSessionCore {
                     Drag                            => vec![ RequestEvent(GtkWidgetEventType::Drag, action_name) ],
                     EditValue                       => vec![ RequestEvent(GtkWidgetEventType::EditValue, action_name) ],
                     SetValue                        => vec![ RequestEvent(GtkWidgetEventType::SetValue, action_name) ],
-                    VirtualScroll(width, height)    => vec![ RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), action_name) ]
+                    VirtualScroll(width, height)    => vec![ RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), action_name) ],
+                    LoadFile(file_name)             => vec![ RequestEvent(GtkWidgetEventType::LoadFile(file_name), action_name) ],
+                    SaveFile(file_name)             => vec![ RequestEvent(GtkWidgetEventType::SaveFile(file_name), action_name) ]
                 }
             })
             .collect()
@@ -1,4 +1,5 @@
 use super::drag::*;
+use super::file::*;
 use super::paint::*;
 use super::layout::*;
 use super::widget::*;
@@ -80,11 +81,11 @@ pub fn process_basic_widget_action<W: GtkUiWidget>(widget: &mut W, flo_gtk: &mut
     use self::GtkWidgetAction::*;
 
     match action {
-        &RequestEvent(event_type, ref action_name)  => process_basic_event_request(widget, flo_gtk, event_type, action_name),
+        &RequestEvent(ref event_type, ref name)     => process_basic_event_request(widget, flo_gtk, event_type, name),
         &Layout(ref layout)                         => process_basic_widget_layout(widget.id(), widget.get_underlying(), flo_gtk, layout),
         &Content(ref content)                       => process_basic_widget_content(widget, flo_gtk, content),
         &Appearance(ref appearance)                 => process_basic_widget_appearance(widget, flo_gtk, appearance),
-        &State(ref state)                           => process_basic_widget_state(widget, state),
+        &State(ref state)                           => process_basic_widget_state(widget, flo_gtk, state),
         &Font(ref font)                             => process_basic_widget_font(widget, flo_gtk, font),
         &Scroll(ref scroll)                         => process_basic_widget_scroll(widget.get_underlying(), flo_gtk, scroll),
         &Popup(ref _popup)                          => (),
@@ -209,7 +210,7 @@ pub fn process_basic_widget_appearance<W: GtkUiWidget>(widget: &W, flo_gtk: &mut
 ///
 /// Processes a basic state command for a widget being managed by FlowBetween
 /// 
-pub fn process_basic_widget_state<W: GtkUiWidget>(widget: &W, state: &WidgetState) {
+pub fn process_basic_widget_state<W: GtkUiWidget>(widget: &W, flo_gtk: &mut FloGtk, state: &WidgetState) {
     use self::WidgetState::*;
 
     match state {
@@ -228,6 +229,10 @@ pub fn process_basic_widget_state<W: GtkUiWidget>(widget: &W, state: &WidgetStat
         },
 
         &SetValueFloat(_value)      => (),
+        &SetValueText(ref value)    => {
+            // Remembered so the value can be written out by a save file action
+            flo_gtk.widget_data().set_widget_data(widget.id(), TextValue(value.clone()));
+        },
         &SetRangeMin(_from)         => (),
         &SetRangeMax(_to)           => ()
     }
@@ -269,7 +274,7 @@ pub fn process_basic_widget_scroll<W: WidgetExt>(_widget: &W, _flo_gtk: &mut Flo
 ///
 /// Performs the actions associated with basic event registration for a widget
 /// 
-pub fn process_basic_event_request<W: GtkUiWidget>(widget: &W, flo_gtk: &mut FloGtk, event_type: GtkWidgetEventType, action_name: &String) {
+pub fn process_basic_event_request<W: GtkUiWidget>(widget: &W, flo_gtk: &mut FloGtk, event_type: &GtkWidgetEventType, action_name: &String) {
     use self::GtkWidgetEventType::*;
     use self::GtkEvent::Event;
         
@@ -278,7 +283,7 @@ pub fn process_basic_event_request<W: GtkUiWidget>(widget: &W, flo_gtk: &mut Flo
     let event_sink  = RefCell::new(flo_gtk.get_event_sink());
 
     match event_type {
-        Click => {
+        &Click => {
             // For basic widgets with no explicit click action, we just detect the button press event
             widget.get_underlying().add_events((gdk::EventMask::BUTTON_PRESS_MASK).bits() as i32);
 
@@ -298,14 +303,22 @@ pub fn process_basic_event_request<W: GtkUiWidget>(widget: &W, flo_gtk: &mut Flo
                 }); 
             },
         
-        Paint(device) => {
+        &Paint(device) => {
             PaintActions::wire_widget(flo_gtk.widget_data(), event_sink, widget, action_name.clone(), device);
         },
 
-        Drag => {
+        &Drag => {
             DragActions::wire_widget(flo_gtk.widget_data(), event_sink.into_inner(), widget, action_name.clone());
         },
         
-        VirtualScroll(_, _) | EditValue | SetValue | Dismiss => { }
+        &LoadFile(ref file_name) => {
+            FileActions::wire_load(event_sink.into_inner(), widget, action_name.clone(), file_name.clone());
+        },
+
+        &SaveFile(ref file_name) => {
+            FileActions::wire_save(flo_gtk.widget_data(), event_sink.into_inner(), widget, action_name.clone(), file_name.clone());
+        },
+
+        &VirtualScroll(_, _) | &EditValue | &SetValue | &Dismiss => { }
     }
 }
@@ -4,6 +4,7 @@ use super::flo_fixed_widget::*;
 use super::flo_popover_widget::*;
 use super::flo_label_widget::*;
 use super::flo_scale_widget::*;
+use super::flo_entry_widget::*;
 use super::flo_rotor_widget::*;
 use super::flo_scroll_widget::*;
 use super::flo_canvas_widget::*;
@@ -43,5 +44,7 @@ pub fn create_widget(id: WidgetId, widget_type: GtkWidgetType, widget_data: Rc<W
             scale.set_draw_value(false);
             Box::new(FloScaleWidget::new(id, scale))
         },
+
+        TextBox             => Box::new(FloEntryWidget::new(id, gtk::Entry::new())),
     }
 }
@@ -0,0 +1,109 @@
+use super::widget::*;
+use super::widget_data::*;
+use super::super::gtk_event::*;
+use super::super::gtk_event_parameter::*;
+
+use gtk;
+use gtk::prelude::*;
+use gdk;
+use futures::*;
+
+use std::fs;
+use std::rc::*;
+use std::cell::*;
+use std::path::{Path, PathBuf};
+
+///
+/// The most recent text value set for a widget (this is what is written out by the save file action)
+///
+pub struct TextValue(pub String);
+
+///
+/// Provides the implementation of the 'load file' and 'save file' actions for Flo widgets
+///
+pub struct FileActions;
+
+impl FileActions {
+    ///
+    /// Wires a widget up so that clicking it asks the user for a file and sends its contents as the event
+    ///
+    pub fn wire_load<W: GtkUiWidget>(event_sink: GtkEventSink, widget: &W, event_name: String, file_name: String) {
+        let widget_id       = widget.id();
+        let mut event_sink  = event_sink;
+
+        Self::connect_click(widget.get_underlying(), move || {
+            let path = Self::choose_file(gtk::FileChooserAction::Open, "Open", "_Open", &file_name);
+
+            // Files that can't be read as text are ignored (as though the user had cancelled)
+            if let Some(contents) = path.and_then(|path| fs::read_to_string(path).ok()) {
+                event_sink.start_send(GtkEvent::Event(widget_id, event_name.clone(), GtkEventParameter::Text(contents))).unwrap();
+            }
+        });
+    }
+
+    ///
+    /// Wires a widget up so that clicking it asks the user where to save the widget's text value before sending the event
+    ///
+    pub fn wire_save<W: GtkUiWidget>(widget_data: Rc<WidgetData>, event_sink: GtkEventSink, widget: &W, event_name: String, file_name: String) {
+        let widget_id       = widget.id();
+        let mut event_sink  = event_sink;
+
+        Self::connect_click(widget.get_underlying(), move || {
+            let path = Self::choose_file(gtk::FileChooserAction::Save, "Save", "_Save", &file_name);
+
+            if let Some(path) = path {
+                let contents = widget_data.get_widget_data::<TextValue>(widget_id)
+                    .map(|value| value.borrow().0.clone())
+                    .unwrap_or_else(|| String::new());
+
+                if fs::write(path, contents).is_ok() {
+                    event_sink.start_send(GtkEvent::Event(widget_id, event_name.clone(), GtkEventParameter::None)).unwrap();
+                }
+            }
+        });
+    }
+
+    ///
+    /// Calls a function when the left mouse button is pressed over a widget
+    ///
+    fn connect_click<ClickFn: 'static+FnMut() -> ()>(widget: &gtk::Widget, on_click: ClickFn) {
+        let on_click = RefCell::new(on_click);
+
+        widget.add_events((gdk::EventMask::BUTTON_PRESS_MASK).bits() as i32);
+        widget.connect_button_press_event(move |_, button| {
+            if button.get_button() == 1 {
+                (&mut *on_click.borrow_mut())();
+                Inhibit(true)
+            } else {
+                Inhibit(false)
+            }
+        });
+    }
+
+    ///
+    /// Shows a file chooser dialog, returning the path that the user picked
+    ///
+    fn choose_file(action: gtk::FileChooserAction, title: &str, accept_label: &str, file_name: &str) -> Option<PathBuf> {
+        let dialog = gtk::FileChooserDialog::new(Some(title), None::<&gtk::Window>, action);
+        dialog.add_button("_Cancel", gtk::ResponseType::Cancel.into());
+        dialog.add_button(accept_label, gtk::ResponseType::Accept.into());
+
+        // Only show files with the same extension as the file name
+        if let Some(extension) = Path::new(file_name).extension() {
+            let filter = gtk::FileFilter::new();
+            filter.add_pattern(&format!("*.{}", extension.to_string_lossy()));
+            dialog.add_filter(&filter);
+        }
+
+        if action == gtk::FileChooserAction::Save {
+            dialog.set_current_name(file_name);
+            dialog.set_do_overwrite_confirmation(true);
+        }
+
+        let response    = dialog.run();
+        let path        = if response == gtk::ResponseType::Accept.into() { dialog.get_filename() } else { None };
+        dialog.destroy();
+
+        path
+    }
+}
@@ -0,0 +1,121 @@
+use super::widget::*;
+use super::basic_widget::*;
+use super::super::gtk_event::*;
+use super::super::gtk_thread::*;
+use super::super::gtk_action::*;
+use super::super::gtk_event_parameter::*;
+use super::super::gtk_widget_event_type::*;
+
+use gtk;
+use gtk::prelude::*;
+use futures::*;
+
+use std::cell::*;
+use std::rc::*;
+
+///
+/// Provides support for the text entry widget
+///
+pub struct FloEntryWidget {
+    /// The ID of the widget
+    id: WidgetId,
+
+    /// The entry box
+    entry: gtk::Entry,
+
+    /// The entry as a widget
+    widget: gtk::Widget,
+
+    /// Flag that indicates if the text is being updated by FlowBetween (and not by the user)
+    setting_text: Rc<RefCell<bool>>
+}
+
+impl FloEntryWidget {
+    ///
+    /// Creates a new entry widget
+    ///
+    pub fn new(id: WidgetId, entry: gtk::Entry) -> FloEntryWidget {
+        FloEntryWidget {
+            id:             id,
+            widget:         entry.clone().upcast::<gtk::Widget>(),
+            entry:          entry,
+            setting_text:   Rc::new(RefCell::new(false))
+        }
+    }
+
+    ///
+    /// Updates the text in the entry box, without generating any edit events
+    ///
+    fn set_text(&self, new_text: &str) {
+        // Don't replace what the user is typing
+        if self.entry.has_focus() {
+            return;
+        }
+
+        if self.entry.get_text().as_ref().map(|text| &**text) != Some(new_text) {
+            *self.setting_text.borrow_mut() = true;
+            self.entry.set_text(new_text);
+            *self.setting_text.borrow_mut() = false;
+        }
+    }
+}
+
+impl GtkUiWidget for FloEntryWidget {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn process(&mut self, flo_gtk: &mut FloGtk, action: &GtkWidgetAction) {
+        use self::GtkWidgetAction::*;
+        use self::WidgetState::*;
+        use self::GtkWidgetEventType::{EditValue, SetValue};
+
+        match action {
+            &State(SetValueText(ref new_text))          => self.set_text(new_text),
+
+            &RequestEvent(SetValue, ref event_name)     => {
+                // Set events are generated when the user presses return or moves the focus away from the entry box
+                let id              = self.id;
+                let sink            = RefCell::new(flo_gtk.get_event_sink());
+                let event_name      = event_name.clone();
+                self.entry.connect_activate(move |widget| {
+                    let text = widget.get_text().unwrap_or_default();
+                    sink.borrow_mut().start_send(GtkEvent::Event(id, event_name.clone(), GtkEventParameter::Text(text))).unwrap();
+                });
+
+                let sink            = RefCell::new(flo_gtk.get_event_sink());
+                let event_name      = event_name.clone();
+                self.entry.connect_focus_out_event(move |widget, _| {
+                    let text = widget.get_text().unwrap_or_default();
+                    sink.borrow_mut().start_send(GtkEvent::Event(id, event_name.clone(), GtkEventParameter::Text(text))).unwrap();
+                    Inhibit(false)
+                });
+            },
+
+            &RequestEvent(EditValue, ref event_name)    => {
+                // Edit events are generated whenever the user changes the text
+                let id              = self.id;
+                let sink            = RefCell::new(flo_gtk.get_event_sink());
+                let event_name      = event_name.clone();
+                let setting_text    = Rc::clone(&self.setting_text);
+
+                self.entry.connect_changed(move |widget| {
+                    if !*setting_text.borrow() {
+                        let text = widget.get_text().unwrap_or_default();
+                        sink.borrow_mut().start_send(GtkEvent::Event(id, event_name.clone(), GtkEventParameter::Text(text))).unwrap();
+                    }
+                });
+            },
+
+            other_action                                => { process_basic_widget_action(self, flo_gtk, other_action); }
+        }
+    }
+
+    fn set_children(&mut self, _children: Vec<Rc<RefCell<GtkUiWidget>>>) {
+        // Entry boxes cannot have child widgets
+    }
+
+    fn get_underlying<'a>(&'a self) -> &'a gtk::Widget {
+        &self.widget
+    }
+}
@@ -7,6 +7,7 @@ pub mod flo_label_widget;
 pub mod flo_fixed_widget;
 pub mod flo_popover_widget;
 pub mod flo_scale_widget;
+pub mod flo_entry_widget;
 pub mod flo_rotor_widget;
 pub mod flo_scroll_widget;
 pub mod flo_canvas_widget;
@@ -20,6 +21,7 @@ mod run_action;
 mod factory;
 mod image;
 mod drag;
+mod file;
 mod paint;
 mod events;
 
@@ -151,10 +151,15 @@ let flo_canvas = (function() {
         let context_stack               = [];
         let clip_stack                  = [];
         let clipped                     = false;
-        let transform                   = [1,0,0, 0,1,0, 0,0,1];
+        let transform                   = flo_matrix.identity();
         let inverse_transform           = null;
         let dash_pattern                = [];
         let set_dash_pattern            = true;
+        let current_gradient            = null;
+        let font_family                 = 'sans-serif';
+        let font_weight                 = 'normal';
+        let font_size                   = 12;
+        let current_text                = '';
         let stored_pixels               = document.createElement('canvas');
         let generate_buffer_on_store    = false;
         let have_stored_image           = false;
@@ -162,6 +167,10 @@ let flo_canvas = (function() {
         let layer_canvases              = null;
         let blend_for_layer             = {};
         let current_layer_id            = 0;
+        let sprites                     = {};
+        let rendered_sprites            = {};
+        let sprite_definition           = null;
+        let sprite_depth                = 0;
 
         ///
         /// Sets the current transform (lack of browser support for currentTransform means we have to track this independently)
@@ -175,24 +184,7 @@ let flo_canvas = (function() {
         /// Multiplies the transformation matrix (lack of browser support again)
         ///
         function transform_multiply(new_transform) {
-            let t1 = transform;
-            let t2 = new_transform;
-
-            let res = [
-                t1[0]*t2[0] + t1[1]*t2[3] + t1[2]*t2[6],
-                t1[0]*t2[1] + t1[1]*t2[4] + t1[2]*t2[7],
-                t1[0]*t2[2] + t1[1]*t2[5] + t1[2]*t2[8],
-
-                t1[3]*t2[0] + t1[4]*t2[3] + t1[5]*t2[6],
-                t1[3]*t2[1] + t1[4]*t2[4] + t1[5]*t2[7],
-                t1[3]*t2[2] + t1[4]*t2[5] + t1[5]*t2[8],
-
-                t1[6]*t2[0] + t1[7]*t2[3] + t1[8]*t2[6],
-                t1[6]*t2[1] + t1[7]*t2[4] + t1[8]*t2[7],
-                t1[6]*t2[2] + t1[7]*t2[5] + t1[8]*t2[8],
-            ];
-
-            transform           = res;
+            transform           = flo_matrix.multiply(transform, new_transform);
             inverse_transform   = null;
         }
 
@@ -228,13 +220,120 @@ let flo_canvas = (function() {
             context.strokeStyle = 'rgba(' + r + ',' + g + ',' + b + ',' + a + ')';
         }
 
+        function new_linear_gradient(x1, y1, x2, y2) {
+            current_gradient = {
+                create:     (t) => {
+                    let start   = flo_matrix.transform_point(t, x1, y1);
+                    let end     = flo_matrix.transform_point(t, x2, y2);
+
+                    return context.createLinearGradient(start[0], start[1], end[0], end[1]);
+                },
+                stops:      [],
+                transform:  flo_matrix.identity()
+            };
+        }
+
+        function new_radial_gradient(x1, y1, r1, x2, y2, r2) {
+            current_gradient = {
+                create:     (t) => {
+                    let start   = flo_matrix.transform_point(t, x1, y1);
+                    let end     = flo_matrix.transform_point(t, x2, y2);
+                    let scale   = flo_matrix.axis_scale(t)[0];
+
+                    return context.createRadialGradient(start[0], start[1], r1*scale, end[0], end[1], r2*scale);
+                },
+                stops:      [],
+                transform:  flo_matrix.identity()
+            };
+        }
+
+        function gradient_stop(pos, r, g, b, a) {
+            if (current_gradient) {
+                r = Math.floor(r*255.0);
+                g = Math.floor(g*255.0);
+                b = Math.floor(b*255.0);
+
+                current_gradient.stops.push([Math.min(1, Math.max(0, pos)), 'rgba(' + r + ',' + g + ',' + b + ',' + a + ')']);
+            }
+        }
+
+        function gradient_transform(transform) {
+            if (current_gradient) {
+                current_gradient.transform = transform;
+            }
+        }
+
+        function create_gradient() {
+            // The gradient transform is applied to the points that define the gradient
+            let gradient = current_gradient.create(current_gradient.transform);
+            current_gradient.stops.forEach(stop => gradient.addColorStop(stop[0], stop[1]));
+
+            return gradient;
+        }
+
+        function fill_gradient() {
+            if (current_gradient) {
+                context.fillStyle = create_gradient();
+            }
+        }
+
+        function stroke_gradient() {
+            if (current_gradient) {
+                context.strokeStyle = create_gradient();
+            }
+        }
+
+        function update_font() {
+            context.font = font_weight + ' ' + font_size + 'px ' + font_family;
+        }
+
+        function font(family, weight) {
+            font_family = family;
+            font_weight = weight;
+            update_font();
+        }
+
+        function set_font_size(size) {
+            font_size = size;
+            update_font();
+        }
+
+        function text_align(align) {
+            context.textAlign = align;
+        }
+
+        function new_text() {
+            current_text = '';
+        }
+
+        function text_char(char_code) {
+            current_text += String.fromCodePoint(char_code);
+        }
+
+        function draw_text(x, y, draw_fn) {
+            // Canvas coordinates run upwards, so flip the text so it's the right way up
+            context.save();
+            context.translate(x, y);
+            context.scale(1, -1);
+            draw_fn(current_text);
+            context.restore();
+        }
+
+        function fill_text(x, y) {
+            draw_text(x, y, text => context.fillText(text, 0, 0));
+        }
+
+        function stroke_text(x, y) {
+            draw_text(x, y, text => context.strokeText(text, 0, 0));
+        }
+
         function line_width(width) {
             context.lineWidth = width;
         }
 
         function line_width_pixels(width) {
             // Length of the first column of the transformation matrix is the scale factor (for the width)
-            let scale = Math.sqrt(transform[0]*transform[0] + transform[3]*transform[3]);
+            let scale = flo_matrix.axis_scale(transform)[0];
             if (scale === 0) scale = 1;
             scale /= window.devicePixelRatio || 1;
 
@@ -290,40 +389,12 @@ let flo_canvas = (function() {
                 pixel_width/2.0,    pixel_height/2.0
             );
 
-            transform_set([ 
-                ratio_x,    0,          pixel_width/2.0,
-                0,          ratio_y,    pixel_height/2.0, 
-                0,          0,          1
-            ]);
+            transform_set(flo_matrix.multiply(flo_matrix.translate(pixel_width/2.0, pixel_height/2.0), flo_matrix.scale(ratio_x, ratio_y)));
         }
 
         function center_region(minx, miny, maxx, maxy) {
-            let pixel_width     = canvas.width;
-            let pixel_height    = canvas.height;
-
-            // Get the current scaling of this canvas
-            let xscale = Math.sqrt(transform[0]*transform[0] + transform[3]*transform[3]);
-            let yscale = Math.sqrt(transform[1]*transform[1] + transform[4]*transform[4]);
-            if (xscale === 0) xscale = 1;
-            if (yscale === 0) yscale = 1;
-
-            // Current X, Y coordinates (centered)
-            let cur_x = (transform[2]-(pixel_width/2.0))/xscale;
-            let cur_y = (transform[5]-(pixel_height/2.0))/yscale;
-            
-            // New center coordinates
-            let center_x = (minx+maxx)/2.0;
-            let center_y = (miny+maxy)/2.0;
-
-            // Compute the offsets and transform the canvas
-            let x_offset = cur_x - center_x;
-            let y_offset = cur_y - center_y;
-
-            multiply_transform([
-                1, 0, x_offset,
-                0, 1, y_offset,
-                0, 0, 1
-            ]);
+            // The region is centered on the middle of the canvas
+            multiply_transform(flo_matrix.center_region(transform, canvas.width/2.0, canvas.height/2.0, minx, miny, maxx, maxy));
         }
 
         function multiply_transform(transform) {
@@ -563,6 +634,9 @@ let flo_canvas = (function() {
                 }
             }
 
+            // Sprites that were only kept for this layer can now be released
+            release_freed_sprites();
+
             // Add a 'set layer' command to the replay
             replay.push([layer, [current_layer_id], current_layer_id]);
         }
@@ -594,13 +668,176 @@ let flo_canvas = (function() {
             fill_color(0,0,0,1);
             stroke_color(0,0,0,1);
             line_width(1.0);
+
+            // Remove any sprites
+            sprites             = {};
+            rendered_sprites    = {};
+            sprite_definition   = null;
+        }
+
+        ///
+        /// Performs a drawing action, or records it if a sprite is being defined
+        ///
+        function perform(action, args) {
+            if (sprite_definition) {
+                sprite_definition.drawing.push([action, args]);
+            } else {
+                replay.push([action, args, current_layer_id]);
+                action.apply(null, args);
+            }
+        }
+
+        function start_sprite(sprite_id) {
+            // Sprites are stored as the list of drawing actions that they perform, which is replayed when they're drawn
+            end_sprite();
+            sprite_definition = { sprite_id: sprite_id, drawing: [] };
+        }
+
+        function end_sprite() {
+            if (sprite_definition) {
+                define_sprite(sprite_definition.sprite_id, sprite_definition.drawing);
+                sprite_definition = null;
+            }
+        }
+
+        function define_sprite(sprite_id, drawing) {
+            // Renderings of any sprite might include this one, so they all need to be regenerated
+            sprites[sprite_id]  = drawing;
+            rendered_sprites    = {};
+        }
+
+        function draw_sprite(sprite_id, sprite_transform) {
+            let drawing = sprites[sprite_id];
+
+            // Sprites can draw other sprites, but we stop if they nest too deeply (so a sprite that draws itself will eventually stop)
+            if (!drawing || sprite_depth >= 16) {
+                return;
+            }
+
+            // Re-use the last rendering of this sprite if it ends up in the same place on the canvas
+            let sprite_canvas_transform = flo_matrix.multiply(transform, sprite_transform);
+            let rendered                = rendered_sprites[sprite_id];
+
+            if (!rendered || rendered.canvas.width !== canvas.width || rendered.canvas.height !== canvas.height || !rendered.transform.every((val, idx) => val === sprite_canvas_transform[idx])) {
+                rendered = { transform: sprite_canvas_transform, canvas: render_sprite(drawing, sprite_canvas_transform) };
+                rendered_sprites[sprite_id] = rendered;
+            }
+
+            // Copy the rendering onto the current layer (the clipping path and blend mode still apply)
+            context.save();
+            context.resetTransform();
+            context.drawImage(rendered.canvas, 0, 0);
+            context.restore();
+        }
+
+        ///
+        /// Renders a sprite's drawing onto a new canvas the same size as the one we're drawing on
+        ///
+        function render_sprite(drawing, sprite_canvas_transform) {
+            let sprite_canvas       = document.createElement('canvas');
+            sprite_canvas.width     = canvas.width;
+            sprite_canvas.height    = canvas.height;
+
+            // The sprite starts with the current drawing style, but no clipping or saved states
+            let sprite_context          = sprite_canvas.getContext('2d');
+            sprite_context.fillStyle    = context.fillStyle;
+            sprite_context.strokeStyle  = context.strokeStyle;
+            sprite_context.lineWidth    = context.lineWidth;
+            sprite_context.lineJoin     = context.lineJoin;
+            sprite_context.lineCap      = context.lineCap;
+            sprite_context.font         = context.font;
+            sprite_context.textAlign    = context.textAlign;
+
+            // Draw on the sprite canvas instead of the current layer while the sprite's instructions are performed
+            let layer_context       = context;
+            let layer_transform     = transform;
+            let layer_clip_stack    = clip_stack;
+            let layer_clipped       = clipped;
+            let layer_context_stack = context_stack;
+
+            context         = sprite_context;
+            clip_stack      = [];
+            clipped         = false;
+            context_stack   = [];
+            set_dash_pattern = true;
+            transform_set(sprite_canvas_transform);
+            context.setTransform(
+                transform[0],transform[3], 
+                transform[1],transform[4], 
+                transform[2],transform[5]
+            );
+
+            ++sprite_depth;
+            drawing.forEach(item => item[0].apply(null, item[1]));
+            --sprite_depth;
+
+            // Go back to drawing on the layer
+            context         = layer_context;
+            clip_stack      = layer_clip_stack;
+            clipped         = layer_clipped;
+            context_stack   = layer_context_stack;
+            set_dash_pattern = true;
+            transform_set(layer_transform);
+
+            return sprite_canvas;
+        }
+
+        function free_sprite(sprite_id) {
+            delete sprites[sprite_id];
+            rendered_sprites = {};
+        }
+
+        ///
+        /// Removes the definitions of any sprites that have been freed and are no longer drawn
+        ///
+        function release_freed_sprites() {
+            // Find the sprites whose most recent definition has been freed
+            let freed = {};
+
+            replay.forEach(item => {
+                if (item[0] === define_sprite) {
+                    freed[item[1][0]] = false;
+                } else if (item[0] === free_sprite) {
+                    freed[item[1][0]] = true;
+                }
+            });
+
+            // Sprites that are still drawn (directly or by another sprite that's kept) need to be kept
+            let changed     = true;
+            let keep_drawn  = item => {
+                if (item[0] === draw_sprite && freed[item[1][0]]) {
+                    freed[item[1][0]]   = false;
+                    changed             = true;
+                }
+            };
+
+            replay.forEach(keep_drawn);
+            while (changed) {
+                changed = false;
+                replay.forEach(item => {
+                    if (item[0] === define_sprite && !freed[item[1][0]]) {
+                        item[1][1].forEach(keep_drawn);
+                    }
+                });
+            }
+
+            // Remove the definitions of the released sprites from the replay
+            replay = replay.filter(item => item[2] !== 'sprite' || !freed[item[1][0]]);
         }
 
         function rewind_to_last_store() {
             if (last_store_pos !== null) {
+                // Sprite definitions apply to the whole canvas so they're kept
+                let sprite_definitions = [];
+
                 while (replay.length > last_store_pos) {
-                    replay.pop();
+                    let item = replay.pop();
+                    if (item[2] === 'sprite') {
+                        sprite_definitions.unshift(item);
+                    }
                 }
+
+                sprite_definitions.forEach(item => replay.push(item));
             }
         }
 
@@ -624,14 +861,14 @@ let flo_canvas = (function() {
         function map_coords(x, y) {
             // Invert the active transformation matrix if it's not already inverted
             if (inverse_transform === null) {
-                inverse_transform = flo_matrix.invert3(transform);
+                inverse_transform = flo_matrix.invert(transform) || flo_matrix.identity();
             }
 
             // Assuming square pixels, map x,y to internal canvas coords
             let ratio = canvas.width / canvas.clientWidth;
 
             // Use the inverse matrix to map the coordinates
-            return flo_matrix.mulvec3(inverse_transform, [x*ratio, y*ratio, 1]);
+            return flo_matrix.transform_point(inverse_transform, x*ratio, y*ratio);
         }
 
         function draw_layers() {
@@ -657,38 +894,55 @@ let flo_canvas = (function() {
         }
 
         return {
-            new_path:           ()              => { replay.push([new_path, [], current_layer_id]);                         new_path();                     },
-            move_to:            (x, y)          => { replay.push([move_to, [x, y], current_layer_id]);                      move_to(x, y);                  },
-            line_to:            (x, y)          => { replay.push([line_to, [x, y], current_layer_id]);                      line_to(x, y);                  },
-            bezier_curve:       (x1, y1, x2, y2, x3, y3) => { replay.push([bezier_curve, [x1, y1, x2, y2, x3, y3], current_layer_id]); bezier_curve(x1, y1, x2, y2, x3, y3); },
-            close_path:         ()              => { replay.push([close_path, [], current_layer_id]);                       close_path();                   },
-            fill:               ()              => { replay.push([fill, [], current_layer_id]);                             fill();                         },
-            stroke:             ()              => { replay.push([stroke, [], current_layer_id]);                           stroke();                       },
-            line_width:         (width)         => { replay.push([line_width, [width], current_layer_id]);                  line_width(width);              },
-            line_width_pixels:  (width)         => { replay.push([line_width_pixels, [width], current_layer_id]);           line_width_pixels(width);       },
-            line_join:          (join)          => { replay.push([line_join, [join], current_layer_id]);                    line_join(join);                },
-            line_cap:           (cap)           => { replay.push([line_cap, [cap], current_layer_id]);                      line_cap(cap);                  },
-            new_dash_pattern:   ()              => { replay.push([new_dash_pattern, [], current_layer_id]);                 new_dash_pattern();             },
-            dash_length:        (length)        => { replay.push([dash_length, [length], current_layer_id]);                dash_length(length);            },
+            new_path:           ()              => { perform(new_path, []); },
+            move_to:            (x, y)          => { perform(move_to, [x, y]); },
+            line_to:            (x, y)          => { perform(line_to, [x, y]); },
+            bezier_curve:       (x1, y1, x2, y2, x3, y3) => { perform(bezier_curve, [x1, y1, x2, y2, x3, y3]); },
+            close_path:         ()              => { perform(close_path, []); },
+            fill:               ()              => { perform(fill, []); },
+            stroke:             ()              => { perform(stroke, []); },
+            line_width:         (width)         => { perform(line_width, [width]); },
+            line_width_pixels:  (width)         => { perform(line_width_pixels, [width]); },
+            line_join:          (join)          => { perform(line_join, [join]); },
+            line_cap:           (cap)           => { perform(line_cap, [cap]); },
+            new_dash_pattern:   ()              => { perform(new_dash_pattern, []); },
+            dash_length:        (length)        => { perform(dash_length, [length]); },
             dash_offset:        (offset)        => { replay.push([dash_offset, [offset], current_layer_id]);                dash_length(offset);            },
-            fill_color:         (r, g, b, a)    => { replay.push([fill_color, [r, g, b, a], current_layer_id]);             fill_color(r, g, b, a);         },
-            stroke_color:       (r, g, b, a)    => { replay.push([stroke_color, [r, g, b, a], current_layer_id]);           stroke_color(r, g, b, a);       },
-            blend_mode:         (mode)          => { replay.push([blend_mode, [mode], current_layer_id]);                   blend_mode(mode);               },
-            identity_transform: ()              => { replay.push([identity_transform, [], current_layer_id]);               identity_transform();           },
-            canvas_height:      (height)        => { replay.push([canvas_height, [height], current_layer_id]);              canvas_height(height);          },
-            center_region:      (x1, y1, x2, y2) => { replay.push([center_region, [x1, y1, x2, y2], current_layer_id]);     center_region(x1, y1, x2, y2);  },
-            multiply_transform: (transform)     => { replay.push([multiply_transform, [transform], current_layer_id]);      multiply_transform(transform);  },
-            unclip:             ()              => { replay.push([unclip, [], current_layer_id]);                           unclip();                       },
-            clip:               ()              => { replay.push([clip, [], current_layer_id]);                             clip();                         },
-            store:              ()              => { replay.push([store, [], current_layer_id]);                            store();                        },
-            restore:            ()              => { replay.push([restore, [], current_layer_id]); rewind_to_last_store();  restore();                      },
-            free_stored_buffer: ()              => { replay.push([free_stored_buffer, [], current_layer_id]); rewind_free_stored(); free_stored_buffer();   },
-            push_state:         ()              => { replay.push([push_state, [], current_layer_id]);                       push_state();                   },
-            pop_state:          ()              => { replay.push([pop_state, [], current_layer_id]);                        pop_state();                    },
-            layer:              (layer_id)      => { replay.push([layer, [layer_id], layer]);                               layer(layer_id);                },
-            layer_blend:        (layer_id, blend_mode) => { replay.push([layer_blend, [layer_id, blend_mode], -1]);         layer_blend(layer_id, blend_mode); },
-            clear_layer:        ()              => { replay.push([clear_layer, [], current_layer_id]);                      clear_layer();                  },
+            fill_color:         (r, g, b, a)    => { perform(fill_color, [r, g, b, a]); },
+            stroke_color:       (r, g, b, a)    => { perform(stroke_color, [r, g, b, a]); },
+            new_linear_gradient: (x1, y1, x2, y2) => { perform(new_linear_gradient, [x1, y1, x2, y2]); },
+            new_radial_gradient: (x1, y1, r1, x2, y2, r2) => { perform(new_radial_gradient, [x1, y1, r1, x2, y2, r2]); },
+            gradient_stop:      (pos, r, g, b, a) => { perform(gradient_stop, [pos, r, g, b, a]); },
+            gradient_transform: (transform)     => { perform(gradient_transform, [transform]); },
+            fill_gradient:      ()              => { perform(fill_gradient, []); },
+            stroke_gradient:    ()              => { perform(stroke_gradient, []); },
+            font:               (family, weight) => { perform(font, [family, weight]); },
+            font_size:          (size)          => { perform(set_font_size, [size]); },
+            text_align:         (align)         => { perform(text_align, [align]); },
+            new_text:           ()              => { perform(new_text, []); },
+            text_char:          (char_code)     => { perform(text_char, [char_code]); },
+            fill_text:          (x, y)          => { perform(fill_text, [x, y]); },
+            stroke_text:        (x, y)          => { perform(stroke_text, [x, y]); },
+            blend_mode:         (mode)          => { perform(blend_mode, [mode]); },
+            identity_transform: ()              => { perform(identity_transform, []); },
+            canvas_height:      (height)        => { perform(canvas_height, [height]); },
+            center_region:      (x1, y1, x2, y2) => { perform(center_region, [x1, y1, x2, y2]); },
+            multiply_transform: (transform)     => { perform(multiply_transform, [transform]); },
+            unclip:             ()              => { perform(unclip, []); },
+            clip:               ()              => { perform(clip, []); },
+            store:              ()              => { if (sprite_definition) return; replay.push([store, [], current_layer_id]);                             store();                        },
+            restore:            ()              => { if (sprite_definition) return; replay.push([restore, [], current_layer_id]); rewind_to_last_store();  restore();                      },
+            free_stored_buffer: ()              => { if (sprite_definition) return; replay.push([free_stored_buffer, [], current_layer_id]); rewind_free_stored(); free_stored_buffer();   },
+            push_state:         ()              => { perform(push_state, []); },
+            pop_state:          ()              => { perform(pop_state, []); },
+            layer:              (layer_id)      => { if (sprite_definition) return; replay.push([layer, [layer_id], layer]);                               layer(layer_id);                },
+            layer_blend:        (layer_id, blend_mode) => { if (sprite_definition) return; replay.push([layer_blend, [layer_id, blend_mode], -1]);         layer_blend(layer_id, blend_mode); },
+            clear_layer:        ()              => { if (sprite_definition) return; replay.push([clear_layer, [], current_layer_id]);                      clear_layer();                  },
             clear_canvas:       ()              => { replay = [ [clear_canvas, [], current_layer_id] ];                     clear_canvas();                 },
+            start_sprite:       (sprite_id)     => { start_sprite(sprite_id); },
+            end_sprite:         ()              => { let definition = sprite_definition; end_sprite(); if (definition) replay.push([define_sprite, [definition.sprite_id, definition.drawing], 'sprite']); },
+            draw_sprite:        (sprite_id, transform) => { perform(draw_sprite, [sprite_id, transform]); },
+            free_sprite:        (sprite_id)     => { if (sprite_definition) return; replay.push([free_sprite, [sprite_id], 'sprite']); free_sprite(sprite_id); },
 
             replay_drawing:     replay_drawing,
             map_coords:         map_coords,
@@ -705,96 +959,200 @@ let flo_canvas = (function() {
     }
 
     ///
-    /// Creates a decoder that will accept a string of serialized canvas data and
-    /// draw it using the provided set of drawing functions
+    /// Creates a reader for canvas data in the string encoding
     ///
-    function create_decoder(draw) {
-        let decoder = (serialized_instructions) => {
-            // Position in the instruction set
-            let pos             = 0;
+    function create_string_reader(serialized_instructions) {
+        // Position in the instruction set
+        let pos             = 0;
 
-            // DataView for decoding floats
-            let float_buffer    = new ArrayBuffer(24);
-            let float_bytes     = new Uint8Array(float_buffer);
-            let float_data      = new DataView(float_buffer);
+        // DataView for decoding floats
+        let float_buffer    = new ArrayBuffer(24);
+        let float_bytes     = new Uint8Array(float_buffer);
+        let float_data      = new DataView(float_buffer);
 
-            ///
-            /// Reads a single character from the instructions
-            ///
-            let read_char = () => {
-                let result = null;
+        ///
+        /// Reads a single character from the instructions
+        ///
+        let read_char = () => {
+            let result = null;
 
-                if (pos < serialized_instructions.length) {
-                    result = serialized_instructions[pos];
-                }
-                ++pos;
+            if (pos < serialized_instructions.length) {
+                result = serialized_instructions[pos];
+            }
+            ++pos;
 
-                return result;
-            };
+            return result;
+        };
 
-            ///
-            /// Returns the value for a particular character fragment
-            ///
-            let char_code_A = 'A'.charCodeAt(0);
-            let char_code_a = 'a'.charCodeAt(0);
-            let char_code_0 = '0'.charCodeAt(0);
-            let fragment_val = (fragment_char) => {
-                let char_code = fragment_char.charCodeAt(0);
-                if (fragment_char >= 'A' && fragment_char <= 'Z') {
-                    return char_code - char_code_A;
-                } else if (fragment_char >= 'a' && fragment_char <= 'z') {
-                    return char_code - char_code_a + 26;
-                } else if (fragment_char >= '0' && fragment_char <= '9') {
-                    return char_code - char_code_0 + 52;
-                } else if (fragment_char === '+') {
-                    return 62;
-                } else if (fragment_char === '/') {
-                    return 63;
-                } else {
-                    return 0;
-                }
-            };
+        ///
+        /// Returns the value for a particular character fragment
+        ///
+        let char_code_A = 'A'.charCodeAt(0);
+        let char_code_a = 'a'.charCodeAt(0);
+        let char_code_0 = '0'.charCodeAt(0);
+        let fragment_val = (fragment_char) => {
+            let char_code = fragment_char.charCodeAt(0);
+            if (fragment_char >= 'A' && fragment_char <= 'Z') {
+                return char_code - char_code_A;
+            } else if (fragment_char >= 'a' && fragment_char <= 'z') {
+                return char_code - char_code_a + 26;
+            } else if (fragment_char >= '0' && fragment_char <= '9') {
+                return char_code - char_code_0 + 52;
+            } else if (fragment_char === '+') {
+                return 62;
+            } else if (fragment_char === '/') {
+                return 63;
+            } else {
+                return 0;
+            }
+        };
 
-            ///
-            /// Reads a 4-byte word into the buffer at the specified offset
-            ///
-            let buffer_word = (offset) => {
-                // Do nothing if we overrun the end of the buffer
-                if (pos + 6 > serialized_instructions.length) {
-                    return;
-                }
-                
-                // Read a fragment
-                let fragment = serialized_instructions.substring(pos, pos+6);
-                pos += 6;
-
-                // Decode it
-                let code_point = [ 0,0,0,0,0,0 ];
-                for (let p = 0; p<6; ++p) {
-                    code_point[p] = fragment_val(fragment[p]);
-                }
+        ///
+        /// Reads a 4-byte word into the buffer at the specified offset
+        ///
+        let buffer_word = (offset) => {
+            // Do nothing if we overrun the end of the buffer
+            if (pos + 6 > serialized_instructions.length) {
+                return;
+            }
+            
+            // Read a fragment
+            let fragment = serialized_instructions.substring(pos, pos+6);
+            pos += 6;
+
+            // Decode it
+            let code_point = [ 0,0,0,0,0,0 ];
+            for (let p = 0; p<6; ++p) {
+                code_point[p] = fragment_val(fragment[p]);
+            }
 
-                float_bytes[offset+3] = (code_point[0])     | ((code_point[1]&0x3)<<6);
-                float_bytes[offset+2] = (code_point[1]>>2)  | ((code_point[2]&0xf)<<4);
-                float_bytes[offset+1] = (code_point[2]>>4)  | (code_point[3]<<2);
-                float_bytes[offset+0] = (code_point[4])     | ((code_point[5]&0x3)<<6);
-            };
+            float_bytes[offset+3] = (code_point[0])     | ((code_point[1]&0x3)<<6);
+            float_bytes[offset+2] = (code_point[1]>>2)  | ((code_point[2]&0xf)<<4);
+            float_bytes[offset+1] = (code_point[2]>>4)  | (code_point[3]<<2);
+            float_bytes[offset+0] = (code_point[4])     | ((code_point[5]&0x3)<<6);
+        };
 
-            ///
-            /// Reads a floating point value
-            ///
-            let read_float = () => {
-                buffer_word(0);
-                return float_data.getFloat32(0);
-            };
+        ///
+        /// Reads a floating point value
+        ///
+        let read_float = () => {
+            buffer_word(0);
+            return float_data.getFloat32(0);
+        };
 
-            ///
-            /// Reads an unsigned int value
-            ///
-            let read_u32 = () => {
-                buffer_word(0);
-                return float_data.getUint32(0);
-            };
+        ///
+        /// Reads an unsigned int value
+        ///
+        let read_u32 = () => {
+            buffer_word(0);
+            return float_data.getUint32(0);
+        };
+
+        return {
+            read_char:  read_char,
+            read_float: read_float,
+            read_u32:   read_u32,
+            read_x:     read_float,
+            read_y:     read_float,
+            position:   () => pos
+        };
+    }
+
+    ///
+    /// Creates a reader for canvas data in the binary encoding
+    ///
+    function create_binary_reader(serialized_instructions) {
+        let data    = new DataView(serialized_instructions);
+        let pos     = 0;
+
+        // Coordinates are stored relative to the previous coordinate
+        let last_x  = 0.0;
+        let last_y  = 0.0;
+
+        // Check the header
+        if (data.byteLength < 3 || data.getUint8(0) !== 'f'.charCodeAt(0) || data.getUint8(1) !== 'c'.charCodeAt(0)) {
+            throw 'Binary canvas data has no header';
+        }
+
+        if (data.getUint8(2) !== 1) {
+            throw 'Unsupported binary canvas encoding version ' + data.getUint8(2);
+        }
+
+        pos = 3;
+
+        ///
+        /// Reads a single character from the instructions
+        ///
+        let read_char = () => {
+            let result = null;
+
+            if (pos < data.byteLength) {
+                result = String.fromCharCode(data.getUint8(pos));
+            }
+            ++pos;
+
+            return result;
+        };
+
+        ///
+        /// Reads a floating point value
+        ///
+        let read_float = () => {
+            let result = data.getFloat32(pos, true);
+            pos += 4;
+            return result;
+        };
+
+        ///
+        /// Reads an unsigned int value
+        ///
+        let read_u32 = () => {
+            let result = data.getUint32(pos, true);
+            pos += 4;
+            return result;
+        };
+
+        ///
+        /// Reads a float stored as a difference from the last value
+        ///
+        let read_squished_float = (last) => {
+            let diff = data.getInt16(pos, true);
+            pos += 2;
+
+            if (diff === -32768) {
+                // Large differences are stored as a full float
+                return last + read_float();
+            } else {
+                // Others are stored as a fixed point value
+                return last + diff/256.0;
+            }
+        };
+
+        return {
+            read_char:  read_char,
+            read_float: read_float,
+            read_u32:   read_u32,
+            read_x:     () => { last_x = read_squished_float(last_x); return last_x; },
+            read_y:     () => { last_y = read_squished_float(last_y); return last_y; },
+            position:   () => pos
+        };
+    }
+
+    ///
+    /// Creates a decoder that will accept serialized canvas data (a string, or an ArrayBuffer
+    /// in the binary encoding) and draw it using the provided set of drawing functions
+    ///
+    function create_decoder(draw) {
+        let decoder = (serialized_instructions) => {
+            let reader = serialized_instructions instanceof ArrayBuffer
+                ? create_binary_reader(serialized_instructions)
+                : create_string_reader(serialized_instructions);
+
+            let read_char   = reader.read_char;
+            let read_float  = reader.read_float;
+            let read_u32    = reader.read_u32;
+            let read_x      = reader.read_x;
+            let read_y      = reader.read_y;
 
             ///
             /// Reads a RGBA colour
@@ -903,6 +1261,80 @@ let flo_canvas = (function() {
                 }
             };
 
+            let decode_gradient = () => {
+                switch (read_char()) {
+                case 'l':   draw.new_linear_gradient(read_float(), read_float(), read_float(), read_float()); break;
+                case 'r':   draw.new_radial_gradient(read_float(), read_float(), read_float(), read_float(), read_float(), read_float()); break;
+                case 's':
+                    {
+                        let pos     = read_float();
+                        let color   = read_rgba();
+                        draw.gradient_stop(pos, color[0], color[1], color[2], color[3]);
+                    }
+                    break;
+                case 't':
+                    {
+                        let transform = [ 1,0,0, 0,1,0, 0,0,1 ];
+                        for (let p=0; p<9; ++p) transform[p] = read_float();
+                        draw.gradient_transform(transform);
+                    }
+                    break;
+                case 'F':   draw.fill_gradient();       break;
+                case 'S':   draw.stroke_gradient();     break;
+                }
+            };
+
+            let decode_font_family = () => {
+                let family = read_char();
+
+                switch (family) {
+                case 'A':   return 'sans-serif';
+                case 'R':   return 'serif';
+                case 'M':   return 'monospace';
+                default:    throw 'Unknown font family: \'' + family + '\'';
+                }
+            };
+
+            let decode_text = () => {
+                switch (read_char()) {
+                case 'f':
+                    {
+                        let family = decode_font_family();
+                        let weight = read_char() === 'B' ? 'bold' : 'normal';
+                        draw.font(family, weight);
+                    }
+                    break;
+                case 's':   draw.font_size(read_float());   break;
+                case 'a':
+                    switch (read_char()) {
+                    case 'L':   draw.text_align('left');    break;
+                    case 'C':   draw.text_align('center');  break;
+                    case 'R':   draw.text_align('right');   break;
+                    }
+                    break;
+                case 'n':   draw.new_text();                break;
+                case 'c':   draw.text_char(read_u32());     break;
+                case 'F':   draw.fill_text(read_x(), read_y());     break;
+                case 'S':   draw.stroke_text(read_x(), read_y());   break;
+                }
+            };
+
+            let decode_sprite = () => {
+                switch (read_char()) {
+                case 's':   draw.start_sprite(read_u32());  break;
+                case 'e':   draw.end_sprite();              break;
+                case 'd':
+                    {
+                        let sprite_id = read_u32();
+                        let transform = [ 1,0,0, 0,1,0, 0,0,1 ];
+                        for (let p=0; p<9; ++p) transform[p] = read_float();
+                        draw.draw_sprite(sprite_id, transform);
+                    }
+                    break;
+                case 'f':   draw.free_sprite(read_u32());   break;
+                }
+            };
+
             let decode_clip = () => {
                 switch (read_char()) {
                 case 'c':   draw.clip();                break;
@@ -926,22 +1358,25 @@ let flo_canvas = (function() {
                     break;
                 
                 case 'N':   decode_new();                               break;
-                case 'm':   draw.move_to(read_float(), read_float());   break;
-                case 'l':   draw.line_to(read_float(), read_float());   break;
-                case 'c':   draw.bezier_curve(read_float(), read_float(), read_float(), read_float(), read_float(), read_float()); break;
+                case 'm':   draw.move_to(read_x(), read_y());           break;
+                case 'l':   draw.line_to(read_x(), read_y());           break;
+                case 'c':   draw.bezier_curve(read_x(), read_y(), read_x(), read_y(), read_x(), read_y()); break;
                 case '.':   draw.close_path();                          break;
                 case 'F':   draw.fill();                                break;
                 case 'S':   draw.stroke();                              break;
                 case 'L':   decode_line();                              break;
                 case 'D':   decode_dash();                              break;
                 case 'C':   decode_color();                             break;
+                case 'G':   decode_gradient();                          break;
+                case 't':   decode_text();                              break;
                 case 'M':   decode_blend_mode();                        break;
                 case 'T':   decode_transform();                         break;
                 case 'Z':   decode_clip();                              break;
+                case 'X':   decode_sprite();                            break;
                 case 'P':   draw.push_state();                          break;
                 case 'p':   draw.pop_state();                           break;
 
-                default:    throw 'Unknown instruction \'' + instruction + '\' at ' + pos;
+                default:    throw 'Unknown instruction \'' + instruction + '\' at ' + reader.position();
                 }
             }
 
@@ -114,6 +114,62 @@ let flo_control = (function () {
         input_element.addEventListener('change', on_change);
     };
 
+    ///
+    /// Sets up a control as a text box
+    ///
+    let load_textbox = (element) => {
+        // Find the input element
+        let input_element = element.getElementsByTagName('input')[0];
+
+        // Retrieve the current value from the object
+        let flo_value = element.flo_value || { 'String': '' };
+
+        ///
+        /// The 'input' event is fired while the user is typing
+        ///
+        function on_input() {
+            let input_handler = element.flo_edit_value || (() => {});
+            input_handler({ 'String': input_element.value });
+        }
+
+        ///
+        /// The 'change' event is fired when the user commits the text (eg, by pressing return or moving focus away)
+        ///
+        function on_change() {
+            let input_handler = element.flo_set_value || (() => {});
+            input_handler({ 'String': input_element.value });
+        }
+
+        /// Updates the text displayed in the text box
+        function set_value(new_property_value) {
+            let value = new_property_value['String'];
+
+            // Don't replace what the user is typing
+            if (value !== undefined && document.activeElement !== input_element) {
+                input_element.value = value;
+            }
+        }
+
+        // Set the initial value
+        set_value(flo_value);
+
+        // Make flo_value a dynamic property
+        Object.defineProperty(element, 'flo_value', {
+            get: () => flo_value,
+            set: new_value => {
+                if (new_value !== flo_value) {
+                    flo_value = new_value;
+                    set_value(new_value);
+                }
+            }
+        });
+
+        // Register event handlers
+        input_element.addEventListener('input', on_input);
+        input_element.addEventListener('change', on_change);
+        input_element.addEventListener('blur', () => set_value(flo_value));
+    };
+
     ///
     /// Adds drag event handling to a node
     ///
@@ -691,6 +747,7 @@ let flo_control = (function () {
 
     return {
         load_slider:            load_slider,
+        load_textbox:           load_textbox,
         load_rotor:             load_rotor,
         load_popup:             load_popup,
         layout_popup:           layout_popup,
@@ -998,6 +998,62 @@ function flowbetween(root_node) {
         }, true);
     };
 
+    ///
+    /// Wires up an action that asks the user to pick a file and sends its contents to the controller
+    ///
+    let wire_load_file = (action_name, node, controller_path, file_name) => {
+        add_action_event(node, 'click', event => {
+            event.preventDefault();
+
+            // Ask the browser for a file with the same extension as the file name
+            let extension   = file_name.lastIndexOf('.') >= 0 ? file_name.substring(file_name.lastIndexOf('.')) : '';
+            let file_input  = document.createElement('input');
+            file_input.type     = 'file';
+            file_input.accept   = extension;
+
+            file_input.addEventListener('change', () => {
+                let file = file_input.files[0];
+                if (!file) {
+                    return;
+                }
+
+                // Send the contents of the file as the value of the action
+                let reader = new FileReader();
+                reader.onload = () => {
+                    note('Load file ' + action_name + ' --> ' + controller_path);
+                    perform_action(controller_path, action_name, { 'Value': { 'String': reader.result } });
+                };
+                reader.readAsText(file);
+            });
+
+            file_input.click();
+        });
+    };
+
+    ///
+    /// Wires up an action that downloads the value of a node as a file
+    ///
+    let wire_save_file = (action_name, node, controller_path, file_name) => {
+        add_action_event(node, 'click', event => {
+            event.preventDefault();
+
+            // The contents of the file are the value bound to this node
+            let value       = node.flo_value && node.flo_value['String'] !== undefined ? node.flo_value['String'] : '';
+            let file_url    = URL.createObjectURL(new Blob([ value ], { type: 'application/octet-stream' }));
+            let link        = document.createElement('a');
+            link.href       = file_url;
+            link.download   = file_name;
+
+            document.body.appendChild(link);
+            link.click();
+            document.body.removeChild(link);
+            URL.revokeObjectURL(file_url);
+
+            note('Save file ' + action_name + ' --> ' + controller_path);
+            perform_action(controller_path, action_name, null);
+        });
+    };
+
     ///
     /// Wires up a drag action to a node
     ///
@@ -1075,6 +1131,12 @@ function flowbetween(root_node) {
         } else if (action_type === 'Drag') {
             wire_drag(action_name, node, controller_path);
 
+        } else if (action_type['LoadFile'] !== undefined) {
+            wire_load_file(action_name, node, controller_path, action_type['LoadFile']);
+
+        } else if (action_type['SaveFile'] !== undefined) {
+            wire_save_file(action_name, node, controller_path, action_type['SaveFile']);
+
         } else if (action_type === 'EditValue') {
             node.flo_edit_value = new_property_value => perform_action(controller_path, action_name, { 'Value': new_property_value });
 
@@ -1588,6 +1650,36 @@ function flowbetween(root_node) {
         };
     })();
 
+    ///
+    /// Decodes a binary websocket message into a list of updates
+    ///
+    /// Binary messages contain the updates as JSON, where any binary canvas updates are left as
+    /// empty arrays. The data for these follows the JSON in order, each preceded by its length.
+    ///
+    let decode_binary_frame = (frame) => {
+        let data        = new DataView(frame);
+        let json_length = data.getUint32(0, true);
+        let json        = new TextDecoder('utf-8').decode(new Uint8Array(frame, 4, json_length));
+        let updates     = JSON.parse(json);
+        let pos         = 4 + json_length;
+
+        updates.forEach(update => {
+            if (update['UpdateCanvas']) {
+                update['UpdateCanvas'].forEach(canvas_update => {
+                    let binary_updates = canvas_update['binary_updates'];
+
+                    if (binary_updates && binary_updates.length === 0) {
+                        let length = data.getUint32(pos, true);
+                        canvas_update['binary_updates'] = frame.slice(pos+4, pos+4+length);
+                        pos += 4 + length;
+                    }
+                });
+            }
+        });
+
+        return updates;
+    };
+
     ///
     /// Connects to a websocket running on a different port on the same server
     ///
@@ -1602,12 +1694,15 @@ function flowbetween(root_node) {
             note('Connecting to websocket at ' + ws_session_url);
 
             // Connect the websocket
-            let websocket = new WebSocket(ws_session_url);
+            let websocket           = new WebSocket(ws_session_url);
+            websocket.binaryType    = 'arraybuffer';
 
             // Add event handlers for it
             websocket.addEventListener('message', (event) => {
                 // Decode the updates from the message
-                let updates = JSON.parse(event.data);
+                let updates = event.data instanceof ArrayBuffer
+                    ? decode_binary_frame(event.data)
+                    : JSON.parse(event.data);
 
                 // Dispatch them
                 current_update_promise = current_update_promise.then(() => dispatch_updates(updates))
@@ -1626,6 +1721,9 @@ function flowbetween(root_node) {
                 // Register this as the socket for this session
                 websocket_for_session[session_id] = websocket;
 
+                // Canvas updates are more compact in the binary encoding, which the websocket can send directly
+                send_request(make_request([ make_event('SetCanvasEncoding', 'Binary') ], session_id));
+
                 // Resolve the promise
                 resolve();
             });
@@ -1834,6 +1932,12 @@ function flowbetween(root_node) {
                 let canvas_name = update['canvas_name'];
                 let updates     = update['updates'];
 
+                if (update['binary_updates']) {
+                    // Binary updates are an ArrayBuffer when they arrive in a binary frame, and an array of bytes when sent as JSON
+                    let binary_updates  = update['binary_updates'];
+                    updates             = binary_updates instanceof ArrayBuffer ? binary_updates : new Uint8Array(binary_updates).buffer;
+                }
+
                 flo_canvas.update_canvas(controller, canvas_name, updates);
             });
 
@@ -7,92 +7,96 @@
 
 /* exported flo_matrix */
 
+///
+/// A port of `Transform2D` from the canvas crate
+///
+/// Transforms are stored as arrays of 9 values, one row of the matrix after another. Points are
+/// treated as column vectors, so the transformed x coordinate is `t[0]*x + t[1]*y + t[2]`.
+///
 let flo_matrix = (function (){
-    ///
-    /// Computes the determinant of a 2x2 matrix
-    ///
-    let det2 = (matrix) => {
-        return (matrix[0]*matrix[3] - (matrix[1]*matrix[2]));
+    let identity = () => {
+        return [1,0,0, 0,1,0, 0,0,1];
+    };
+
+    let translate = (x, y) => {
+        return [1,0,x, 0,1,y, 0,0,1];
+    };
+
+    let scale = (x, y) => {
+        return [x,0,0, 0,y,0, 0,0,1];
     };
-    
+
     ///
-    /// Computes the minor of an element in a 3x3 matrix
+    /// Returns the transformation that applies `b` followed by `a`
     ///
-    let minor3 = (matrix, col, row) => {
-        let small_matrix = [];
-
-        let pos = 0;
-        for (let y=0; y<3; ++y) {
-            if (y !== row) {
-                for (let x=0; x<3; ++x) {
-                    if (x !== col) {
-                        small_matrix.push(matrix[pos]);
-                    }
-                    ++pos;
-                }
-            } else {
-                pos+=3;
-            }
-        }
-
-        return det2(small_matrix);
+    let multiply = (a, b) => {
+        let row = (r) => [
+            a[r]*b[0] + a[r+1]*b[3] + a[r+2]*b[6],
+            a[r]*b[1] + a[r+1]*b[4] + a[r+2]*b[7],
+            a[r]*b[2] + a[r+1]*b[5] + a[r+2]*b[8]
+        ];
+
+        return row(0).concat(row(3), row(6));
+    };
+
+    let determinant = (t) => {
+        return t[0]*(t[4]*t[8] - t[5]*t[7]) - t[1]*(t[3]*t[8] - t[5]*t[6]) + t[2]*(t[3]*t[7] - t[4]*t[6]);
     };
 
     ///
-    /// Computes the cofactor of an element in a 3x3 matrix
+    /// Returns the inverse of a transformation, or null if it has no inverse (eg, a scale by 0)
     ///
-    let cofactor3 = (matrix, col, row) => {
-        let minor   = minor3(matrix, col, row);
-        let sign    = (col&1) ^ (row&1);
-
-        if (sign !== 0) {
-            return -minor;
-        } else {
-            return minor;
-        }
+    let invert = (t) => {
+        let det = determinant(t);
+        if (det === 0 || !isFinite(det)) { return null; }
+
+        let inv = 1.0/det;
+
+        return [
+            (t[4]*t[8] - t[5]*t[7])*inv, (t[2]*t[7] - t[1]*t[8])*inv, (t[1]*t[5] - t[2]*t[4])*inv,
+            (t[5]*t[6] - t[3]*t[8])*inv, (t[0]*t[8] - t[2]*t[6])*inv, (t[2]*t[3] - t[0]*t[5])*inv,
+            (t[3]*t[7] - t[4]*t[6])*inv, (t[1]*t[6] - t[0]*t[7])*inv, (t[0]*t[4] - t[1]*t[3])*inv
+        ];
     };
 
     ///
-    /// Computes the determinant of a 3x3 matrix
+    /// Applies a transformation to a point, returning the transformed point as [x, y]
     ///
-    let det3 = (matrix) => {
-        return matrix[0]*cofactor3(matrix, 0,0) + matrix[1]*cofactor3(matrix, 1,0) + matrix[2]*cofactor3(matrix, 2,0);
+    let transform_point = (t, x, y) => {
+        return [t[0]*x + t[1]*y + t[2], t[3]*x + t[4]*y + t[5]];
     };
 
     ///
-    /// Inverts a 3x3 matrix
+    /// The translation applied after a transformation by a center region instruction (moves the center of the
+    /// region to the point that the transformation currently maps to the view origin)
     ///
-    let invert3 = (matrix) => {
-        let cofactors = [
-            cofactor3(matrix, 0,0), cofactor3(matrix, 1,0), cofactor3(matrix, 2,0),
-            cofactor3(matrix, 0,1), cofactor3(matrix, 1,1), cofactor3(matrix, 2,1),
-            cofactor3(matrix, 0,2), cofactor3(matrix, 1,2), cofactor3(matrix, 2,2)
-        ];
+    let center_region = (t, origin_x, origin_y, minx, miny, maxx, maxy) => {
+        let inverse = invert(t);
+        if (inverse === null) { return identity(); }
 
-        let det     = matrix[0]*cofactors[0] + matrix[1]*cofactors[1] + matrix[2]*cofactors[2];
-        let inv_det = 1.0/det;
+        let origin      = transform_point(inverse, origin_x, origin_y);
+        let center_x    = (minx+maxx)/2.0;
+        let center_y    = (miny+maxy)/2.0;
 
-        return [
-            inv_det * cofactors[0], inv_det * cofactors[3], inv_det * cofactors[6],
-            inv_det * cofactors[1], inv_det * cofactors[4], inv_det * cofactors[7],
-            inv_det * cofactors[2], inv_det * cofactors[5], inv_det * cofactors[8]
-        ];
+        return translate(origin[0]-center_x, origin[1]-center_y);
     };
 
     ///
-    /// Multiplies a 3x3 matrix with a 3D vector
+    /// The scale factors along the x and y axes of a transformation (the lengths of its first two columns)
     ///
-    let mulvec3 = (matrix, vector) => {
-        return [
-            matrix[0]*vector[0] + matrix[1]*vector[1] + matrix[2]*vector[2],
-            matrix[3]*vector[0] + matrix[4]*vector[1] + matrix[5]*vector[2],
-            matrix[6]*vector[0] + matrix[7]*vector[1] + matrix[8]*vector[2]
-        ];
+    let axis_scale = (t) => {
+        return [Math.sqrt(t[0]*t[0] + t[3]*t[3]), Math.sqrt(t[1]*t[1] + t[4]*t[4])];
     };
 
     return {
-        det3:       det3,
-        invert3:    invert3,
-        mulvec3:    mulvec3
+        identity:           identity,
+        translate:          translate,
+        scale:              scale,
+        multiply:           multiply,
+        determinant:        determinant,
+        invert:             invert,
+        transform_point:    transform_point,
+        center_region:      center_region,
+        axis_scale:         axis_scale
     };
 })();
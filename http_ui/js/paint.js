@@ -22,6 +22,17 @@ let flo_paint = (function() {
     let supports_pointer_events = 'onpointerdown' in window;
     let supports_touch_events   = 'ontouchstart' in window;
 
+    ///
+    /// Reads the modifier keys that were held down during an event
+    ///
+    let event_modifiers = (event) => {
+        return {
+            shift:      event.shiftKey || false,
+            control:    event.ctrlKey || event.metaKey || false,
+            alt:        event.altKey || false
+        };
+    };
+
     ///
     /// Converts a MouseEvent to a Paint object.
     ///
@@ -53,7 +64,8 @@ let flo_paint = (function() {
             location:   [x, y],
             pressure:   mouse_event.mozPressure || 0.5,
             tilt_x:     0,
-            tilt_y:     0
+            tilt_y:     0,
+            modifiers:  event_modifiers(mouse_event)
         };
     };
 
@@ -91,7 +103,8 @@ let flo_paint = (function() {
             location:   [x, y],
             pressure:   touch.force || 0.5,
             tilt_x:     0,
-            tilt_y:     0
+            tilt_y:     0,
+            modifiers:  event_modifiers(touch_event)
         };
     };
 
@@ -125,7 +138,8 @@ let flo_paint = (function() {
             location:   [x, y],
             pressure:   pointer_event.pressure,
             tilt_x:     pointer_event.tiltX,
-            tilt_y:     pointer_event.tiltY
+            tilt_y:     pointer_event.tiltY,
+            modifiers:  event_modifiers(pointer_event)
         };
     };
 
@@ -1,3 +1,17 @@
+use std::mem;
+
+///
+/// The formats that canvas updates can be sent in
+///
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum CanvasEncodingFormat {
+    /// Updates are sent using the string canvas encoding
+    Text,
+
+    /// Updates are sent using the binary canvas encoding
+    Binary
+}
+
 ///
 /// Data stored for a canvas update event
 ///
@@ -14,9 +28,15 @@ pub struct CanvasUpdate {
     canvas_name: String,
 
     ///
-    /// The updates that should be applied for this canvas 
+    /// The updates that should be applied for this canvas
     ///
-    updates: String
+    updates: String,
+
+    ///
+    /// The updates for this canvas in the binary encoding, if it's in use (updates is empty in this case)
+    ///
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    binary_updates: Option<Vec<u8>>
 }
 
 impl CanvasUpdate {
@@ -24,7 +44,27 @@ impl CanvasUpdate {
         CanvasUpdate {
             controller:     controller,
             canvas_name:    canvas_name,
-            updates:        updates
+            updates:        updates,
+            binary_updates: None
+        }
+    }
+
+    ///
+    /// Creates a canvas update with some updates in the binary encoding
+    ///
+    pub fn new_binary(controller: String, canvas_name: String, binary_updates: Vec<u8>) -> CanvasUpdate {
+        CanvasUpdate {
+            controller:     controller,
+            canvas_name:    canvas_name,
+            updates:        String::new(),
+            binary_updates: Some(binary_updates)
         }
     }
-}
\ No newline at end of file
+
+    ///
+    /// Removes the binary updates from this update, leaving an empty placeholder
+    ///
+    pub fn take_binary_updates(&mut self) -> Option<Vec<u8>> {
+        self.binary_updates.as_mut().map(|binary_updates| mem::replace(binary_updates, vec![]))
+    }
+}
@@ -1,3 +1,5 @@
+use super::canvas_update::*;
+
 use ui::ActionParameter;
 
 ///
@@ -23,5 +25,10 @@ pub enum Event {
     ///
     /// Sends a tick event to the controllers
     /// 
-    Tick
+    Tick,
+
+    ///
+    /// Requests that future canvas updates are sent in a particular format
+    ///
+    SetCanvasEncoding(CanvasEncodingFormat)
 }
\ No newline at end of file
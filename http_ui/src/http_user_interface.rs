@@ -25,7 +25,10 @@ pub struct HttpUserInterface<CoreUi> {
     ui_tree: BindRef<Control>,
 
     /// The base path of the instance (where URIs are generated relative to)
-    base_path: String
+    base_path: String,
+
+    /// The format that canvas updates are sent in
+    canvas_format: Arc<Mutex<CanvasEncodingFormat>>
 }
 
 impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
@@ -36,9 +39,10 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
         let ui_tree = ui.ui_tree();
 
         HttpUserInterface {
-            core_ui:    ui,
-            ui_tree:    ui_tree,
-            base_path:  base_path
+            core_ui:        ui,
+            ui_tree:        ui_tree,
+            base_path:      base_path,
+            canvas_format:  Arc::new(Mutex::new(CanvasEncodingFormat::Text))
         }
     }
 
@@ -60,6 +64,9 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
             UiRefresh   => UiEvent::Tick,
             Tick        => UiEvent::Tick,
 
+            // The canvas format is handled by the input sink: the core still needs an event so that an update is generated
+            SetCanvasEncoding(_) => UiEvent::Tick,
+
             Action(controller_path, action_name, action_parameter) => UiEvent::Action(controller_path, action_name, action_parameter)
         }
     }
@@ -112,11 +119,7 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
     /// 
     /// Mainly this means encoding the content of the update
     /// 
-    fn map_canvas_diff(canvas_diff: CanvasDiff) -> CanvasUpdate {
-        // Encode the updates from the diff
-        let mut encoded_updates = String::new();
-        canvas_diff.updates.encode_canvas(&mut encoded_updates);
-
+    fn map_canvas_diff(canvas_diff: CanvasDiff, canvas_format: CanvasEncodingFormat) -> CanvasUpdate {
         // Create the HTTP version of the controller path
         let controller_path = join(canvas_diff.controller.iter()
             .map(|component| utf8_percent_encode(&*component, DEFAULT_ENCODE_SET)),
@@ -125,8 +128,22 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
         // Canvas name also needs to be encoded
         let canvas_name     = utf8_percent_encode(&canvas_diff.canvas_name, DEFAULT_ENCODE_SET).to_string();
 
-        // Can now generate an update
-        CanvasUpdate::new(controller_path, canvas_name, encoded_updates)
+        // Encode the updates from the diff and generate the update
+        match canvas_format {
+            CanvasEncodingFormat::Text => {
+                let mut encoded_updates = String::new();
+                canvas_diff.updates.encode_canvas(&mut encoded_updates);
+
+                CanvasUpdate::new(controller_path, canvas_name, encoded_updates)
+            },
+
+            CanvasEncodingFormat::Binary => {
+                let mut encoded_updates = vec![];
+                canvas_diff.updates.encode_canvas(&mut encoded_updates);
+
+                CanvasUpdate::new_binary(controller_path, canvas_name, encoded_updates)
+            }
+        }
     }
 
     ///
@@ -146,7 +163,7 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
     ///
     /// Maps a single core update to a HTTP update
     /// 
-    fn map_core_update(core_update: UiUpdate, base_path: &str, ui_tree: &Control) -> Vec<Update> {
+    fn map_core_update(core_update: UiUpdate, base_path: &str, ui_tree: &Control, canvas_format: CanvasEncodingFormat) -> Vec<Update> {
         use self::UiUpdate::*;
 
         match core_update {
@@ -160,7 +177,7 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
                 )]
             },
             
-            UpdateCanvas(canvas_diffs) => vec![Update::UpdateCanvas(canvas_diffs.into_iter().map(|diff| Self::map_canvas_diff(diff)).collect())],
+            UpdateCanvas(canvas_diffs) => vec![Update::UpdateCanvas(canvas_diffs.into_iter().map(|diff| Self::map_canvas_diff(diff, canvas_format)).collect())],
 
             UpdateViewModel(view_model_diffs) => vec![Update::UpdateViewModel(view_model_diffs)]
         }
@@ -169,12 +186,12 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
     ///
     /// Converts updates from the core into HTTP updates
     /// 
-    fn core_updates_to_http_updates(core_update: Vec<UiUpdate>, base_path: &str, ui_tree: &Control) -> Vec<Update> {
+    fn core_updates_to_http_updates(core_update: Vec<UiUpdate>, base_path: &str, ui_tree: &Control, canvas_format: CanvasEncodingFormat) -> Vec<Update> {
         use self::UiUpdate::*;
 
         let is_start    = core_update.len() > 0 && core_update[0] == Start;
         let base_update = core_update.into_iter()
-                .flat_map(|core_update| Self::map_core_update(core_update, base_path, ui_tree).into_iter())
+                .flat_map(|core_update| Self::map_core_update(core_update, base_path, ui_tree, canvas_format).into_iter())
                 .collect();
 
         if is_start {
@@ -205,10 +222,19 @@ impl<CoreUi: CoreUserInterface> UserInterface<Vec<Event>, Vec<Update>, ()> for H
         // Get the core event sink
         let core_sink   = self.core_ui.get_input_sink();
 
+        // Requests to change the canvas format are applied as the events arrive
+        let canvas_format = Arc::clone(&self.canvas_format);
+
         // Create a sink that turns HTTP events into core events
-        let mapped_sink = core_sink.with_flat_map(|http_events: Vec<_>| {
+        let mapped_sink = core_sink.with_flat_map(move |http_events: Vec<_>| {
             let core_events = http_events.into_iter()
-                .map(|evt| Self::http_event_to_core_event(evt))
+                .map(|evt| {
+                    if let &Event::SetCanvasEncoding(new_format) = &evt {
+                        *canvas_format.lock().unwrap() = new_format;
+                    }
+
+                    Self::http_event_to_core_event(evt)
+                })
                 .collect();
             stream::once(Ok(core_events))
         });
@@ -222,14 +248,16 @@ impl<CoreUi: CoreUserInterface> UserInterface<Vec<Event>, Vec<Update>, ()> for H
         let core_updates = self.core_ui.get_updates();
 
         // Fetch the extra components we need to map events from this object
-        let ui_tree     = BindRef::clone(&self.ui_tree);
-        let base_path   = self.base_path.clone();
+        let ui_tree         = BindRef::clone(&self.ui_tree);
+        let base_path       = self.base_path.clone();
+        let canvas_format   = Arc::clone(&self.canvas_format);
 
         // Turn into HTTP updates
         let mapped_updates = core_updates.map(move |core_updates| {
-            let ui_tree = ui_tree.get();
+            let ui_tree         = ui_tree.get();
+            let canvas_format   = *canvas_format.lock().unwrap();
 
-            Self::core_updates_to_http_updates(core_updates, &base_path, &ui_tree)
+            Self::core_updates_to_http_updates(core_updates, &base_path, &ui_tree, canvas_format)
         });
 
         // These are the results
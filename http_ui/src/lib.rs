@@ -27,6 +27,7 @@ mod ui_handler;
 mod null_session;
 pub mod minidom;
 mod canvas_update;
+mod update_frame;
 mod parked_future;
 mod lazy_future;
 
@@ -40,3 +41,4 @@ pub use self::htmlcontrol::*;
 pub use self::ui_handler::*;
 pub use self::null_session::*;
 pub use self::canvas_update::*;
+pub use self::update_frame::*;
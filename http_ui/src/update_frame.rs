@@ -0,0 +1,100 @@
+use super::update::*;
+
+use serde_json;
+
+///
+/// A set of updates encoded as a single websocket message
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum UpdateFrame {
+    /// Updates encoded as JSON
+    Text(String),
+
+    /// Updates that include binary canvas updates
+    Binary(Vec<u8>)
+}
+
+///
+/// Writes a u32 as little-endian bytes
+///
+fn write_u32(value: u32, target: &mut Vec<u8>) {
+    target.extend([
+        (value&0xff) as u8,
+        ((value>>8)&0xff) as u8,
+        ((value>>16)&0xff) as u8,
+        ((value>>24)&0xff) as u8
+    ].iter());
+}
+
+impl UpdateFrame {
+    ///
+    /// Encodes a set of updates as a websocket message
+    ///
+    /// Updates are sent as JSON unless there are binary canvas updates. Binary frames start with the
+    /// length of the JSON as a little-endian u32 followed by the JSON itself, where the binary updates
+    /// are left as empty arrays. The binary updates follow in the order they appear in the JSON, each
+    /// preceded by its length.
+    ///
+    pub fn from_updates(mut updates: Vec<Update>) -> UpdateFrame {
+        // Move the binary data out of the updates
+        let mut binary_data = vec![];
+
+        for update in updates.iter_mut() {
+            if let &mut Update::UpdateCanvas(ref mut canvas_updates) = update {
+                for canvas_update in canvas_updates.iter_mut() {
+                    if let Some(data) = canvas_update.take_binary_updates() {
+                        binary_data.push(data);
+                    }
+                }
+            }
+        }
+
+        let json = serde_json::to_string(&updates).unwrap();
+
+        if binary_data.is_empty() {
+            UpdateFrame::Text(json)
+        } else {
+            let mut frame = vec![];
+
+            write_u32(json.len() as u32, &mut frame);
+            frame.extend(json.bytes());
+
+            for data in binary_data {
+                write_u32(data.len() as u32, &mut frame);
+                frame.extend(data);
+            }
+
+            UpdateFrame::Binary(frame)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::canvas_update::*;
+
+    #[test]
+    fn text_updates_are_json() {
+        let updates = vec![Update::UpdateCanvas(vec![CanvasUpdate::new("controller".to_string(), "canvas".to_string(), "Np".to_string())])];
+        let json    = serde_json::to_string(&updates).unwrap();
+
+        assert!(UpdateFrame::from_updates(updates) == UpdateFrame::Text(json));
+    }
+
+    #[test]
+    fn binary_updates_follow_json() {
+        let updates = vec![Update::UpdateCanvas(vec![CanvasUpdate::new_binary("controller".to_string(), "canvas".to_string(), vec![1, 2, 3])])];
+        let frame   = UpdateFrame::from_updates(updates);
+
+        if let UpdateFrame::Binary(frame) = frame {
+            let json_len    = (frame[0] as usize) | ((frame[1] as usize)<<8) | ((frame[2] as usize)<<16) | ((frame[3] as usize)<<24);
+            let json        = String::from_utf8(frame[4..(4+json_len)].to_vec()).unwrap();
+
+            assert!(json.contains("\"binary_updates\":[]"));
+            assert!(&frame[(4+json_len)..] == &[3, 0, 0, 0, 1, 2, 3]);
+        } else {
+            assert!(false);
+        }
+    }
+}
@@ -47,10 +47,13 @@ impl<Session: ActixSession+'static> FloWsSession<Session> {
         let update_stream = self.session.lock().unwrap().http_ui().get_updates();
         let update_stream = fut::wrap_stream::<_, Self>(update_stream);
 
-        // Updates are sent to the websocket
+        // Updates are sent to the websocket (binary canvas updates need a binary frame)
         let update_stream = update_stream
-            .map(|update, _actor, _ctx| serde_json::to_string(&update).unwrap())
-            .map(|update, _actor, ctx| ctx.text(update));
+            .map(|update, _actor, _ctx| UpdateFrame::from_updates(update))
+            .map(|frame, _actor, ctx| match frame {
+                UpdateFrame::Text(text)     => ctx.text(text),
+                UpdateFrame::Binary(data)   => ctx.binary(data)
+            });
         
         // Spawn the updates on the context
         ctx.spawn(update_stream.finish());
@@ -23,6 +23,18 @@ pub struct NanoVgDrawingState {
     /// Current fill paint option
     fill: NanoVgPaint,
 
+    /// The gradient that's currently being defined
+    gradient: Option<NanoVgGradient>,
+
+    /// The font family, weight and size to use for text
+    font: (flo_canvas::FontFamily, flo_canvas::CanvasFontWeight, f32),
+
+    /// How text is aligned relative to where it's drawn
+    text_align: flo_canvas::TextAlignment,
+
+    /// The text run that will be drawn by the next text instruction
+    text: String,
+
     /// Current fill options
     fill_options: FillOptions,
 
@@ -43,6 +55,10 @@ impl NanoVgDrawingState {
             path:               vec![],
             stroke:             NanoVgPaint::Color(nanovg::Color::new(0.0, 0.0, 0.0, 1.0)),
             fill:               NanoVgPaint::Color(nanovg::Color::new(0.0, 0.0, 0.0, 1.0)),
+            gradient:           None,
+            font:               (flo_canvas::FontFamily::SansSerif, flo_canvas::CanvasFontWeight::Normal, 12.0),
+            text_align:         flo_canvas::TextAlignment::Left,
+            text:               String::new(),
             fill_options:       FillOptions { antialias: true },
             stroke_options:     StrokeOptions { width: 1.0, line_cap: LineCap::Butt, line_join: LineJoin::Miter, miter_limit: 16.0, antialias: true },
             path_options:       PathOptions { clip: Clip::None, composite_operation: CompositeOperation::Basic(BasicCompositeOperation::SourceOver), alpha: 1.0, transform: Some(viewport.to_transform()) }
@@ -89,6 +105,57 @@ impl NanoVgDrawingState {
         self.path_options.clone());
     }
 
+    ///
+    /// Draws the current text run on the current frame
+    ///
+    /// nanovg looks fonts up by name, so fonts called 'sans-serif', 'serif' and 'monospace' (with
+    /// a '-bold' suffix for the bold weights) need to be loaded into the context for text to appear.
+    /// nanovg can't outline text, so stroked text is filled with the stroke colour.
+    ///
+    fn draw_text<'a>(&self, x: f32, y: f32, paint: &NanoVgPaint, frame: &Frame<'a>) {
+        let (family, weight, size) = self.font;
+
+        let family = match family {
+            flo_canvas::FontFamily::SansSerif   => "sans-serif",
+            flo_canvas::FontFamily::Serif       => "serif",
+            flo_canvas::FontFamily::Monospace   => "monospace"
+        };
+        let font_name = match weight {
+            flo_canvas::CanvasFontWeight::Normal  => family.to_string(),
+            flo_canvas::CanvasFontWeight::Bold    => format!("{}-bold", family)
+        };
+
+        let font = match Font::find(frame.context(), &font_name) {
+            Ok(font)    => font,
+            Err(_)      => { return; }
+        };
+
+        let align = match self.text_align {
+            flo_canvas::TextAlignment::Left     => Alignment::new().left().baseline(),
+            flo_canvas::TextAlignment::Center   => Alignment::new().center().baseline(),
+            flo_canvas::TextAlignment::Right    => Alignment::new().right().baseline()
+        };
+
+        let color = match paint {
+            &NanoVgPaint::Color(ref color) => color.clone(),
+            &NanoVgPaint::Gradient(_)      => nanovg::Color::new(0.0, 0.0, 0.0, 1.0)
+        };
+
+        // Canvas coordinates run upwards, so the text needs to be flipped to be the right way up
+        let flip        = Transform::new().scale(1.0, -1.0);
+        let position    = Transform::new().translate(x, y);
+        let transform   = self.path_options.transform.clone()
+            .map(|transform| flip * position * transform);
+
+        frame.text(font, (0.0, 0.0), &self.text, TextOptions {
+            color:      color,
+            size:       size,
+            align:      align,
+            transform:  transform,
+            ..TextOptions::default()
+        });
+    }
+
     ///
     /// Converts a canvas blending mode into a nanovg blending mdoe
     /// 
@@ -212,6 +279,19 @@ impl NanoVgDrawingState {
             DashOffset(offset)                          => { /* Dashed paths are not supported by nanovg */ },
             FillColor(col)                              => { self.fill = col.into(); },
             StrokeColor(col)                            => { self.stroke = col.into(); },
+            NewLinearGradient(start, end)               => { self.gradient = Some(NanoVgGradient::Linear(start, end, vec![])); },
+            NewRadialGradient(_, r1, end, r2)           => { /* nanovg radial gradients are always concentric */ self.gradient = Some(NanoVgGradient::Radial(end, r1, r2, vec![])); },
+            GradientStop(_pos, col)                     => { if let Some(ref mut gradient) = self.gradient { gradient.add_stop(col); } },
+            GradientTransform(transform)                => { if let Some(ref mut gradient) = self.gradient { gradient.transform(transform); } },
+            FillGradient                                => { if let Some(ref gradient) = self.gradient { self.fill = gradient.into(); } },
+            StrokeGradient                              => { if let Some(ref gradient) = self.gradient { self.stroke = gradient.into(); } },
+            Font(family, weight)                        => { self.font.0 = family; self.font.1 = weight; },
+            FontSize(size)                              => { self.font.2 = size; },
+            TextAlign(alignment)                        => { self.text_align = alignment; },
+            NewText                                     => { self.text = String::new(); },
+            TextChar(c)                                 => { self.text.push(c); },
+            FillText(x, y)                              => { self.draw_text(x, y, &self.fill, frame); },
+            StrokeText(x, y)                            => { self.draw_text(x, y, &self.stroke, frame); },
             BlendMode(blend)                            => { self.path_options.composite_operation = Self::blend_mode(blend); },
             IdentityTransform                           => { self.path_options.transform = Some(self.viewport.to_transform()) },
             CanvasHeight(height)                        => { self.canvas_height(height); },
@@ -227,7 +307,11 @@ impl NanoVgDrawingState {
             ClearCanvas                                 => { },
             Layer(layer_id)                             => { },
             LayerBlend(layer_id, mode)                  => { },
-            ClearLayer                                  => { }
+            ClearLayer                                  => { },
+            StartSprite(sprite_id)                      => { },
+            EndSprite                                   => { },
+            DrawSprite(sprite_id, transform)            => { },
+            FreeSprite(sprite_id)                       => { }
         }
     }
 }
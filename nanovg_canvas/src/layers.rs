@@ -41,7 +41,10 @@ pub struct NanoVgLayers {
     pending_for_layer: Vec<Draw>,
 
     /// The layers, as a map of layer IDs to the framebuffers that they are stored upon
-    layers: HashMap<u32, Layer>
+    layers: HashMap<u32, Layer>,
+
+    /// The sprites that have been defined
+    sprites: SpriteCache
 }
 
 impl NanoVgLayers {
@@ -57,7 +60,8 @@ impl NanoVgLayers {
             scale_factor:       scale_factor,
             state_stack:        vec![],
             pending_for_layer:  vec![],
-            layers:             HashMap::new()
+            layers:             HashMap::new(),
+            sprites:            SpriteCache::new()
         }
     }
 
@@ -81,7 +85,18 @@ impl NanoVgLayers {
     /// Performs a drawing action on this layers object
     /// 
     pub fn draw(&mut self, action: Draw) {
+        // Sprite definitions are recorded rather than drawn
+        if self.sprites.record(&action) {
+            return;
+        }
+
         match action {
+            Draw::DrawSprite(sprite_id, transform) => {
+                // Sprites are drawn by queueing up their instructions for the current layer
+                let sprite_actions = self.sprites.render_sprite(sprite_id, transform);
+                self.pending_for_layer.extend(sprite_actions);
+            },
+
             Draw::Layer(layer_id) => {
                 // Flush actions for the current layer
                 self.flush();
@@ -117,6 +132,7 @@ impl NanoVgLayers {
         self.layers         = HashMap::new();
         self.current_layer  = 0;
         self.state          = NanoVgDrawingState::new(self.viewport.clone());
+        self.sprites        = SpriteCache::new();
     }
 
     ///
@@ -9,6 +9,77 @@ pub enum NanoVgPaint {
     Gradient(Gradient)
 }
 
+///
+/// A gradient that's being defined by the canvas instructions
+///
+/// nanovg gradients only have two colours, so only the first and last stops are used when rendering
+///
+#[derive(Clone)]
+pub enum NanoVgGradient {
+    Linear((f32, f32), (f32, f32), Vec<flo_canvas::Color>),
+    Radial((f32, f32), f32, f32, Vec<flo_canvas::Color>)
+}
+
+impl NanoVgGradient {
+    ///
+    /// Adds a colour stop to this gradient
+    ///
+    pub fn add_stop(&mut self, color: flo_canvas::Color) {
+        match self {
+            &mut NanoVgGradient::Linear(_, _, ref mut stops)     => stops.push(color),
+            &mut NanoVgGradient::Radial(_, _, _, ref mut stops)  => stops.push(color)
+        }
+    }
+
+    ///
+    /// Applies a transformation to the coordinates of this gradient
+    ///
+    pub fn transform(&mut self, transform: flo_canvas::Transform2D) {
+        let flo_canvas::Transform2D(a, b, _) = transform;
+        let transform_point = |(x, y): (f32, f32)| (a.0*x + a.1*y + a.2, b.0*x + b.1*y + b.2);
+        let scale           = (a.0*a.0 + b.0*b.0).sqrt();
+
+        match self {
+            &mut NanoVgGradient::Linear(ref mut start, ref mut end, _) => {
+                *start  = transform_point(*start);
+                *end    = transform_point(*end);
+            },
+
+            &mut NanoVgGradient::Radial(ref mut center, ref mut inner_radius, ref mut outer_radius, _) => {
+                *center         = transform_point(*center);
+                *inner_radius   = *inner_radius * scale;
+                *outer_radius   = *outer_radius * scale;
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a NanoVgGradient> for NanoVgPaint {
+    fn from(item: &NanoVgGradient) -> NanoVgPaint {
+        let to_color = |col: Option<&flo_canvas::Color>| {
+            let (r, g, b, a) = col.map(|col| col.to_rgba_components()).unwrap_or((0.0, 0.0, 0.0, 1.0));
+            Color::new(r, g, b, a)
+        };
+
+        match item {
+            &NanoVgGradient::Linear(start, end, ref stops) => NanoVgPaint::Gradient(Gradient::Linear {
+                start:          start,
+                end:            end,
+                start_color:    to_color(stops.first()),
+                end_color:      to_color(stops.last())
+            }),
+
+            &NanoVgGradient::Radial(center, inner_radius, outer_radius, ref stops) => NanoVgPaint::Gradient(Gradient::Radial {
+                center:         center,
+                inner_radius:   inner_radius,
+                outer_radius:   outer_radius,
+                start_color:    to_color(stops.first()),
+                end_color:      to_color(stops.last())
+            })
+        }
+    }
+}
+
 impl From<flo_canvas::Color> for NanoVgPaint {
     fn from(item: flo_canvas::Color) -> NanoVgPaint {
         let (r, g, b, a) = item.to_rgba_components();
@@ -28,7 +28,15 @@ pub enum ActionTrigger {
     SetValue,
 
     /// Divides a scrollable region into a grid, and generates an event whenever the region in the top-left corner changes
-    VirtualScroll(f32, f32)
+    VirtualScroll(f32, f32),
+
+    /// User clicked this item and picked a file to load (the parameter is a file name whose extension is used to filter the files
+    /// that can be picked). The action is sent with the contents of the file as a string value.
+    LoadFile(String),
+
+    /// User clicked this item and picked where to save its value (the parameter is the suggested file name). The string bound to
+    /// the item's `State::Value` is written to the file, then the action is sent.
+    SaveFile(String)
 }
 
 ///
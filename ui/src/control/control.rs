@@ -78,6 +78,11 @@ impl Control {
         Self::new(ControlType::Rotor)
     }
 
+    /// Creates a new text box control
+    pub fn text_box() -> Control {
+        Self::new(ControlType::TextBox)
+    }
+
     /// Adds an attribute to this control
     pub fn add_attribute(&mut self, attribute: ControlAttribute) {
         self.attributes.push(attribute);
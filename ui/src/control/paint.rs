@@ -39,6 +39,34 @@ pub enum PaintAction {
     Cancel
 }
 
+///
+/// The modifier keys that were held down during a painting event
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct PaintModifiers {
+    /// The shift key is held down
+    pub shift: bool,
+
+    /// The control (or command) key is held down
+    pub control: bool,
+
+    /// The alt (or option) key is held down
+    pub alt: bool
+}
+
+impl PaintModifiers {
+    ///
+    /// Modifiers for a painting event where no keys are held down
+    ///
+    pub fn none() -> PaintModifiers {
+        PaintModifiers {
+            shift:      false,
+            control:    false,
+            alt:        false
+        }
+    }
+}
+
 ///
 /// Data for a painting event
 ///
@@ -60,5 +88,8 @@ pub struct Painting {
     pub tilt_x: f32,
 
     /// Y tilt (-90 to 90)
-    pub tilt_y: f32
+    pub tilt_y: f32,
+
+    /// The modifier keys held down during this event
+    pub modifiers: PaintModifiers
 }
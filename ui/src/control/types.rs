@@ -32,5 +32,8 @@ pub enum ControlType {
     Slider,
 
     /// A circular slider that represents its value by how much it is rotated
-    Rotor
+    Rotor,
+
+    /// Allows the user to enter a line of text
+    TextBox
 }
@@ -177,11 +177,11 @@ impl CanvasStateCore {
             let canvas_updates = Self::updates_for(&mut canvas);
 
             if canvas_updates.len() > 0 {
-                // If this canvas has changed, encode its updates
+                // If this canvas has changed, encode its updates (with any redundant instructions removed)
                 updates.push(CanvasDiff {
                     controller:     path.controller_path.clone(),
                     canvas_name:    path.canvas_name.clone(),
-                    updates:        canvas_updates
+                    updates:        optimise_drawing(canvas_updates)
                 });
             }
         }